@@ -146,6 +146,7 @@ async fn flight_plan_parcel_scenario() -> Result<(), Status> {
     //
     let data = Id {
         id: flight_plan_id.to_owned(),
+        include_archived: false,
     };
     let response = match client.get_linked_ids(data.clone()).await {
         Ok(response) => response.into_inner(),
@@ -297,6 +298,7 @@ async fn itinerary_scenario() -> Result<(), Status> {
     match link_client
         .get_linked_ids(Id {
             id: itinerary_id.clone(),
+            include_archived: false,
         })
         .await
     {
@@ -322,7 +324,13 @@ async fn itinerary_scenario() -> Result<(), Status> {
         }
 
         // Get the new linked list
-        match link_client.get_linked_ids(Id { id: itinerary_id }).await {
+        match link_client
+            .get_linked_ids(Id {
+                id: itinerary_id,
+                include_archived: false,
+            })
+            .await
+        {
             Ok(result) => println!("Got linked flight_plan ids: {:?}", result),
             Err(e) => panic!("Could not get linked flight_plans for itinerary: {}", e),
         }
@@ -560,6 +568,7 @@ async fn vertipad_scenario(vertiports: &vertiport::List) -> Result<vertipad::Lis
                     occupied: true,
                     ..vertipad.clone().data.unwrap()
                 }),
+                expected_updated_at: None,
                 mask: Some(FieldMask {
                     paths: vec!["occupied".to_string()],
                 }),
@@ -841,6 +850,7 @@ async fn flight_plan_scenario(
                     flight_status: flight_plan::FlightStatus::InFlight as i32,
                     ..new_fp.clone().data.unwrap()
                 }),
+                expected_updated_at: None,
                 mask: Some(FieldMask {
                     paths: vec!["flight_status".to_string()],
                 }),
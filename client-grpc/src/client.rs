@@ -0,0 +1,231 @@
+//! Generic gRPC client
+//!
+//! A [`GrpcClient`] connects to the server on first use and keeps the connection for all further
+//! requests, until it has been invalidated. Its [`ClientOptions`] are shared by all clones of the
+//! client, so all [`Clients`](crate::Clients) created together use the same options.
+
+use std::sync::Arc;
+use tokio::sync::Mutex;
+use tonic::metadata::{errors::InvalidMetadataValue, AsciiMetadataValue};
+use tonic::transport::ClientTlsConfig;
+
+/// Metadata key used to send the bearer token
+const AUTHORIZATION_KEY: &str = "authorization";
+
+/// Options used for the connections and requests of a [`GrpcClient`]
+#[derive(Debug, Clone, Default)]
+pub struct ClientOptions {
+    /// `authorization` metadata value added to all requests
+    authorization: Option<AsciiMetadataValue>,
+    /// TLS configuration used for the connections, plaintext is used if not set
+    tls_config: Option<ClientTlsConfig>,
+}
+
+impl ClientOptions {
+    /// Uses TLS for all connections, see [`tls`](crate::tls)
+    pub fn with_tls_config(mut self, tls_config: ClientTlsConfig) -> Self {
+        self.tls_config = Some(tls_config);
+        self
+    }
+
+    /// Returns the TLS configuration used for the connections, if any
+    pub fn get_tls_config(&self) -> Option<&ClientTlsConfig> {
+        self.tls_config.as_ref()
+    }
+
+    /// Adds the provided bearer token to the `authorization` metadata of all requests
+    ///
+    /// # Errors
+    ///
+    /// Returns [`InvalidMetadataValue`] if the token contains characters which are not allowed
+    /// in gRPC metadata
+    pub fn with_bearer_token(mut self, token: &str) -> Result<Self, InvalidMetadataValue> {
+        self.authorization = Some(format!("Bearer {}", token).parse()?);
+        Ok(self)
+    }
+}
+
+/// gRPC client keeping a single connection to the server
+#[derive(Debug, Clone)]
+pub struct GrpcClient<T> {
+    inner: Arc<Mutex<Option<T>>>,
+    address: String,
+    name: String,
+    options: Arc<ClientOptions>,
+}
+
+impl<T> GrpcClient<T> {
+    /// Creates a new client for the provided server, using the default [`ClientOptions`].
+    /// No connection is made until the client is used.
+    pub fn new_client(server_host: &str, server_port: u16, name: &str) -> Self {
+        Self::new_client_with_options(server_host, server_port, name, ClientOptions::default())
+    }
+
+    /// Creates a new client for the provided server, using the provided [`ClientOptions`].
+    /// No connection is made until the client is used.
+    pub fn new_client_with_options(
+        server_host: &str,
+        server_port: u16,
+        name: &str,
+        options: ClientOptions,
+    ) -> Self {
+        Self {
+            inner: Arc::new(Mutex::new(None)),
+            address: format!("http://{}:{}", server_host, server_port),
+            name: name.to_owned(),
+            options: Arc::new(options),
+        }
+    }
+}
+
+/// Creates the connection of a gRPC client
+#[tonic::async_trait]
+pub trait ClientConnect<T> {
+    /// Connects to the server, returning the connected gRPC client
+    async fn connect(&self) -> Result<T, tonic::transport::Error>;
+}
+
+/// Generic gRPC client functions used by the service clients
+#[tonic::async_trait]
+pub trait Client<T>
+where
+    Self: Sized + ClientConnect<T>,
+    T: Send + Clone,
+{
+    /// Returns the name of the client
+    fn get_name(&self) -> String;
+
+    /// Returns the address of the server
+    fn get_address(&self) -> String;
+
+    /// Returns the options used for the client's connections and requests
+    fn get_options(&self) -> &ClientOptions;
+
+    /// Returns the connected gRPC client, connecting to the server if needed
+    ///
+    /// # Errors
+    ///
+    /// Returns [`tonic::Status`] with [`tonic::Code::Unavailable`] if no connection could be made
+    async fn get_client(&self) -> Result<T, tonic::Status>;
+
+    /// Drops the current connection, a new connection will be made for the next request
+    async fn invalidate(&mut self);
+
+    /// Returns a [`tonic::Request`] for the provided message, with the trace context of the
+    /// current span (see [`with_trace_context`](crate::trace_context::with_trace_context)) and
+    /// the configured bearer token added to its metadata
+    fn new_request<M>(&self, message: M) -> tonic::Request<M> {
+        let mut request = crate::trace_context::with_trace_context(message);
+        if let Some(authorization) = &self.get_options().authorization {
+            request
+                .metadata_mut()
+                .insert(AUTHORIZATION_KEY, authorization.clone());
+        }
+        request
+    }
+}
+
+#[tonic::async_trait]
+impl<T> Client<T> for GrpcClient<T>
+where
+    Self: ClientConnect<T>,
+    T: Send + Clone,
+{
+    fn get_name(&self) -> String {
+        self.name.clone()
+    }
+
+    fn get_address(&self) -> String {
+        self.address.clone()
+    }
+
+    fn get_options(&self) -> &ClientOptions {
+        &self.options
+    }
+
+    async fn get_client(&self) -> Result<T, tonic::Status> {
+        let mut client = self.inner.lock().await;
+        if let Some(client) = &*client {
+            return Ok(client.clone());
+        }
+
+        grpc_info!("Connecting {} client to [{}].", self.name, self.address);
+        match self.connect().await {
+            Ok(connected) => {
+                *client = Some(connected.clone());
+                Ok(connected)
+            }
+            Err(e) => {
+                grpc_error!(
+                    "Could not connect {} client to [{}]: {}",
+                    self.name,
+                    self.address,
+                    e
+                );
+                Err(tonic::Status::unavailable(format!(
+                    "Could not connect to {}.",
+                    self.address
+                )))
+            }
+        }
+    }
+
+    async fn invalidate(&mut self) {
+        *self.inner.lock().await = None;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Client without an actual connection, used to test the client functions
+    type TestClient = GrpcClient<()>;
+
+    #[tonic::async_trait]
+    impl ClientConnect<()> for TestClient {
+        async fn connect(&self) -> Result<(), tonic::transport::Error> {
+            Ok(())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_new_client() {
+        let client = TestClient::new_client("localhost", 50051, "test");
+        assert_eq!(client.get_name(), "test");
+        assert_eq!(client.get_address(), "http://localhost:50051");
+        assert!(client.get_client().await.is_ok());
+    }
+
+    #[test]
+    fn test_new_request() {
+        let client = TestClient::new_client("localhost", 50051, "test");
+        let request = client.new_request(());
+        assert!(request.metadata().get(AUTHORIZATION_KEY).is_none());
+
+        let options = ClientOptions::default().with_bearer_token("token").unwrap();
+        let client = TestClient::new_client_with_options("localhost", 50051, "test", options);
+        let request = client.new_request(());
+        assert_eq!(
+            request.metadata().get(AUTHORIZATION_KEY).unwrap(),
+            "Bearer token"
+        );
+
+        assert!(ClientOptions::default()
+            .with_bearer_token("invalid\ntoken")
+            .is_err());
+    }
+
+    #[test]
+    fn test_tls_config() {
+        assert!(ClientOptions::default().get_tls_config().is_none());
+
+        let options = ClientOptions::default().with_tls_config(ClientTlsConfig::new());
+        let client = TestClient::new_client_with_options("localhost", 50051, "test", options);
+        assert!(client.get_options().get_tls_config().is_some());
+
+        // Clients created without the configuration are not affected
+        let client = TestClient::new_client("localhost", 50051, "test");
+        assert!(client.get_options().get_tls_config().is_none());
+    }
+}
@@ -4,10 +4,13 @@
 /// macros module exposing gRPC include macro
 mod macros;
 
+pub mod client;
 pub mod link_service;
 pub mod resources;
+pub mod schedule_service;
 pub mod simple_service;
 pub mod simple_service_linked;
+pub mod tls;
 
 #[cfg(test)]
 mod enum_tests;
@@ -16,6 +19,53 @@ mod enum_tests;
 pub mod search {
     include!("../includes/search.rs");
 }
+/// Provide schedule (calendar) parsing and time interval helpers
+///
+/// The `schedule` fields of vertiports, vertipads and vehicles can be accessed as [`Calendar`](calendar::Calendar)
+/// through the [`Schedule`](calendar::Schedule) trait.
+///
+/// # Examples
+/// ```
+/// use lib_common::time::{Duration, TimeZone, Utc};
+/// use svc_storage_client_grpc::prelude::*;
+///
+/// let mut vertiport = vertiport::mock::get_data_obj();
+/// vertiport.set_calendar(&Calendar {
+///     events: vec![calendar::Event {
+///         start: Utc.with_ymd_and_hms(2023, 3, 6, 18, 0, 0).unwrap(),
+///         duration: Duration::hours(14),
+///         recurrence: Some(calendar::Recurrence {
+///             frequency: calendar::Frequency::Daily,
+///             interval: 1,
+///             by_day: vec![],
+///             until: None,
+///             count: None,
+///         }),
+///     }],
+/// });
+/// assert_eq!(
+///     vertiport.schedule,
+///     Some(String::from("DTSTART:20230306T180000Z;DURATION:PT14H\nRRULE:FREQ=DAILY"))
+/// );
+///
+/// let start = Utc.with_ymd_and_hms(2023, 3, 7, 0, 0, 0).unwrap();
+/// let end = Utc.with_ymd_and_hms(2023, 3, 8, 0, 0, 0).unwrap();
+/// let available = vertiport.get_available_intervals(&(start, end)).unwrap();
+/// assert_eq!(
+///     available,
+///     vec![(start + Duration::hours(8), start + Duration::hours(18))]
+/// );
+/// ```
+pub mod calendar {
+    include!("../includes/calendar.rs");
+}
+/// Provide W3C trace context propagation through gRPC metadata
+///
+/// The trace context of the current [`tracing`] span is added to all requests made by the
+/// clients, so the server's spans will be part of the same trace.
+pub mod trace_context {
+    include!("../includes/trace_context.rs");
+}
 pub mod prelude;
 
 use prelude::*;
@@ -4,7 +4,7 @@
 #[tonic::async_trait]
 pub trait Client<T>
 where
-    Self: Sized + lib_common::grpc::Client<T> + lib_common::grpc::ClientConnect<T>,
+    Self: Sized + crate::client::Client<T>,
     T: Send + Clone,
 {
     /// The type expected for List structs.
@@ -117,6 +117,7 @@ where
     ///     let result = link_client
     ///         .unlink(Id {
     ///             id: user_id,
+    ///             include_archived: false,
     ///         })
     ///         .await;
     ///     Ok(())
@@ -151,6 +152,7 @@ where
     ///     let result = link_client
     ///         .get_linked_ids(Id {
     ///             id: user_id,
+    ///             include_archived: false,
     ///         })
     ///         .await;
     ///     Ok(())
@@ -188,6 +190,7 @@ where
     ///     let result = link_client
     ///         .get_linked(Id {
     ///             id: user_id,
+    ///             include_archived: false,
     ///         })
     ///         .await;
     ///     Ok(())
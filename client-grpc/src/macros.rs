@@ -25,14 +25,22 @@ macro_rules! grpc_client_mod {
                     ".service.rs"
                 ));
 
+                impl $crate::simple_service::SearchResultList for List {
+                    type Item = Object;
+
+                    fn into_page(self) -> (Vec<Object>, Option<String>) {
+                        (self.list, self.next_cursor)
+                    }
+                }
+
                 cfg_if::cfg_if! {
                     if #[cfg(feature = "stub_backends")] {
                         use svc_storage::grpc::server::$resource::{GrpcServer, RpcServiceServer};
-                        lib_common::grpc_mock_client!(RpcServiceClient, RpcServiceServer, GrpcServer);
+                        grpc_stub_client!(RpcServiceClient, RpcServiceServer, GrpcServer);
                     } else {
                         use tonic::async_trait;
-                        use lib_common::grpc::Client;
-                        lib_common::grpc_client!(RpcServiceClient);
+                        use $crate::client::Client;
+                        grpc_tls_client!(RpcServiceClient);
                     }
                 }
                 cfg_if::cfg_if! {
@@ -44,8 +52,12 @@ macro_rules! grpc_client_mod {
                         lazy_static! {
                             /// In memory data used for mock client implementation
                             pub static ref MEM_DATA: Mutex<Vec<Object>> = Mutex::new(Vec::new());
+                            /// In memory archived data used for mock restore implementation
+                            pub static ref ARCHIVED_DATA: Mutex<Vec<Object>> = Mutex::new(Vec::new());
                             /// In memory data used for mock link client implementation
                             pub static ref MEM_DATA_LINKS: Mutex<HashMap<String, HashMap<String, Vec<String>>>> = Mutex::new(HashMap::new());
+                            /// Broadcast channel used to send change events to all mock `watch` streams
+                            pub static ref CHANGE_SENDER: tokio::sync::broadcast::Sender<ChangeEvent> = tokio::sync::broadcast::channel(1024).0;
                         }
                     }
                 }
@@ -83,14 +95,22 @@ macro_rules! grpc_client_linked_mod {
                     ".service.rs"
                 ));
 
+                impl $crate::simple_service::SearchResultList for RowDataList {
+                    type Item = RowData;
+
+                    fn into_page(self) -> (Vec<RowData>, Option<String>) {
+                        (self.list, self.next_cursor)
+                    }
+                }
+
                 cfg_if::cfg_if! {
                     if #[cfg(feature = "stub_backends")] {
                         use svc_storage::grpc::server::$resource::{GrpcServer, RpcServiceLinkedServer};
-                        lib_common::grpc_mock_client!(RpcServiceLinkedClient, RpcServiceLinkedServer, GrpcServer);
+                        grpc_stub_client!(RpcServiceLinkedClient, RpcServiceLinkedServer, GrpcServer);
                     } else {
                         use tonic::async_trait;
-                        use lib_common::grpc::Client;
-                        lib_common::grpc_client!(RpcServiceLinkedClient);
+                        use $crate::client::Client;
+                        grpc_tls_client!(RpcServiceLinkedClient);
                     }
                 }
                 cfg_if::cfg_if! {
@@ -119,6 +139,75 @@ macro_rules! grpc_client_linked_mod {
     };
 }
 
+#[macro_export]
+/// Generates the `ClientConnect` implementation for a gRPC client,
+/// connecting with TLS if configured, see [`tls`](crate::tls)
+///
+/// Expects `async_trait` and [`Client`](crate::client::Client) to be in scope
+macro_rules! grpc_tls_client {
+    ($rpc_service_client:ident) => {
+        #[async_trait]
+        impl $crate::client::ClientConnect<$rpc_service_client<tonic::transport::Channel>>
+            for $crate::client::GrpcClient<$rpc_service_client<tonic::transport::Channel>>
+        {
+            async fn connect(
+                &self,
+            ) -> Result<$rpc_service_client<tonic::transport::Channel>, tonic::transport::Error>
+            {
+                let channel =
+                    $crate::tls::connect(self.get_address(), self.get_options().get_tls_config())
+                        .await?;
+                Ok($rpc_service_client::new(channel))
+            }
+        }
+    };
+}
+
+#[macro_export]
+/// Generates the `ClientConnect` implementation for a gRPC client,
+/// connecting to an in-process server when using the `stub_backends` feature
+macro_rules! grpc_stub_client {
+    ($rpc_service_client:ident, $rpc_service_server:ident, $grpc_server:ident) => {
+        #[tonic::async_trait]
+        impl $crate::client::ClientConnect<$rpc_service_client<tonic::transport::Channel>>
+            for $crate::client::GrpcClient<$rpc_service_client<tonic::transport::Channel>>
+        {
+            async fn connect(
+                &self,
+            ) -> Result<$rpc_service_client<tonic::transport::Channel>, tonic::transport::Error>
+            {
+                let (client, server) = tokio::io::duplex(1024);
+                let service = $rpc_service_server::new($grpc_server::default());
+                tokio::spawn(async move {
+                    tonic::transport::Server::builder()
+                        .add_service(service)
+                        .serve_with_incoming(futures::stream::iter(vec![Ok::<_, std::io::Error>(
+                            server,
+                        )]))
+                        .await
+                });
+
+                // The in-process connection can only be used once
+                let mut client = Some(client);
+                let channel = tonic::transport::Endpoint::try_from("http://[::]:50051")?
+                    .connect_with_connector(tower::service_fn(move |_: tonic::transport::Uri| {
+                        let client = client.take();
+                        async move {
+                            client.ok_or_else(|| {
+                                std::io::Error::new(
+                                    std::io::ErrorKind::Other,
+                                    "In-process connection has already been used",
+                                )
+                            })
+                        }
+                    }))
+                    .await?;
+                Ok($rpc_service_client::new(channel))
+            }
+        }
+    };
+}
+
 /// Generates Client implementation for link gRPC clients
 #[cfg(not(feature = "stub_client"))]
 #[macro_export]
@@ -136,7 +225,7 @@ macro_rules! link_grpc_client {
                 ) -> Result<tonic::Response<()>, tonic::Status> {
                     grpc_info!("{} client.", self.get_name());
                     grpc_debug!("request: {:?}", request);
-                    self.get_client().await?.link(request).await
+                    self.get_client().await?.link(self.new_request(request)).await
                 }
 
                 async fn replace_linked(
@@ -145,7 +234,7 @@ macro_rules! link_grpc_client {
                 ) -> Result<tonic::Response<()>, tonic::Status> {
                     grpc_info!("{} client.", self.get_name());
                     grpc_debug!("request: {:?}", request);
-                    self.get_client().await?.replace_linked(request).await
+                    self.get_client().await?.replace_linked(self.new_request(request)).await
                 }
 
                 async fn unlink(
@@ -154,7 +243,7 @@ macro_rules! link_grpc_client {
                 ) -> Result<tonic::Response<()>, tonic::Status> {
                     grpc_info!("{} client.", self.get_name());
                     grpc_debug!("request: {:?}", request);
-                    self.get_client().await?.unlink(request).await
+                    self.get_client().await?.unlink(self.new_request(request)).await
                 }
 
                 async fn get_linked_ids(
@@ -163,7 +252,7 @@ macro_rules! link_grpc_client {
                 ) -> Result<tonic::Response<$crate::IdList>, tonic::Status> {
                     grpc_info!("{} client.", self.get_name());
                     grpc_debug!("request: {:?}", request);
-                    self.get_client().await?.get_linked_ids(request).await
+                    self.get_client().await?.get_linked_ids(self.new_request(request)).await
                 }
 
                 async fn get_linked(
@@ -172,7 +261,7 @@ macro_rules! link_grpc_client {
                 ) -> Result<tonic::Response<Self::OtherList>, tonic::Status> {
                     grpc_info!("{} client.", self.get_name());
                     grpc_debug!("request: {:?}", request);
-                    self.get_client().await?.get_linked(request).await
+                    self.get_client().await?.get_linked(self.new_request(request)).await
                 }
 
                 async fn is_ready(
@@ -182,7 +271,7 @@ macro_rules! link_grpc_client {
                     // only show is_ready calls if log level is debug. This will be called 5times per second by the health checks.
                     grpc_debug!("{} client.", self.get_name());
                     grpc_debug!("request: {:?}", request);
-                    self.get_client().await?.is_ready(request).await
+                    self.get_client().await?.is_ready(self.new_request(request)).await
                 }
             }
         )+
@@ -413,7 +502,10 @@ macro_rules! link_grpc_client {
                                 grpc_error!("(MOCK) {}", error);
                                 return Err(tonic::Status::not_found(error));
                             }
-                            Ok(tonic::Response::new(Self::OtherList { list: other_resource_list }))
+                            Ok(tonic::Response::new(Self::OtherList {
+                                list: other_resource_list,
+                                ..Default::default()
+                            }))
                         },
                         _ => Err(tonic::Status::not_found("Not found")),
                     }
@@ -432,6 +524,177 @@ macro_rules! link_grpc_client {
     };
 }
 
+/// Generates [`Schedule`](crate::calendar::Schedule) implementations for resources with a `schedule` field
+#[macro_export]
+macro_rules! schedule_data_impl {
+    ($($resource:ident),+) => {
+        $(
+            impl $crate::calendar::Schedule for $resource::Data {
+                fn get_schedule(&self) -> Option<&str> {
+                    self.schedule.as_deref()
+                }
+                fn set_schedule(&mut self, schedule: Option<String>) {
+                    self.schedule = schedule;
+                }
+            }
+        )+
+    };
+}
+
+/// Generates Client implementation for schedule gRPC clients
+#[cfg(not(feature = "stub_client"))]
+#[macro_export]
+macro_rules! schedule_grpc_client {
+    ($($resource:ident, $rpc_schedule_client:ident),+) => {
+        $(
+            #[tonic::async_trait]
+            impl $crate::ScheduleClient<$rpc_schedule_client<Channel>> for GrpcClient<$rpc_schedule_client<Channel>> {
+                type ConflictsRequest = $resource::ConflictsRequest;
+                type List = $resource::List;
+                type AvailabilityRequest = $resource::AvailabilityRequest;
+                type AvailabilityResponse = $resource::AvailabilityResponse;
+
+                async fn get_conflicts(
+                    &self,
+                    request: Self::ConflictsRequest,
+                ) -> Result<tonic::Response<Self::List>, tonic::Status> {
+                    grpc_info!("{} client.", self.get_name());
+                    grpc_debug!("request: {:?}", request);
+                    self.get_client().await?.get_conflicts(self.new_request(request)).await
+                }
+
+                async fn get_availability(
+                    &self,
+                    request: Self::AvailabilityRequest,
+                ) -> Result<tonic::Response<Self::AvailabilityResponse>, tonic::Status> {
+                    grpc_info!("{} client.", self.get_name());
+                    grpc_debug!("request: {:?}", request);
+                    self.get_client().await?.get_availability(self.new_request(request)).await
+                }
+
+                async fn is_ready(
+                    &self,
+                    request: $crate::ReadyRequest,
+                ) -> Result<tonic::Response<$crate::ReadyResponse>, tonic::Status> {
+                    // only show is_ready calls if log level is debug. This will be called 5times per second by the health checks.
+                    grpc_debug!("{} client.", self.get_name());
+                    grpc_debug!("request: {:?}", request);
+                    self.get_client().await?.is_ready(self.new_request(request)).await
+                }
+            }
+        )+
+    };
+}
+
+/// Generates Client implementation for schedule gRPC MOCK clients
+#[cfg(feature = "stub_client")]
+#[macro_export]
+macro_rules! schedule_grpc_client {
+    ($($resource:ident, $rpc_schedule_client:ident),+) => {
+        $(
+            #[tonic::async_trait]
+            impl $crate::ScheduleClient<$rpc_schedule_client<Channel>> for GrpcClient<$rpc_schedule_client<Channel>> {
+                type ConflictsRequest = $resource::ConflictsRequest;
+                type List = $resource::List;
+                type AvailabilityRequest = $resource::AvailabilityRequest;
+                type AvailabilityResponse = $resource::AvailabilityResponse;
+
+                async fn get_conflicts(
+                    &self,
+                    request: Self::ConflictsRequest,
+                ) -> Result<tonic::Response<Self::List>, tonic::Status> {
+                    grpc_warn!("(MOCK) {} client.", self.get_name());
+                    grpc_debug!("(MOCK) request: {:?}", request);
+
+                    let (start, end): (lib_common::time::DateTime<lib_common::time::Utc>, lib_common::time::DateTime<lib_common::time::Utc>) =
+                        match (request.timeslot_start, request.timeslot_end) {
+                            (Some(start), Some(end)) => (start.into(), end.into()),
+                            _ => {
+                                return Err(tonic::Status::invalid_argument(
+                                    "Both timeslot_start and timeslot_end should be provided.",
+                                ))
+                            }
+                        };
+                    let overlaps = |pad_id: &str, slot_start: &Option<lib_common::time::Timestamp>, slot_end: &Option<lib_common::time::Timestamp>| {
+                        match (slot_start, slot_end) {
+                            (Some(slot_start), Some(slot_end)) => {
+                                pad_id == request.vertipad_id
+                                    && lib_common::time::DateTime::<lib_common::time::Utc>::from(slot_start.clone()) < end
+                                    && lib_common::time::DateTime::<lib_common::time::Utc>::from(slot_end.clone()) > start
+                            }
+                            _ => false,
+                        }
+                    };
+
+                    let mut resource_list = $resource::MEM_DATA.lock().await.clone();
+                    resource_list.retain(|object| {
+                        if Some(&object.id) == request.exclude_id.as_ref() || object.deleted_at.is_some() {
+                            return false;
+                        }
+                        match &object.data {
+                            Some(data) => {
+                                data.flight_status != $resource::FlightStatus::Cancelled as i32
+                                    && (overlaps(&data.origin_vertipad_id, &data.origin_timeslot_start, &data.origin_timeslot_end)
+                                        || overlaps(&data.target_vertipad_id, &data.target_timeslot_start, &data.target_timeslot_end))
+                            }
+                            None => false,
+                        }
+                    });
+                    Ok(tonic::Response::new(Self::List {
+                        list: resource_list,
+                        ..Default::default()
+                    }))
+                }
+
+                async fn get_availability(
+                    &self,
+                    request: Self::AvailabilityRequest,
+                ) -> Result<tonic::Response<Self::AvailabilityResponse>, tonic::Status> {
+                    grpc_warn!("(MOCK) {} client.", self.get_name());
+                    grpc_debug!("(MOCK) request: {:?}", request);
+
+                    // Schedules and reservations are not taken into account by the mock, the
+                    // requested vertipad is always available during the full time range.
+                    let (start, end) = match (request.time_start, request.time_end) {
+                        (Some(start), Some(end)) => (start, end),
+                        _ => {
+                            return Err(tonic::Status::invalid_argument(
+                                "Both time_start and time_end should be provided.",
+                            ))
+                        }
+                    };
+                    let list = match (request.vertipad_id, request.vertiport_id) {
+                        (Some(vertipad_id), None) => vec![$resource::VertipadAvailability {
+                            vertipad_id,
+                            vertiport_id: String::from(""),
+                            free: vec![$resource::TimeInterval {
+                                start: Some(start),
+                                end: Some(end),
+                            }],
+                        }],
+                        (None, Some(_)) => vec![],
+                        _ => {
+                            return Err(tonic::Status::invalid_argument(
+                                "Either a vertipad_id or a vertiport_id should be provided.",
+                            ))
+                        }
+                    };
+                    Ok(tonic::Response::new(Self::AvailabilityResponse { list }))
+                }
+
+                async fn is_ready(
+                    &self,
+                    request: $crate::ReadyRequest,
+                ) -> Result<tonic::Response<$crate::ReadyResponse>, tonic::Status> {
+                    grpc_warn!("(MOCK) {} client.", self.get_name());
+                    grpc_debug!("(MOCK) request: {:?}", request);
+                    Ok(tonic::Response::new($crate::ReadyResponse { ready: true }))
+                }
+            }
+        )+
+    };
+}
+
 /// Generates Client implementation for simple gRPC clients
 #[cfg(not(feature = "stub_client"))]
 #[macro_export]
@@ -445,6 +708,8 @@ macro_rules! simple_grpc_client {
                 type UpdateObject = $resource::UpdateObject;
                 type List = $resource::List;
                 type Response = $resource::Response;
+                type InsertBatchRequest = $resource::InsertBatchRequest;
+                type ChangeEvent = $resource::ChangeEvent;
 
                 async fn get_by_id(
                     &self,
@@ -452,7 +717,7 @@ macro_rules! simple_grpc_client {
                 ) -> Result<tonic::Response<Self::Object>, tonic::Status> {
                     grpc_info!("{} client.", self.get_name());
                     grpc_debug!("request: {:?}", request);
-                    self.get_client().await?.get_by_id(request).await
+                    self.get_client().await?.get_by_id(self.new_request(request)).await
                 }
 
                 async fn search(
@@ -461,7 +726,27 @@ macro_rules! simple_grpc_client {
                 ) -> Result<tonic::Response<Self::List>, tonic::Status> {
                     grpc_info!("{} client.", self.get_name());
                     grpc_debug!("request: {:?}", request);
-                    self.get_client().await?.search(request).await
+                    self.get_client().await?.search(self.new_request(request)).await
+                }
+
+                async fn search_stream(
+                    &self,
+                    request: $crate::AdvancedSearchFilter,
+                ) -> Result<tonic::Response<futures::stream::BoxStream<'static, Result<Self::Object, tonic::Status>>>, tonic::Status> {
+                    grpc_info!("{} client.", self.get_name());
+                    grpc_debug!("request: {:?}", request);
+                    let response = self.get_client().await?.search_stream(self.new_request(request)).await?;
+                    Ok(response.map(futures::StreamExt::boxed))
+                }
+
+                async fn watch(
+                    &self,
+                    request: $crate::AdvancedSearchFilter,
+                ) -> Result<tonic::Response<futures::stream::BoxStream<'static, Result<Self::ChangeEvent, tonic::Status>>>, tonic::Status> {
+                    grpc_info!("{} client.", self.get_name());
+                    grpc_debug!("request: {:?}", request);
+                    let response = self.get_client().await?.watch(self.new_request(request)).await?;
+                    Ok(response.map(futures::StreamExt::boxed))
                 }
 
                 async fn insert(
@@ -470,7 +755,16 @@ macro_rules! simple_grpc_client {
                 ) -> Result<tonic::Response<Self::Response>, tonic::Status> {
                     grpc_info!("{} client.", self.get_name());
                     grpc_debug!("request: {:?}", request);
-                    self.get_client().await?.insert(request).await
+                    self.get_client().await?.insert(self.new_request(request)).await
+                }
+
+                async fn insert_batch(
+                    &self,
+                    request: Self::InsertBatchRequest,
+                ) -> Result<tonic::Response<$crate::InsertBatchResponse>, tonic::Status> {
+                    grpc_info!("{} client.", self.get_name());
+                    grpc_debug!("request: {:?}", request);
+                    self.get_client().await?.insert_batch(self.new_request(request)).await
                 }
 
                 async fn update(
@@ -479,16 +773,29 @@ macro_rules! simple_grpc_client {
                 ) -> Result<tonic::Response<Self::Response>, tonic::Status> {
                     grpc_info!("{} client.", self.get_name());
                     grpc_debug!("request: {:?}", request);
-                    self.get_client().await?.update(request).await
+                    self.get_client().await?.update(self.new_request(request)).await
                 }
 
-                async fn delete(
+                async fn delete<R>(
+                    &self,
+                    request: R,
+                ) -> Result<tonic::Response<()>, tonic::Status>
+                where
+                    R: Into<$crate::DeleteRequest> + Send + 'static,
+                {
+                    let request: $crate::DeleteRequest = request.into();
+                    grpc_info!("{} client.", self.get_name());
+                    grpc_debug!("request: {:?}", request);
+                    self.get_client().await?.delete(self.new_request(request)).await
+                }
+
+                async fn restore(
                     &self,
                     request: $crate::Id,
-                ) -> Result<tonic::Response<()>, tonic::Status> {
+                ) -> Result<tonic::Response<Self::Object>, tonic::Status> {
                     grpc_info!("{} client.", self.get_name());
                     grpc_debug!("request: {:?}", request);
-                    self.get_client().await?.delete(request).await
+                    self.get_client().await?.restore(self.new_request(request)).await
                 }
 
                 async fn is_ready(
@@ -498,7 +805,7 @@ macro_rules! simple_grpc_client {
                     // only show is_ready calls if log level is debug. This will be called 5times per second by the health checks.
                     grpc_debug!("{} client.", self.get_name());
                     grpc_debug!("request: {:?}", request);
-                    self.get_client().await?.is_ready(request).await
+                    self.get_client().await?.is_ready(self.new_request(request)).await
                 }
             }
         )+
@@ -518,6 +825,8 @@ macro_rules! simple_grpc_client {
                 type UpdateObject = $resource::UpdateObject;
                 type List = $resource::List;
                 type Response = $resource::Response;
+                type InsertBatchRequest = $resource::InsertBatchRequest;
+                type ChangeEvent = $resource::ChangeEvent;
 
                 async fn get_by_id(
                     &self,
@@ -553,7 +862,10 @@ macro_rules! simple_grpc_client {
                     if filters.len() == 0 {
                         grpc_debug!("(MOCK) no filters provided, returning all.");
                         return Ok(tonic::Response::new(Self::List {
-                            list
+                            total_count: Some(list.len() as i64),
+                            list,
+                            has_more: false,
+                            next_cursor: None,
                         }));
                     }
 
@@ -563,44 +875,8 @@ macro_rules! simple_grpc_client {
                     }
                     grpc_debug!("(MOCK) unfiltered serialized objects: {:?}", unfiltered);
 
-                    let mut collected: Vec<serde_json::Value> = vec![];
-                    for filter in filters {
-                        let operator: PredicateOperator =
-                            match PredicateOperator::try_from(filter.predicate_operator) {
-                                Ok(val) => val,
-                                Err(e) => {
-                                    return Err(tonic::Status::internal(format!(
-                                        "Can't convert i32 [{}] into PredicateOperator Enum value: {}",
-                                        filter.predicate_operator, e
-                                    )));
-                                }
-                            };
-
-                        match filter.comparison_operator {
-                            Some(comparison_operator) => match ComparisonOperator::try_from(comparison_operator) {
-                                Ok(comparison_operator) => match comparison_operator {
-                                    ComparisonOperator::And => {
-                                        let unfiltered = collected.clone();
-                                        collected = vec![];
-                                        $crate::search::filter_for_operator(&filter.search_field, &filter.search_value, &unfiltered, &mut collected, operator)
-                                            .map_err(|e| tonic::Status::internal(format!("Could not get filtered values for provided filter: {}", e)))?
-                                    }
-                                    ComparisonOperator::Or => {
-                                        $crate::search::filter_for_operator(&filter.search_field, &filter.search_value, &unfiltered, &mut collected, operator)
-                                            .map_err(|e| tonic::Status::internal(format!("Could not get filtered values for provided filter: {}", e)))?
-                                    }
-                                }
-                                Err(e) => {
-                                    return Err(tonic::Status::internal(format!(
-                                        "Can't convert i32 [{}] into ComparisonOperator Enum value: {}",
-                                        comparison_operator, e
-                                    )));
-                                }
-                            },
-                            None => $crate::search::filter_for_operator(&filter.search_field, &filter.search_value, &unfiltered, &mut collected, operator)
-                                .map_err(|e| tonic::Status::internal(format!("Could not get filtered values for provided filter: {}", e)))?
-                        };
-                    }
+                    let collected: Vec<serde_json::Value> = $crate::search::filter_for_options(&filters, &unfiltered)
+                        .map_err(|e| tonic::Status::internal(format!("Could not get filtered values for provided filter: {}", e)))?;
                     let mut filtered: Vec<Self::Object> = vec![];
                     for val in collected.iter() {
                         filtered.push(
@@ -609,11 +885,73 @@ macro_rules! simple_grpc_client {
                         );
                     }
                     let response = Self::List {
-                        list: filtered
+                        total_count: Some(filtered.len() as i64),
+                        list: filtered,
+                        has_more: false,
+                        next_cursor: None,
                     };
                     Ok(tonic::Response::new(response))
                 }
 
+                async fn search_stream(
+                    &self,
+                    request: $crate::AdvancedSearchFilter,
+                ) -> Result<tonic::Response<futures::stream::BoxStream<'static, Result<Self::Object, tonic::Status>>>, tonic::Status> {
+                    grpc_warn!("(MOCK) {} client.", self.get_name());
+                    let list = <Self as $crate::SimpleClient<$resource::RpcServiceClient<Channel>>>::search(self, request).await?.into_inner().list;
+                    Ok(tonic::Response::new(futures::StreamExt::boxed(futures::stream::iter(list.into_iter().map(Ok)))))
+                }
+
+                async fn watch(
+                    &self,
+                    request: $crate::AdvancedSearchFilter,
+                ) -> Result<tonic::Response<futures::stream::BoxStream<'static, Result<Self::ChangeEvent, tonic::Status>>>, tonic::Status> {
+                    grpc_warn!("(MOCK) {} client.", self.get_name());
+                    grpc_debug!("(MOCK) request: {:?}", request);
+                    let filters = request.filters;
+                    let receiver = $resource::CHANGE_SENDER.subscribe();
+                    let stream = futures::stream::unfold(Some(receiver), move |receiver| {
+                        let filters = filters.clone();
+                        async move {
+                            let mut receiver = receiver?;
+                            loop {
+                                let event: $resource::ChangeEvent = match receiver.recv().await {
+                                    Ok(event) => event,
+                                    Err(tokio::sync::broadcast::error::RecvError::Lagged(count)) => {
+                                        let error = format!("Watch stream could not keep up, [{}] change events have been dropped", count);
+                                        return Some((Err(tonic::Status::data_loss(error)), None));
+                                    }
+                                    Err(tokio::sync::broadcast::error::RecvError::Closed) => return None,
+                                };
+                                if filters.len() == 0 {
+                                    return Some((Ok(event), Some(receiver)));
+                                }
+
+                                let object = match &event.object {
+                                    Some(object) => object,
+                                    None => continue,
+                                };
+                                let value = match serde_json::to_value(object) {
+                                    Ok(value) => value,
+                                    Err(e) => {
+                                        let error = format!("Could not convert [{:?}] to json value: {}", object, e);
+                                        return Some((Err(tonic::Status::internal(error)), None));
+                                    }
+                                };
+                                match $crate::search::filter_for_options(&filters, &vec![value]) {
+                                    Ok(collected) if collected.len() > 0 => return Some((Ok(event), Some(receiver))),
+                                    Ok(_) => continue,
+                                    Err(e) => {
+                                        let error = format!("Could not get filtered values for provided filter: {}", e);
+                                        return Some((Err(tonic::Status::internal(error)), None));
+                                    }
+                                }
+                            }
+                        }
+                    });
+                    Ok(tonic::Response::new(futures::StreamExt::boxed(stream)))
+                }
+
                 async fn insert(
                     &self,
                     request: Self::Data,
@@ -624,6 +962,7 @@ macro_rules! simple_grpc_client {
                     let object = Self::Object {
                         id: lib_common::uuid::Uuid::new_v4().to_string(),
                         data: Some(request),
+                        deleted_at: None,
                     };
                     let response = Self::Response {
                         object: Some(object.clone()),
@@ -633,9 +972,48 @@ macro_rules! simple_grpc_client {
                         })
                     };
                     mem_data.push(object.clone());
+                    let _ = $resource::CHANGE_SENDER.send(Self::ChangeEvent {
+                        change_type: $crate::ChangeType::Insert as i32,
+                        id: object.id.clone(),
+                        object: Some(object),
+                    });
                     Ok(tonic::Response::new(response))
                 }
 
+                async fn insert_batch(
+                    &self,
+                    request: Self::InsertBatchRequest,
+                ) -> Result<tonic::Response<$crate::InsertBatchResponse>, tonic::Status> {
+                    grpc_warn!("(MOCK) {} client.", self.get_name());
+                    grpc_debug!("(MOCK) request: {:?}", request);
+                    let mut mem_data = $resource::MEM_DATA.lock().await;
+                    let mut results = vec![];
+                    for data in request.list {
+                        let object = Self::Object {
+                            id: lib_common::uuid::Uuid::new_v4().to_string(),
+                            data: Some(data),
+                            deleted_at: None,
+                        };
+                        results.push(super::InsertBatchResult {
+                            id: Some(object.id.clone()),
+                            validation_result: Some(super::ValidationResult {
+                                success: true,
+                                errors: Vec::new()
+                            })
+                        });
+                        mem_data.push(object.clone());
+                        let _ = $resource::CHANGE_SENDER.send(Self::ChangeEvent {
+                            change_type: $crate::ChangeType::Insert as i32,
+                            id: object.id.clone(),
+                            object: Some(object),
+                        });
+                    }
+                    Ok(tonic::Response::new(super::InsertBatchResponse {
+                        inserted_count: results.len() as i32,
+                        results,
+                    }))
+                }
+
                 async fn update(
                     &self,
                     request: Self::UpdateObject,
@@ -651,6 +1029,11 @@ macro_rules! simple_grpc_client {
                                     ..request.data.clone().ok_or(tonic::Status::invalid_argument("No update data given."))?
                                 }
                             );
+                            let _ = $resource::CHANGE_SENDER.send(Self::ChangeEvent {
+                                change_type: $crate::ChangeType::Update as i32,
+                                id: object.id.clone(),
+                                object: Some(object.clone()),
+                            });
 
                             let response = Self::Response {
                                 object: Some(object.clone()),
@@ -666,18 +1049,97 @@ macro_rules! simple_grpc_client {
                     Err(tonic::Status::not_found("Not found"))
                 }
 
-                async fn delete(
+                async fn delete<R>(
                     &self,
-                    request: $crate::Id,
-                ) -> Result<tonic::Response<()>, tonic::Status> {
+                    request: R,
+                ) -> Result<tonic::Response<()>, tonic::Status>
+                where
+                    R: Into<$crate::DeleteRequest> + Send + 'static,
+                {
+                    let request: $crate::DeleteRequest = request.into();
                     grpc_warn!("(MOCK) {} client.", self.get_name());
                     grpc_debug!("(MOCK) request: {:?}", request);
                     let id = request.id;
                     let mut list = $resource::MEM_DATA.lock().await;
+                    if let Some(object) = list.iter().find(|object| object.id == id) {
+                        let _ = $resource::CHANGE_SENDER.send(Self::ChangeEvent {
+                            change_type: $crate::ChangeType::Delete as i32,
+                            id: id.clone(),
+                            object: Some(object.clone()),
+                        });
+                        // Keep archived records around so they can be restored
+                        if $crate::resources::is_mock_archivable(stringify!($resource)) {
+                            $resource::ARCHIVED_DATA.lock().await.push(object.clone());
+                        }
+                    }
                     list.retain(|object| object.id != id);
                     Ok(tonic::Response::new(()))
                 }
 
+                async fn restore(
+                    &self,
+                    request: $crate::Id,
+                ) -> Result<tonic::Response<Self::Object>, tonic::Status> {
+                    grpc_warn!("(MOCK) {} client.", self.get_name());
+                    grpc_debug!("(MOCK) request: {:?}", request);
+                    let id = request.id;
+
+                    if !$crate::resources::is_mock_archivable(stringify!($resource)) {
+                        let error = format!(
+                            "Restore is not supported for [{}], resource can not be archived.",
+                            stringify!($resource)
+                        );
+                        grpc_info!("(MOCK) {}", error);
+                        return Err(tonic::Status::unimplemented(error));
+                    }
+
+                    let mut list = $resource::MEM_DATA.lock().await;
+                    if list.iter().any(|object| object.id == id) {
+                        let error = format!(
+                            "Restore refused for [{}] with id [{}], record is not archived.",
+                            stringify!($resource),
+                            id
+                        );
+                        grpc_info!("(MOCK) {}", error);
+                        return Err(tonic::Status::failed_precondition(error));
+                    }
+
+                    let mut archived = $resource::ARCHIVED_DATA.lock().await;
+                    let index = match archived.iter().position(|object| object.id == id) {
+                        Some(index) => index,
+                        None => {
+                            grpc_error!("(MOCK) No [{}] found for specified uuid [{}].", stringify!($resource), id);
+                            return Err(tonic::Status::not_found("Could not find any resource for the provided id"));
+                        }
+                    };
+
+                    let mut others: Vec<serde_json::Value> = vec![];
+                    for val in list.iter() {
+                        others.push(serde_json::to_value(val).map_err(|e| tonic::Status::internal(format!("Could not convert [{:?}] to json value: {}", val, e)))?);
+                    }
+                    let restored = serde_json::to_value(&archived[index])
+                        .map_err(|e| tonic::Status::internal(format!("Could not convert [{:?}] to json value: {}", archived[index], e)))?;
+                    let unique_fields = $crate::resources::get_mock_unique_fields(stringify!($resource));
+                    if let Some(fields) = $crate::search::find_unique_violation(&restored, &others, &unique_fields) {
+                        let error = format!(
+                            "Restore refused for [{}], a record with the same [{}] value(s) already exists.",
+                            stringify!($resource),
+                            fields
+                        );
+                        grpc_info!("(MOCK) {}", error);
+                        return Err(tonic::Status::already_exists(error));
+                    }
+
+                    let object = archived.remove(index);
+                    list.push(object.clone());
+                    let _ = $resource::CHANGE_SENDER.send(Self::ChangeEvent {
+                        change_type: $crate::ChangeType::Update as i32,
+                        id: id.clone(),
+                        object: Some(object.clone()),
+                    });
+                    Ok(tonic::Response::new(object))
+                }
+
                 async fn is_ready(
                     &self,
                     request: $crate::ReadyRequest,
@@ -713,7 +1175,7 @@ macro_rules! simple_linked_grpc_client {
                 ) -> Result<tonic::Response<()>, tonic::Status> {
                     grpc_warn!("{} client.", self.get_name());
                     grpc_debug!("request: {:?}", request);
-                    self.get_client().await?.unlink(request).await
+                    self.get_client().await?.unlink(self.new_request(request)).await
                 }
 
                 async fn get_linked_ids(
@@ -722,7 +1184,7 @@ macro_rules! simple_linked_grpc_client {
                 ) -> Result<tonic::Response<$crate::IdList>, tonic::Status> {
                     grpc_warn!("{} client.", self.get_name());
                     grpc_debug!("request: {:?}", request);
-                    self.get_client().await?.get_linked_ids(request).await
+                    self.get_client().await?.get_linked_ids(self.new_request(request)).await
                 }
 
                 async fn get_linked(
@@ -731,7 +1193,7 @@ macro_rules! simple_linked_grpc_client {
                 ) -> Result<tonic::Response<Self::OtherList>, tonic::Status> {
                     grpc_warn!("{} client.", self.get_name());
                     grpc_debug!("request: {:?}", request);
-                    self.get_client().await?.get_linked(request).await
+                    self.get_client().await?.get_linked(self.new_request(request)).await
                 }
 
                 async fn get_by_id(
@@ -740,7 +1202,7 @@ macro_rules! simple_linked_grpc_client {
                 ) -> Result<tonic::Response<Self::LinkedObject>, tonic::Status> {
                     grpc_info!("{} client.", self.get_name());
                     grpc_debug!("request: {:?}", request);
-                    self.get_client().await?.get_by_id(request).await
+                    self.get_client().await?.get_by_id(self.new_request(request)).await
                 }
 
                 async fn search(
@@ -749,7 +1211,17 @@ macro_rules! simple_linked_grpc_client {
                 ) -> Result<tonic::Response<Self::LinkedRowDataList>, tonic::Status> {
                     grpc_info!("{} client.", self.get_name());
                     grpc_debug!("request: {:?}", request);
-                    self.get_client().await?.search(request).await
+                    self.get_client().await?.search(self.new_request(request)).await
+                }
+
+                async fn search_stream(
+                    &self,
+                    request: $crate::AdvancedSearchFilter,
+                ) -> Result<tonic::Response<futures::stream::BoxStream<'static, Result<Self::LinkedRowData, tonic::Status>>>, tonic::Status> {
+                    grpc_info!("{} client.", self.get_name());
+                    grpc_debug!("request: {:?}", request);
+                    let response = self.get_client().await?.search_stream(self.new_request(request)).await?;
+                    Ok(response.map(futures::StreamExt::boxed))
                 }
 
                 async fn insert(
@@ -758,7 +1230,7 @@ macro_rules! simple_linked_grpc_client {
                 ) -> Result<tonic::Response<Self::LinkedResponse>, tonic::Status> {
                     grpc_info!("{} client.", self.get_name());
                     grpc_debug!("request: {:?}", request);
-                    self.get_client().await?.insert(request).await
+                    self.get_client().await?.insert(self.new_request(request)).await
                 }
 
                 async fn update(
@@ -767,16 +1239,20 @@ macro_rules! simple_linked_grpc_client {
                 ) -> Result<tonic::Response<Self::LinkedResponse>, tonic::Status> {
                     grpc_info!("{} client.", self.get_name());
                     grpc_debug!("request: {:?}", request);
-                    self.get_client().await?.update(request).await
+                    self.get_client().await?.update(self.new_request(request)).await
                 }
 
-                async fn delete(
+                async fn delete<R>(
                     &self,
-                    request: $crate::Ids,
-                ) -> Result<tonic::Response<()>, tonic::Status> {
+                    request: R,
+                ) -> Result<tonic::Response<()>, tonic::Status>
+                where
+                    R: Into<$crate::LinkedDeleteRequest> + Send + 'static,
+                {
+                    let request: $crate::LinkedDeleteRequest = request.into();
                     grpc_info!("{} client.", self.get_name());
                     grpc_debug!("request: {:?}", request);
-                    self.get_client().await?.delete(request).await
+                    self.get_client().await?.delete(self.new_request(request)).await
                 }
 
                 async fn is_ready(
@@ -786,7 +1262,7 @@ macro_rules! simple_linked_grpc_client {
                     // only show is_ready calls if log level is debug. This will be called 5times per second by the health checks.
                     grpc_debug!("{} client.", self.get_name());
                     grpc_debug!("request: {:?}", request);
-                    self.get_client().await?.is_ready(request).await
+                    self.get_client().await?.is_ready(self.new_request(request)).await
                 }
             }
         )+
@@ -879,7 +1355,10 @@ macro_rules! simple_linked_grpc_client {
                         grpc_error!("(MOCK) {}", error);
                         return Err(tonic::Status::not_found(error));
                     }
-                    Ok(tonic::Response::new(Self::OtherList { list: other_resource_list }))
+                    Ok(tonic::Response::new(Self::OtherList {
+                        list: other_resource_list,
+                        ..Default::default()
+                    }))
                 }
 
                 async fn get_by_id(
@@ -961,7 +1440,10 @@ macro_rules! simple_linked_grpc_client {
                     if filters.len() == 0 {
                         grpc_debug!("(MOCK) no filters provided, returning all.");
                         return Ok(tonic::Response::new(Self::LinkedRowDataList {
-                            list
+                            total_count: Some(list.len() as i64),
+                            list,
+                            has_more: false,
+                            next_cursor: None,
                         }));
                     }
 
@@ -999,44 +1481,8 @@ macro_rules! simple_linked_grpc_client {
                     }
                     grpc_debug!("(MOCK) unfiltered serialized objects: {:?}", unfiltered);
 
-                    let mut collected: Vec<serde_json::Value> = vec![];
-                    for filter in filters {
-                        let operator: PredicateOperator =
-                            match PredicateOperator::try_from(filter.predicate_operator) {
-                                Ok(val) => val,
-                                Err(e) => {
-                                    return Err(tonic::Status::internal(format!(
-                                        "Can't convert i32 [{}] into PredicateOperator Enum value: {}",
-                                        filter.predicate_operator, e
-                                    )));
-                                }
-                            };
-
-                        match filter.comparison_operator {
-                            Some(comparison_operator) => match ComparisonOperator::try_from(comparison_operator) {
-                                Ok(comparison_operator) => match comparison_operator {
-                                    ComparisonOperator::And => {
-                                        let unfiltered = collected.clone();
-                                        collected = vec![];
-                                        $crate::search::filter_for_operator(&filter.search_field, &filter.search_value, &unfiltered, &mut collected, operator)
-                                            .map_err(|e| tonic::Status::internal(format!("Could not get filtered values for provided filter: {}", e)))?
-                                    }
-                                    ComparisonOperator::Or => {
-                                        $crate::search::filter_for_operator(&filter.search_field, &filter.search_value, &unfiltered, &mut collected, operator)
-                                            .map_err(|e| tonic::Status::internal(format!("Could not get filtered values for provided filter: {}", e)))?
-                                    }
-                                }
-                                Err(e) => {
-                                    return Err(tonic::Status::internal(format!(
-                                        "Can't convert i32 [{}] into ComparisonOperator Enum value: {}",
-                                        comparison_operator, e
-                                    )));
-                                }
-                            },
-                            None => $crate::search::filter_for_operator(&filter.search_field, &filter.search_value, &unfiltered, &mut collected, operator)
-                                .map_err(|e| tonic::Status::internal(format!("Could not get filtered values for provided filter: {}", e)))?
-                        };
-                    }
+                    let collected: Vec<serde_json::Value> = $crate::search::filter_for_options(&filters, &unfiltered)
+                        .map_err(|e| tonic::Status::internal(format!("Could not get filtered values for provided filter: {}", e)))?;
 
                     let mut filtered: Vec<Self::LinkedRowData> = vec![];
                     for val in collected.iter() {
@@ -1058,11 +1504,23 @@ macro_rules! simple_linked_grpc_client {
                         filtered.push(row_data);
                     }
                     let response = Self::LinkedRowDataList {
-                        list: filtered
+                        total_count: Some(filtered.len() as i64),
+                        list: filtered,
+                        has_more: false,
+                        next_cursor: None,
                     };
                     Ok(tonic::Response::new(response))
                 }
 
+                async fn search_stream(
+                    &self,
+                    request: $crate::AdvancedSearchFilter,
+                ) -> Result<tonic::Response<futures::stream::BoxStream<'static, Result<Self::LinkedRowData, tonic::Status>>>, tonic::Status> {
+                    grpc_warn!("(MOCK) {} client.", self.get_name());
+                    let list = <Self as $crate::SimpleLinkedClient<$linked_resource::RpcServiceLinkedClient<Channel>>>::search(self, request).await?.into_inner().list;
+                    Ok(tonic::Response::new(futures::StreamExt::boxed(futures::stream::iter(list.into_iter().map(Ok)))))
+                }
+
                 async fn insert(
                     &self,
                     request: Self::LinkedRowData,
@@ -1166,10 +1624,14 @@ macro_rules! simple_linked_grpc_client {
                     return Ok(tonic::Response::new(response));
                 }
 
-                async fn delete(
+                async fn delete<R>(
                     &self,
-                    request: $crate::Ids,
-                ) -> Result<tonic::Response<()>, tonic::Status> {
+                    request: R,
+                ) -> Result<tonic::Response<()>, tonic::Status>
+                where
+                    R: Into<$crate::LinkedDeleteRequest> + Send + 'static,
+                {
+                    let request: $crate::LinkedDeleteRequest = request.into();
                     grpc_warn!("(MOCK) {} client.", self.get_name());
                     grpc_debug!("(MOCK) request: {:?}", request);
                     let ids = request.ids;
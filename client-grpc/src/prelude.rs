@@ -4,13 +4,18 @@ pub use crate::resources::geo_types::*;
 pub use crate::resources::*;
 pub use crate::Clients;
 
+pub use crate::calendar;
+pub use calendar::{Calendar, Schedule};
+
 pub use crate::link_service;
+pub use crate::schedule_service;
 pub use crate::simple_service;
 pub use crate::simple_service_linked;
 pub use link_service::Client as LinkClient;
+pub use schedule_service::Client as ScheduleClient;
 pub use simple_service::Client as SimpleClient;
 pub use simple_service_linked::Client as SimpleLinkedClient;
 
-pub use lib_common::grpc::Client;
+pub use crate::client::{Client, ClientOptions};
 pub use lib_common::time::Timestamp;
 pub use prost_types::FieldMask;
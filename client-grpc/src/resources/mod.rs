@@ -7,12 +7,48 @@ use crate::{Deserialize, IntoParams, Serialize, ToSchema};
 
 pub mod geo_types;
 
+impl From<Id> for DeleteRequest {
+    fn from(id: Id) -> Self {
+        Self {
+            id: id.id,
+            expected_updated_at: None,
+        }
+    }
+}
+
+impl From<Ids> for LinkedDeleteRequest {
+    fn from(ids: Ids) -> Self {
+        Self {
+            ids: ids.ids,
+            expected_updated_at: None,
+        }
+    }
+}
+
+/// Returns `true` if records of the provided resource are archived when deleted, mirroring the
+/// server's resource definitions for the mock clients
+#[cfg(feature = "stub_client")]
+pub(crate) fn is_mock_archivable(resource: &str) -> bool {
+    !matches!(resource, "adsb" | "itinerary")
+}
+
+/// Returns the unique fields of the provided resource, mirroring the server's resource
+/// definitions for the mock clients
+#[cfg(feature = "stub_client")]
+pub(crate) fn get_mock_unique_fields(resource: &str) -> Vec<Vec<String>> {
+    match resource {
+        "user" => vec![vec![String::from("email")]],
+        "vehicle" => vec![vec![String::from("registration_number")]],
+        _ => vec![],
+    }
+}
+
 cfg_if::cfg_if! {
     if #[cfg(any(feature = "all_resources", feature = "any_resource"))] {
         use tonic::transport::Channel;
 
         use super::*;
-        use lib_common::grpc::{Client, GrpcClient};
+        use crate::client::{Client, ClientOptions, GrpcClient};
 
         #[cfg(not(feature = "stub_backends"))]
         use tonic::async_trait;
@@ -22,7 +58,7 @@ cfg_if::cfg_if! {
                 use std::str::FromStr;
                 use std::collections::HashMap;
             } else {
-                use lib_common::grpc::ClientConnect;
+                use crate::client::ClientConnect;
             }
         }
 
@@ -41,6 +77,21 @@ cfg_if::cfg_if! {
                 simple_grpc_client!(flight_plan);
                 /// GrpcClient implementation for flight_plan RpcServiceClient
                 pub type FlightPlanClient = GrpcClient<flight_plan::RpcServiceClient<Channel>>;
+
+                /// GrpcClient implementation for flight_plan RpcScheduleClient
+                pub type FlightPlanScheduleClient = GrpcClient<flight_plan::rpc_schedule_client::RpcScheduleClient<Channel>>;
+                use flight_plan::rpc_schedule_client::RpcScheduleClient as FlightPlanRpcScheduleClient;
+
+                cfg_if::cfg_if! {
+                    if #[cfg(feature = "stub_backends")] {
+                        use svc_storage::grpc::server::flight_plan_schedule::{RpcScheduleServer, GrpcServer as FlightPlanScheduleGrpcServer};
+                        grpc_stub_client!(FlightPlanRpcScheduleClient, RpcScheduleServer, FlightPlanScheduleGrpcServer);
+                    } else {
+                        grpc_tls_client!(FlightPlanRpcScheduleClient);
+                    }
+                }
+
+                schedule_grpc_client!(flight_plan, FlightPlanRpcScheduleClient);
             }
         }
 
@@ -82,15 +133,15 @@ cfg_if::cfg_if! {
                         use svc_storage::grpc::server::group_vehicle::{RpcVehicleLinkServer, GrpcServer as GroupVehicleGrpcServer};
                         use svc_storage::grpc::server::group_vertipad::{RpcVertipadLinkServer, GrpcServer as GroupVertipadGrpcServer};
                         use svc_storage::grpc::server::group_vertiport::{RpcVertiportLinkServer, GrpcServer as GroupVertiportGrpcServer};
-                        lib_common::grpc_mock_client!(GroupRpcUserLinkClient, RpcUserLinkServer, GroupUserGrpcServer);
-                        lib_common::grpc_mock_client!(GroupRpcVehicleLinkClient, RpcVehicleLinkServer, GroupVehicleGrpcServer);
-                        lib_common::grpc_mock_client!(GroupRpcVertipadLinkClient, RpcVertipadLinkServer, GroupVertipadGrpcServer);
-                        lib_common::grpc_mock_client!(GroupRpcVertiportLinkClient, RpcVertiportLinkServer, GroupVertiportGrpcServer);
+                        grpc_stub_client!(GroupRpcUserLinkClient, RpcUserLinkServer, GroupUserGrpcServer);
+                        grpc_stub_client!(GroupRpcVehicleLinkClient, RpcVehicleLinkServer, GroupVehicleGrpcServer);
+                        grpc_stub_client!(GroupRpcVertipadLinkClient, RpcVertipadLinkServer, GroupVertipadGrpcServer);
+                        grpc_stub_client!(GroupRpcVertiportLinkClient, RpcVertiportLinkServer, GroupVertiportGrpcServer);
                     } else {
-                        lib_common::grpc_client!(GroupRpcUserLinkClient);
-                        lib_common::grpc_client!(GroupRpcVehicleLinkClient);
-                        lib_common::grpc_client!(GroupRpcVertipadLinkClient);
-                        lib_common::grpc_client!(GroupRpcVertiportLinkClient);
+                        grpc_tls_client!(GroupRpcUserLinkClient);
+                        grpc_tls_client!(GroupRpcVehicleLinkClient);
+                        grpc_tls_client!(GroupRpcVertipadLinkClient);
+                        grpc_tls_client!(GroupRpcVertiportLinkClient);
                     }
                 }
 
@@ -135,9 +186,9 @@ cfg_if::cfg_if! {
                 cfg_if::cfg_if! {
                     if #[cfg(feature = "stub_backends")] {
                         use svc_storage::grpc::server::itinerary_flight_plan::{RpcFlightPlanLinkServer, GrpcServer as ItineraryFlightPlanGrpcServer};
-                        lib_common::grpc_mock_client!(ItineraryRpcFlightPlanLinkClient, RpcFlightPlanLinkServer, ItineraryFlightPlanGrpcServer);
+                        grpc_stub_client!(ItineraryRpcFlightPlanLinkClient, RpcFlightPlanLinkServer, ItineraryFlightPlanGrpcServer);
                     } else {
-                        lib_common::grpc_client!(ItineraryRpcFlightPlanLinkClient);
+                        grpc_tls_client!(ItineraryRpcFlightPlanLinkClient);
                     }
                 }
 
@@ -201,9 +252,9 @@ cfg_if::cfg_if! {
                 cfg_if::cfg_if! {
                     if #[cfg(feature = "stub_backends")] {
                         use svc_storage::grpc::server::user_group::{RpcGroupLinkServer as UserRpcGroupLinkServer, GrpcServer as UserGroupGrpcServer};
-                        lib_common::grpc_mock_client!(UserRpcGroupLinkClient, UserRpcGroupLinkServer, UserGroupGrpcServer);
+                        grpc_stub_client!(UserRpcGroupLinkClient, UserRpcGroupLinkServer, UserGroupGrpcServer);
                     } else {
-                        lib_common::grpc_client!(UserRpcGroupLinkClient);
+                        grpc_tls_client!(UserRpcGroupLinkClient);
                     }
                 }
 
@@ -220,6 +271,7 @@ cfg_if::cfg_if! {
             if #[cfg(feature = "vehicle")] {
                 grpc_client_mod!(vehicle);
                 simple_grpc_client!(vehicle);
+                schedule_data_impl!(vehicle);
                 /// GrpcClient implementation for vehicle RpcServiceClient
                 pub type VehicleClient = GrpcClient<vehicle::RpcServiceClient<Channel>>;
 
@@ -230,9 +282,9 @@ cfg_if::cfg_if! {
                 cfg_if::cfg_if! {
                     if #[cfg(feature = "stub_backends")] {
                         use svc_storage::grpc::server::vehicle_group::{RpcGroupLinkServer as VehicleRpcGroupLinkServer, GrpcServer as VehicleGroupGrpcServer};
-                        lib_common::grpc_mock_client!(VehicleRpcGroupLinkClient, VehicleRpcGroupLinkServer, VehicleGroupGrpcServer);
+                        grpc_stub_client!(VehicleRpcGroupLinkClient, VehicleRpcGroupLinkServer, VehicleGroupGrpcServer);
                     } else {
-                        lib_common::grpc_client!(VehicleRpcGroupLinkClient);
+                        grpc_tls_client!(VehicleRpcGroupLinkClient);
                     }
                 }
 
@@ -245,10 +297,20 @@ cfg_if::cfg_if! {
             }
         }
 
+        cfg_if::cfg_if! {
+            if #[cfg(feature = "vehicle_model")] {
+                grpc_client_mod!(vehicle_model);
+                simple_grpc_client!(vehicle_model);
+                /// GrpcClient implementation for vehicle_model RpcServiceClient
+                pub type VehicleModelClient = GrpcClient<vehicle_model::RpcServiceClient<Channel>>;
+            }
+        }
+
         cfg_if::cfg_if! {
             if #[cfg(feature = "vertipad")] {
                 grpc_client_mod!(vertipad);
                 simple_grpc_client!(vertipad);
+                schedule_data_impl!(vertipad);
                 /// GrpcClient implementation for vertipad RpcServiceClient
                 pub type VertipadClient = GrpcClient<vertipad::RpcServiceClient<Channel>>;
 
@@ -259,9 +321,9 @@ cfg_if::cfg_if! {
                 cfg_if::cfg_if! {
                     if #[cfg(feature = "stub_backends")] {
                         use svc_storage::grpc::server::vertipad_group::{RpcGroupLinkServer as VertipadRpcGroupLinkServer, GrpcServer as VertipadGroupGrpcServer};
-                        lib_common::grpc_mock_client!(VertipadRpcGroupLinkClient, VertipadRpcGroupLinkServer, VertipadGroupGrpcServer);
+                        grpc_stub_client!(VertipadRpcGroupLinkClient, VertipadRpcGroupLinkServer, VertipadGroupGrpcServer);
                     } else {
-                        lib_common::grpc_client!(VertipadRpcGroupLinkClient);
+                        grpc_tls_client!(VertipadRpcGroupLinkClient);
                     }
                 }
 
@@ -278,6 +340,7 @@ cfg_if::cfg_if! {
             if #[cfg(feature = "vertiport")] {
                 grpc_client_mod!(vertiport);
                 simple_grpc_client!(vertiport);
+                schedule_data_impl!(vertiport);
                 /// GrpcClient implementation for vertiport RpcServiceClient
                 pub type VertiportClient = GrpcClient<vertiport::RpcServiceClient<Channel>>;
 
@@ -288,9 +351,9 @@ cfg_if::cfg_if! {
                 cfg_if::cfg_if! {
                     if #[cfg(feature = "stub_backends")] {
                         use svc_storage::grpc::server::vertiport_group::{RpcGroupLinkServer as VertiportRpcGroupLinkServer, GrpcServer as VertiportGroupGrpcServer};
-                        lib_common::grpc_mock_client!(VertiportRpcGroupLinkClient, VertiportRpcGroupLinkServer, VertiportGroupGrpcServer);
+                        grpc_stub_client!(VertiportRpcGroupLinkClient, VertiportRpcGroupLinkServer, VertiportGroupGrpcServer);
                     } else {
-                        lib_common::grpc_client!(VertiportRpcGroupLinkClient);
+                        grpc_tls_client!(VertiportRpcGroupLinkClient);
                     }
                 }
 
@@ -312,6 +375,9 @@ cfg_if::cfg_if! {
             #[cfg(feature = "flight_plan")]
             /// GrpcClient representation of the FlightPlanClient
             pub flight_plan: FlightPlanClient,
+            #[cfg(feature = "flight_plan")]
+            /// GrpcClient representation of the FlightPlanScheduleClient
+            pub flight_plan_schedule: FlightPlanScheduleClient,
             #[cfg(feature = "flight_plan_parcel")]
             /// GrpcClient representation of the FlightPlanParcelClient
             pub flight_plan_parcel: FlightPlanParcelClient,
@@ -362,6 +428,9 @@ cfg_if::cfg_if! {
             #[cfg(feature = "vehicle")]
             /// GrpcClient representation of the VehicleGroupClient
             pub vehicle_group_link: VehicleGroupLinkClient,
+            #[cfg(feature = "vehicle_model")]
+            /// GrpcClient representation of the VehicleModelClient
+            pub vehicle_model: VehicleModelClient,
             #[cfg(feature = "vertipad")]
             /// GrpcClient representation of the VertipadClient
             pub vertipad: VertipadClient,
@@ -377,70 +446,97 @@ cfg_if::cfg_if! {
         }
 
         impl Clients {
+            /// Provides a way to get and connect all clients at once, using TLS for all
+            /// connections. See [`tls`](crate::tls) for details.
+            pub fn new_with_tls(host: String, port: u16, tls_config: tonic::transport::ClientTlsConfig) -> Self {
+                Self::new_with_options(host, port, ClientOptions::default().with_tls_config(tls_config))
+            }
+
             /// Provides a way to get and connect all clients at once.
             pub fn new(host: String, port: u16) -> Self {
+                Self::new_with_options(host, port, ClientOptions::default())
+            }
+
+            /// Provides a way to get and connect all clients at once, using the provided
+            /// [`ClientOptions`] for all clients.
+            ///
+            /// # Examples
+            /// ```
+            /// use svc_storage_client_grpc::prelude::*;
+            ///
+            /// let options = ClientOptions::default().with_bearer_token("token").unwrap();
+            /// let clients = Clients::new_with_options("svc-storage".to_string(), 50051, options);
+            /// ```
+            pub fn new_with_options(host: String, port: u16, options: ClientOptions) -> Self {
                 #[cfg(feature = "adsb")]
-                let adsb = AdsbClient::new_client(&host, port, "adsb");
+                let adsb = AdsbClient::new_client_with_options(&host, port, "adsb", options.clone());
 
                 #[cfg(feature = "flight_plan")]
-                let flight_plan = FlightPlanClient::new_client(&host, port, "flight_plan");
+                let flight_plan = FlightPlanClient::new_client_with_options(&host, port, "flight_plan", options.clone());
+                #[cfg(feature = "flight_plan")]
+                let flight_plan_schedule = FlightPlanScheduleClient::new_client_with_options(&host, port, "flight_plan_schedule", options.clone());
 
                 #[cfg(feature = "flight_plan_parcel")]
-                let flight_plan_parcel = FlightPlanParcelClient::new_client(&host, port, "flight_plan_parcel");
+                let flight_plan_parcel = FlightPlanParcelClient::new_client_with_options(&host, port, "flight_plan_parcel", options.clone());
 
                 #[cfg(feature = "group")]
-                let group = GroupClient::new_client(&host, port, "group");
+                let group = GroupClient::new_client_with_options(&host, port, "group", options.clone());
                 #[cfg(feature = "group")]
-                let group_user_link = GroupUserLinkClient::new_client(&host, port, "group_user_link");
+                let group_user_link = GroupUserLinkClient::new_client_with_options(&host, port, "group_user_link", options.clone());
                 #[cfg(feature = "group")]
-                let group_vehicle_link = GroupVehicleLinkClient::new_client(&host, port, "group_vehicle_link");
+                let group_vehicle_link = GroupVehicleLinkClient::new_client_with_options(&host, port, "group_vehicle_link", options.clone());
                 #[cfg(feature = "group")]
-                let group_vertipad_link = GroupVertipadLinkClient::new_client(&host, port, "group_vertipad_link");
+                let group_vertipad_link = GroupVertipadLinkClient::new_client_with_options(&host, port, "group_vertipad_link", options.clone());
                 #[cfg(feature = "group")]
-                let group_vertiport_link = GroupVertiportLinkClient::new_client(&host, port, "group_vertiport_link");
+                let group_vertiport_link = GroupVertiportLinkClient::new_client_with_options(&host, port, "group_vertiport_link", options.clone());
 
                 #[cfg(feature = "itinerary")]
-                let itinerary = ItineraryClient::new_client(&host, port, "itinerary");
+                let itinerary = ItineraryClient::new_client_with_options(&host, port, "itinerary", options.clone());
                 #[cfg(feature = "itinerary")]
-                let itinerary_flight_plan_link = ItineraryFlightPlanLinkClient::new_client(&host, port, "itinerary_flight_plan_link");
+                let itinerary_flight_plan_link = ItineraryFlightPlanLinkClient::new_client_with_options(&host, port, "itinerary_flight_plan_link", options.clone());
 
                 #[cfg(feature = "parcel")]
-                let parcel = ParcelClient::new_client(&host, port, "parcel");
+                let parcel = ParcelClient::new_client_with_options(&host, port, "parcel", options.clone());
 
                 #[cfg(feature = "parcel_scan")]
-                let parcel_scan = ParcelScanClient::new_client(&host, port, "parcel_scan");
+                let parcel_scan = ParcelScanClient::new_client_with_options(&host, port, "parcel_scan", options.clone());
 
                 #[cfg(feature = "pilot")]
-                let pilot = PilotClient::new_client(&host, port, "pilot");
+                let pilot = PilotClient::new_client_with_options(&host, port, "pilot", options.clone());
 
                 #[cfg(feature = "scanner")]
-                let scanner = ScannerClient::new_client(&host, port, "scanner");
+                let scanner = ScannerClient::new_client_with_options(&host, port, "scanner", options.clone());
 
                 #[cfg(feature = "user")]
-                let user = UserClient::new_client(&host, port, "user");
+                let user = UserClient::new_client_with_options(&host, port, "user", options.clone());
                 #[cfg(feature = "user")]
-                let user_group_link = UserGroupLinkClient::new_client(&host, port, "user_group_link");
+                let user_group_link = UserGroupLinkClient::new_client_with_options(&host, port, "user_group_link", options.clone());
 
                 #[cfg(feature = "vehicle")]
-                let vehicle = VehicleClient::new_client(&host, port, "vehicle");
+                let vehicle = VehicleClient::new_client_with_options(&host, port, "vehicle", options.clone());
                 #[cfg(feature = "vehicle")]
-                let vehicle_group_link = VehicleGroupLinkClient::new_client(&host, port, "vehicle_group_link");
+                let vehicle_group_link = VehicleGroupLinkClient::new_client_with_options(&host, port, "vehicle_group_link", options.clone());
+
+                #[cfg(feature = "vehicle_model")]
+                let vehicle_model = VehicleModelClient::new_client_with_options(&host, port, "vehicle_model", options.clone());
 
                 #[cfg(feature = "vertipad")]
-                let vertipad = VertipadClient::new_client(&host, port, "vertipad");
+                let vertipad = VertipadClient::new_client_with_options(&host, port, "vertipad", options.clone());
                 #[cfg(feature = "vertipad")]
-                let vertipad_group_link = VertipadGroupLinkClient::new_client(&host, port, "vertipad_group_link");
+                let vertipad_group_link = VertipadGroupLinkClient::new_client_with_options(&host, port, "vertipad_group_link", options.clone());
 
                 #[cfg(feature = "vertiport")]
-                let vertiport = VertiportClient::new_client(&host, port, "vertiport");
+                let vertiport = VertiportClient::new_client_with_options(&host, port, "vertiport", options.clone());
                 #[cfg(feature = "vertiport")]
-                let vertiport_group_link = VertiportGroupLinkClient::new_client(&host, port, "vertiport_group_link");
+                let vertiport_group_link = VertiportGroupLinkClient::new_client_with_options(&host, port, "vertiport_group_link", options.clone());
 
                 Clients {
                     #[cfg(feature = "adsb")]
                     adsb,
                     #[cfg(feature = "flight_plan")]
                     flight_plan,
+                    #[cfg(feature = "flight_plan")]
+                    flight_plan_schedule,
                     #[cfg(feature = "flight_plan_parcel")]
                     flight_plan_parcel,
                     #[cfg(feature = "group")]
@@ -473,6 +569,8 @@ cfg_if::cfg_if! {
                     vehicle,
                     #[cfg(feature = "vehicle")]
                     vehicle_group_link,
+                    #[cfg(feature = "vehicle_model")]
+                    vehicle_model,
                     #[cfg(feature = "vertipad")]
                     vertipad,
                     #[cfg(feature = "vertipad")]
@@ -494,6 +592,18 @@ cfg_if::cfg_if! {
             pub fn new(_host: String, _port: u16) -> Self {
                 Self {}
             }
+
+            /// Provides a way to get and connect all clients at once, using TLS for all
+            /// connections. See [`tls`](crate::tls) for details.
+            pub fn new_with_tls(host: String, port: u16, _tls_config: tonic::transport::ClientTlsConfig) -> Self {
+                Self::new(host, port)
+            }
+
+            /// Provides a way to get and connect all clients at once, using the provided
+            /// [`ClientOptions`](crate::client::ClientOptions) for all clients.
+            pub fn new_with_options(host: String, port: u16, _options: crate::client::ClientOptions) -> Self {
+                Self::new(host, port)
+            }
         }
     }
 }
@@ -0,0 +1,128 @@
+//! GRPC Schedule Service traits
+
+/// Generic gRPC object traits to provide wrappers for schedule functions
+#[tonic::async_trait]
+pub trait Client<T>
+where
+    Self: Sized + crate::client::Client<T>,
+    T: Send + Clone,
+{
+    /// The type expected for ConflictsRequest structs.
+    type ConflictsRequest;
+    /// The type expected for List structs.
+    type List;
+    /// The type expected for AvailabilityRequest structs.
+    type AvailabilityRequest;
+    /// The type expected for AvailabilityResponse structs.
+    type AvailabilityResponse;
+
+    /// Returns a [`tonic::Response`] containing a [`List`](Self::List) of all records reserving
+    /// the requested resource during (part of) the requested timeslot.
+    ///
+    /// Takes a [`ConflictsRequest`](Self::ConflictsRequest) with the id of the resource and the
+    /// proposed timeslot. A record id can be provided to exclude from the results, e.g. the
+    /// record being rescheduled.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`tonic::Status`] with [`tonic::Code::InvalidArgument`] if the provided ids or timeslot are invalid.
+    /// Returns [`tonic::Status`] with [`tonic::Code::Internal`] if any error is returned from the db search result.
+    /// Returns [`tonic::Status`] with [`tonic::Code::Unknown`] if the server is not ready.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use lib_common::grpc::get_endpoint_from_env;
+    /// use lib_common::time::{Duration, Utc};
+    /// use svc_storage_client_grpc::prelude::*;
+    ///
+    /// async fn example () -> Result<(), Box<dyn std::error::Error>> {
+    ///     let (host, port) = get_endpoint_from_env("SERVER_HOSTNAME", "SERVER_PORT_GRPC");
+    ///     let clients = Clients::new(host, port);
+    ///     let now = Utc::now();
+    ///     let result = clients.flight_plan_schedule
+    ///         .get_conflicts(flight_plan::ConflictsRequest {
+    ///             vertipad_id: String::from("40ef6e51-c7db-4ce7-a806-a754d6baa641"),
+    ///             timeslot_start: Some(now.into()),
+    ///             timeslot_end: Some((now + Duration::minutes(10)).into()),
+    ///             exclude_id: None,
+    ///         })
+    ///         .await;
+    ///     Ok(())
+    /// }
+    /// ```
+    async fn get_conflicts(
+        &self,
+        request: Self::ConflictsRequest,
+    ) -> Result<tonic::Response<Self::List>, tonic::Status>;
+
+    /// Returns a [`tonic::Response`] containing an [`AvailabilityResponse`](Self::AvailabilityResponse)
+    /// with the free time intervals of the requested vertipad, or of all vertipads of the requested
+    /// vertiport.
+    ///
+    /// Takes an [`AvailabilityRequest`](Self::AvailabilityRequest) with either a vertipad id or a
+    /// vertiport id and the requested time range. A vertipad is not available if it is disabled,
+    /// during the events of the vertipad's and vertiport's schedules and during the timeslots
+    /// reserved by flight plans.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`tonic::Status`] with [`tonic::Code::InvalidArgument`] if the provided ids or time range are invalid.
+    /// Returns [`tonic::Status`] with [`tonic::Code::NotFound`] if the provided vertipad or vertiport could not be found.
+    /// Returns [`tonic::Status`] with [`tonic::Code::Internal`] if any error is returned from a db call or a schedule could not be parsed.
+    /// Returns [`tonic::Status`] with [`tonic::Code::Unknown`] if the server is not ready.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use lib_common::grpc::get_endpoint_from_env;
+    /// use lib_common::time::{Duration, Utc};
+    /// use svc_storage_client_grpc::prelude::*;
+    ///
+    /// async fn example () -> Result<(), Box<dyn std::error::Error>> {
+    ///     let (host, port) = get_endpoint_from_env("SERVER_HOSTNAME", "SERVER_PORT_GRPC");
+    ///     let clients = Clients::new(host, port);
+    ///     let now = Utc::now();
+    ///     let result = clients.flight_plan_schedule
+    ///         .get_availability(flight_plan::AvailabilityRequest {
+    ///             vertipad_id: Some(String::from("40ef6e51-c7db-4ce7-a806-a754d6baa641")),
+    ///             vertiport_id: None,
+    ///             time_start: Some(now.into()),
+    ///             time_end: Some((now + Duration::hours(2)).into()),
+    ///         })
+    ///         .await;
+    ///     Ok(())
+    /// }
+    /// ```
+    async fn get_availability(
+        &self,
+        request: Self::AvailabilityRequest,
+    ) -> Result<tonic::Response<Self::AvailabilityResponse>, tonic::Status>;
+
+    /// Returns a [`tonic::Response`] containing a [`ReadyResponse`](crate::ReadyResponse)
+    /// Takes an [`ReadyRequest`](crate::ReadyRequest)
+    ///
+    /// # Errors
+    ///
+    /// Returns [`tonic::Status`] with [`tonic::Code::Unknown`] if the server is not ready.
+    ///
+    /// # Examples
+    /// ```
+    /// use lib_common::grpc::get_endpoint_from_env;
+    /// use svc_storage_client_grpc::prelude::*;
+    ///
+    /// async fn example () -> Result<(), Box<dyn std::error::Error>> {
+    ///     let (host, port) = get_endpoint_from_env("SERVER_HOSTNAME", "SERVER_PORT_GRPC");
+    ///     let clients = Clients::new(host, port);
+    ///     let response = clients.flight_plan_schedule
+    ///         .is_ready(ReadyRequest {})
+    ///         .await?;
+    ///     println!("RESPONSE={:?}", response.into_inner());
+    ///     Ok(())
+    /// }
+    /// ```
+    async fn is_ready(
+        &self,
+        request: crate::ReadyRequest,
+    ) -> Result<tonic::Response<crate::ReadyResponse>, tonic::Status>;
+}
@@ -1,10 +1,64 @@
 //! GRPC Simple Service traits
 
+use futures::stream::{self, BoxStream, StreamExt, TryStreamExt};
+
+/// Number of results requested per page by `search_all` if no `results_per_page` was provided
+pub const SEARCH_ALL_RESULTS_PER_PAGE: i32 = 1000;
+
+/// Provides access to the results and cursor of a paged search response
+pub trait SearchResultList: Send {
+    /// The type of the items contained in the list
+    type Item: Send;
+
+    /// Returns the list items and the cursor pointing to the next page, if available
+    fn into_page(self) -> (Vec<Self::Item>, Option<String>);
+}
+
+/// Returns a [`BoxStream`] yielding all results for the provided filter,
+/// following the `next_cursor` of each page until no more results are available.
+pub(crate) fn search_all_pages<'a, L, F>(
+    mut filter: crate::AdvancedSearchFilter,
+    search: F,
+) -> BoxStream<'a, Result<L::Item, tonic::Status>>
+where
+    L: SearchResultList + 'a,
+    F: Fn(
+            crate::AdvancedSearchFilter,
+        ) -> futures::future::BoxFuture<'a, Result<tonic::Response<L>, tonic::Status>>
+        + Send
+        + 'a,
+{
+    if filter.results_per_page <= 0 {
+        filter.results_per_page = SEARCH_ALL_RESULTS_PER_PAGE;
+    }
+    if filter.page_number <= 0 {
+        filter.page_number = 1;
+    }
+
+    stream::try_unfold(Some(filter), move |filter| {
+        let page = filter.map(|filter| (search(filter.clone()), filter));
+        async move {
+            let (page, filter) = match page {
+                Some(page) => page,
+                None => return Ok(None),
+            };
+            let (items, next_cursor) = page.await?.into_inner().into_page();
+            let next_filter = next_cursor.map(|cursor| filter.cursor(cursor));
+            Ok(Some((
+                stream::iter(items.into_iter().map(Ok::<L::Item, tonic::Status>)),
+                next_filter,
+            )))
+        }
+    })
+    .try_flatten()
+    .boxed()
+}
+
 /// Generic gRPC object traits to provide wrappers for simple `Resource` functions
 #[tonic::async_trait]
 pub trait Client<T>
 where
-    Self: Sized + lib_common::grpc::Client<T> + lib_common::grpc::ClientConnect<T>,
+    Self: Sized + crate::client::Client<T>,
     T: Send + Clone,
 {
     /// The type expected for Data structs.
@@ -17,6 +71,10 @@ where
     type List;
     /// The type expected for Response structs.
     type Response;
+    /// The type expected for InsertBatchRequest structs.
+    type InsertBatchRequest;
+    /// The type expected for ChangeEvent structs.
+    type ChangeEvent;
 
     /// Returns a [`tonic::Response`] containing the [`Object`](Self::Object)
     ///
@@ -40,7 +98,7 @@ where
     ///     let client = clients.flight_plan;
     ///     let flight_plan_id = String::from("40ef6e51-c7db-4ce7-a806-a754d6baa641");
     ///
-    ///     client.get_by_id(Id { id: flight_plan_id }).await?;
+    ///     client.get_by_id(Id { id: flight_plan_id, include_archived: false }).await?;
     ///
     ///     Ok(())
     /// }
@@ -114,16 +172,74 @@ where
         request: Self::Data,
     ) -> Result<tonic::Response<Self::Response>, tonic::Status>;
 
+    /// Returns a [`tonic::Response`] containing an [`InsertBatchResponse`](crate::InsertBatchResponse) with an
+    /// [`InsertBatchResult`](crate::InsertBatchResult) for each provided item, in the same order
+    ///
+    /// Takes an [`InsertBatchRequest`](Self::InsertBatchRequest) with a list of [`Data`](Self::Data) objects to insert at once.
+    /// Every item will be validated separately and all valid items will be inserted using a single database transaction.
+    /// If `all_or_nothing` is set, nothing will be inserted when any of the items fails validation.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`tonic::Status`] with [`tonic::Code::Internal`] if any error is returned from a db call, no items will be inserted in that case.
+    /// Returns [`tonic::Status`] with [`tonic::Code::Unknown`] if the server is not ready.
+    ///
+    /// # Examples
+    /// ```
+    /// use lib_common::grpc::get_endpoint_from_env;
+    /// use svc_storage_client_grpc::prelude::*;
+    /// use vertipad::*;
+    ///
+    /// async fn example () -> Result<(), Box<dyn std::error::Error>> {
+    ///     let (host, port) = get_endpoint_from_env("SERVER_HOSTNAME", "SERVER_PORT_GRPC");
+    ///     let clients = Clients::new(host, port);
+    ///     let client = clients.vertipad;
+    ///
+    ///     let vertiport_id = "53acfe06-dd9b-42e8-8cb4-12a2fb2fa693".to_owned();
+    ///     let list = vec!["Vertipad 1", "Vertipad 2"]
+    ///         .into_iter()
+    ///         .map(|name| Data {
+    ///             vertiport_id: vertiport_id.clone(),
+    ///             name: name.to_owned(),
+    ///             geo_location: Some(GeoPointZ { x: 4.9160036, y: 52.3745905, z: 0.0 }),
+    ///             enabled: true,
+    ///             occupied: false,
+    ///             schedule: None,
+    ///             created_at: None,
+    ///             updated_at: None,
+    ///         })
+    ///         .collect();
+    ///
+    ///     let response = client
+    ///         .insert_batch(InsertBatchRequest {
+    ///             list,
+    ///             all_or_nothing: true,
+    ///         })
+    ///         .await?
+    ///         .into_inner();
+    ///     println!("Inserted {} vertipads", response.inserted_count);
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    async fn insert_batch(
+        &self,
+        request: Self::InsertBatchRequest,
+    ) -> Result<tonic::Response<crate::InsertBatchResponse>, tonic::Status>;
+
     /// Returns a [`tonic::Response`] containing a [`Response`](Self::Response) object
     /// of the updated record after saving the provided [`UpdateObject`](Self::UpdateObject)
     ///
     /// The given data will be validated before insert.
     /// Any errors found during validation will be added to the [`ValidationResult`](crate::ValidationResult).
     /// A field [`prost_types::FieldMask`] can be provided to restrict updates to specific fields.
+    /// An `expected_updated_at` value can be provided to only apply the update if the record has not been changed since.
+    /// The record's current `updated_at` value is returned in the `x-updated-at` response metadata by `get_by_id` and `update`.
     ///
     /// # Errors
     ///
     /// Returns [`tonic::Status`] with [`tonic::Code::Cancelled`] if the [`Request`](tonic::Request) doesn't contain any data.
+    /// Returns [`tonic::Status`] with [`tonic::Code::FailedPrecondition`] if the provided `expected_updated_at` does not match the record's `updated_at` value.
     /// Returns [`tonic::Status`] with [`tonic::Code::Internal`] if any error is returned from a db call.
     /// Returns [`tonic::Status`] with [`tonic::Code::Internal`] if the provided Id can not be converted to a [`lib_common::uuid::Uuid`].
     /// Returns [`tonic::Status`] with [`tonic::Code::Internal`] if the resulting tokio_postgres::Row data could not be converted into [`Data`](Self::Data).
@@ -141,7 +257,7 @@ where
     ///     let client = clients.flight_plan;
     ///
     ///     let id = "53acfe06-dd9b-42e8-8cb4-12a2fb2fa693".to_owned();
-    ///     let response = match client.get_by_id(Id { id: id.clone() }).await {
+    ///     let response = match client.get_by_id(Id { id: id.clone(), include_archived: false }).await {
     ///         Ok(res) => {
     ///           println!("RESPONSE Flight Plan By ID={:?}", res);
     ///           res
@@ -158,6 +274,7 @@ where
     ///             flight_status: FlightStatus::InFlight as i32,
     ///             ..flight_plan
     ///         }),
+    ///         expected_updated_at: None,
     ///         mask: Some(FieldMask {
     ///             paths: vec!["flight_status".to_owned()],
     ///         }),
@@ -171,13 +288,16 @@ where
         request: Self::UpdateObject,
     ) -> Result<tonic::Response<Self::Response>, tonic::Status>;
 
-    /// Takes an [`Id`](crate::Id) object to remove the associated records
+    /// Takes a [`DeleteRequest`](crate::DeleteRequest) (or an [`Id`](crate::Id)) object to remove the associated records
     /// from the database.
     ///
+    /// An `expected_updated_at` value can be provided to only delete the record if it has not been changed since.
+    ///
     /// # Errors
     ///
     /// Returns [`tonic::Status`] with [`tonic::Code::NotFound`] if no record is
     /// found in the database for the provided id field and value combination.
+    /// Returns [`tonic::Status`] with [`tonic::Code::FailedPrecondition`] if the provided `expected_updated_at` does not match the record's `updated_at` value.
     /// Returns [`tonic::Status`] with [`tonic::Code::Internal`] if any error is returned from a db call.
     /// Returns [`tonic::Status`] with [`tonic::Code::Internal`] if the provided Id can not be converted to a [`lib_common::uuid::Uuid`].
     /// Returns [`tonic::Status`] with [`tonic::Code::Unknown`] if the server is not ready.
@@ -194,16 +314,57 @@ where
     ///     let client = clients.flight_plan;
     ///
     ///     let flight_plan_id = String::from("40ef6e51-c7db-4ce7-a806-a754d6baa641");
-    ///     client.delete(Id { id: flight_plan_id } ).await?;
+    ///     client.delete(Id { id: flight_plan_id, include_archived: false } ).await?;
     ///
     ///     Ok(())
     /// }
     /// ```
-    async fn delete(&self, request: crate::Id) -> Result<tonic::Response<()>, tonic::Status>;
+    async fn delete<R>(&self, request: R) -> Result<tonic::Response<()>, tonic::Status>
+    where
+        R: Into<crate::DeleteRequest> + Send + 'static;
+
+    /// Takes an [`Id`](crate::Id) object to restore the associated archived record
+    /// by clearing its `deleted_at` value.
+    ///
+    /// The resource's unique fields will be checked against all non archived records before restoring.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`tonic::Status`] with [`tonic::Code::NotFound`] if no record is returned from the database.
+    /// Returns [`tonic::Status`] with [`tonic::Code::FailedPrecondition`] if the record is not archived.
+    /// Returns [`tonic::Status`] with [`tonic::Code::AlreadyExists`] if restoring the record would violate a unique constraint.
+    /// Returns [`tonic::Status`] with [`tonic::Code::Unimplemented`] if the resource does not support archiving.
+    /// Returns [`tonic::Status`] with [`tonic::Code::Internal`] if any error is returned from a db call.
+    /// Returns [`tonic::Status`] with [`tonic::Code::Unknown`] if the server is not ready.
+    ///
+    /// # Examples
+    /// ```
+    /// use lib_common::grpc::get_endpoint_from_env;
+    /// use svc_storage_client_grpc::prelude::*;
+    /// use flight_plan::*;
+    ///
+    /// async fn example () -> Result<(), Box<dyn std::error::Error>> {
+    ///     let (host, port) = get_endpoint_from_env("SERVER_HOSTNAME", "SERVER_PORT_GRPC");
+    ///     let clients = Clients::new(host, port);
+    ///     let client = clients.flight_plan;
+    ///
+    ///     let flight_plan_id = String::from("40ef6e51-c7db-4ce7-a806-a754d6baa641");
+    ///     let response = client.restore(Id { id: flight_plan_id, include_archived: false } ).await?;
+    ///     println!("RESPONSE={:?}", response.into_inner());
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    async fn restore(
+        &self,
+        request: crate::Id,
+    ) -> Result<tonic::Response<Self::Object>, tonic::Status>;
 
     /// Search database records using an advanced filter
     ///
-    /// This method supports paged results.
+    /// This method supports paged results. The returned [`List`](Self::List) contains the
+    /// `total_count` of records matching the filter and a `has_more` flag indicating if
+    /// more pages are available.
     ///
     /// # Errors
     ///
@@ -224,9 +385,12 @@ where
     ///
     ///     let pilot_id = "a2093c5e-9bbe-4f0f-97ee-276b43fa3759".to_owned();
     ///     let filter = AdvancedSearchFilter::search_equals("pilot_id".to_owned(), pilot_id)
-    ///         .and_is_not_null("origin_timeslot_start".to_owned());
+    ///         .and_is_not_null("origin_timeslot_start".to_owned())
+    ///         .results_per_page(10)
+    ///         .page_number(1);
     ///
-    ///     client.search(filter).await?;
+    ///     let result = client.search(filter).await?.into_inner();
+    ///     println!("Found {} flight plans, more pages: {}", result.total_count.unwrap_or(0), result.has_more);
     ///
     ///     Ok(())
     /// }
@@ -236,6 +400,137 @@ where
         request: crate::AdvancedSearchFilter,
     ) -> Result<tonic::Response<Self::List>, tonic::Status>;
 
+    /// Search database records using an advanced filter, streaming the results
+    ///
+    /// Each [`Object`](Self::Object) is received as a separate message, so large result sets
+    /// are not limited by the maximum message size and don't need to be kept in memory as a whole.
+    /// Paging options in the filter are applied the same way as for [`search`](Self::search).
+    ///
+    /// # Errors
+    ///
+    /// Returns [`tonic::Status`] with [`tonic::Code::Internal`] if any error is returned from the db search.
+    /// Returns [`tonic::Status`] with [`tonic::Code::Unknown`] if the server is not ready.
+    /// The stream yields a [`tonic::Status`] with [`tonic::Code::Internal`] if a database row could not be converted into an [`Object`](Self::Object).
+    ///
+    /// # Examples
+    /// ```
+    /// use futures::TryStreamExt;
+    /// use lib_common::grpc::get_endpoint_from_env;
+    /// use svc_storage_client_grpc::prelude::*;
+    /// use flight_plan::*;
+    ///
+    /// async fn example () -> Result<(), Box<dyn std::error::Error>> {
+    ///     let (host, port) = get_endpoint_from_env("SERVER_HOSTNAME", "SERVER_PORT_GRPC");
+    ///     let clients = Clients::new(host, port);
+    ///     let client = clients.flight_plan;
+    ///
+    ///     let filter = AdvancedSearchFilter::search_is_not_null("origin_timeslot_start".to_owned());
+    ///
+    ///     let mut results = client.search_stream(filter).await?.into_inner();
+    ///     while let Some(object) = results.try_next().await? {
+    ///         println!("{:?}", object);
+    ///     }
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    async fn search_stream(
+        &self,
+        request: crate::AdvancedSearchFilter,
+    ) -> Result<
+        tonic::Response<BoxStream<'static, Result<Self::Object, tonic::Status>>>,
+        tonic::Status,
+    >;
+
+    /// Watch for changes of records matching the provided advanced filter
+    ///
+    /// Returns a stream yielding a [`ChangeEvent`](Self::ChangeEvent) for every insert, update
+    /// and delete made after the stream has been opened. The stream stays open until it's dropped.
+    /// An empty filter will yield events for all records.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`tonic::Status`] with [`tonic::Code::Unknown`] if the server is not ready.
+    /// The stream yields a [`tonic::Status`] with [`tonic::Code::DataLoss`] and closes if the client could not keep up with the change events.
+    /// The stream yields a [`tonic::Status`] with [`tonic::Code::Internal`] and closes if the filter could not be applied.
+    ///
+    /// # Examples
+    /// ```
+    /// use futures::TryStreamExt;
+    /// use lib_common::grpc::get_endpoint_from_env;
+    /// use svc_storage_client_grpc::prelude::*;
+    /// use flight_plan::*;
+    ///
+    /// async fn example () -> Result<(), Box<dyn std::error::Error>> {
+    ///     let (host, port) = get_endpoint_from_env("SERVER_HOSTNAME", "SERVER_PORT_GRPC");
+    ///     let clients = Clients::new(host, port);
+    ///     let client = clients.flight_plan;
+    ///
+    ///     let filter = AdvancedSearchFilter::search_equals(
+    ///         "vehicle_id".to_owned(),
+    ///         "53acfe06-dd9b-42e8-8cb4-12a2fb2fa693".to_owned(),
+    ///     );
+    ///
+    ///     let mut events = client.watch(filter).await?.into_inner();
+    ///     while let Some(event) = events.try_next().await? {
+    ///         println!("{:?}: {:?}", event.change_type(), event.object);
+    ///     }
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    async fn watch(
+        &self,
+        request: crate::AdvancedSearchFilter,
+    ) -> Result<
+        tonic::Response<BoxStream<'static, Result<Self::ChangeEvent, tonic::Status>>>,
+        tonic::Status,
+    >;
+
+    /// Returns a stream of all [`Object`](Self::Object)s matching the provided filter
+    ///
+    /// Pages through the search results using the `next_cursor` returned by each
+    /// [`search`](Self::search) call until all results are retrieved.
+    /// Uses [`SEARCH_ALL_RESULTS_PER_PAGE`] as page size if no `results_per_page` was provided.
+    ///
+    /// # Errors
+    ///
+    /// The stream yields a [`tonic::Status`] if any of the [`search`](Self::search) calls returns an error.
+    ///
+    /// # Examples
+    /// ```
+    /// use futures::TryStreamExt;
+    /// use lib_common::grpc::get_endpoint_from_env;
+    /// use svc_storage_client_grpc::prelude::*;
+    /// use adsb::*;
+    ///
+    /// async fn example () -> Result<(), Box<dyn std::error::Error>> {
+    ///     let (host, port) = get_endpoint_from_env("SERVER_HOSTNAME", "SERVER_PORT_GRPC");
+    ///     let clients = Clients::new(host, port);
+    ///     let client = clients.adsb;
+    ///
+    ///     let filter = AdvancedSearchFilter::search_is_not_null("network_timestamp".to_owned())
+    ///         .results_per_page(500);
+    ///
+    ///     let mut results = client.search_all(filter);
+    ///     while let Some(object) = results.try_next().await? {
+    ///         println!("{:?}", object);
+    ///     }
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    fn search_all(
+        &self,
+        request: crate::AdvancedSearchFilter,
+    ) -> BoxStream<'_, Result<Self::Object, tonic::Status>>
+    where
+        Self: Sync,
+        Self::List: SearchResultList<Item = Self::Object>,
+    {
+        search_all_pages(request, move |filter| self.search(filter))
+    }
+
     /// Returns a [`tonic::Response`] containing a [`ReadyResponse`](crate::ReadyResponse)
     /// Takes an [`ReadyRequest`](crate::ReadyRequest)
     ///
@@ -1,10 +1,13 @@
 //! GRPC Simple Service traits
 
+use crate::simple_service::{search_all_pages, SearchResultList};
+use futures::stream::BoxStream;
+
 /// Generic gRPC object traits to provide wrappers for simple `Resource` functions
 #[tonic::async_trait]
 pub trait Client<T>
 where
-    Self: Sized + lib_common::grpc::Client<T> + lib_common::grpc::ClientConnect<T>,
+    Self: Sized + crate::client::Client<T>,
     T: Send + Clone,
 {
     /// The type expected for Data structs.
@@ -45,7 +48,7 @@ where
     ///     let clients = Clients::new(host, port);
     ///     let link_client = clients.flight_plan_parcel;
     ///     let flight_plan_id = String::from("40ef6e51-c7db-4ce7-a806-a754d6baa641");
-    ///     link_client.unlink(Id {id: flight_plan_id}).await?;
+    ///     link_client.unlink(Id {id: flight_plan_id, include_archived: false, include_archived: false}).await?;
     ///
     ///     Ok(())
     /// }
@@ -75,7 +78,7 @@ where
     ///     let clients = Clients::new(host, port);
     ///     let link_client = clients.flight_plan_parcel;
     ///     let flight_plan_id = String::from("40ef6e51-c7db-4ce7-a806-a754d6baa641");
-    ///     link_client.get_linked_ids(Id {id: flight_plan_id}).await?;
+    ///     link_client.get_linked_ids(Id {id: flight_plan_id, include_archived: false, include_archived: false}).await?;
     ///
     ///     Ok(())
     /// }
@@ -108,7 +111,7 @@ where
     ///     let clients = Clients::new(host, port);
     ///     let link_client = clients.flight_plan_parcel;
     ///     let flight_plan_id = String::from("40ef6e51-c7db-4ce7-a806-a754d6baa641");
-    ///     link_client.get_linked(Id {id: flight_plan_id}).await?;
+    ///     link_client.get_linked(Id {id: flight_plan_id, include_archived: false, include_archived: false}).await?;
     ///
     ///     Ok(())
     /// }
@@ -210,10 +213,13 @@ where
     /// The given data will be validated before insert.
     /// Any errors found during validation will be added to the [`ValidationResult`](crate::ValidationResult).
     /// A field [`prost_types::FieldMask`] can be provided to restrict updates to specific fields.
+    /// An `expected_updated_at` value can be provided to only apply the update if the record has not been changed since.
+    /// The record's current `updated_at` value is returned in the `x-updated-at` response metadata by `get_by_id` and `update`.
     ///
     /// # Errors
     ///
     /// Returns [`tonic::Status`] with [`tonic::Code::Cancelled`] if the [`Request`](tonic::Request) doesn't contain any data.
+    /// Returns [`tonic::Status`] with [`tonic::Code::FailedPrecondition`] if the provided `expected_updated_at` does not match the record's `updated_at` value.
     /// Returns [`tonic::Status`] with [`tonic::Code::Internal`] if any error is returned from a db call.
     /// Returns [`tonic::Status`] with [`tonic::Code::Internal`] if the provided Id can not be converted to a [`lib_common::uuid::Uuid`].
     /// Returns [`tonic::Status`] with [`tonic::Code::Internal`] if the resulting `tokio_postgres::Row` data could not be converted into [`Data`](Self::LinkedData).
@@ -246,6 +252,7 @@ where
     ///             acquire: false,
     ///             deliver: true,
     ///         }),
+    ///         expected_updated_at: None,
     ///         mask: Some(FieldMask {
     ///             paths: vec!["acquire".to_owned()],
     ///         }),
@@ -260,12 +267,15 @@ where
         request: Self::LinkedUpdateObject,
     ) -> Result<tonic::Response<Self::LinkedResponse>, tonic::Status>;
 
-    /// Takes an [`Ids`](crate::Ids) object to remove the associated records from the database.
+    /// Takes a [`LinkedDeleteRequest`](crate::LinkedDeleteRequest) (or an [`Ids`](crate::Ids)) object to remove the associated records from the database.
+    ///
+    /// An `expected_updated_at` value can be provided to only delete the record if it has not been changed since.
     ///
     /// # Errors
     ///
     /// Returns [`tonic::Status`] with [`tonic::Code::NotFound`] if no record is
     /// found in the database for the provided id field and value combination.
+    /// Returns [`tonic::Status`] with [`tonic::Code::FailedPrecondition`] if the provided `expected_updated_at` does not match the record's `updated_at` value.
     /// Returns [`tonic::Status`] with [`tonic::Code::Internal`] if any error is returned from a db call.
     /// Returns [`tonic::Status`] with [`tonic::Code::Internal`] if the provided Ids can not be converted to a [`lib_common::uuid::Uuid`].
     /// Returns [`tonic::Status`] with [`tonic::Code::Unknown`] if the server is not ready.
@@ -299,11 +309,15 @@ where
     ///     Ok(())
     /// }
     /// ```
-    async fn delete(&self, request: crate::Ids) -> Result<tonic::Response<()>, tonic::Status>;
+    async fn delete<R>(&self, request: R) -> Result<tonic::Response<()>, tonic::Status>
+    where
+        R: Into<crate::LinkedDeleteRequest> + Send + 'static;
 
     /// Search database records using an advanced filter
     ///
-    /// This method supports paged results.
+    /// This method supports paged results. The returned [`RowDataList`](Self::LinkedRowDataList)
+    /// contains the `total_count` of records matching the filter and a `has_more` flag
+    /// indicating if more pages are available.
     ///
     /// # Errors
     ///
@@ -324,9 +338,12 @@ where
     ///
     ///     let parcel_id = "a2093c5e-9bbe-4f0f-97ee-276b43fa3759".to_owned();
     ///     let filter = AdvancedSearchFilter::search_equals("deliver".to_owned(), true.to_string())
-    ///         .and_equals("parcel_id".to_owned(), parcel_id);
+    ///         .and_equals("parcel_id".to_owned(), parcel_id)
+    ///         .results_per_page(10)
+    ///         .page_number(1);
     ///
-    ///     link_client.search(filter).await?;
+    ///     let result = link_client.search(filter).await?.into_inner();
+    ///     println!("Found {} parcels, more pages: {}", result.total_count.unwrap_or(0), result.has_more);
     ///
     ///     Ok(())
     /// }
@@ -336,6 +353,87 @@ where
         request: crate::AdvancedSearchFilter,
     ) -> Result<tonic::Response<Self::LinkedRowDataList>, tonic::Status>;
 
+    /// Search database records using an advanced filter, streaming the results
+    ///
+    /// Each [`RowData`](Self::LinkedRowData) object is received as a separate message, so large
+    /// result sets are not limited by the maximum message size.
+    /// Paging options in the filter are applied the same way as for [`search`](Self::search).
+    ///
+    /// # Errors
+    ///
+    /// Returns [`tonic::Status`] with [`tonic::Code::Internal`] if any error is returned from the db search.
+    /// Returns [`tonic::Status`] with [`tonic::Code::Unknown`] if the server is not ready.
+    /// The stream yields a [`tonic::Status`] with [`tonic::Code::Internal`] if a database row could not be converted into [`RowData`](Self::LinkedRowData).
+    ///
+    /// # Examples
+    /// ```
+    /// use futures::TryStreamExt;
+    /// use lib_common::grpc::get_endpoint_from_env;
+    /// use svc_storage_client_grpc::prelude::*;
+    /// use flight_plan_parcel::*;
+    ///
+    /// async fn example () -> Result<(), Box<dyn std::error::Error>> {
+    ///     let (host, port) = get_endpoint_from_env("SERVER_HOSTNAME", "SERVER_PORT_GRPC");
+    ///     let clients = Clients::new(host, port);
+    ///     let link_client = clients.flight_plan_parcel;
+    ///
+    ///     let filter = AdvancedSearchFilter::search_equals("deliver".to_owned(), true.to_string());
+    ///     let row_data: Vec<RowData> = link_client
+    ///         .search_stream(filter)
+    ///         .await?
+    ///         .into_inner()
+    ///         .try_collect()
+    ///         .await?;
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    async fn search_stream(
+        &self,
+        request: crate::AdvancedSearchFilter,
+    ) -> Result<
+        tonic::Response<BoxStream<'static, Result<Self::LinkedRowData, tonic::Status>>>,
+        tonic::Status,
+    >;
+
+    /// Returns a stream of all [`RowData`](Self::LinkedRowData) objects matching the provided filter
+    ///
+    /// Pages through the search results using the `next_cursor` returned by each
+    /// [`search`](Self::search) call until all results are retrieved.
+    ///
+    /// # Errors
+    ///
+    /// The stream yields a [`tonic::Status`] if any of the [`search`](Self::search) calls returns an error.
+    ///
+    /// # Examples
+    /// ```
+    /// use futures::TryStreamExt;
+    /// use lib_common::grpc::get_endpoint_from_env;
+    /// use svc_storage_client_grpc::prelude::*;
+    /// use flight_plan_parcel::*;
+    ///
+    /// async fn example () -> Result<(), Box<dyn std::error::Error>> {
+    ///     let (host, port) = get_endpoint_from_env("SERVER_HOSTNAME", "SERVER_PORT_GRPC");
+    ///     let clients = Clients::new(host, port);
+    ///     let link_client = clients.flight_plan_parcel;
+    ///
+    ///     let filter = AdvancedSearchFilter::search_equals("deliver".to_owned(), true.to_string());
+    ///     let row_data: Vec<RowData> = link_client.search_all(filter).try_collect().await?;
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    fn search_all(
+        &self,
+        request: crate::AdvancedSearchFilter,
+    ) -> BoxStream<'_, Result<Self::LinkedRowData, tonic::Status>>
+    where
+        Self: Sync,
+        Self::LinkedRowDataList: SearchResultList<Item = Self::LinkedRowData>,
+    {
+        search_all_pages(request, move |filter| self.search(filter))
+    }
+
     /// Returns a [`tonic::Response`] containing a [`ReadyResponse`](crate::ReadyResponse)
     /// Takes an [`ReadyRequest`](crate::ReadyRequest)
     ///
@@ -0,0 +1,96 @@
+//! TLS for the gRPC client connections
+//!
+//! Connections to the server are encrypted if a [`ClientTlsConfig`] has been provided, using
+//! [`ClientOptions::with_tls_config`](crate::client::ClientOptions::with_tls_config) or
+//! [`Clients::new_with_tls`](crate::Clients::new_with_tls). The configuration only applies to the
+//! clients created with it and is used for each new connection, so renewed certificates can be
+//! used by creating new clients.
+
+use std::fs;
+use std::io;
+use tonic::transport::{Certificate, Channel, ClientTlsConfig, Endpoint, Error, Identity};
+
+/// Creates a TLS configuration from PEM encoded files
+///
+/// The server certificate is verified using `ca_cert`. The `client_identity` certificate and key
+/// files are presented to servers requiring client certificates (mTLS). The `domain_name` is
+/// only needed if the server certificate has been issued for another name than the host used
+/// to connect.
+///
+/// # Errors
+///
+/// Returns [`io::Error`] if any of the files could not be read
+///
+/// # Examples
+/// ```no_run
+/// use svc_storage_client_grpc::prelude::*;
+/// use svc_storage_client_grpc::tls;
+///
+/// let tls_config = tls::load_config(
+///     "/etc/tls/ca.crt",
+///     Some(("/etc/tls/client.crt", "/etc/tls/client.key")),
+///     Some("svc-storage"),
+/// )
+/// .unwrap();
+/// let clients = Clients::new_with_tls("svc-storage".to_string(), 50051, tls_config);
+/// ```
+pub fn load_config(
+    ca_cert: &str,
+    client_identity: Option<(&str, &str)>,
+    domain_name: Option<&str>,
+) -> Result<ClientTlsConfig, io::Error> {
+    let mut config =
+        ClientTlsConfig::new().ca_certificate(Certificate::from_pem(fs::read(ca_cert)?));
+    if let Some((cert, key)) = client_identity {
+        config = config.identity(Identity::from_pem(fs::read(cert)?, fs::read(key)?));
+    }
+    if let Some(domain_name) = domain_name {
+        config = config.domain_name(domain_name);
+    }
+    Ok(config)
+}
+
+/// Connects a channel to the provided server address, using TLS if a configuration is provided
+pub async fn connect(address: String, config: Option<&ClientTlsConfig>) -> Result<Channel, Error> {
+    match config {
+        Some(config) => {
+            Endpoint::from_shared(to_https(&address))?
+                .tls_config(config.clone())?
+                .connect()
+                .await
+        }
+        None => Endpoint::from_shared(address)?.connect().await,
+    }
+}
+
+/// Returns the provided address using the `https` scheme, which is required for TLS connections
+fn to_https(address: &str) -> String {
+    match address.split_once("://") {
+        Some((_, authority)) => format!("https://{}", authority),
+        None => format!("https://{}", address),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_to_https() {
+        assert_eq!(
+            to_https("http://localhost:50051"),
+            "https://localhost:50051"
+        );
+        assert_eq!(
+            to_https("https://localhost:50051"),
+            "https://localhost:50051"
+        );
+        assert_eq!(to_https("localhost:50051"), "https://localhost:50051");
+    }
+
+    #[test]
+    fn test_load_config() {
+        let result = load_config("/does/not/exist.crt", None, None);
+        assert!(result.is_err());
+    }
+}
@@ -1,7 +1,8 @@
 //! Integration Tests
 pub mod utils;
 
-use lib_common::grpc::Client as GrpcClient;
+use lib_common::time::Duration;
+use svc_storage_client_grpc::client::Client as GrpcClient;
 use svc_storage_client_grpc::prelude::Id;
 use utils::resources::*;
 use utils::{assert_init_done, get_clients};
@@ -18,6 +19,9 @@ async fn test_adsb_scenario() {
 
     test_valid(&client, inserted.list.len()).await;
 
+    // Check if we can page through all messages using cursors
+    test_search_all(&client, inserted.list.len()).await;
+
     // Check if we can get a single message based on their id
     let _object_from_db: Object = get_by_id(&client, &inserted.list[0].id).await;
 
@@ -96,6 +100,20 @@ async fn test_vertipad_scenario() {
     // Check if we can delete the vertipad
     delete_one(&client, &new_object.id).await;
 
+    // Check if we can insert multiple vertipads at once
+    let vertiports = vertiport::get_list().await;
+    let batch_objects = test_insert_batch(
+        &client,
+        vec![
+            mock::get_data_obj_for_vertiport(&vertiports.list[0]),
+            mock::get_data_obj_for_vertiport(&vertiports.list[1]),
+        ],
+    )
+    .await;
+    for object in batch_objects {
+        delete_one(&client, &object.id).await;
+    }
+
     test_filtered(&client).await;
 }
 
@@ -115,16 +133,50 @@ async fn test_vehicle_scenario() {
     let _object_from_db: Object = get_by_id(&client, &inserted.list[0].id).await;
 
     // Check if we can insert a new vehicle
-    // TODO: It seems like there are currently no checks created in the database for referencing
-    // IDs. Once added, the mock data should be updated to contain valid IDs as well.
-    let new_object = insert_one(&client, mock::get_data_obj()).await;
+    let vehicle_models = vehicle_model::get_list().await;
+    let mut data = mock::get_data_obj();
+    data.vehicle_model_id = vehicle_models.list[0].id.clone();
+    let new_object = insert_one(&client, data).await;
 
     // Check if we can update the newly inserted vehicle with new data
-    test_update_one(&client, &new_object.id, mock::get_data_obj()).await;
+    let mut data = mock::get_data_obj();
+    data.vehicle_model_id = vehicle_models.list[1].id.clone();
+    test_update_one(&client, &new_object.id, data).await;
 
     // Check if we can delete the vehicle
     delete_one(&client, &new_object.id).await;
 
+    // Check if we can restore the deleted vehicle
+    restore_one(&client, &new_object.id).await;
+    delete_one(&client, &new_object.id).await;
+
+    test_filtered(&client).await;
+}
+
+#[tokio::test]
+async fn test_vehicle_model_scenario() {
+    assert_init_done().await;
+
+    use vehicle_model::*;
+
+    let client = get_clients().vehicle_model;
+    assert_eq!(client.get_name(), NAME);
+    let inserted: &List = get_list().await;
+
+    test_not_deleted(&client, inserted.list.len()).await;
+
+    // Check if we can get a single vehicle_model based on their id
+    let _object_from_db: Object = get_by_id(&client, &inserted.list[0].id).await;
+
+    // Check if we can insert a new vehicle_model
+    let new_object = insert_one(&client, mock::get_data_obj()).await;
+
+    // Check if we can update the newly inserted vehicle_model with new data
+    test_update_one(&client, &new_object.id, mock::get_data_obj()).await;
+
+    // Check if we can delete the vehicle_model
+    delete_one(&client, &new_object.id).await;
+
     test_filtered(&client).await;
 }
 
@@ -173,14 +225,18 @@ async fn test_flight_plan_scenario() {
     let _object_from_db: Object = get_by_id(&client, &inserted.list[0].id).await;
 
     // Check if we can insert a new flight plan using a existing object's data so we know the related
-    // IDs are valid.
-    let new_object = insert_one(&client, inserted.list[0].data.clone().unwrap()).await;
+    // IDs are valid. The timeslots are moved so they don't conflict with the existing object.
+    let new_object = insert_one(
+        &client,
+        move_timeslots(inserted.list[0].data.clone().unwrap(), Duration::days(100)),
+    )
+    .await;
 
     // Check if we can update the newly inserted flight_plan with different data
     test_update_one(
         &client,
         &new_object.id,
-        inserted.list[1].data.clone().unwrap(),
+        move_timeslots(inserted.list[1].data.clone().unwrap(), Duration::days(100)),
     )
     .await;
 
@@ -188,6 +244,31 @@ async fn test_flight_plan_scenario() {
     delete_one(&client, &new_object.id).await;
 
     test_filtered(&client).await;
+
+    // Check if the flight plans reserving a timeslot are returned as conflicts
+    test_get_conflicts(&inserted.list[0]).await;
+
+    // State transitions and timeslot reservations are not checked by the stub backends
+    #[cfg(not(any(feature = "stub_backends", feature = "stub_client")))]
+    {
+        // Check if concurrent state transitions can't bypass each other
+        test_concurrent_transitions(
+            &client,
+            move_timeslots(inserted.list[0].data.clone().unwrap(), Duration::days(365)),
+        )
+        .await;
+
+        // Check if conflicting timeslot reservations are rejected
+        test_timeslot_conflicts(&client, &inserted.list[0]).await;
+        test_insert_batch_conflicts(&client, &inserted.list[0]).await;
+
+        // Check if cancelled flight plans release their timeslots
+        test_cancelled_release(
+            &client,
+            move_timeslots(inserted.list[0].data.clone().unwrap(), Duration::days(300)),
+        )
+        .await;
+    }
 }
 
 #[tokio::test]
@@ -202,6 +283,7 @@ async fn test_flight_plan_parcel_scenario() {
     let inserted: &List = get_list().await;
 
     test_valid(&client, inserted.list.len()).await;
+    test_search_stream(&client, inserted.list.len()).await;
 
     // Check if we can get a single flight_plan_parcel based on their id
     let _object_from_db: Object = get_by_id(
@@ -363,6 +445,7 @@ async fn test_itinerary_flight_plan_scenario() {
     let result = client
         .unlink(Id {
             id: itineraries.list[0].id.clone(),
+            include_archived: false,
         })
         .await;
     it_debug!("{:?}", result);
@@ -444,6 +527,7 @@ async fn test_parcel_scan_scenario() {
     let inserted: &List = get_list().await;
 
     test_not_deleted(&client, inserted.list.len()).await;
+    test_search_stream(&client, inserted.list.len()).await;
 
     // Check if we can get a single parcel_scan based on their id
     let _object_from_db: Object = get_by_id(&client, &inserted.list[0].id).await;
@@ -469,8 +553,8 @@ async fn test_parcel_scan_scenario() {
     )
     .await;
 
-    // Check if we can delete the parcel_scan
-    delete_one(&client, &new_object.id).await;
+    // Check if we receive a change event when deleting the parcel_scan
+    test_watch_delete(&client, &new_object.id).await;
 
     test_filtered(&client).await;
 }
@@ -504,6 +588,7 @@ async fn test_user_group_scenario() {
     let result = client
         .unlink(Id {
             id: users.list[0].id.clone(),
+            include_archived: false,
         })
         .await;
     it_debug!("{:?}", result);
@@ -539,6 +624,7 @@ async fn test_group_user_scenario() {
     let result = client
         .unlink(Id {
             id: groups.list[0].id.clone(),
+            include_archived: false,
         })
         .await;
     it_debug!("{:?}", result);
@@ -574,6 +660,7 @@ async fn test_vehicle_group_scenario() {
     let result = client
         .unlink(Id {
             id: vehicles.list[0].id.clone(),
+            include_archived: false,
         })
         .await;
     it_debug!("{:?}", result);
@@ -609,6 +696,7 @@ async fn test_group_vehicle_scenario() {
     let result = client
         .unlink(Id {
             id: groups.list[0].id.clone(),
+            include_archived: false,
         })
         .await;
     it_debug!("{:?}", result);
@@ -644,6 +732,7 @@ async fn test_vertipad_group_scenario() {
     let result = client
         .unlink(Id {
             id: vertipads.list[0].id.clone(),
+            include_archived: false,
         })
         .await;
     it_debug!("{:?}", result);
@@ -682,6 +771,7 @@ async fn test_group_vertipad_scenario() {
     let result = client
         .unlink(Id {
             id: groups.list[0].id.clone(),
+            include_archived: false,
         })
         .await;
     it_debug!("{:?}", result);
@@ -717,6 +807,7 @@ async fn test_vertiport_group_scenario() {
     let result = client
         .unlink(Id {
             id: vertiports.list[0].id.clone(),
+            include_archived: false,
         })
         .await;
     it_debug!("{:?}", result);
@@ -755,6 +846,7 @@ async fn test_group_vertiport_scenario() {
     let result = client
         .unlink(Id {
             id: groups.list[0].id.clone(),
+            include_archived: false,
         })
         .await;
     it_debug!("{:?}", result);
@@ -804,8 +804,20 @@ async fn scenario_telemetry(client: &AdsbClient) -> Result<(), Box<dyn std::erro
     }
 
     // clean up test data
-    client.delete(Id { id: id_1 }).await.unwrap();
-    client.delete(Id { id: id_2 }).await.unwrap();
+    client
+        .delete(Id {
+            id: id_1,
+            include_archived: false,
+        })
+        .await
+        .unwrap();
+    client
+        .delete(Id {
+            id: id_2,
+            include_archived: false,
+        })
+        .await
+        .unwrap();
 
     Ok(())
 }
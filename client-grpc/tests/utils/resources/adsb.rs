@@ -1,6 +1,7 @@
 //! Adsb test helper functions
 
 use crate::utils::get_clients;
+use futures::TryStreamExt;
 use svc_storage_client_grpc::prelude::*;
 use svc_storage_client_grpc::resources::AdsbClient;
 use tokio::sync::OnceCell;
@@ -39,7 +40,10 @@ pub async fn get_list() -> &'static List {
             assert!(response.clone().data.is_some());
         }
 
-        List { list: objects }
+        List {
+            list: objects,
+            ..Default::default()
+        }
     })
     .await
 }
@@ -60,9 +64,36 @@ pub async fn test_valid(client: &AdsbClient, num_expected: usize) {
     assert_eq!(result.unwrap().into_inner().list.len(), num_expected);
 }
 
+/// get all objects from the database using a cursor based stream
+pub async fn test_search_all(client: &AdsbClient, num_expected: usize) {
+    let message_filter =
+        AdvancedSearchFilter::search_is_not_null("message_type".to_owned()).results_per_page(2);
+
+    it_info!("Starting search_all {}", NAME);
+    let result: Result<Vec<Object>, tonic::Status> =
+        client.search_all(message_filter).try_collect().await;
+
+    it_debug!("{:?}", result);
+    assert!(result.is_ok());
+
+    let objects = result.unwrap();
+    assert_eq!(objects.len(), num_expected);
+
+    // Make sure we didn't get any duplicates while paging through the results
+    let mut ids: Vec<&String> = objects.iter().map(|object| &object.id).collect();
+    ids.sort();
+    ids.dedup();
+    assert_eq!(ids.len(), num_expected);
+}
+
 /// Get object for id
 pub async fn get_by_id(client: &AdsbClient, id: &str) -> Object {
-    let result = client.get_by_id(Id { id: id.to_owned() }).await;
+    let result = client
+        .get_by_id(Id {
+            id: id.to_owned(),
+            include_archived: false,
+        })
+        .await;
     it_debug!("{:?}", result);
     assert!(result.is_ok());
     let from_db: Object = result.unwrap().into_inner();
@@ -73,7 +104,12 @@ pub async fn get_by_id(client: &AdsbClient, id: &str) -> Object {
 
 /// Delete for given id
 pub async fn delete_one(client: &AdsbClient, id: &str) {
-    let result = client.delete(Id { id: id.to_owned() }).await;
+    let result = client
+        .delete(Id {
+            id: id.to_owned(),
+            include_archived: false,
+        })
+        .await;
     it_debug!("{:?}", result);
     assert!(result.is_ok());
 }
@@ -101,6 +137,7 @@ pub async fn test_update_one(client: &AdsbClient, id: &str, new_data: Data) {
     let object = UpdateObject {
         id: id.to_owned(),
         data: Some(new_data.clone()),
+        expected_updated_at: None,
         mask: None,
     };
     let result = client.update(object.clone()).await;
@@ -1,7 +1,7 @@
 //! flight_plan test helper functions
 
 use crate::utils::get_clients;
-use lib_common::time::{Duration, Utc};
+use lib_common::time::{DateTime, Duration, Timestamp, Utc};
 use svc_storage_client_grpc::prelude::*;
 use svc_storage_client_grpc::resources::FlightPlanClient;
 use tokio::sync::OnceCell;
@@ -20,18 +20,24 @@ pub async fn get_list() -> &'static List {
         let vertipads = super::vertipad::get_list().await;
         let pilots = super::pilot::get_list().await;
 
-        // generate 5 random future flight_plans
+        // generate 5 random future flight_plans, departing on different days so their timeslots
+        // don't conflict
+        let now = Utc::now();
         let mut data: Vec<Data> = vec![];
-        for _ in 0..5 {
-            let mut object = mock::get_future_data_obj();
+        for index in 0..5 {
+            let mut object =
+                set_departure(mock::get_future_data_obj(), now + Duration::days(index + 1));
             object.origin_vertipad_id = vertipads.list[0].id.clone();
             object.target_vertipad_id = vertipads.list[2].id.clone();
             object.vehicle_id = vehicles.list[0].id.clone();
             data.push(object);
         }
-        // generate 5 random completed flight_plans
+        // generate 5 random completed flight_plans, departing on different days as well
         for index in 0..5 {
-            let mut object = mock::get_past_data_obj();
+            let mut object = set_departure(
+                mock::get_past_data_obj(),
+                now - Duration::days(index as i64 + 1),
+            );
             object.origin_vertipad_id = vertipads.list[2].id.clone();
             object.target_vertipad_id = vertipads.list[0].id.clone();
             object.vehicle_id = vehicles.list[1].id.clone();
@@ -56,7 +62,10 @@ pub async fn get_list() -> &'static List {
             assert!(response.clone().data.is_some());
         }
 
-        List { list: objects }
+        List {
+            list: objects,
+            ..Default::default()
+        }
     })
     .await
 }
@@ -79,7 +88,12 @@ pub async fn test_not_deleted(client: &FlightPlanClient, num_expected: usize) {
 
 // Get object for id
 pub async fn get_by_id(client: &FlightPlanClient, id: &str) -> Object {
-    let result = client.get_by_id(Id { id: id.to_owned() }).await;
+    let result = client
+        .get_by_id(Id {
+            id: id.to_owned(),
+            include_archived: false,
+        })
+        .await;
     it_debug!("{:?}", result);
     assert!(result.is_ok());
     let from_db: Object = result.unwrap().into_inner();
@@ -90,7 +104,12 @@ pub async fn get_by_id(client: &FlightPlanClient, id: &str) -> Object {
 
 // Delete for given id
 pub async fn delete_one(client: &FlightPlanClient, id: &str) {
-    let result = client.delete(Id { id: id.to_owned() }).await;
+    let result = client
+        .delete(Id {
+            id: id.to_owned(),
+            include_archived: false,
+        })
+        .await;
     it_debug!("{:?}", result);
     assert!(result.is_ok());
 }
@@ -137,6 +156,7 @@ pub async fn test_update_one(client: &FlightPlanClient, id: &str, new_data: Data
     let object = UpdateObject {
         id: id.to_owned(),
         data: Some(new_data.clone()),
+        expected_updated_at: None,
         mask: None,
     };
     it_debug!("Update object: {:?}", object);
@@ -167,3 +187,261 @@ pub async fn test_update_one(client: &FlightPlanClient, id: &str, new_data: Data
     assert_eq!(data.vehicle_id, new_data.vehicle_id);
     assert_eq!(data.weather_conditions, new_data.weather_conditions);
 }
+
+/// Returns the provided data with all timeslots moved by the given offset, so it doesn't
+/// conflict with the timeslots reserved by the provided data
+pub fn move_timeslots(data: Data, offset: Duration) -> Data {
+    let move_timeslot = |timeslot: Option<Timestamp>| {
+        timeslot.map(|timeslot| {
+            let timeslot: DateTime<Utc> = timeslot.into();
+            (timeslot + offset).into()
+        })
+    };
+    Data {
+        origin_timeslot_start: move_timeslot(data.origin_timeslot_start),
+        origin_timeslot_end: move_timeslot(data.origin_timeslot_end),
+        target_timeslot_start: move_timeslot(data.target_timeslot_start),
+        target_timeslot_end: move_timeslot(data.target_timeslot_end),
+        ..data
+    }
+}
+
+/// Returns the provided data with all timeslots moved, so the origin timeslot starts at the given
+/// departure time
+pub fn set_departure(data: Data, departure: DateTime<Utc>) -> Data {
+    let offset = match &data.origin_timeslot_start {
+        Some(start) => departure - DateTime::<Utc>::from(start.clone()),
+        None => Duration::zero(),
+    };
+    move_timeslots(data, offset)
+}
+
+/// Races two conflicting state transitions of the same flight plan, only one of them should be applied
+pub async fn test_concurrent_transitions(client: &FlightPlanClient, data: Data) {
+    let object = insert_one(
+        client,
+        Data {
+            flight_status: FlightStatus::Boarding as i32,
+            ..data
+        },
+    )
+    .await;
+
+    // Neither IN_FLIGHT nor CANCELLED can transition into the other
+    let update = |flight_status: FlightStatus| UpdateObject {
+        id: object.id.clone(),
+        data: Some(Data {
+            flight_status: flight_status as i32,
+            ..object.data.clone().unwrap()
+        }),
+        expected_updated_at: None,
+        mask: Some(FieldMask {
+            paths: vec![String::from("flight_status")],
+        }),
+    };
+    let (in_flight, cancelled) = tokio::join!(
+        client.update(update(FlightStatus::InFlight)),
+        client.update(update(FlightStatus::Cancelled))
+    );
+    it_debug!("{:?}, {:?}", in_flight, cancelled);
+
+    let applied: Vec<FlightStatus> = [
+        (FlightStatus::InFlight, in_flight),
+        (FlightStatus::Cancelled, cancelled),
+    ]
+    .into_iter()
+    .filter_map(|(flight_status, result)| {
+        let validation_result = result.ok()?.into_inner().validation_result?;
+        validation_result.success.then_some(flight_status)
+    })
+    .collect();
+    assert_eq!(applied.len(), 1);
+
+    let data = get_by_id(client, &object.id).await.data.unwrap();
+    assert_eq!(data.flight_status, applied[0] as i32);
+}
+
+/// Inserts and updates conflicting with the timeslots reserved by the provided flight plan should
+/// be rejected with a failed validation result
+pub async fn test_timeslot_conflicts(client: &FlightPlanClient, reserved: &Object) {
+    let reserved_data = reserved.data.clone().unwrap();
+
+    let result = client.insert(reserved_data.clone()).await;
+    it_debug!("{:?}", result);
+    assert!(result.is_ok());
+    let response: Response = result.unwrap().into_inner();
+    assert!(response.object.is_none());
+    let validation_result = response.validation_result.unwrap();
+    assert!(!validation_result.success);
+    assert!(validation_result
+        .errors
+        .iter()
+        .any(|error| error.field == "origin_vertipad_id"));
+
+    // A flight plan which doesn't conflict can't be moved into the reserved timeslot either
+    let object = insert_one(
+        client,
+        move_timeslots(reserved_data.clone(), Duration::days(200)),
+    )
+    .await;
+    let result = client
+        .update(UpdateObject {
+            id: object.id.clone(),
+            data: Some(reserved_data),
+            expected_updated_at: None,
+            mask: None,
+        })
+        .await;
+    it_debug!("{:?}", result);
+    assert!(result.is_ok());
+    let response: Response = result.unwrap().into_inner();
+    assert!(!response.validation_result.unwrap().success);
+
+    let data = get_by_id(client, &object.id).await.data.unwrap();
+    assert_eq!(data, object.data.unwrap());
+}
+
+/// Conflicting items of a batch should only prevent the other items from being inserted if
+/// `all_or_nothing` is set
+pub async fn test_insert_batch_conflicts(client: &FlightPlanClient, reserved: &Object) {
+    let reserved_data = reserved.data.clone().unwrap();
+    let list = vec![
+        reserved_data.clone(),
+        move_timeslots(reserved_data, Duration::days(400)),
+    ];
+
+    let result = client
+        .insert_batch(InsertBatchRequest {
+            list: list.clone(),
+            all_or_nothing: true,
+        })
+        .await;
+    it_debug!("{:?}", result);
+    assert!(result.is_ok());
+    let response = result.unwrap().into_inner();
+    assert_eq!(response.inserted_count, 0);
+    assert!(response.results.iter().all(|result| result.id.is_none()));
+    assert!(
+        !response.results[0]
+            .validation_result
+            .clone()
+            .unwrap()
+            .success
+    );
+
+    let result = client
+        .insert_batch(InsertBatchRequest {
+            list: list.clone(),
+            all_or_nothing: false,
+        })
+        .await;
+    it_debug!("{:?}", result);
+    assert!(result.is_ok());
+    let response = result.unwrap().into_inner();
+    assert_eq!(response.inserted_count, 1);
+    assert_eq!(response.results.len(), list.len());
+
+    // Only the conflicting item should have been rejected
+    let conflicting = &response.results[0];
+    assert!(conflicting.id.is_none());
+    assert!(conflicting
+        .validation_result
+        .clone()
+        .unwrap()
+        .errors
+        .iter()
+        .any(|error| error.field == "origin_vertipad_id"));
+
+    let clean = &response.results[1];
+    assert!(clean.validation_result.clone().unwrap().success);
+    let object = get_by_id(client, clean.id.as_ref().unwrap()).await;
+    assert_eq!(object.data.as_ref(), Some(&list[1]));
+}
+
+/// Cancelled flight plans should release their timeslots
+pub async fn test_cancelled_release(client: &FlightPlanClient, data: Data) {
+    let object = insert_one(
+        client,
+        Data {
+            flight_status: FlightStatus::Draft as i32,
+            ..data.clone()
+        },
+    )
+    .await;
+
+    let result = client
+        .update(UpdateObject {
+            id: object.id.clone(),
+            data: Some(Data {
+                flight_status: FlightStatus::Cancelled as i32,
+                ..object.data.clone().unwrap()
+            }),
+            expected_updated_at: None,
+            mask: Some(FieldMask {
+                paths: vec![String::from("flight_status")],
+            }),
+        })
+        .await;
+    it_debug!("{:?}", result);
+    assert!(result.is_ok());
+    assert!(
+        result
+            .unwrap()
+            .into_inner()
+            .validation_result
+            .unwrap()
+            .success
+    );
+
+    // The same timeslots can be reserved again
+    let _object = insert_one(client, data).await;
+}
+
+/// The conflicts of a timeslot should include the flight plans reserving (part of) it
+pub async fn test_get_conflicts(reserved: &Object) {
+    let client = get_clients().flight_plan_schedule;
+    let data = reserved.data.clone().unwrap();
+    let get_conflicts = |start: Option<Timestamp>, end: Option<Timestamp>, exclude: bool| {
+        client.get_conflicts(ConflictsRequest {
+            vertipad_id: data.origin_vertipad_id.clone(),
+            timeslot_start: start,
+            timeslot_end: end,
+            exclude_id: exclude.then(|| reserved.id.clone()),
+        })
+    };
+
+    let result = get_conflicts(
+        data.origin_timeslot_start.clone(),
+        data.origin_timeslot_end.clone(),
+        false,
+    )
+    .await;
+    it_debug!("{:?}", result);
+    assert!(result.is_ok());
+    let list: List = result.unwrap().into_inner();
+    assert!(list.list.iter().any(|object| object.id == reserved.id));
+
+    // The reserved flight plan can be excluded, when it's being rescheduled for example
+    let result = get_conflicts(
+        data.origin_timeslot_start.clone(),
+        data.origin_timeslot_end.clone(),
+        true,
+    )
+    .await;
+    it_debug!("{:?}", result);
+    assert!(result.is_ok());
+    let list: List = result.unwrap().into_inner();
+    assert!(!list.list.iter().any(|object| object.id == reserved.id));
+
+    // Timeslots starting when the reservation ends don't conflict
+    let result = get_conflicts(
+        data.origin_timeslot_end.clone(),
+        move_timeslots(data.clone(), Duration::hours(1)).origin_timeslot_end,
+        false,
+    )
+    .await;
+    it_debug!("{:?}", result);
+    assert!(result.is_ok());
+    let list: List = result.unwrap().into_inner();
+    assert!(!list.list.iter().any(|object| object.id == reserved.id));
+}
@@ -1,6 +1,7 @@
 //! FlightPlanParcel test helper functions
 
 use crate::utils::{get_clients, hashmap_from_ids};
+use futures::TryStreamExt;
 use std::collections::HashMap;
 use svc_storage_client_grpc::prelude::*;
 use svc_storage_client_grpc::resources::FlightPlanParcelClient;
@@ -68,6 +69,22 @@ pub async fn test_valid(client: &FlightPlanParcelClient, num_expected: usize) {
     assert_eq!(result.unwrap().into_inner().list.len(), num_expected);
 }
 
+// test get all objects from the database using the streaming search
+pub async fn test_search_stream(client: &FlightPlanParcelClient, num_expected: usize) {
+    let not_deleted_filter = AdvancedSearchFilter::search_is_not_null("acquire".to_owned());
+
+    it_info!("Starting search_stream {}", NAME);
+    let result = client.search_stream(not_deleted_filter).await;
+    assert!(result.is_ok());
+
+    let result: Result<Vec<RowData>, tonic::Status> =
+        result.unwrap().into_inner().try_collect().await;
+    it_debug!("{:?}", result);
+    assert!(result.is_ok());
+
+    assert_eq!(result.unwrap().len(), num_expected);
+}
+
 // Get object for id
 pub async fn get_by_id(client: &FlightPlanParcelClient, ids: &Ids) -> Object {
     let result = client.get_by_id(ids.clone()).await;
@@ -138,7 +155,12 @@ pub async fn test_filtered(client: &FlightPlanParcelClient) {
 
 // Get linked objects for id
 pub async fn get_linked(client: FlightPlanParcelClient, id: &String) -> parcel::List {
-    let result = client.get_linked(Id { id: id.to_owned() }).await;
+    let result = client
+        .get_linked(Id {
+            id: id.to_owned(),
+            include_archived: false,
+        })
+        .await;
     it_debug!("{:?}", result);
     assert!(result.is_ok());
     let from_db: parcel::List = result.unwrap().into_inner();
@@ -149,7 +171,12 @@ pub async fn get_linked(client: FlightPlanParcelClient, id: &String) -> parcel::
 
 // check linked id for id
 pub async fn check_linked_ids(client: &FlightPlanParcelClient, id: &str) -> IdList {
-    let result = client.get_linked_ids(Id { id: id.to_owned() }).await;
+    let result = client
+        .get_linked_ids(Id {
+            id: id.to_owned(),
+            include_archived: false,
+        })
+        .await;
     it_debug!("{:?}", result);
     assert!(result.is_ok());
     let from_db: IdList = result.unwrap().into_inner();
@@ -166,6 +193,7 @@ pub async fn test_update_one(client: &FlightPlanParcelClient, ids: &[FieldValue]
     let object = UpdateObject {
         ids: ids.to_owned(),
         data: Some(new_data),
+        expected_updated_at: None,
         mask: None,
     };
     let result = client.update(object.clone()).await;
@@ -55,7 +55,10 @@ pub async fn get_list() -> &'static List {
             objects.push(child.clone());
         }
 
-        List { list: objects }
+        List {
+            list: objects,
+            ..Default::default()
+        }
     })
     .await
 }
@@ -78,7 +81,12 @@ pub async fn test_not_deleted(client: &GroupClient, num_expected: usize) {
 
 // Get object for id
 pub async fn get_by_id(client: &GroupClient, id: &str) -> Object {
-    let result = client.get_by_id(Id { id: id.to_owned() }).await;
+    let result = client
+        .get_by_id(Id {
+            id: id.to_owned(),
+            include_archived: false,
+        })
+        .await;
     it_debug!("{:?}", result);
     assert!(result.is_ok());
     let from_db: Object = result.unwrap().into_inner();
@@ -89,7 +97,12 @@ pub async fn get_by_id(client: &GroupClient, id: &str) -> Object {
 
 // Delete for given id
 pub async fn delete_one(client: &GroupClient, id: &str) {
-    let result = client.delete(Id { id: id.to_owned() }).await;
+    let result = client
+        .delete(Id {
+            id: id.to_owned(),
+            include_archived: false,
+        })
+        .await;
     it_debug!("{:?}", result);
     assert!(result.is_ok());
 }
@@ -133,6 +146,7 @@ pub async fn test_update_one(client: &GroupClient, id: &str, new_data: Data) {
     let object = UpdateObject {
         id: id.to_owned(),
         data: Some(new_data.clone()),
+        expected_updated_at: None,
         mask: None,
     };
     let result = client.update(object.clone()).await;
@@ -38,7 +38,12 @@ pub async fn create_single_link(client: &LinkClient, id: &str, link_ids: &mut Ve
 
 // Get linked objects for id
 pub async fn get_linked(client: &LinkClient, id: &String) -> vertiport::List {
-    let result = client.get_linked(Id { id: id.to_owned() }).await;
+    let result = client
+        .get_linked(Id {
+            id: id.to_owned(),
+            include_archived: false,
+        })
+        .await;
     it_debug!("{:?}", result);
     assert!(result.is_ok());
     let from_db: vertiport::List = result.unwrap().into_inner();
@@ -49,7 +54,12 @@ pub async fn get_linked(client: &LinkClient, id: &String) -> vertiport::List {
 
 // check linked ids for id
 pub async fn check_linked_ids(client: &LinkClient, id: &str, existing: &vertiport::List) {
-    let result = client.get_linked_ids(Id { id: id.to_owned() }).await;
+    let result = client
+        .get_linked_ids(Id {
+            id: id.to_owned(),
+            include_archived: false,
+        })
+        .await;
     it_debug!("{:?}", result);
     assert!(result.is_ok());
     let from_db: IdList = result.unwrap().into_inner();
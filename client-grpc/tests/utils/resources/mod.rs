@@ -17,6 +17,7 @@ pub mod user;
 pub mod user_group;
 pub mod vehicle;
 pub mod vehicle_group;
+pub mod vehicle_model;
 pub mod vertipad;
 pub mod vertipad_group;
 pub mod vertiport;
@@ -1,5 +1,6 @@
 //! ParcelScan test helper functions
 use crate::utils::get_clients;
+use futures::TryStreamExt;
 use lib_common::time::Utc;
 use svc_storage_client_grpc::prelude::*;
 use svc_storage_client_grpc::resources::ParcelScanClient;
@@ -44,7 +45,10 @@ pub async fn get_list() -> &'static List {
             assert!(response.clone().data.is_some());
         }
 
-        List { list: objects }
+        List {
+            list: objects,
+            ..Default::default()
+        }
     })
     .await
 }
@@ -65,9 +69,52 @@ pub async fn test_not_deleted(client: &ParcelScanClient, num_expected: usize) {
     assert_eq!(result.unwrap().into_inner().list.len(), num_expected);
 }
 
+// get all objects from the database which are not deleted using the streaming search
+pub async fn test_search_stream(client: &ParcelScanClient, num_expected: usize) {
+    let not_deleted_filter = AdvancedSearchFilter::search_is_null("deleted_at".to_owned());
+
+    it_info!("Starting search_stream {}", NAME);
+    let result = client.search_stream(not_deleted_filter).await;
+    assert!(result.is_ok());
+
+    let result: Result<Vec<Object>, tonic::Status> =
+        result.unwrap().into_inner().try_collect().await;
+    it_debug!("{:?}", result);
+    assert!(result.is_ok());
+
+    assert_eq!(result.unwrap().len(), num_expected);
+}
+
+// Watch for changes and check if an event is received when deleting the object for given id
+pub async fn test_watch_delete(client: &ParcelScanClient, id: &str) {
+    it_info!("Starting watch {}", NAME);
+    let result = client.watch(AdvancedSearchFilter::default()).await;
+    assert!(result.is_ok());
+    let mut events = result.unwrap().into_inner();
+
+    delete_one(client, id).await;
+
+    // The event is sent before the delete call returns, so we don't need to wait for it
+    let mut event = None;
+    while let Some(next) = events.try_next().await.unwrap() {
+        if next.id == id {
+            event = Some(next);
+            break;
+        }
+    }
+    it_debug!("{:?}", event);
+    assert!(event.is_some());
+    assert_eq!(event.unwrap().change_type(), ChangeType::Delete);
+}
+
 // Get object for id
 pub async fn get_by_id(client: &ParcelScanClient, id: &str) -> Object {
-    let result = client.get_by_id(Id { id: id.to_owned() }).await;
+    let result = client
+        .get_by_id(Id {
+            id: id.to_owned(),
+            include_archived: false,
+        })
+        .await;
     it_debug!("{:?}", result);
     assert!(result.is_ok());
     let from_db: Object = result.unwrap().into_inner();
@@ -78,7 +125,12 @@ pub async fn get_by_id(client: &ParcelScanClient, id: &str) -> Object {
 
 // Delete for given id
 pub async fn delete_one(client: &ParcelScanClient, id: &str) {
-    let result = client.delete(Id { id: id.to_owned() }).await;
+    let result = client
+        .delete(Id {
+            id: id.to_owned(),
+            include_archived: false,
+        })
+        .await;
     it_debug!("{:?}", result);
     assert!(result.is_ok());
 }
@@ -120,6 +172,7 @@ pub async fn test_update_one(client: &ParcelScanClient, id: &str, new_data: Data
     let object = UpdateObject {
         id: id.to_owned(),
         data: Some(new_data.clone()),
+        expected_updated_at: None,
         mask: None,
     };
     let result = client.update(object.clone()).await;
@@ -45,7 +45,10 @@ pub async fn get_list() -> &'static List {
             assert!(response.clone().data.is_some());
         }
 
-        List { list: objects }
+        List {
+            list: objects,
+            ..Default::default()
+        }
     })
     .await
 }
@@ -68,7 +71,12 @@ pub async fn test_not_deleted(client: &ScannerClient, num_expected: usize) {
 
 // Get object for id
 pub async fn get_by_id(client: &ScannerClient, id: &str) -> Object {
-    let result = client.get_by_id(Id { id: id.to_owned() }).await;
+    let result = client
+        .get_by_id(Id {
+            id: id.to_owned(),
+            include_archived: false,
+        })
+        .await;
     it_debug!("{:?}", result);
     assert!(result.is_ok());
     let from_db: Object = result.unwrap().into_inner();
@@ -79,7 +87,12 @@ pub async fn get_by_id(client: &ScannerClient, id: &str) -> Object {
 
 // Delete for given id
 pub async fn delete_one(client: &ScannerClient, id: &str) {
-    let result = client.delete(Id { id: id.to_owned() }).await;
+    let result = client
+        .delete(Id {
+            id: id.to_owned(),
+            include_archived: false,
+        })
+        .await;
     it_debug!("{:?}", result);
     assert!(result.is_ok());
 }
@@ -129,6 +142,7 @@ pub async fn test_update_one(client: &ScannerClient, id: &str, new_data: Data) {
     let object = UpdateObject {
         id: id.to_owned(),
         data: Some(new_data.clone()),
+        expected_updated_at: None,
         mask: None,
     };
     let result = client.update(object.clone()).await;
@@ -17,6 +17,7 @@ pub async fn get_list() -> &'static List {
         assert_eq!(client.get_name(), NAME);
 
         let vertipads = super::vertipad::get_list().await;
+        let vehicle_models = super::vehicle_model::get_list().await;
 
         // create a map for our vertiport -> vertipads
         let mut vertiport_vertipads: HashMap<String, Vec<String>> = HashMap::new();
@@ -35,11 +36,13 @@ pub async fn get_list() -> &'static List {
         for index in 0..5 {
             let mut object = mock::get_data_obj();
             object.description = Some(format!("Mock vehicle {}", index + 1));
+            object.vehicle_model_id = vehicle_models.list[index].id.clone();
             data.push(object);
         }
         for (vertiport, vertipads) in &vertiport_vertipads {
             let mut object = mock::get_data_obj();
             object.description = Some(format!("Mock vehicle vertiports {}", vertiport.clone()));
+            object.vehicle_model_id = vehicle_models.list[0].id.clone();
             object.hangar_id = Some(vertiport.clone());
             object.hangar_bay_id = Some(vertipads[0].clone());
             data.push(object);
@@ -62,7 +65,10 @@ pub async fn get_list() -> &'static List {
             assert!(response.clone().data.is_some());
         }
 
-        List { list: objects }
+        List {
+            list: objects,
+            ..Default::default()
+        }
     })
     .await
 }
@@ -85,7 +91,12 @@ pub async fn test_not_deleted(client: &VehicleClient, num_expected: usize) {
 
 // Get object for id
 pub async fn get_by_id(client: &VehicleClient, id: &str) -> Object {
-    let result = client.get_by_id(Id { id: id.to_owned() }).await;
+    let result = client
+        .get_by_id(Id {
+            id: id.to_owned(),
+            include_archived: false,
+        })
+        .await;
     it_debug!("{:?}", result);
     assert!(result.is_ok());
     let from_db: Object = result.unwrap().into_inner();
@@ -96,9 +107,37 @@ pub async fn get_by_id(client: &VehicleClient, id: &str) -> Object {
 
 // Delete for given id
 pub async fn delete_one(client: &VehicleClient, id: &str) {
-    let result = client.delete(Id { id: id.to_owned() }).await;
+    let result = client
+        .delete(Id {
+            id: id.to_owned(),
+            include_archived: false,
+        })
+        .await;
+    it_debug!("{:?}", result);
+    assert!(result.is_ok());
+}
+
+// Restore archived object for given id
+pub async fn restore_one(client: &VehicleClient, id: &str) {
+    let result = client
+        .restore(Id {
+            id: id.to_owned(),
+            include_archived: false,
+        })
+        .await;
     it_debug!("{:?}", result);
     assert!(result.is_ok());
+    assert_eq!(result.unwrap().into_inner().id, *id);
+
+    // The record is no longer archived, so restoring it again should be refused
+    let result = client
+        .restore(Id {
+            id: id.to_owned(),
+            include_archived: false,
+        })
+        .await;
+    it_debug!("{:?}", result);
+    assert_eq!(result.unwrap_err().code(), tonic::Code::FailedPrecondition);
 }
 
 pub async fn insert_one(client: &VehicleClient, data: Data) -> Object {
@@ -140,6 +179,7 @@ pub async fn test_update_one(client: &VehicleClient, id: &str, new_data: Data) {
     let object = UpdateObject {
         id: id.to_owned(),
         data: Some(new_data.clone()),
+        expected_updated_at: None,
         mask: None,
     };
     let result = client.update(object.clone()).await;
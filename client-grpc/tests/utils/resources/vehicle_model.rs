@@ -0,0 +1,160 @@
+//! Vehicle Model test helper functions
+
+use crate::utils::get_clients;
+use svc_storage_client_grpc::prelude::*;
+use svc_storage_client_grpc::resources::VehicleModelClient;
+use tokio::sync::OnceCell;
+
+pub use vehicle_model::*;
+
+pub(crate) static LIST: OnceCell<List> = OnceCell::const_new();
+pub(crate) static NAME: &str = "vehicle_model";
+
+pub async fn get_list() -> &'static List {
+    LIST.get_or_init(|| async move {
+        let client = get_clients().vehicle_model;
+        assert_eq!(client.get_name(), NAME);
+
+        // generate 5 cargo vehicle models
+        let mut data: Vec<Data> = vec![];
+        for _ in 0..5 {
+            let object = mock::get_data_obj();
+            data.push(object);
+        }
+        // generate 5 passenger vehicle models
+        for _ in 0..5 {
+            let mut object = mock::get_data_obj();
+            object.r#type = VehicleModelType::VtolPassenger.into();
+            data.push(object);
+        }
+
+        let mut objects = vec![];
+
+        // Insert vehicle_model for each mock object
+        for item in data {
+            it_info!("Starting insert {}", NAME);
+            let result = client.insert(item.clone()).await;
+            it_debug!("{:?}", result);
+            assert!(result.is_ok());
+
+            let response: Response = (result.unwrap()).into_inner();
+            assert!(response.object.is_some());
+            let response = response.object.unwrap();
+            objects.push(response.clone());
+
+            assert!(response.clone().data.is_some());
+        }
+
+        List {
+            list: objects,
+            ..Default::default()
+        }
+    })
+    .await
+}
+
+// get all objects from the database which are not deleted (eg: the `deleted_at` column is NULL
+pub async fn test_not_deleted(client: &VehicleModelClient, num_expected: usize) {
+    let not_deleted_filter = AdvancedSearchFilter::search_is_null("deleted_at".to_owned())
+        .page_number(1)
+        .results_per_page(50);
+
+    // Check if all vehicle_models can be retrieved from the backend
+    it_info!("Starting search {}", NAME);
+    let result = client.search(not_deleted_filter.clone()).await;
+
+    it_debug!("{:?}", result);
+    assert!(result.is_ok());
+
+    let list = result.unwrap().into_inner();
+    assert_eq!(list.list.len(), num_expected);
+    assert_eq!(list.total_count, Some(num_expected as i64));
+    assert!(!list.has_more);
+}
+
+// Get object for id
+pub async fn get_by_id(client: &VehicleModelClient, id: &str) -> Object {
+    let result = client
+        .get_by_id(Id {
+            id: id.to_owned(),
+            include_archived: false,
+        })
+        .await;
+    it_debug!("{:?}", result);
+    assert!(result.is_ok());
+    let from_db: Object = result.unwrap().into_inner();
+    assert_eq!(from_db.id, *id);
+
+    from_db
+}
+
+// Delete for given id
+pub async fn delete_one(client: &VehicleModelClient, id: &str) {
+    let result = client
+        .delete(Id {
+            id: id.to_owned(),
+            include_archived: false,
+        })
+        .await;
+    it_debug!("{:?}", result);
+    assert!(result.is_ok());
+}
+
+pub async fn insert_one(client: &VehicleModelClient, data: Data) -> Object {
+    let result = client.insert(data.clone()).await;
+    it_debug!("{:?}", result);
+    assert!(result.is_ok());
+
+    let response: Response = (result.unwrap()).into_inner();
+    assert!(response.object.is_some());
+    let object = response.object.unwrap();
+
+    assert!(object.clone().data.is_some());
+    let data_from_db = object.clone().data.unwrap();
+
+    // Make sure the object created and returned from the database is the same
+    // as the object we used to insert the data
+    assert_eq!(data_from_db, data);
+
+    object
+}
+
+pub async fn test_filtered(client: &VehicleModelClient) {
+    let type_filter = AdvancedSearchFilter::search_is_null("deleted_at".to_owned())
+        .and_equals(
+            "type".to_owned(),
+            format!("{}", VehicleModelType::VtolPassenger as i32),
+        )
+        .page_number(1)
+        .results_per_page(50);
+
+    let result = client.search(type_filter.clone()).await;
+    it_debug!("{:?}", result);
+
+    assert!(result.is_ok());
+
+    // We've inserted 5 passenger vehicle models
+    assert_eq!(result.unwrap().into_inner().list.len(), 5);
+}
+
+pub async fn test_update_one(client: &VehicleModelClient, id: &str, new_data: Data) {
+    let object = UpdateObject {
+        id: id.to_owned(),
+        data: Some(new_data.clone()),
+        expected_updated_at: None,
+        mask: None,
+    };
+    let result = client.update(object.clone()).await;
+    it_debug!("{:?}", result);
+    assert!(result.is_ok());
+
+    // Test if the updated values are indeed reflected in the database
+    let result = get_by_id(client, id).await;
+    let data: Data = result.data.unwrap();
+
+    assert_eq!(data.manufacturer_id, new_data.manufacturer_id);
+    assert_eq!(data.model_name, new_data.model_name);
+    assert_eq!(data.r#type, new_data.r#type);
+    assert_eq!(data.max_payload_kg, new_data.max_payload_kg);
+    assert_eq!(data.max_range_km, new_data.max_range_km);
+}
@@ -46,7 +46,10 @@ pub async fn get_list() -> &'static List {
             assert!(response.clone().data.is_some());
         }
 
-        List { list: objects }
+        List {
+            list: objects,
+            ..Default::default()
+        }
     })
     .await
 }
@@ -69,7 +72,12 @@ pub async fn test_not_deleted(client: &VertipadClient, num_expected: usize) {
 
 // Get object for id
 pub async fn get_by_id(client: &VertipadClient, id: &str) -> Object {
-    let result = client.get_by_id(Id { id: id.to_owned() }).await;
+    let result = client
+        .get_by_id(Id {
+            id: id.to_owned(),
+            include_archived: false,
+        })
+        .await;
     it_debug!("{:?}", result);
     assert!(result.is_ok());
     let from_db: Object = result.unwrap().into_inner();
@@ -80,7 +88,12 @@ pub async fn get_by_id(client: &VertipadClient, id: &str) -> Object {
 
 // Delete for given id
 pub async fn delete_one(client: &VertipadClient, id: &str) {
-    let result = client.delete(Id { id: id.to_owned() }).await;
+    let result = client
+        .delete(Id {
+            id: id.to_owned(),
+            include_archived: false,
+        })
+        .await;
     it_debug!("{:?}", result);
     assert!(result.is_ok());
 }
@@ -104,6 +117,33 @@ pub async fn insert_one(client: &VertipadClient, data: Data) -> Object {
     object
 }
 
+pub async fn test_insert_batch(client: &VertipadClient, list: Vec<Data>) -> Vec<Object> {
+    it_info!("Starting insert_batch {}", NAME);
+    let result = client
+        .insert_batch(InsertBatchRequest {
+            list: list.clone(),
+            all_or_nothing: true,
+        })
+        .await;
+    it_debug!("{:?}", result);
+    assert!(result.is_ok());
+
+    let response = result.unwrap().into_inner();
+    assert_eq!(response.inserted_count as usize, list.len());
+    assert_eq!(response.results.len(), list.len());
+
+    // Make sure every item was inserted with the data we provided
+    let mut objects = vec![];
+    for (result, data) in response.results.iter().zip(list.iter()) {
+        assert!(result.validation_result.clone().unwrap().success);
+        let object = get_by_id(client, result.id.as_ref().unwrap()).await;
+        assert_eq!(object.data.as_ref().unwrap(), data);
+        objects.push(object);
+    }
+
+    objects
+}
+
 pub async fn test_filtered(client: &VertipadClient) {
     let vertiports = super::vertiport::get_list().await;
     let vertiport_filter = AdvancedSearchFilter::search_is_null("deleted_at".to_owned())
@@ -124,6 +164,7 @@ pub async fn test_update_one(client: &VertipadClient, id: &str, new_data: Data)
     let object = UpdateObject {
         id: id.to_owned(),
         data: Some(new_data.clone()),
+        expected_updated_at: None,
         mask: None,
     };
     let result = client.update(object.clone()).await;
@@ -23,6 +23,7 @@ fn get_types() -> Vec<String> {
         "scanner".to_owned(),
         "user".to_owned(),
         "vehicle".to_owned(),
+        "vehicle_model".to_owned(),
         "vertipad".to_owned(),
         "vertiport".to_owned(),
         "flight_plan_parcel".to_owned(),
@@ -73,6 +74,26 @@ fn build_proto(
         .build_client(client)
         .compile(&service_files, &[proto_dir])?;
 
+    // Compile admin service file, only provided by the server
+    if server {
+        get_grpc_builder_config(&format!("{}/{}", cur_dir, out_path))
+            .build_server(true)
+            .build_client(false)
+            .compile(
+                &[format!("{}/svc-storage-grpc-admin-service.proto", proto_dir)],
+                &[proto_dir],
+            )?;
+
+        // Compile audit service file, only provided by the server
+        get_grpc_builder_config(&format!("{}/{}", cur_dir, out_path))
+            .build_server(true)
+            .build_client(false)
+            .compile(
+                &[format!("{}/svc-storage-grpc-audit.proto", proto_dir)],
+                &[proto_dir],
+            )?;
+    }
+
     Ok(())
 }
 
@@ -91,6 +112,8 @@ fn get_grpc_builder_config(out_path: &str) -> tonic_build::Builder {
         .type_attribute("FilterOption", "#[derive(Eq)]")
         .type_attribute("SortOption", "#[derive(Eq)]")
         .type_attribute("SortOrder", "#[derive(num_derive::FromPrimitive)]")
+        .type_attribute("ChangeType", "#[derive(num_derive::FromPrimitive)]")
+        .type_attribute("AuditAction", "#[derive(num_derive::FromPrimitive)]")
         .type_attribute("PredicateOperator", "#[derive(num_derive::FromPrimitive)]")
         .type_attribute("ComparisonOperator", "#[derive(num_derive::FromPrimitive)]")
         .type_attribute("ScannerType", "#[derive(num_derive::FromPrimitive)]")
@@ -99,9 +122,12 @@ fn get_grpc_builder_config(out_path: &str) -> tonic_build::Builder {
         .type_attribute("FlightPriority", "#[derive(num_derive::FromPrimitive)]")
         .type_attribute("ParcelStatus", "#[derive(num_derive::FromPrimitive)]")
         .type_attribute("AuthMethod", "#[derive(num_derive::FromPrimitive)]")
+        .type_attribute("VehicleModelType", "#[derive(num_derive::FromPrimitive)]")
         // Add serde derive attributes for structs
         .type_attribute("Id", "#[derive(Serialize, Deserialize)]")
         .type_attribute("Ids", "#[derive(Serialize, Deserialize)]")
+        .type_attribute("DeleteRequest", "#[derive(Serialize, Deserialize)]")
+        .type_attribute("LinkedDeleteRequest", "#[derive(Serialize, Deserialize)]")
         .type_attribute("IdList", "#[derive(Serialize, Deserialize)]")
         .type_attribute("List", "#[derive(Serialize, Deserialize)]")
         .type_attribute("RowDataList", "#[derive(Serialize, Deserialize)]")
@@ -179,6 +205,14 @@ fn add_utoipa_attributes(
             "next_maintenance",
             "#[schema(schema_with = crate::timestamp_schema)]",
         )
+        .field_attribute(
+            "UpdateObject.expected_updated_at",
+            "#[schema(schema_with = crate::timestamp_schema)]",
+        )
+        .field_attribute(
+            "Object.deleted_at",
+            "#[schema(schema_with = crate::timestamp_schema)]",
+        )
         // Add utoipa derive attributes for structs
         .type_attribute("FieldValue", "#[derive(ToSchema, IntoParams)]")
         .type_attribute("Id", "#[derive(ToSchema, IntoParams)]")
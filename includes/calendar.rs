@@ -0,0 +1,956 @@
+use lib_common::time::{
+    DateTime, Datelike, Duration, NaiveDate, NaiveDateTime, TimeZone, Utc, Weekday,
+};
+use std::fmt;
+use std::str::FromStr;
+
+/// Time interval, from the first value (inclusive) until the second value (exclusive)
+pub type Interval = (DateTime<Utc>, DateTime<Utc>);
+
+/// Error returned when a schedule could not be parsed into a [`Calendar`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CalendarError(pub String);
+impl fmt::Display for CalendarError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+impl std::error::Error for CalendarError {}
+
+/// Recurrence frequency of an [`Event`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Frequency {
+    /// The event recurs every `interval` days
+    Daily,
+    /// The event recurs every `interval` weeks
+    Weekly,
+}
+
+/// Recurrence rule (`RRULE`) of an [`Event`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Recurrence {
+    /// recurrence frequency
+    pub frequency: Frequency,
+    /// number of days or weeks between recurrences
+    pub interval: u32,
+    /// days of the week the event occurs on, defaults to the weekday of the event's start if empty
+    pub by_day: Vec<Weekday>,
+    /// last possible start of an occurrence
+    pub until: Option<DateTime<Utc>>,
+    /// maximum number of occurrences
+    pub count: Option<u32>,
+}
+
+/// (Recurring) event of a [`Calendar`], during which the scheduled resource is unavailable
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Event {
+    /// start of the first occurrence
+    pub start: DateTime<Utc>,
+    /// duration of each occurrence
+    pub duration: Duration,
+    /// recurrence rule, the event only occurs once if not provided
+    pub recurrence: Option<Recurrence>,
+}
+
+/// Schedule of a resource, such as a vertiport, vertipad or vehicle
+///
+/// A schedule consists of events during which the resource is unavailable. Events can be provided
+/// as RFC 5545 `VEVENT` blocks, or in the compact form where each event starts with a `DTSTART`
+/// line. Each event needs a `DTSTART` and either a `DURATION` or `DTEND`, and can have an `RRULE`.
+///
+/// The [`Display`](fmt::Display) implementation returns the normalized compact form, in which
+/// each event is written as a `DTSTART` and `DURATION` line, optionally followed by an `RRULE` line.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct Calendar {
+    /// events during which the resource is unavailable
+    pub events: Vec<Event>,
+}
+
+/// Properties which are allowed in a schedule, but are not used
+const IGNORED_PROPERTIES: [&str; 8] = [
+    "VERSION",
+    "PRODID",
+    "CALSCALE",
+    "METHOD",
+    "UID",
+    "DTSTAMP",
+    "SUMMARY",
+    "DESCRIPTION",
+];
+
+impl FromStr for Calendar {
+    type Err = CalendarError;
+
+    fn from_str(schedule: &str) -> Result<Self, Self::Err> {
+        let mut events: Vec<Event> = vec![];
+        let mut current: Option<EventProperties> = None;
+        let mut in_vevent = false;
+        for (index, line) in schedule.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() {
+                continue;
+            }
+            let line_number = index + 1;
+            let line_error =
+                |error: String| CalendarError(format!("line {}: {}", line_number, error));
+
+            match line {
+                "BEGIN:VCALENDAR" | "END:VCALENDAR" => continue,
+                "BEGIN:VEVENT" => {
+                    if in_vevent {
+                        return Err(line_error(String::from(
+                            "BEGIN:VEVENT found before END:VEVENT",
+                        )));
+                    }
+                    if let Some(properties) = current.take() {
+                        events.push(properties.into_event()?);
+                    }
+                    current = Some(EventProperties::new(line_number));
+                    in_vevent = true;
+                    continue;
+                }
+                "END:VEVENT" => {
+                    match current.take() {
+                        Some(properties) if in_vevent => events.push(properties.into_event()?),
+                        _ => {
+                            return Err(line_error(String::from(
+                                "END:VEVENT found before BEGIN:VEVENT",
+                            )))
+                        }
+                    }
+                    in_vevent = false;
+                    continue;
+                }
+                _ => {}
+            }
+
+            if let Some(rule) = line.strip_prefix("RRULE:") {
+                match current.as_mut() {
+                    Some(properties) => properties.set_rule(rule).map_err(line_error)?,
+                    None => return Err(line_error(String::from("RRULE found before DTSTART"))),
+                }
+                continue;
+            }
+
+            for property in line.split(';') {
+                let (name, value) = property
+                    .split_once(':')
+                    .ok_or_else(|| line_error(format!("invalid property [{}]", property)))?;
+                let name = name.trim();
+                // Outside of VEVENT blocks, each DTSTART starts a new event
+                if name == "DTSTART" && !in_vevent {
+                    if let Some(properties) = current.take() {
+                        events.push(properties.into_event()?);
+                    }
+                    current = Some(EventProperties::new(line_number));
+                }
+                match current.as_mut() {
+                    Some(properties) => properties.set(name, value).map_err(line_error)?,
+                    None if IGNORED_PROPERTIES.contains(&name) => {}
+                    None => return Err(line_error(format!("{} found before DTSTART", name))),
+                }
+            }
+        }
+
+        if let Some(properties) = current {
+            if in_vevent {
+                return Err(CalendarError(format!(
+                    "line {}: no END:VEVENT found for BEGIN:VEVENT",
+                    properties.line
+                )));
+            }
+            events.push(properties.into_event()?);
+        }
+        Ok(Calendar { events })
+    }
+}
+
+impl fmt::Display for Calendar {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let events: Vec<String> = self.events.iter().map(|event| event.to_string()).collect();
+        f.write_str(&events.join("\n"))
+    }
+}
+
+impl fmt::Display for Event {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "DTSTART:{};DURATION:{}",
+            format_date_time(&self.start),
+            format_duration(&self.duration)
+        )?;
+        if let Some(recurrence) = &self.recurrence {
+            write!(f, "\nRRULE:{}", recurrence)?;
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for Recurrence {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let frequency = match self.frequency {
+            Frequency::Daily => "DAILY",
+            Frequency::Weekly => "WEEKLY",
+        };
+        let mut parts = vec![format!("FREQ={}", frequency)];
+        if self.interval != 1 {
+            parts.push(format!("INTERVAL={}", self.interval));
+        }
+        if !self.by_day.is_empty() {
+            let mut by_day = self.by_day.clone();
+            by_day.sort_by_key(|day| day.num_days_from_monday());
+            by_day.dedup();
+            let by_day: Vec<&str> = by_day.iter().map(format_weekday).collect();
+            parts.push(format!("BYDAY={}", by_day.join(",")));
+        }
+        if let Some(until) = &self.until {
+            parts.push(format!("UNTIL={}", format_date_time(until)));
+        }
+        if let Some(count) = self.count {
+            parts.push(format!("COUNT={}", count));
+        }
+        f.write_str(&parts.join(";"))
+    }
+}
+
+/// Properties of the event currently being parsed
+struct EventProperties {
+    /// line number the event starts on
+    line: usize,
+    start: Option<DateTime<Utc>>,
+    end: Option<DateTime<Utc>>,
+    duration: Option<Duration>,
+    recurrence: Option<Recurrence>,
+}
+
+impl EventProperties {
+    fn new(line: usize) -> Self {
+        Self {
+            line,
+            start: None,
+            end: None,
+            duration: None,
+            recurrence: None,
+        }
+    }
+
+    /// Sets the value of the provided property
+    fn set(&mut self, name: &str, value: &str) -> Result<(), String> {
+        match name {
+            "DTSTART" => set_once(&mut self.start, name, parse_date_time(value)?),
+            "DTEND" => set_once(&mut self.end, name, parse_date_time(value)?),
+            "DURATION" => set_once(&mut self.duration, name, parse_duration(value)?),
+            "RRULE" => self.set_rule(value),
+            _ if IGNORED_PROPERTIES.contains(&name) => Ok(()),
+            _ => Err(format!("unsupported property [{}]", name)),
+        }
+    }
+
+    /// Sets the recurrence, parsed from the value of an `RRULE` line
+    fn set_rule(&mut self, rule: &str) -> Result<(), String> {
+        if self.recurrence.is_some() {
+            return Err(String::from(
+                "multiple RRULE lines found for a single event",
+            ));
+        }
+        self.recurrence = Some(parse_rule(rule)?);
+        Ok(())
+    }
+
+    /// Converts the properties into an [`Event`]
+    fn into_event(self) -> Result<Event, CalendarError> {
+        let line_error = |error: &str| CalendarError(format!("line {}: {}", self.line, error));
+
+        let start = self
+            .start
+            .ok_or_else(|| line_error("no DTSTART found for event"))?;
+        let duration = match (self.duration, self.end) {
+            (Some(duration), None) => duration,
+            (None, Some(end)) => end - start,
+            _ => {
+                return Err(line_error(
+                    "either DURATION or DTEND should be provided for event",
+                ))
+            }
+        };
+        if duration <= Duration::zero() {
+            return Err(line_error("event should have a positive duration"));
+        }
+
+        Ok(Event {
+            start,
+            duration,
+            recurrence: self.recurrence,
+        })
+    }
+}
+
+/// Sets the provided field, returning an error if it already has a value
+fn set_once<T>(field: &mut Option<T>, name: &str, value: T) -> Result<(), String> {
+    if field.is_some() {
+        return Err(format!("multiple {} values found for a single event", name));
+    }
+    *field = Some(value);
+    Ok(())
+}
+
+impl Calendar {
+    /// Returns the merged intervals during which the resource is unavailable, within the provided interval
+    pub fn get_busy_intervals(&self, interval: &Interval) -> Vec<Interval> {
+        let busy = self
+            .events
+            .iter()
+            .flat_map(|event| event.get_occurrences(interval))
+            .map(|(start, end)| (start.max(interval.0), end.min(interval.1)))
+            .collect();
+        merge_intervals(busy)
+    }
+}
+
+impl Event {
+    /// Returns the intervals of all occurrences overlapping the provided interval, sorted by start
+    pub fn get_occurrences(&self, interval: &Interval) -> Vec<Interval> {
+        let (from, to) = *interval;
+        let recurrence = match &self.recurrence {
+            Some(recurrence) => recurrence,
+            None => {
+                let end = self.start + self.duration;
+                if self.start < to && end > from {
+                    return vec![(self.start, end)];
+                }
+                return vec![];
+            }
+        };
+
+        let first_day = self.start.date_naive();
+        let time = self.start.time();
+        // Occurrences need to be counted from the start if a COUNT is provided, otherwise all
+        // occurrences which end before the requested interval can be skipped.
+        let mut day = match recurrence.count {
+            Some(_) => first_day,
+            None => first_day.max((from - self.duration).date_naive()),
+        };
+        let mut count = 0;
+        let mut occurrences = vec![];
+        while day <= to.date_naive() {
+            let start = Utc.from_utc_datetime(&day.and_time(time));
+            if recurrence.matches(first_day, day) && start >= self.start {
+                if recurrence.until.map_or(false, |until| start > until)
+                    || recurrence.count.map_or(false, |max| count >= max)
+                {
+                    break;
+                }
+                count += 1;
+
+                let end = start + self.duration;
+                if start < to && end > from {
+                    occurrences.push((start, end));
+                }
+            }
+            day = match day.succ_opt() {
+                Some(day) => day,
+                None => break,
+            };
+        }
+        occurrences
+    }
+}
+
+impl Recurrence {
+    /// Returns `true` if the provided day is one of the days the recurrence occurs on, starting
+    /// from the provided first day
+    fn matches(&self, first_day: NaiveDate, day: NaiveDate) -> bool {
+        let interval = self.interval.max(1) as i64;
+        let weekday_matches = match self.frequency {
+            Frequency::Weekly if self.by_day.is_empty() => day.weekday() == first_day.weekday(),
+            _ => self.by_day.is_empty() || self.by_day.contains(&day.weekday()),
+        };
+        let period_matches = match self.frequency {
+            Frequency::Daily => (day - first_day).num_days() % interval == 0,
+            Frequency::Weekly => {
+                ((get_week_start(day) - get_week_start(first_day)).num_days() / 7) % interval == 0
+            }
+        };
+        weekday_matches && period_matches
+    }
+}
+
+/// Merges overlapping and adjacent intervals, returning the result sorted by start
+pub fn merge_intervals(mut intervals: Vec<Interval>) -> Vec<Interval> {
+    intervals.retain(|(start, end)| start < end);
+    intervals.sort();
+
+    let mut merged: Vec<Interval> = Vec::with_capacity(intervals.len());
+    for (start, end) in intervals {
+        match merged.last_mut() {
+            Some(last) if start <= last.1 => last.1 = last.1.max(end),
+            _ => merged.push((start, end)),
+        }
+    }
+    merged
+}
+
+/// Returns the parts of the provided interval which are not covered by any of the busy intervals
+pub fn get_free_intervals(interval: &Interval, busy: Vec<Interval>) -> Vec<Interval> {
+    let mut free = vec![];
+    let mut start = interval.0;
+    for (busy_start, busy_end) in merge_intervals(busy) {
+        if busy_end <= start {
+            continue;
+        }
+        if busy_start >= interval.1 {
+            break;
+        }
+        if busy_start > start {
+            free.push((start, busy_start));
+        }
+        start = busy_end;
+    }
+    if start < interval.1 {
+        free.push((start, interval.1));
+    }
+    free
+}
+
+/// Typed access to the `schedule` field of a resource
+pub trait Schedule {
+    /// Returns the raw `schedule` value
+    fn get_schedule(&self) -> Option<&str>;
+    /// Sets the raw `schedule` value
+    fn set_schedule(&mut self, schedule: Option<String>);
+
+    /// Parses the `schedule` value into a [`Calendar`], an empty [`Calendar`] is returned if no
+    /// schedule has been set
+    fn get_calendar(&self) -> Result<Calendar, CalendarError> {
+        match self.get_schedule() {
+            Some(schedule) => schedule.parse(),
+            None => Ok(Calendar::default()),
+        }
+    }
+
+    /// Sets the `schedule` value to the normalized form of the provided [`Calendar`]
+    fn set_calendar(&mut self, calendar: &Calendar) {
+        self.set_schedule(Some(calendar.to_string()));
+    }
+
+    /// Returns the parts of the provided interval during which the resource is available
+    /// according to its schedule
+    fn get_available_intervals(&self, interval: &Interval) -> Result<Vec<Interval>, CalendarError> {
+        let busy = self.get_calendar()?.get_busy_intervals(interval);
+        Ok(get_free_intervals(interval, busy))
+    }
+}
+
+/// Returns the Monday of the week the provided day is part of
+fn get_week_start(day: NaiveDate) -> NaiveDate {
+    day - Duration::days(day.weekday().num_days_from_monday() as i64)
+}
+
+/// Parses the value of an `RRULE` line into a [`Recurrence`]
+fn parse_rule(rule: &str) -> Result<Recurrence, String> {
+    let mut frequency = None;
+    let mut recurrence = Recurrence {
+        frequency: Frequency::Daily,
+        interval: 1,
+        by_day: vec![],
+        until: None,
+        count: None,
+    };
+    for part in rule.split(';') {
+        let (name, value) = part
+            .split_once('=')
+            .ok_or_else(|| format!("invalid RRULE part [{}]", part))?;
+        let value = value.trim();
+        match name.trim() {
+            "FREQ" => {
+                frequency = Some(match value {
+                    "DAILY" => Frequency::Daily,
+                    "WEEKLY" => Frequency::Weekly,
+                    _ => {
+                        return Err(format!(
+                            "unsupported FREQ [{}], should be DAILY or WEEKLY",
+                            value
+                        ))
+                    }
+                })
+            }
+            "INTERVAL" => {
+                recurrence.interval = match value.parse::<u32>() {
+                    Ok(interval) if interval > 0 => interval,
+                    _ => return Err(format!("invalid INTERVAL [{}]", value)),
+                }
+            }
+            "BYDAY" => {
+                for day in value.split(',') {
+                    recurrence.by_day.push(parse_weekday(day)?);
+                }
+            }
+            "UNTIL" => recurrence.until = Some(parse_date_time(value)?),
+            "COUNT" => {
+                recurrence.count = Some(
+                    value
+                        .parse::<u32>()
+                        .map_err(|_| format!("invalid COUNT [{}]", value))?,
+                )
+            }
+            "WKST" if value == "MO" => {}
+            _ => return Err(format!("unsupported RRULE part [{}]", part)),
+        }
+    }
+
+    recurrence.frequency = frequency.ok_or_else(|| String::from("no FREQ found in RRULE"))?;
+    recurrence
+        .by_day
+        .sort_by_key(|day| day.num_days_from_monday());
+    recurrence.by_day.dedup();
+    if recurrence.until.is_some() && recurrence.count.is_some() {
+        return Err(String::from(
+            "UNTIL and COUNT can not both be provided in RRULE",
+        ));
+    }
+    Ok(recurrence)
+}
+
+/// Parses a UTC date-time value (`YYYYMMDDTHHMMSSZ`)
+fn parse_date_time(value: &str) -> Result<DateTime<Utc>, String> {
+    let date_time = NaiveDateTime::parse_from_str(value.trim(), "%Y%m%dT%H%M%SZ").map_err(|e| {
+        format!(
+            "invalid date-time [{}], expected UTC format YYYYMMDDTHHMMSSZ: {}",
+            value, e
+        )
+    })?;
+    Ok(Utc.from_utc_datetime(&date_time))
+}
+
+/// Parses a duration value (e.g. `P1W`, `P1DT12H` or `PT14H30M`)
+fn parse_duration(value: &str) -> Result<Duration, String> {
+    let error = || format!("invalid DURATION [{}]", value);
+    let value = value.trim();
+    let chars = match value.strip_prefix('P') {
+        Some(chars) => chars,
+        None => return Err(error()),
+    };
+
+    let mut duration = Duration::zero();
+    let mut number = String::new();
+    let mut time = false;
+    let mut found = false;
+    for c in chars.chars() {
+        if c.is_ascii_digit() {
+            number.push(c);
+            continue;
+        }
+        if c == 'T' && !time && number.is_empty() {
+            time = true;
+            continue;
+        }
+        let n = number.parse::<i64>().map_err(|_| error())?;
+        number.clear();
+        duration = duration
+            + match (c, time) {
+                ('W', false) => Duration::weeks(n),
+                ('D', false) => Duration::days(n),
+                ('H', true) => Duration::hours(n),
+                ('M', true) => Duration::minutes(n),
+                ('S', true) => Duration::seconds(n),
+                _ => return Err(error()),
+            };
+        found = true;
+    }
+    if !found || !number.is_empty() {
+        return Err(error());
+    }
+    Ok(duration)
+}
+
+/// Formats a date-time value in UTC format (`YYYYMMDDTHHMMSSZ`)
+fn format_date_time(date_time: &DateTime<Utc>) -> String {
+    date_time.format("%Y%m%dT%H%M%SZ").to_string()
+}
+
+/// Formats a duration value in hours, minutes and seconds (e.g. `PT14H30M`)
+fn format_duration(duration: &Duration) -> String {
+    let seconds = duration.num_seconds();
+    let mut value = String::from("PT");
+    if seconds >= 3600 {
+        value.push_str(&format!("{}H", seconds / 3600));
+    }
+    if seconds % 3600 >= 60 {
+        value.push_str(&format!("{}M", seconds % 3600 / 60));
+    }
+    if seconds % 60 > 0 || seconds == 0 {
+        value.push_str(&format!("{}S", seconds % 60));
+    }
+    value
+}
+
+/// Formats a weekday as two letter value (e.g. `MO`)
+fn format_weekday(day: &Weekday) -> &'static str {
+    match day {
+        Weekday::Mon => "MO",
+        Weekday::Tue => "TU",
+        Weekday::Wed => "WE",
+        Weekday::Thu => "TH",
+        Weekday::Fri => "FR",
+        Weekday::Sat => "SA",
+        Weekday::Sun => "SU",
+    }
+}
+
+/// Parses a two letter weekday value (e.g. `MO`)
+fn parse_weekday(value: &str) -> Result<Weekday, String> {
+    match value.trim() {
+        "MO" => Ok(Weekday::Mon),
+        "TU" => Ok(Weekday::Tue),
+        "WE" => Ok(Weekday::Wed),
+        "TH" => Ok(Weekday::Thu),
+        "FR" => Ok(Weekday::Fri),
+        "SA" => Ok(Weekday::Sat),
+        "SU" => Ok(Weekday::Sun),
+        _ => Err(format!("invalid BYDAY value [{}]", value)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CAL_WORKDAYS_8AM_6PM: &str = "\
+DTSTART:20221020T180000Z;DURATION:PT14H
+RRULE:FREQ=WEEKLY;BYDAY=MO,TU,WE,TH,FR
+DTSTART:20221022T000000Z;DURATION:PT24H
+RRULE:FREQ=WEEKLY;BYDAY=SA,SU";
+
+    fn time(day: u32, hour: u32) -> DateTime<Utc> {
+        // March 2023, the 6th is a Monday
+        Utc.with_ymd_and_hms(2023, 3, day, hour, 0, 0).unwrap()
+    }
+
+    #[test]
+    fn test_parse_calendar() {
+        let calendar: Calendar = CAL_WORKDAYS_8AM_6PM.parse().unwrap();
+        assert_eq!(calendar.events.len(), 2);
+        assert_eq!(
+            calendar.events[0],
+            Event {
+                start: Utc.with_ymd_and_hms(2022, 10, 20, 18, 0, 0).unwrap(),
+                duration: Duration::hours(14),
+                recurrence: Some(Recurrence {
+                    frequency: Frequency::Weekly,
+                    interval: 1,
+                    by_day: vec![
+                        Weekday::Mon,
+                        Weekday::Tue,
+                        Weekday::Wed,
+                        Weekday::Thu,
+                        Weekday::Fri
+                    ],
+                    until: None,
+                    count: None,
+                }),
+            }
+        );
+
+        let calendar: Calendar = "DTSTART:20230306T080000Z;DTEND:20230306T093000Z"
+            .parse()
+            .unwrap();
+        assert_eq!(calendar.events[0].duration, Duration::minutes(90));
+        assert_eq!(calendar.events[0].recurrence, None);
+
+        assert_eq!("".parse::<Calendar>().unwrap(), Calendar::default());
+    }
+
+    #[test]
+    fn test_parse_calendar_invalid() {
+        let invalid = [
+            "RRULE:FREQ=DAILY",
+            "DTSTART:20230306T080000Z",
+            "DTSTART:2023-03-06 08:00;DURATION:PT1H",
+            "DTSTART:20230306T080000Z;DURATION:1H",
+            "DTSTART:20230306T080000Z;DURATION:PT0H",
+            "DTSTART:20230306T080000Z;DURATION:P1H",
+            "DTSTART:20230306T080000Z;DURATION:PT1H;LOCATION:pad",
+            "DTSTART:20230306T080000Z;DURATION:PT1H\nRRULE:FREQ=MONTHLY",
+            "DTSTART:20230306T080000Z;DURATION:PT1H\nRRULE:BYDAY=MO",
+            "DTSTART:20230306T080000Z;DURATION:PT1H\nRRULE:FREQ=WEEKLY;BYDAY=XX",
+            "DTSTART:20230306T080000Z;DURATION:PT1H\nRRULE:FREQ=DAILY;COUNT=2;UNTIL=20230310T000000Z",
+            "DTSTART:20230306T080000Z;DURATION:PT1H\nRRULE:FREQ=DAILY\nRRULE:FREQ=DAILY",
+            "DTSTART:20230306T080000Z;DURATION:PT1H;DURATION:PT2H",
+            "DURATION:PT1H",
+            "BEGIN:VEVENT\nDTSTART:20230306T080000Z\nDURATION:PT1H",
+            "BEGIN:VEVENT\nBEGIN:VEVENT\nDTSTART:20230306T080000Z\nDURATION:PT1H\nEND:VEVENT",
+            "DTSTART:20230306T080000Z;DURATION:PT1H\nEND:VEVENT",
+            "BEGIN:VEVENT\nDTSTART:20230306T080000Z\nDTSTART:20230307T080000Z\nDURATION:PT1H\nEND:VEVENT",
+            "DTSTART;TZID=Europe/Amsterdam:20230306T080000;DURATION:PT1H",
+        ];
+        for schedule in invalid {
+            assert!(
+                schedule.parse::<Calendar>().is_err(),
+                "expected error for [{}]",
+                schedule
+            );
+        }
+
+        assert_eq!(
+            "DTSTART:20230306T080000Z;DURATION:PT1H\n\nRRULE:FREQ=YEARLY".parse::<Calendar>(),
+            Err(CalendarError(String::from(
+                "line 3: unsupported FREQ [YEARLY], should be DAILY or WEEKLY"
+            )))
+        );
+    }
+
+    #[test]
+    fn test_parse_calendar_vevent() {
+        let calendar: Calendar = "\
+BEGIN:VCALENDAR
+VERSION:2.0
+PRODID:-//Aetheric//Schedule//EN
+BEGIN:VEVENT
+UID:maintenance@vertiport
+SUMMARY:Weekly maintenance
+DTSTART:20230306T080000Z
+DTEND:20230306T100000Z
+RRULE:FREQ=WEEKLY;INTERVAL=2;COUNT=4
+END:VEVENT
+BEGIN:VEVENT
+DTSTART:20230310T120000Z
+DURATION:PT30M
+END:VEVENT
+END:VCALENDAR"
+            .parse()
+            .unwrap();
+
+        assert_eq!(
+            calendar,
+            Calendar {
+                events: vec![
+                    Event {
+                        start: time(6, 8),
+                        duration: Duration::hours(2),
+                        recurrence: Some(Recurrence {
+                            frequency: Frequency::Weekly,
+                            interval: 2,
+                            by_day: vec![],
+                            until: None,
+                            count: Some(4),
+                        }),
+                    },
+                    Event {
+                        start: time(10, 12),
+                        duration: Duration::minutes(30),
+                        recurrence: None,
+                    },
+                ]
+            }
+        );
+
+        assert_eq!(
+            "BEGIN:VEVENT\nDTSTART:20230306T080000Z\nLOCATION:pad\nEND:VEVENT".parse::<Calendar>(),
+            Err(CalendarError(String::from(
+                "line 3: unsupported property [LOCATION]"
+            )))
+        );
+        assert_eq!(
+            "BEGIN:VEVENT\nDTSTART:20230306T080000Z\nEND:VEVENT".parse::<Calendar>(),
+            Err(CalendarError(String::from(
+                "line 1: either DURATION or DTEND should be provided for event"
+            )))
+        );
+    }
+
+    #[test]
+    fn test_calendar_display() {
+        // The compact form is already normalized
+        let calendar: Calendar = CAL_WORKDAYS_8AM_6PM.parse().unwrap();
+        assert_eq!(calendar.to_string(), CAL_WORKDAYS_8AM_6PM);
+
+        let calendar: Calendar = "\
+BEGIN:VEVENT
+DTSTART:20230306T080000Z
+DTEND:20230306T100130Z
+RRULE:FREQ=DAILY;BYDAY=SU,MO,MO;UNTIL=20230320T000000Z;INTERVAL=1
+END:VEVENT
+BEGIN:VEVENT
+DTSTART:20230310T120000Z
+DURATION:P1DT30M
+RRULE:FREQ=WEEKLY;COUNT=3;INTERVAL=2
+END:VEVENT"
+            .parse()
+            .unwrap();
+        let normalized = "\
+DTSTART:20230306T080000Z;DURATION:PT2H1M30S
+RRULE:FREQ=DAILY;BYDAY=MO,SU;UNTIL=20230320T000000Z
+DTSTART:20230310T120000Z;DURATION:PT24H30M
+RRULE:FREQ=WEEKLY;INTERVAL=2;COUNT=3";
+        assert_eq!(calendar.to_string(), normalized);
+        assert_eq!(normalized.parse::<Calendar>().unwrap(), calendar);
+
+        assert_eq!(Calendar::default().to_string(), "");
+    }
+
+    #[test]
+    fn test_schedule() {
+        struct Resource {
+            schedule: Option<String>,
+        }
+        impl Schedule for Resource {
+            fn get_schedule(&self) -> Option<&str> {
+                self.schedule.as_deref()
+            }
+            fn set_schedule(&mut self, schedule: Option<String>) {
+                self.schedule = schedule;
+            }
+        }
+
+        let mut resource = Resource { schedule: None };
+        let interval = (time(6, 0), time(7, 0));
+        assert_eq!(resource.get_calendar(), Ok(Calendar::default()));
+        assert_eq!(
+            resource.get_available_intervals(&interval),
+            Ok(vec![interval])
+        );
+
+        let calendar: Calendar = "DTSTART:20230306T120000Z;DTEND:20230306T130000Z"
+            .parse()
+            .unwrap();
+        resource.set_calendar(&calendar);
+        assert_eq!(
+            resource.schedule,
+            Some(String::from("DTSTART:20230306T120000Z;DURATION:PT1H"))
+        );
+        assert_eq!(resource.get_calendar(), Ok(calendar));
+        assert_eq!(
+            resource.get_available_intervals(&interval),
+            Ok(vec![(time(6, 0), time(6, 12)), (time(6, 13), time(7, 0))])
+        );
+
+        resource.set_schedule(Some(String::from("DTSTART:invalid")));
+        assert!(resource.get_calendar().is_err());
+        assert!(resource.get_available_intervals(&interval).is_err());
+    }
+
+    #[test]
+    fn test_parse_duration() {
+        assert_eq!(parse_duration("PT14H"), Ok(Duration::hours(14)));
+        assert_eq!(
+            parse_duration("P1DT2H30M15S"),
+            Ok(Duration::days(1)
+                + Duration::hours(2)
+                + Duration::minutes(30)
+                + Duration::seconds(15))
+        );
+        assert_eq!(parse_duration("P2W"), Ok(Duration::weeks(2)));
+        assert!(parse_duration("P").is_err());
+        assert!(parse_duration("PT").is_err());
+        assert!(parse_duration("P1M").is_err());
+        assert!(parse_duration("PT1").is_err());
+    }
+
+    #[test]
+    fn test_get_busy_intervals() {
+        let calendar: Calendar = CAL_WORKDAYS_8AM_6PM.parse().unwrap();
+
+        // Friday until Sunday
+        let busy = calendar.get_busy_intervals(&(time(10, 0), time(12, 12)));
+        assert_eq!(
+            busy,
+            vec![(time(10, 0), time(10, 8)), (time(10, 18), time(12, 12))]
+        );
+
+        // Within working hours
+        let busy = calendar.get_busy_intervals(&(time(7, 9), time(7, 17)));
+        assert!(busy.is_empty());
+    }
+
+    #[test]
+    fn test_get_occurrences() {
+        // Every other day, 3 times
+        let event = Event {
+            start: time(6, 8),
+            duration: Duration::hours(1),
+            recurrence: Some(Recurrence {
+                frequency: Frequency::Daily,
+                interval: 2,
+                by_day: vec![],
+                until: None,
+                count: Some(3),
+            }),
+        };
+        assert_eq!(
+            event.get_occurrences(&(time(1, 0), time(31, 0))),
+            vec![
+                (time(6, 8), time(6, 9)),
+                (time(8, 8), time(8, 9)),
+                (time(10, 8), time(10, 9)),
+            ]
+        );
+        assert_eq!(
+            event.get_occurrences(&(time(7, 0), time(31, 0))),
+            vec![(time(8, 8), time(8, 9)), (time(10, 8), time(10, 9))]
+        );
+
+        // Every other week on the start's weekday, until the 20th
+        let event = Event {
+            start: time(6, 8),
+            duration: Duration::hours(1),
+            recurrence: Some(Recurrence {
+                frequency: Frequency::Weekly,
+                interval: 2,
+                by_day: vec![],
+                until: Some(time(20, 8)),
+                count: None,
+            }),
+        };
+        assert_eq!(
+            event.get_occurrences(&(time(1, 0), time(31, 0))),
+            vec![(time(6, 8), time(6, 9)), (time(20, 8), time(20, 9))]
+        );
+
+        // Single event, overlapping the start of the interval
+        let event = Event {
+            start: time(6, 8),
+            duration: Duration::hours(2),
+            recurrence: None,
+        };
+        assert_eq!(
+            event.get_occurrences(&(time(6, 9), time(6, 12))),
+            vec![(time(6, 8), time(6, 10))]
+        );
+        assert!(event
+            .get_occurrences(&(time(6, 10), time(6, 12)))
+            .is_empty());
+    }
+
+    #[test]
+    fn test_get_free_intervals() {
+        let interval = (time(6, 8), time(6, 18));
+        assert_eq!(get_free_intervals(&interval, vec![]), vec![interval]);
+        assert_eq!(
+            get_free_intervals(
+                &interval,
+                vec![
+                    (time(6, 12), time(6, 13)),
+                    (time(6, 6), time(6, 9)),
+                    (time(6, 12), time(6, 14)),
+                    (time(6, 17), time(6, 20)),
+                ]
+            ),
+            vec![(time(6, 9), time(6, 12)), (time(6, 14), time(6, 17))]
+        );
+        assert!(get_free_intervals(&interval, vec![(time(6, 0), time(7, 0))]).is_empty());
+    }
+
+    #[test]
+    fn test_merge_intervals() {
+        assert_eq!(
+            merge_intervals(vec![
+                (time(6, 12), time(6, 13)),
+                (time(6, 8), time(6, 10)),
+                (time(6, 10), time(6, 11)),
+                (time(6, 14), time(6, 14)),
+            ]),
+            vec![(time(6, 8), time(6, 11)), (time(6, 12), time(6, 13))]
+        );
+    }
+}
@@ -20,10 +20,13 @@ impl AdvancedSearchFilter {
                 search_value: values,
                 predicate_operator: predicate_operator.into(),
                 comparison_operator: None,
+                group_filters: vec![],
             }],
             page_number: 0,
             results_per_page: -1,
             order_by: vec![],
+            cursor: None,
+            include_archived: false,
         }
     }
 
@@ -203,10 +206,85 @@ impl AdvancedSearchFilter {
             search_value: values,
             predicate_operator: predicate_operator.into(),
             comparison_operator: Some(comparison_operator.into()),
+            group_filters: vec![],
         });
         self
     }
 
+    fn add_group<F>(mut self, group: F, comparison_operator: Option<ComparisonOperator>) -> Self
+    where
+        F: FnOnce(Self) -> Self,
+    {
+        let group = group(Self::default());
+        self.filters.push(FilterOption {
+            search_field: String::from(""),
+            search_value: vec![],
+            predicate_operator: PredicateOperator::Equals.into(),
+            comparison_operator: comparison_operator.map(|operator| operator.into()),
+            group_filters: group.filters,
+        });
+        self
+    }
+
+    /// returns a new [AdvancedSearchFilter] object starting with a group of filters
+    ///
+    /// The provided `group` closure receives an empty [AdvancedSearchFilter] and should return
+    /// the filters that need to be combined between parentheses.
+    /// The comparison operator of the first filter in the group will be ignored.
+    ///
+    /// Examples:
+    /// ```
+    /// use svc_storage::resources::AdvancedSearchFilter;
+    /// let filter = AdvancedSearchFilter::search_group(|g| {
+    ///     g.or_equals(String::from("origin_vertipad_id"), String::from("53acfe06-dd9b-42e8-8cb4-12a2fb2fa693"))
+    ///         .or_equals(String::from("target_vertipad_id"), String::from("53acfe06-dd9b-42e8-8cb4-12a2fb2fa693"))
+    /// })
+    /// .and_is_null(String::from("deleted_at"));
+    /// ```
+    pub fn search_group<F>(group: F) -> Self
+    where
+        F: FnOnce(Self) -> Self,
+    {
+        Self {
+            page_number: 0,
+            results_per_page: -1,
+            ..Self::default()
+        }
+        .add_group(group, None)
+    }
+    /// wrapper function for internal `add_group` function returning [Self]
+    ///
+    /// Adds a [FilterOption] to `filters` using:
+    /// * group_filters: the filters returned by the provided `group` closure
+    /// * comparison operator: [ComparisonOperator::And]
+    ///
+    /// Examples:
+    /// ```
+    /// use svc_storage::resources::AdvancedSearchFilter;
+    /// let filter = AdvancedSearchFilter::search_in(String::from("flight_status"), vec![String::from("1"), String::from("2")])
+    ///     .and_group(|g| {
+    ///         g.or_equals(String::from("origin_vertipad_id"), String::from("53acfe06-dd9b-42e8-8cb4-12a2fb2fa693"))
+    ///             .or_equals(String::from("target_vertipad_id"), String::from("53acfe06-dd9b-42e8-8cb4-12a2fb2fa693"))
+    ///     });
+    /// ```
+    pub fn and_group<F>(self, group: F) -> Self
+    where
+        F: FnOnce(Self) -> Self,
+    {
+        self.add_group(group, Some(ComparisonOperator::And))
+    }
+    /// wrapper function for internal `add_group` function returning [Self]
+    ///
+    /// Adds a [FilterOption] to `filters` using:
+    /// * group_filters: the filters returned by the provided `group` closure
+    /// * comparison operator: [ComparisonOperator::Or]
+    pub fn or_group<F>(self, group: F) -> Self
+    where
+        F: FnOnce(Self) -> Self,
+    {
+        self.add_group(group, Some(ComparisonOperator::Or))
+    }
+
     /// wrapper function for internal `add_filter` function returning [Self]
     ///
     /// Adds a [FilterOption] to `filters` using:
@@ -706,6 +784,24 @@ impl AdvancedSearchFilter {
         self.page_number = page;
         self
     }
+
+    /// sets `cursor` field with given `cursor`
+    ///
+    /// The cursor should be the `next_cursor` value of a previous search result.
+    /// The `page_number` will be ignored if a cursor is provided.
+    pub fn cursor(mut self, cursor: String) -> Self {
+        self.cursor = Some(cursor);
+        self
+    }
+
+    /// sets `include_archived` field with given `include_archived`
+    ///
+    /// Archived records (with a `deleted_at` value) are excluded from the search results
+    /// unless this is set to `true`.
+    pub fn include_archived(mut self, include_archived: bool) -> Self {
+        self.include_archived = include_archived;
+        self
+    }
 }
 
 /// Helper function for search library to get a single value from the provided
@@ -729,7 +825,147 @@ pub(crate) fn get_single_search_value(search_value: &Vec<String>) -> Result<Stri
     }
 }
 
-#[cfg(any(feature = "stub_client", feature = "stub_server"))]
+/// Helper function to filter the provided `unfiltered` list in memory using the given `filters`.
+/// Used by the server to match `watch` events and by the mock implementations for `search`.
+///
+/// Filters are combined the same way the server's search query does, using their comparison
+/// operator and defaulting to `AND` if none was provided. `AND` takes precedence over `OR`, so
+/// `a OR b AND c` matches the same objects as `a OR (b AND c)`. Filters with `group_filters` are
+/// evaluated as a whole. Empty groups are rejected.
+// allow dead_code is added for the client library since it only needs this
+// function while using the stub_client feature. The server needs it at all
+// times though.
+#[allow(dead_code)]
+pub(crate) fn filter_for_options(
+    filters: &[FilterOption],
+    unfiltered: &Vec<serde_json::Value>,
+) -> Result<Vec<serde_json::Value>, String> {
+    let mut collected: Vec<serde_json::Value> = vec![];
+    for object in unfiltered {
+        if matches_filter_options(filters, object)? {
+            collected.push(object.clone());
+        }
+    }
+
+    Ok(collected)
+}
+
+/// Helper function for [`filter_for_options`] to check if the given `object` matches the
+/// provided `filters`.
+///
+/// The filters are split into `OR` separated terms of `AND` combined filters. The object
+/// matches if all filters of any of the terms match.
+#[allow(dead_code)]
+fn matches_filter_options(
+    filters: &[FilterOption],
+    object: &serde_json::Value,
+) -> Result<bool, String> {
+    let mut matches_any_term = false;
+    let mut matches_term = true;
+    for (index, filter) in filters.iter().enumerate() {
+        let comparison_operator = match filter.comparison_operator {
+            // The first filter (of a group) doesn't have anything to compare with
+            _ if index == 0 => ComparisonOperator::And,
+            Some(comparison_operator) => match ComparisonOperator::try_from(comparison_operator) {
+                Ok(comparison_operator) => comparison_operator,
+                Err(e) => {
+                    return Err(format!(
+                        "Can't convert i32 [{}] into ComparisonOperator Enum value: {}",
+                        comparison_operator, e
+                    ));
+                }
+            },
+            // Same as the server, filters are combined using AND if no operator was provided
+            None => ComparisonOperator::And,
+        };
+        if comparison_operator == ComparisonOperator::Or {
+            matches_any_term |= matches_term;
+            matches_term = true;
+        }
+
+        if filter.group_filters.is_empty() && filter.search_field.is_empty() {
+            return Err(String::from(
+                "Error in advanced search parameters. Filter groups should contain at least one filter",
+            ));
+        }
+
+        // All filters are evaluated, so invalid filters are rejected the same way the
+        // server does, regardless of the results of the other filters.
+        let matches = if !filter.group_filters.is_empty() {
+            matches_filter_options(&filter.group_filters, object)?
+        } else {
+            let operator: PredicateOperator =
+                match PredicateOperator::try_from(filter.predicate_operator) {
+                    Ok(val) => val,
+                    Err(e) => {
+                        return Err(format!(
+                            "Can't convert i32 [{}] into PredicateOperator Enum value: {}",
+                            filter.predicate_operator, e
+                        ));
+                    }
+                };
+            let mut filtered: Vec<serde_json::Value> = vec![];
+            filter_for_operator(
+                &filter.search_field,
+                &filter.search_value,
+                &vec![object.clone()],
+                &mut filtered,
+                operator,
+            )?;
+            !filtered.is_empty()
+        };
+        matches_term &= matches;
+    }
+
+    Ok(matches_any_term || matches_term)
+}
+
+/// Helper function for [`filter_for_operator`] to check if the given `value` matches the provided
+/// SQL `LIKE` pattern.
+///
+/// Same as the database, `%` matches any sequence of characters, `_` matches any single
+/// character and `\` escapes the next character.
+#[allow(dead_code)]
+fn like_matches(value: &str, pattern: &str) -> bool {
+    let value: Vec<char> = value.chars().collect();
+    let pattern: Vec<char> = pattern.chars().collect();
+
+    // matches[i] is set if the first i characters of the value match the pattern so far
+    let mut matches = vec![false; value.len() + 1];
+    matches[0] = true;
+    let mut index = 0;
+    while index < pattern.len() {
+        let mut next = vec![false; value.len() + 1];
+        match pattern[index] {
+            '%' => {
+                let mut matched = false;
+                for (i, next) in next.iter_mut().enumerate() {
+                    matched |= matches[i];
+                    *next = matched;
+                }
+            }
+            character => {
+                let escaped = character == '\\' && index + 1 < pattern.len();
+                if escaped {
+                    index += 1;
+                }
+                let expected = pattern[index];
+                for (i, character) in value.iter().enumerate() {
+                    next[i + 1] =
+                        matches[i] && ((!escaped && expected == '_') || *character == expected);
+                }
+            }
+        }
+        matches = next;
+        index += 1;
+    }
+
+    matches[value.len()]
+}
+
+/// Helper function for [`filter_for_options`] to add all objects of the `unfiltered` list
+/// matching the given predicate to the `filtered` list.
+#[allow(dead_code)]
 pub(crate) fn filter_for_operator(
     search_field: &str,
     search_values: &Vec<String>,
@@ -747,9 +983,9 @@ pub(crate) fn filter_for_operator(
         };
 
         let ids = &object["ids"];
-        println!("(filter_for_operator) (MOCK) test ids field [{}].", ids,);
+        grpc_debug!("(filter_for_operator) test ids field [{}].", ids,);
         if val == serde_json::Value::Null && *ids != serde_json::Value::Null {
-            println!("(filter_for_operator) (MOCK) found ids [{}].", ids,);
+            grpc_debug!("(filter_for_operator) found ids [{}].", ids,);
             serde_json::from_value::<Vec<super::FieldValue>>(ids.clone())
                 .map_err(|e| {
                     format!(
@@ -762,9 +998,10 @@ pub(crate) fn filter_for_operator(
                 .map(|id| val = serde_json::Value::String(id.value.clone()));
         }
 
-        println!(
-            "(filter_for_operator) (MOCK) got value [{}] for object [{}].",
-            val, object
+        grpc_debug!(
+            "(filter_for_operator) got value [{}] for object [{}].",
+            val,
+            object
         );
         match operator {
             PredicateOperator::Equals => {
@@ -775,12 +1012,13 @@ pub(crate) fn filter_for_operator(
                 } else {
                     cmp_val = format!("{}", val);
                 }
-                println!(
-                    "(filter_for_operator) (MOCK) Equals filter with value [{}] for val [{}].",
-                    search_val, cmp_val
+                grpc_debug!(
+                    "(filter_for_operator) Equals filter with value [{}] for val [{}].",
+                    search_val,
+                    cmp_val
                 );
                 if cmp_val == *search_val {
-                    println!("(filter_for_operator) (MOCK) Equals found!");
+                    grpc_debug!("(filter_for_operator) Equals found!");
                     filtered.push(object.clone())
                 }
             }
@@ -792,12 +1030,13 @@ pub(crate) fn filter_for_operator(
                 } else {
                     cmp_val = format!("{}", val);
                 }
-                println!(
-                    "(filter_for_operator) (MOCK) NotEquals filter with value [{}] for val [{}].",
-                    search_val, val
+                grpc_debug!(
+                    "(filter_for_operator) NotEquals filter with value [{}] for val [{}].",
+                    search_val,
+                    val
                 );
                 if cmp_val != *search_val {
-                    println!("(filter_for_operator) (MOCK) NotEquals found!");
+                    grpc_debug!("(filter_for_operator) NotEquals found!");
                     filtered.push(object.clone())
                 }
             }
@@ -809,16 +1048,17 @@ pub(crate) fn filter_for_operator(
                     cmp_val = format!("{}", val);
                 }
 
-                println!(
-                    "(filter_for_operator) (MOCK) In filter with values [{:?}] for val [{}].",
-                    search_values, cmp_val
+                grpc_debug!(
+                    "(filter_for_operator) In filter with values [{:?}] for val [{}].",
+                    search_values,
+                    cmp_val
                 );
 
                 let _ = search_values
                     .iter()
                     .find(|&search_val| search_val == &cmp_val)
                     .map(|_| {
-                        println!("(filter_for_operator) (MOCK) In found!");
+                        grpc_debug!("(filter_for_operator) In found!");
                         filtered.push(object.clone())
                     });
             }
@@ -830,23 +1070,25 @@ pub(crate) fn filter_for_operator(
                     cmp_val = format!("{}", val);
                 }
 
-                println!(
-                    "(filter_for_operator) (MOCK) NotIn filter with values [{:?}] for val [{}].",
-                    search_values, cmp_val
+                grpc_debug!(
+                    "(filter_for_operator) NotIn filter with values [{:?}] for val [{}].",
+                    search_values,
+                    cmp_val
                 );
 
                 let _ = search_values
                     .iter()
                     .find(|&search_val| search_val == &cmp_val)
                     .ok_or_else(|| {
-                        println!("(filter_for_operator) (MOCK) NotIn found!");
+                        grpc_debug!("(filter_for_operator) NotIn found!");
                         filtered.push(object.clone());
                     });
             }
             PredicateOperator::Between => {
-                println!(
-                    "(filter_for_operator) (MOCK) Between filter with values [{:?}] for val [{}].",
-                    search_values, val
+                grpc_debug!(
+                    "(filter_for_operator) Between filter with values [{:?}] for val [{}].",
+                    search_values,
+                    val
                 );
                 let mut values: std::collections::VecDeque<String> = search_values.clone().into();
 
@@ -862,15 +1104,15 @@ pub(crate) fn filter_for_operator(
                         return Err("Error in advanced search parameters. Between operator is expecting 2 values but got only one.".to_string());
                     }
                 };
-                println!(
-                    "(filter_for_operator) (MOCK) Found min [{}] and max [{}] values to compare with.",
+                grpc_debug!(
+                    "(filter_for_operator) Found min [{}] and max [{}] values to compare with.",
                     min,
                     max
                 );
 
                 if let Some(num_val) = val.as_f64() {
-                    println!(
-                        "(filter_for_operator) (MOCK) Can convert val to number, got [{}].",
+                    grpc_debug!(
+                        "(filter_for_operator) Can convert val to number, got [{}].",
                         num_val
                     );
                     let num_min = min.parse::<f64>().map_err(|e| {
@@ -880,15 +1122,15 @@ pub(crate) fn filter_for_operator(
                         format!("Could not convert search_value max [{}] to f64: {}", max, e)
                     })?;
                     if num_val >= num_min && num_val <= num_max {
-                        println!("(filter_for_operator) (MOCK) Between found!");
+                        grpc_debug!("(filter_for_operator) Between found!");
                         filtered.push(object.clone())
                     }
                 } else if let Ok(date_val) = lib_common::time::DateTime::parse_from_rfc3339(
                     val.as_str()
                         .ok_or("Could not convert provided value to string.")?,
                 ) {
-                    println!(
-                        "(filter_for_operator) (MOCK) Can convert val to date, got [{}].",
+                    grpc_debug!(
+                        "(filter_for_operator) Can convert val to date, got [{}].",
                         date_val
                     );
                     let date_min =
@@ -907,33 +1149,30 @@ pub(crate) fn filter_for_operator(
                             )
                         })?;
                     if date_val >= date_min && date_val <= date_max {
-                        println!("(filter_for_operator) (MOCK) Between found!");
+                        grpc_debug!("(filter_for_operator) Between found!");
                         filtered.push(object.clone())
                     }
                 } else {
                     grpc_warn!(
-                        "(filter_for_operator) (MOCK) Can't convert val [{}] to number or date, don't know what to do.",
+                        "(filter_for_operator) Can't convert val [{}] to number or date, don't know what to do.",
                         &val.to_string()
                     );
                 }
             }
             PredicateOperator::IsNull => {
-                println!(
-                    "(filter_for_operator) (MOCK) IsNull filter for value [{}].",
-                    val
-                );
+                grpc_debug!("(filter_for_operator) IsNull filter for value [{}].", val);
                 if val.is_null() {
-                    println!("(filter_for_operator) (MOCK) IsNull found!");
+                    grpc_debug!("(filter_for_operator) IsNull found!");
                     filtered.push(object.clone())
                 }
             }
             PredicateOperator::IsNotNull => {
-                println!(
-                    "(filter_for_operator) (MOCK) IsNotNull filter for value [{}].",
+                grpc_debug!(
+                    "(filter_for_operator) IsNotNull filter for value [{}].",
                     val
                 );
                 if !val.is_null() {
-                    println!("(filter_for_operator) (MOCK) IsNotNull found!");
+                    grpc_debug!("(filter_for_operator) IsNotNull found!");
                     filtered.push(object.clone())
                 }
             }
@@ -945,12 +1184,13 @@ pub(crate) fn filter_for_operator(
                 } else {
                     cmp_val = format!("{}", val);
                 }
-                println!(
-                    "(filter_for_operator) (MOCK) Ilike filter with value [{}] for val [{}].",
-                    search_val, cmp_val
+                grpc_debug!(
+                    "(filter_for_operator) Ilike filter with value [{}] for val [{}].",
+                    search_val,
+                    cmp_val
                 );
-                if cmp_val.to_lowercase().contains(&search_val.to_lowercase()) {
-                    println!("(filter_for_operator) (MOCK) Ilike found!");
+                if like_matches(&cmp_val.to_lowercase(), &search_val.to_lowercase()) {
+                    grpc_debug!("(filter_for_operator) Ilike found!");
                     filtered.push(object.clone())
                 }
             }
@@ -962,24 +1202,26 @@ pub(crate) fn filter_for_operator(
                 } else {
                     cmp_val = format!("{}", val);
                 }
-                println!(
-                    "(filter_for_operator) (MOCK) Like filter with value [{}] for val [{}].",
-                    search_val, cmp_val
+                grpc_debug!(
+                    "(filter_for_operator) Like filter with value [{}] for val [{}].",
+                    search_val,
+                    cmp_val
                 );
-                if cmp_val.contains(&search_val) {
-                    println!("(filter_for_operator) (MOCK) Like found!");
+                if like_matches(&cmp_val, &search_val) {
+                    grpc_debug!("(filter_for_operator) Like found!");
                     filtered.push(object.clone())
                 }
             }
             PredicateOperator::Greater => {
                 let search_val: String = get_single_search_value(search_values)?;
-                println!(
-                    "(filter_for_operator) (MOCK) Greater filter with value [{:?}] for val [{}].",
-                    search_val, val
+                grpc_debug!(
+                    "(filter_for_operator) Greater filter with value [{:?}] for val [{}].",
+                    search_val,
+                    val
                 );
                 if let Some(num_val) = val.as_f64() {
-                    println!(
-                        "(filter_for_operator) (MOCK) Can convert val to number, got [{}].",
+                    grpc_debug!(
+                        "(filter_for_operator) Can convert val to number, got [{}].",
                         num_val
                     );
                     let num_search_val = search_val.parse::<f64>().map_err(|e| {
@@ -989,15 +1231,15 @@ pub(crate) fn filter_for_operator(
                         )
                     })?;
                     if num_val > num_search_val {
-                        println!("(filter_for_operator) (MOCK) Greater found!");
+                        grpc_debug!("(filter_for_operator) Greater found!");
                         filtered.push(object.clone())
                     }
                 } else if let Ok(date_val) = lib_common::time::DateTime::parse_from_rfc3339(
                     val.as_str()
                         .ok_or("Could not convert provided value to string.")?,
                 ) {
-                    println!(
-                        "(filter_for_operator) (MOCK) Can convert val to date, got [{}].",
+                    grpc_debug!(
+                        "(filter_for_operator) Can convert val to date, got [{}].",
                         date_val
                     );
                     let search_date = lib_common::time::DateTime::parse_from_rfc3339(&search_val)
@@ -1008,26 +1250,26 @@ pub(crate) fn filter_for_operator(
                         )
                     })?;
                     if date_val > search_date {
-                        println!("(filter_for_operator) (MOCK) Greater found!");
+                        grpc_debug!("(filter_for_operator) Greater found!");
                         filtered.push(object.clone())
                     }
                 } else {
                     grpc_warn!(
-                        "(filter_for_operator) (MOCK) Can't convert val [{}] to number or date, don't know what to do.",
+                        "(filter_for_operator) Can't convert val [{}] to number or date, don't know what to do.",
                         &val.to_string()
                     );
                 }
             }
             PredicateOperator::GreaterOrEqual => {
                 let search_val: String = get_single_search_value(search_values)?;
-                println!(
-                    "(filter_for_operator) (MOCK) GreaterOrEqual filter with value [{:?}] for val [{}].",
+                grpc_debug!(
+                    "(filter_for_operator) GreaterOrEqual filter with value [{:?}] for val [{}].",
                     search_val,
                     val
                 );
                 if let Some(num_val) = val.as_f64() {
-                    println!(
-                        "(filter_for_operator) (MOCK) Can convert val to number, got [{}].",
+                    grpc_debug!(
+                        "(filter_for_operator) Can convert val to number, got [{}].",
                         num_val
                     );
                     let num_search_val = search_val.parse::<f64>().map_err(|e| {
@@ -1037,15 +1279,15 @@ pub(crate) fn filter_for_operator(
                         )
                     })?;
                     if num_val >= num_search_val {
-                        println!("(filter_for_operator) (MOCK) GreaterOrEqual found!");
+                        grpc_debug!("(filter_for_operator) GreaterOrEqual found!");
                         filtered.push(object.clone())
                     }
                 } else if let Ok(date_val) = lib_common::time::DateTime::parse_from_rfc3339(
                     val.as_str()
                         .ok_or("Could not convert provided value to string.")?,
                 ) {
-                    println!(
-                        "(filter_for_operator) (MOCK) Can convert val to date, got [{}].",
+                    grpc_debug!(
+                        "(filter_for_operator) Can convert val to date, got [{}].",
                         date_val
                     );
                     let search_date = lib_common::time::DateTime::parse_from_rfc3339(&search_val)
@@ -1056,25 +1298,26 @@ pub(crate) fn filter_for_operator(
                         )
                     })?;
                     if date_val >= search_date {
-                        println!("(filter_for_operator) (MOCK) GreaterOrEqual found!");
+                        grpc_debug!("(filter_for_operator) GreaterOrEqual found!");
                         filtered.push(object.clone())
                     }
                 } else {
                     grpc_warn!(
-                        "(filter_for_operator) (MOCK) Can't convert val [{}] to number or date, don't know what to do.",
+                        "(filter_for_operator) Can't convert val [{}] to number or date, don't know what to do.",
                         &val.to_string()
                     );
                 }
             }
             PredicateOperator::Less => {
                 let search_val: String = get_single_search_value(search_values)?;
-                println!(
-                    "(filter_for_operator) (MOCK) Less filter with value [{:?}] for val [{}].",
-                    search_val, val
+                grpc_debug!(
+                    "(filter_for_operator) Less filter with value [{:?}] for val [{}].",
+                    search_val,
+                    val
                 );
                 if let Some(num_val) = val.as_f64() {
-                    println!(
-                        "(filter_for_operator) (MOCK) Can convert val to number, got [{}].",
+                    grpc_debug!(
+                        "(filter_for_operator) Can convert val to number, got [{}].",
                         num_val
                     );
                     let num_search_val = search_val.parse::<f64>().map_err(|e| {
@@ -1084,15 +1327,15 @@ pub(crate) fn filter_for_operator(
                         )
                     })?;
                     if num_val < num_search_val {
-                        println!("(filter_for_operator) (MOCK) Less found!");
+                        grpc_debug!("(filter_for_operator) Less found!");
                         filtered.push(object.clone())
                     }
                 } else if let Ok(date_val) = lib_common::time::DateTime::parse_from_rfc3339(
                     val.as_str()
                         .ok_or("Could not convert provided value to string.")?,
                 ) {
-                    println!(
-                        "(filter_for_operator) (MOCK) Can convert val to date, got [{}].",
+                    grpc_debug!(
+                        "(filter_for_operator) Can convert val to date, got [{}].",
                         date_val
                     );
                     let search_date = lib_common::time::DateTime::parse_from_rfc3339(&search_val)
@@ -1103,26 +1346,26 @@ pub(crate) fn filter_for_operator(
                         )
                     })?;
                     if date_val < search_date {
-                        println!("(filter_for_operator) (MOCK) Less found!");
+                        grpc_debug!("(filter_for_operator) Less found!");
                         filtered.push(object.clone())
                     }
                 } else {
                     grpc_warn!(
-                        "(filter_for_operator) (MOCK) Can't convert val [{}] to number or date, don't know what to do.",
+                        "(filter_for_operator) Can't convert val [{}] to number or date, don't know what to do.",
                         &val.to_string()
                     );
                 }
             }
             PredicateOperator::LessOrEqual => {
                 let search_val: String = get_single_search_value(search_values)?;
-                println!(
-                    "(filter_for_operator) (MOCK) LessOrEqual filter with value [{:?}] for val [{}].",
+                grpc_debug!(
+                    "(filter_for_operator) LessOrEqual filter with value [{:?}] for val [{}].",
                     search_val,
                     val
                 );
                 if let Some(num_val) = val.as_f64() {
-                    println!(
-                        "(filter_for_operator) (MOCK) Can convert val to number, got [{}].",
+                    grpc_debug!(
+                        "(filter_for_operator) Can convert val to number, got [{}].",
                         num_val
                     );
                     let num_search_val = search_val.parse::<f64>().map_err(|e| {
@@ -1132,15 +1375,15 @@ pub(crate) fn filter_for_operator(
                         )
                     })?;
                     if num_val <= num_search_val {
-                        println!("(filter_for_operator) (MOCK) LessOrEqual found!");
+                        grpc_debug!("(filter_for_operator) LessOrEqual found!");
                         filtered.push(object.clone())
                     }
                 } else if let Ok(date_val) = lib_common::time::DateTime::parse_from_rfc3339(
                     val.as_str()
                         .ok_or("Could not convert provided value to string.")?,
                 ) {
-                    println!(
-                        "(filter_for_operator) (MOCK) Can convert val to date, got [{}].",
+                    grpc_debug!(
+                        "(filter_for_operator) Can convert val to date, got [{}].",
                         date_val
                     );
                     let search_date = lib_common::time::DateTime::parse_from_rfc3339(&search_val)
@@ -1151,48 +1394,23 @@ pub(crate) fn filter_for_operator(
                         )
                     })?;
                     if date_val <= search_date {
-                        println!("(filter_for_operator) (MOCK) LessOrEqual found!");
+                        grpc_debug!("(filter_for_operator) LessOrEqual found!");
                         filtered.push(object.clone())
                     }
                 } else {
                     grpc_warn!(
-                        "(filter_for_operator) (MOCK) Can't convert val [{}] to number or date, don't know what to do.",
+                        "(filter_for_operator) Can't convert val [{}] to number or date, don't know what to do.",
                         &val.to_string()
                     );
                 }
             }
-            PredicateOperator::GeoIntersect => {
-                /*
-                filter_str = format!(
-                r#" st_intersect(st_geomfromtext(${}), "{}")"#,
-                next_param_index, search_col.col_name,
-                );
-                search_col.set_value(get_single_search_value(values)?);
-                params.push(search_col.clone());
-                next_param_index += 1;
-                */
-            }
-            PredicateOperator::GeoWithin => {
-                /*
-                filter_str = format!(
-                r#" st_within(st_geomfromtext(${}), "{}")"#,
-                next_param_index, search_col.col_name,
-                );
-                search_col.set_value(get_single_search_value(values)?);
-                params.push(search_col.clone());
-                next_param_index += 1;
-                */
-            }
-            PredicateOperator::GeoDisjoint => {
-                /*
-                filter_str = format!(
-                r#" st_disjoint(st_geomfromtext(${}), "{}")"#,
-                next_param_index, search_col.col_name,
-                );
-                search_col.set_value(get_single_search_value(values)?);
-                params.push(search_col.clone());
-                next_param_index += 1;
-                */
+            PredicateOperator::GeoIntersect
+            | PredicateOperator::GeoWithin
+            | PredicateOperator::GeoDisjoint => {
+                return Err(format!(
+                    "Error in advanced search parameters. The [{}] operator can't be applied in memory.",
+                    operator.as_str_name()
+                ));
             }
         }
     }
@@ -1200,6 +1418,30 @@ pub(crate) fn filter_for_operator(
     Ok(())
 }
 
+/// Helper function for the mock implementations to check the `unique_fields` of the provided
+/// `object` against the `others` list, the same way the server does before restoring a record.
+///
+/// Returns the first list of fields for which one of the `others` has the same value(s), joined
+/// by `, `.
+#[cfg(any(feature = "stub_client", feature = "stub_server"))]
+pub(crate) fn find_unique_violation(
+    object: &serde_json::Value,
+    others: &[serde_json::Value],
+    unique_fields: &[Vec<String>],
+) -> Option<String> {
+    unique_fields
+        .iter()
+        .find(|fields| {
+            others.iter().any(|other| {
+                other["id"] != object["id"]
+                    && fields
+                        .iter()
+                        .all(|field| other["data"][field] == object["data"][field])
+            })
+        })
+        .map(|fields| fields.join(", "))
+}
+
 #[cfg(test)]
 mod tests {
     use super::super::SortOrder;
@@ -1926,6 +2168,193 @@ mod tests {
         );
     }
 
+    // Test search, and, or options for filter groups
+    #[test]
+    fn test_search_group() {
+        let filter = AdvancedSearchFilter::search_group(|g| {
+            g.or_equals(String::from("group_equals"), String::from("test"))
+                .or_is_null(String::from("group_is_null"))
+        })
+        .and_group(|g| g.and_equals(String::from("and_group"), String::from("test")))
+        .or_group(|g| {
+            g.or_equals(String::from("or_group"), String::from("test"))
+                .and_group(|g| g.and_is_not_null(String::from("nested_group")))
+        });
+
+        assert_eq!(filter.filters.len(), 3);
+        assert_eq!(filter.page_number, 0);
+        assert_eq!(filter.results_per_page, -1);
+
+        let filter_option1 = &filter.filters[0];
+        assert_eq!(filter_option1.comparison_operator, None);
+        assert_eq!(filter_option1.group_filters.len(), 2);
+        assert_eq!(filter_option1.group_filters[0].search_field, "group_equals");
+        assert_eq!(
+            filter_option1.group_filters[1].predicate_operator,
+            PredicateOperator::IsNull as i32
+        );
+        assert_eq!(
+            filter_option1.group_filters[1].comparison_operator,
+            Some(ComparisonOperator::Or as i32)
+        );
+
+        let filter_option2 = &filter.filters[1];
+        assert_eq!(
+            filter_option2.comparison_operator,
+            Some(ComparisonOperator::And as i32)
+        );
+        assert_eq!(filter_option2.group_filters.len(), 1);
+        assert_eq!(filter_option2.group_filters[0].search_field, "and_group");
+
+        let filter_option3 = &filter.filters[2];
+        assert_eq!(
+            filter_option3.comparison_operator,
+            Some(ComparisonOperator::Or as i32)
+        );
+        assert_eq!(filter_option3.group_filters.len(), 2);
+        assert_eq!(filter_option3.group_filters[1].group_filters.len(), 1);
+        assert_eq!(
+            filter_option3.group_filters[1].group_filters[0].search_field,
+            "nested_group"
+        );
+    }
+
+    #[cfg(any(feature = "stub_client", feature = "stub_server"))]
+    #[test]
+    fn test_filter_for_options_default_and() {
+        let unfiltered = vec![
+            serde_json::json!({"id": "1", "data": {"name": "a", "kind": "x"}}),
+            serde_json::json!({"id": "2", "data": {"name": "a", "kind": "y"}}),
+            serde_json::json!({"id": "3", "data": {"name": "b", "kind": "x"}}),
+        ];
+
+        // Filters without comparison operator should be combined using AND
+        let mut filter =
+            AdvancedSearchFilter::search_equals(String::from("name"), String::from("a"))
+                .and_equals(String::from("kind"), String::from("x"));
+        filter.filters[1].comparison_operator = None;
+        let result = filter_for_options(&filter.filters, &unfiltered);
+        assert!(result.is_ok(), "Expected 'Ok' but got {:?}", result);
+        assert_eq!(result.unwrap(), vec![unfiltered[0].clone()]);
+
+        // Same for groups
+        let filter = AdvancedSearchFilter::search_equals(String::from("name"), String::from("a"))
+            .add_group(
+                |g| g.and_equals(String::from("kind"), String::from("y")),
+                None,
+            );
+        let result = filter_for_options(&filter.filters, &unfiltered);
+        assert!(result.is_ok(), "Expected 'Ok' but got {:?}", result);
+        assert_eq!(result.unwrap(), vec![unfiltered[1].clone()]);
+
+        // Empty groups should be rejected
+        let filter = AdvancedSearchFilter::search_equals(String::from("name"), String::from("a"))
+            .or_group(|g| g);
+        assert!(filter_for_options(&filter.filters, &unfiltered).is_err());
+    }
+
+    #[test]
+    fn test_filter_for_options_precedence() {
+        let unfiltered = vec![
+            serde_json::json!({"id": "1", "data": {"name": "a", "kind": "x"}}),
+            serde_json::json!({"id": "2", "data": {"name": "b", "kind": "x"}}),
+            serde_json::json!({"id": "3", "data": {"name": "b", "kind": "y"}}),
+        ];
+
+        // AND should take precedence over OR, same as the server's search query
+        let filter = AdvancedSearchFilter::search_equals(String::from("name"), String::from("a"))
+            .or_equals(String::from("name"), String::from("b"))
+            .and_equals(String::from("kind"), String::from("y"));
+        let result = filter_for_options(&filter.filters, &unfiltered);
+        assert!(result.is_ok(), "Expected 'Ok' but got {:?}", result);
+        assert_eq!(
+            result.unwrap(),
+            vec![unfiltered[0].clone(), unfiltered[2].clone()]
+        );
+
+        // Groups are evaluated as a whole
+        let filter = AdvancedSearchFilter::search_group(|g| {
+            g.or_equals(String::from("name"), String::from("a"))
+                .or_equals(String::from("name"), String::from("b"))
+        })
+        .and_equals(String::from("kind"), String::from("y"));
+        let result = filter_for_options(&filter.filters, &unfiltered);
+        assert!(result.is_ok(), "Expected 'Ok' but got {:?}", result);
+        assert_eq!(result.unwrap(), vec![unfiltered[2].clone()]);
+
+        // Geo operators can't be applied in memory
+        let filter = AdvancedSearchFilter::search_equals(String::from("name"), String::from("a"))
+            .or_geo_within(String::from("location"), String::from("POLYGON EMPTY"));
+        assert!(filter_for_options(&filter.filters, &unfiltered).is_err());
+    }
+
+    #[test]
+    fn test_like_matches() {
+        assert!(like_matches("vertiport", "vertiport"));
+        assert!(like_matches("vertiport", "vert%"));
+        assert!(like_matches("vertiport", "%port"));
+        assert!(like_matches("vertiport", "%ti%"));
+        assert!(like_matches("vertiport", "v_rtiport"));
+        assert!(like_matches("", "%"));
+        assert!(like_matches("100%", "100\\%"));
+        assert!(like_matches("a_b", "a\\_b"));
+
+        // Unlike a substring check, the whole value should match the pattern
+        assert!(!like_matches("vertiport", "ti"));
+        assert!(!like_matches("vertiport", "vert"));
+        assert!(!like_matches("vertiport", "_ertipor"));
+        assert!(!like_matches("1000", "100\\%"));
+        assert!(!like_matches("axb", "a\\_b"));
+    }
+
+    #[cfg(any(feature = "stub_client", feature = "stub_server"))]
+    #[test]
+    fn test_find_unique_violation() {
+        let object = serde_json::json!({"id": "1", "data": {"email": "a@b.c", "name": "a"}});
+        let others = vec![
+            object.clone(),
+            serde_json::json!({"id": "2", "data": {"email": "d@e.f", "name": "a"}}),
+        ];
+        let unique_fields = vec![vec![String::from("email")]];
+
+        // The object itself should not be considered a duplicate
+        assert_eq!(
+            find_unique_violation(&object, &others, &unique_fields),
+            None
+        );
+
+        let duplicate = serde_json::json!({"id": "3", "data": {"email": "d@e.f", "name": "b"}});
+        assert_eq!(
+            find_unique_violation(&duplicate, &others, &unique_fields),
+            Some(String::from("email"))
+        );
+        assert_eq!(find_unique_violation(&duplicate, &others, &[]), None);
+    }
+
+    #[test]
+    fn test_search_cursor() {
+        let filter = AdvancedSearchFilter::search_is_null(String::from("deleted_at"))
+            .results_per_page(10)
+            .page_number(2);
+        assert_eq!(filter.cursor, None);
+
+        let filter = filter.cursor(String::from("cursor"));
+        assert_eq!(filter.cursor, Some(String::from("cursor")));
+        assert_eq!(filter.results_per_page, 10);
+        assert_eq!(filter.page_number, 2);
+    }
+
+    #[test]
+    fn test_search_include_archived() {
+        let filter =
+            AdvancedSearchFilter::search_equals(String::from("name"), String::from("test"));
+        assert!(!filter.include_archived);
+
+        let filter = filter.include_archived(true);
+        assert!(filter.include_archived);
+        assert_eq!(filter.filters.len(), 1);
+    }
+
     #[test]
     fn test_predicate_operator_as_str_name() {
         assert_eq!(PredicateOperator::Equals.as_str_name(), "EQUALS");
@@ -0,0 +1,101 @@
+use opentelemetry::global;
+use opentelemetry::propagation::{Extractor, Injector};
+use opentelemetry::trace::TraceContextExt;
+use tonic::metadata::{AsciiMetadataKey, KeyRef, MetadataMap};
+use tracing_opentelemetry::OpenTelemetrySpanExt;
+
+/// Reads trace context values from gRPC metadata, used to extract the trace context with
+/// the global [`TextMapPropagator`](opentelemetry::propagation::TextMapPropagator)
+#[derive(Debug)]
+pub struct MetadataExtractor<'a>(pub &'a MetadataMap);
+
+impl Extractor for MetadataExtractor<'_> {
+    fn get(&self, key: &str) -> Option<&str> {
+        self.0.get(key).and_then(|value| value.to_str().ok())
+    }
+
+    fn keys(&self) -> Vec<&str> {
+        self.0
+            .keys()
+            .map(|key| match key {
+                KeyRef::Ascii(key) => key.as_str(),
+                KeyRef::Binary(key) => key.as_str(),
+            })
+            .collect()
+    }
+}
+
+/// Writes trace context values to gRPC metadata, used to inject the trace context with
+/// the global [`TextMapPropagator`](opentelemetry::propagation::TextMapPropagator)
+#[derive(Debug)]
+pub struct MetadataInjector<'a>(pub &'a mut MetadataMap);
+
+impl Injector for MetadataInjector<'_> {
+    fn set(&mut self, key: &str, value: String) {
+        // Values which are not valid metadata are skipped
+        let key = AsciiMetadataKey::from_bytes(key.as_bytes());
+        if let (Ok(key), Ok(value)) = (key, value.parse()) {
+            self.0.insert(key, value);
+        }
+    }
+}
+
+/// Sets the trace context found in the provided gRPC metadata (W3C `traceparent` when using the
+/// `TraceContextPropagator`) as parent of the current span.
+///
+/// The current span is left untouched if the metadata doesn't contain a valid trace context.
+pub fn set_parent_from_metadata(metadata: &MetadataMap) {
+    let context = global::get_text_map_propagator(|propagator| {
+        propagator.extract(&MetadataExtractor(metadata))
+    });
+    if context.span().span_context().is_valid() {
+        tracing::Span::current().set_parent(context);
+    }
+}
+
+/// Returns a [`tonic::Request`] for the provided message, with the trace context of the current
+/// span added to its metadata.
+///
+/// The trace context is injected using the global
+/// [`TextMapPropagator`](opentelemetry::propagation::TextMapPropagator), so nothing will be added
+/// unless the application has configured one (like the W3C `TraceContextPropagator`).
+pub fn with_trace_context<T>(message: T) -> tonic::Request<T> {
+    let mut request = tonic::Request::new(message);
+    let context = tracing::Span::current().context();
+    global::get_text_map_propagator(|propagator| {
+        propagator.inject_context(&context, &mut MetadataInjector(request.metadata_mut()))
+    });
+    request
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TRACEPARENT: &str = "00-0af7651916cd43dd8448eb211c80319c-b7ad6b7169203331-01";
+
+    #[test]
+    fn test_metadata_extractor() {
+        let mut metadata = MetadataMap::new();
+        metadata.insert("traceparent", TRACEPARENT.parse().unwrap());
+
+        let extractor = MetadataExtractor(&metadata);
+        assert_eq!(extractor.get("traceparent"), Some(TRACEPARENT));
+        assert_eq!(extractor.get("tracestate"), None);
+        assert_eq!(extractor.keys(), vec!["traceparent"]);
+    }
+
+    #[test]
+    fn test_metadata_injector() {
+        let mut metadata = MetadataMap::new();
+
+        let mut injector = MetadataInjector(&mut metadata);
+        injector.set("traceparent", TRACEPARENT.to_owned());
+        // Invalid keys and values are skipped
+        injector.set("invalid key", String::from("value"));
+        injector.set("tracestate", String::from("invalid\nvalue"));
+
+        assert_eq!(metadata.len(), 1);
+        assert_eq!(metadata.get("traceparent").unwrap(), TRACEPARENT);
+    }
+}
@@ -0,0 +1,27 @@
+use super::{Data, VehicleModelType};
+use lib_common::uuid::Uuid;
+use rand::Rng;
+
+/// Creates a new [Data] object with fields set with random data
+pub fn get_data_obj() -> Data {
+    let mut rng = rand::thread_rng();
+
+    Data {
+        manufacturer_id: Uuid::new_v4().to_string(),
+        model_name: format!("Mock Model {}", rng.gen_range(1..1000)),
+        r#type: VehicleModelType::VtolCargo as i32,
+        max_payload_kg: rng.gen_range(100.0..1000.0),
+        max_range_km: rng.gen_range(50.0..500.0),
+    }
+}
+
+#[test]
+fn test_get_data_obj() {
+    let data: Data = get_data_obj();
+
+    assert!(Uuid::parse_str(&data.manufacturer_id).is_ok());
+    assert!(data.model_name.starts_with("Mock Model "));
+    assert!(VehicleModelType::try_from(data.r#type) == Ok(VehicleModelType::VtolCargo));
+    assert!(data.max_payload_kg >= 100.0 && data.max_payload_kg < 1000.0);
+    assert!(data.max_range_km >= 50.0 && data.max_range_km < 500.0);
+}
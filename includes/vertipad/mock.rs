@@ -176,6 +176,7 @@ fn test_get_data_obj_for_vertiport() {
     let data: Data = get_data_obj_for_vertiport(&super::super::vertiport::Object {
         id: vertiport_id.clone(),
         data: Some(vertiport),
+        deleted_at: None,
     });
 
     assert!(Uuid::parse_str(&data.vertiport_id).is_ok());
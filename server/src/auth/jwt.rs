@@ -0,0 +1,330 @@
+//! Bearer token validation
+//!
+//! Bearer tokens are JSON Web Tokens (JWT) signed with one of the keys of a JSON Web Key Set
+//! (JWKS) file. The key is selected using the token's `kid` header.
+//!
+//! The signing algorithm is taken from the key, never from the token. Keys should specify their
+//! algorithm (`alg`), unless it is determined by the key's curve (`EC` and `OKP` keys).
+
+use crate::common::ArrErr;
+
+use jsonwebtoken::jwk::{AlgorithmParameters, EllipticCurve, Jwk, JwkSet};
+use jsonwebtoken::{decode, decode_header, Algorithm, DecodingKey, Validation};
+use serde::Deserialize;
+use std::fs;
+use std::str::FromStr;
+
+/// Claims read from a validated token
+#[derive(Debug, Deserialize)]
+struct Claims {
+    /// the subject the token was issued for
+    sub: String,
+}
+
+/// Validates bearer tokens using the keys of a JWKS
+#[derive(Debug, Clone)]
+pub struct JwtValidator {
+    /// keys used to validate token signatures
+    jwks: JwkSet,
+    /// optional issuer (`iss` claim) tokens should have been issued by
+    issuer: Option<String>,
+    /// optional audience (`aud` claim) tokens should have been issued for
+    audience: Option<String>,
+}
+
+impl JwtValidator {
+    /// Creates a new validator using the provided keys
+    pub fn new(jwks: JwkSet, issuer: Option<String>, audience: Option<String>) -> Self {
+        Self {
+            jwks,
+            issuer,
+            audience,
+        }
+    }
+
+    /// Creates a new validator using the keys of the provided JWKS file
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ArrErr`] if the file could not be read or doesn't contain a valid JWKS
+    pub fn from_file(
+        path: &str,
+        issuer: Option<String>,
+        audience: Option<String>,
+    ) -> Result<Self, ArrErr> {
+        let contents = fs::read_to_string(path).map_err(|e| {
+            let error = format!("Unable to read JWKS file [{}]: {}", path, e);
+            auth_error!("{}", error);
+            ArrErr::Error(error)
+        })?;
+        let jwks: JwkSet = serde_json::from_str(&contents).map_err(|e| {
+            let error = format!("Unable to parse JWKS file [{}]: {}", path, e);
+            auth_error!("{}", error);
+            ArrErr::Error(error)
+        })?;
+
+        auth_info!(
+            "Loaded [{}] keys from JWKS file [{}].",
+            jwks.keys.len(),
+            path
+        );
+        Ok(Self::new(jwks, issuer, audience))
+    }
+
+    /// Validates the provided token, returning its subject (`sub` claim)
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ArrErr`] if no key could be found for the token
+    /// Returns [`ArrErr`] if the key's algorithm can't be determined (see [`get_key_algorithm`])
+    /// Returns [`ArrErr`] if the token's algorithm doesn't match the key's algorithm
+    /// Returns [`ArrErr`] if the token's signature, expiry, issuer or audience is invalid
+    pub fn validate(&self, token: &str) -> Result<String, ArrErr> {
+        let header = decode_header(token)
+            .map_err(|e| ArrErr::Error(format!("Invalid token header: {}", e)))?;
+        let jwk = self.get_key(header.kid.as_deref())?;
+
+        // Only accept the algorithm the key was issued for
+        let algorithm = get_key_algorithm(jwk)?;
+        if header.alg != algorithm {
+            return Err(ArrErr::Error(format!(
+                "Token algorithm [{:?}] does not match the key algorithm [{:?}].",
+                header.alg, algorithm
+            )));
+        }
+        let key = DecodingKey::from_jwk(jwk)
+            .map_err(|e| ArrErr::Error(format!("Invalid key in JWKS: {}", e)))?;
+
+        let mut validation = Validation::new(algorithm);
+        if let Some(issuer) = &self.issuer {
+            validation.set_issuer(&[issuer]);
+        }
+        match &self.audience {
+            Some(audience) => validation.set_audience(&[audience]),
+            None => validation.validate_aud = false,
+        }
+
+        decode::<Claims>(token, &key, &validation)
+            .map(|data| data.claims.sub)
+            .map_err(|e| ArrErr::Error(format!("Invalid token: {}", e)))
+    }
+
+    /// Returns the key matching the provided key id.
+    /// The first key is used for tokens without a key id.
+    fn get_key(&self, kid: Option<&str>) -> Result<&Jwk, ArrErr> {
+        let key = match kid {
+            Some(kid) => self.jwks.find(kid),
+            None => self.jwks.keys.first(),
+        };
+        key.ok_or_else(|| {
+            ArrErr::Error(format!(
+                "No key found in JWKS for key id [{}].",
+                kid.unwrap_or_default()
+            ))
+        })
+    }
+}
+
+/// Returns the signing algorithm of the provided key, taken from its algorithm (`alg`) or, if not
+/// specified, from its curve
+///
+/// # Errors
+///
+/// Returns [`ArrErr`] if the key's algorithm is not a signing algorithm
+/// Returns [`ArrErr`] if the key has no algorithm and its key type is used by multiple algorithms
+/// (`RSA` and `oct` keys) or its curve is not supported
+fn get_key_algorithm(jwk: &Jwk) -> Result<Algorithm, ArrErr> {
+    if let Some(algorithm) = &jwk.common.key_algorithm {
+        return Algorithm::from_str(&format!("{:?}", algorithm)).map_err(|_| {
+            ArrErr::Error(format!(
+                "Key algorithm [{:?}] is not a signing algorithm.",
+                algorithm
+            ))
+        });
+    }
+
+    let curve = match &jwk.algorithm {
+        AlgorithmParameters::EllipticCurve(params) => &params.curve,
+        AlgorithmParameters::OctetKeyPair(params) => &params.curve,
+        AlgorithmParameters::RSA(_) | AlgorithmParameters::OctetKey(_) => {
+            return Err(ArrErr::Error(String::from(
+                "Key has no algorithm, which is required for RSA and oct keys.",
+            )))
+        }
+    };
+    match curve {
+        EllipticCurve::P256 => Ok(Algorithm::ES256),
+        EllipticCurve::P384 => Ok(Algorithm::ES384),
+        EllipticCurve::Ed25519 => Ok(Algorithm::EdDSA),
+        EllipticCurve::P521 => Err(ArrErr::Error(String::from(
+            "Key curve [P-521] is not supported.",
+        ))),
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use crate::test_util::*;
+    use jsonwebtoken::{encode, Algorithm, EncodingKey, Header};
+    use lib_common::time::Utc;
+
+    /// Secret of the test key, base64url encoded in [`get_jwks`]
+    const SECRET: &[u8] = b"svc-storage-unit-test-secret-key-012";
+
+    /// Returns a JWKS containing a single HS256 key with key id `test`
+    pub(crate) fn get_jwks() -> JwkSet {
+        serde_json::from_value(serde_json::json!({
+            "keys": [{
+                "kty": "oct",
+                "kid": "test",
+                "alg": "HS256",
+                "k": "c3ZjLXN0b3JhZ2UtdW5pdC10ZXN0LXNlY3JldC1rZXktMDEy"
+            }]
+        }))
+        .unwrap()
+    }
+
+    /// Returns a token for the provided subject, signed with the test key
+    pub(crate) fn get_token(sub: &str, iss: &str, expires_in: i64) -> String {
+        let header = Header {
+            kid: Some(String::from("test")),
+            ..Header::new(Algorithm::HS256)
+        };
+        let claims = serde_json::json!({
+            "sub": sub,
+            "iss": iss,
+            "exp": Utc::now().timestamp() + expires_in,
+        });
+        encode(&header, &claims, &EncodingKey::from_secret(SECRET)).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_validate() {
+        assert_init_done().await;
+        ut_info!("start");
+
+        let validator = JwtValidator::new(get_jwks(), Some(String::from("arrow")), None);
+
+        let result = validator.validate(&get_token("svc-scheduler", "arrow", 3600));
+        ut_debug!("{:?}", result);
+        assert_eq!(result.unwrap(), "svc-scheduler");
+
+        // Expired tokens are rejected
+        let result = validator.validate(&get_token("svc-scheduler", "arrow", -3600));
+        ut_debug!("{:?}", result);
+        assert!(result.is_err());
+
+        // Tokens from other issuers are rejected
+        let result = validator.validate(&get_token("svc-scheduler", "other", 3600));
+        ut_debug!("{:?}", result);
+        assert!(result.is_err());
+
+        // Tokens signed with another key are rejected
+        let token = encode(
+            &Header {
+                kid: Some(String::from("test")),
+                ..Header::new(Algorithm::HS256)
+            },
+            &serde_json::json!({ "sub": "svc-scheduler", "exp": Utc::now().timestamp() + 3600 }),
+            &EncodingKey::from_secret(b"another-secret"),
+        )
+        .unwrap();
+        let result = validator.validate(&token);
+        ut_debug!("{:?}", result);
+        assert!(result.is_err());
+
+        // Invalid tokens are rejected
+        let result = validator.validate("invalid");
+        ut_debug!("{:?}", result);
+        assert!(result.is_err());
+
+        ut_info!("success");
+    }
+
+    #[tokio::test]
+    async fn test_validate_unknown_key() {
+        assert_init_done().await;
+        ut_info!("start");
+
+        let validator = JwtValidator::new(get_jwks(), None, None);
+        let token = encode(
+            &Header {
+                kid: Some(String::from("unknown")),
+                ..Header::new(Algorithm::HS256)
+            },
+            &serde_json::json!({ "sub": "svc-scheduler", "exp": Utc::now().timestamp() + 3600 }),
+            &EncodingKey::from_secret(SECRET),
+        )
+        .unwrap();
+        let result = validator.validate(&token);
+        ut_debug!("{:?}", result);
+        assert!(result.is_err());
+
+        // A key algorithm mismatch is rejected, even if the signature is valid
+        let token = encode(
+            &Header {
+                kid: Some(String::from("test")),
+                ..Header::new(Algorithm::HS384)
+            },
+            &serde_json::json!({ "sub": "svc-scheduler", "exp": Utc::now().timestamp() + 3600 }),
+            &EncodingKey::from_secret(SECRET),
+        )
+        .unwrap();
+        let result = validator.validate(&token);
+        ut_debug!("{:?}", result);
+        assert!(result.is_err());
+
+        // The algorithm can't be chosen by the token if the key doesn't specify it
+        let mut jwks = get_jwks();
+        jwks.keys[0].common.key_algorithm = None;
+        let validator = JwtValidator::new(jwks, None, None);
+        let result = validator.validate(&get_token("svc-scheduler", "arrow", 3600));
+        ut_debug!("{:?}", result);
+        assert!(result.is_err());
+
+        ut_info!("success");
+    }
+
+    #[tokio::test]
+    async fn test_get_key_algorithm() {
+        assert_init_done().await;
+        ut_info!("start");
+
+        let jwks = get_jwks();
+        assert_eq!(get_key_algorithm(&jwks.keys[0]).unwrap(), Algorithm::HS256);
+
+        // The curve determines the algorithm of EC keys without algorithm
+        let jwk: Jwk = serde_json::from_value(serde_json::json!({
+            "kty": "EC",
+            "crv": "P-384",
+            "x": "AA",
+            "y": "AA"
+        }))
+        .unwrap();
+        assert_eq!(get_key_algorithm(&jwk).unwrap(), Algorithm::ES384);
+
+        // Encryption algorithms can't be used to sign tokens
+        let jwk: Jwk = serde_json::from_value(serde_json::json!({
+            "kty": "RSA",
+            "alg": "RSA-OAEP",
+            "n": "AA",
+            "e": "AQAB"
+        }))
+        .unwrap();
+        assert!(get_key_algorithm(&jwk).is_err());
+
+        ut_info!("success");
+    }
+
+    #[tokio::test]
+    async fn test_from_file() {
+        assert_init_done().await;
+        ut_info!("start");
+
+        let result = JwtValidator::from_file("/does/not/exist.json", None, None);
+        assert!(result.is_err());
+
+        ut_info!("success");
+    }
+}
@@ -0,0 +1,329 @@
+//! gRPC request authentication and authorization
+//!
+//! Provides a [`tower::Layer`] which can be added to the tonic server to reject requests from
+//! callers which could not be authenticated (`UNAUTHENTICATED`) or are not allowed to call the
+//! requested method (`PERMISSION_DENIED`). A [`Layer`] is used instead of a tonic interceptor,
+//! since interceptors don't have access to the requested method and can't be async.
+
+use super::{get_resource, Authorizer, Identity};
+use crate::common::ArrErr;
+use crate::config::Config;
+use crate::grpc::ACTOR_METADATA_KEY;
+use crate::metrics::layer::split_path;
+
+use futures::future::BoxFuture;
+use hyper::header::{HeaderValue, AUTHORIZATION};
+use hyper::{HeaderMap, Request, Response};
+use std::sync::Arc;
+use std::task::{Context, Poll};
+use tonic::body::BoxBody;
+use tonic::transport::server::{TcpConnectInfo, TlsConnectInfo};
+use tower::{Layer, Service};
+
+/// gRPC health service, which can be called without authentication
+pub(crate) const HEALTH_SERVICE: &str = "grpc.health.v1.Health";
+
+/// Prefix added to actors provided in the request metadata of requests which are not authenticated
+pub const UNVERIFIED_ACTOR_PREFIX: &str = "unverified:";
+
+/// [`Layer`] wrapping gRPC services with [`Auth`]
+///
+/// All requests are accepted if no [`Authorizer`] is provided (`auth_enabled` not set).
+///
+/// # Examples
+/// ```
+/// use svc_storage::auth::layer::AuthLayer;
+/// use svc_storage::Config;
+/// pub async fn server() {
+///     let (_, health_service) = tonic_health::server::health_reporter();
+///     let auth_layer = AuthLayer::from_config(&Config::default()).unwrap();
+///     tonic::transport::Server::builder()
+///         .layer(auth_layer)
+///         .add_service(health_service)
+///         .serve("0.0.0.0:50051".parse().unwrap());
+/// }
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct AuthLayer {
+    authorizer: Option<Arc<Authorizer>>,
+}
+
+impl AuthLayer {
+    /// Creates a new layer checking all requests with the provided [`Authorizer`]
+    pub fn new(authorizer: Authorizer) -> Self {
+        Self {
+            authorizer: Some(Arc::new(authorizer)),
+        }
+    }
+
+    /// Creates a new layer using the provided [`Config`]
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ArrErr`] if the [`Authorizer`] could not be created from the configuration
+    pub fn from_config(config: &Config) -> Result<Self, ArrErr> {
+        if !config.auth_enabled {
+            auth_warn!("Authentication disabled, all gRPC requests will be accepted.");
+            return Ok(Self::default());
+        }
+        Ok(Self::new(Authorizer::from_config(config)?))
+    }
+}
+
+impl<S> Layer<S> for AuthLayer {
+    type Service = Auth<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        Auth {
+            inner,
+            authorizer: self.authorizer.clone(),
+        }
+    }
+}
+
+/// [`Service`] authenticating the caller of each gRPC request and checking the caller's
+/// permissions before passing the request to the inner service
+///
+/// The caller's [`Identity`] is added to the request extensions and is used as actor for the
+/// audit log, replacing any actor provided in the request metadata. Actors provided for requests
+/// which are not authenticated are prefixed with [`UNVERIFIED_ACTOR_PREFIX`].
+#[derive(Debug, Clone)]
+pub struct Auth<S> {
+    inner: S,
+    authorizer: Option<Arc<Authorizer>>,
+}
+
+impl<S, ReqBody> Service<Request<ReqBody>> for Auth<S>
+where
+    S: Service<Request<ReqBody>, Response = Response<BoxBody>> + Clone + Send + 'static,
+    S::Future: Send + 'static,
+    ReqBody: Send + 'static,
+{
+    type Response = S::Response;
+    type Error = S::Error;
+    type Future = BoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, mut request: Request<ReqBody>) -> Self::Future {
+        // Use the service which has been driven to readiness, leaving a clone in its place
+        let clone = self.inner.clone();
+        let mut inner = std::mem::replace(&mut self.inner, clone);
+
+        let (service, method) = split_path(request.uri().path());
+        let authorizer = match &self.authorizer {
+            Some(authorizer) if service != HEALTH_SERVICE => authorizer.clone(),
+            _ => {
+                set_unverified_actor(request.headers_mut());
+                return Box::pin(inner.call(request));
+            }
+        };
+
+        let peer_subject = get_peer_subject(&request);
+        let identity =
+            authorizer.authenticate(get_bearer_token(request.headers()), peer_subject.as_deref());
+        Box::pin(async move {
+            let identity = match identity {
+                Ok(identity) => identity,
+                Err(status) => return Ok(status.to_http()),
+            };
+            let resource = get_resource(&service);
+            if let Err(status) = authorizer.authorize(&identity, &resource, &method).await {
+                return Ok(status.to_http());
+            }
+
+            set_actor(request.headers_mut(), &identity);
+            request.extensions_mut().insert(identity);
+            inner.call(request).await
+        })
+    }
+}
+
+/// Returns the bearer token provided in the `authorization` header, if any
+fn get_bearer_token(headers: &HeaderMap) -> Option<&str> {
+    headers
+        .get(AUTHORIZATION)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.strip_prefix("Bearer "))
+        .map(str::trim)
+}
+
+/// Returns the common name of the client certificate provided by the caller, if any.
+/// Client certificates are only available if the server has been configured to verify them.
+#[cfg(not(tarpaulin_include))]
+// no_coverage: (R5) Requires a TLS connection, should be part of integration tests
+fn get_peer_subject<B>(request: &Request<B>) -> Option<String> {
+    let certs = request
+        .extensions()
+        .get::<TlsConnectInfo<TcpConnectInfo>>()
+        .and_then(|info| info.peer_certs())?;
+    let cert = certs.first()?;
+    let (_, cert) = x509_parser::parse_x509_certificate(cert.get_ref()).ok()?;
+    let subject = cert.subject().iter_common_name().next()?;
+    subject.as_str().ok().map(String::from)
+}
+
+/// Sets the caller's [`Identity`] as actor in the request metadata, see [`ACTOR_METADATA_KEY`]
+fn set_actor(headers: &mut HeaderMap, identity: &Identity) {
+    match HeaderValue::from_str(&identity.to_string()) {
+        Ok(value) => {
+            headers.insert(ACTOR_METADATA_KEY, value);
+        }
+        Err(e) => {
+            auth_warn!("Could not set actor for [{}]: {}", identity, e);
+            headers.remove(ACTOR_METADATA_KEY);
+        }
+    }
+}
+
+/// Prefixes the actor provided in the request metadata with [`UNVERIFIED_ACTOR_PREFIX`], since it
+/// can't be trusted without authentication
+fn set_unverified_actor(headers: &mut HeaderMap) {
+    let actor = match headers.get(ACTOR_METADATA_KEY).map(HeaderValue::to_str) {
+        Some(Ok(actor)) if !actor.is_empty() => format!("{}{}", UNVERIFIED_ACTOR_PREFIX, actor),
+        Some(_) => {
+            headers.remove(ACTOR_METADATA_KEY);
+            return;
+        }
+        None => return,
+    };
+    match HeaderValue::from_str(&actor) {
+        Ok(value) => {
+            headers.insert(ACTOR_METADATA_KEY, value);
+        }
+        Err(e) => {
+            auth_warn!("Could not set unverified actor [{}]: {}", actor, e);
+            headers.remove(ACTOR_METADATA_KEY);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::auth::jwt;
+    use crate::metrics::layer::get_status_code;
+    use crate::test_util::*;
+    use hyper::Body;
+    use std::collections::HashMap;
+    use std::convert::Infallible;
+    use tonic::Code;
+    use tower::ServiceExt;
+
+    /// Returns a layer allowing the `svc-scheduler` service to read vertiports
+    fn get_layer() -> AuthLayer {
+        let jwt = jwt::JwtValidator::new(jwt::tests::get_jwks(), None, None);
+        let acl_permissions =
+            HashMap::from([(String::from("operators"), String::from("vertiport:read"))]);
+        let service_acl_groups =
+            HashMap::from([(String::from("svc_scheduler"), String::from("operators"))]);
+        AuthLayer::new(Authorizer::new(Some(jwt), &acl_permissions, &service_acl_groups).unwrap())
+    }
+
+    /// Sends a request for the provided path through the layer, returning the response's
+    /// gRPC status code and the actor received by the inner service
+    async fn call(layer: &AuthLayer, path: &str, token: Option<&str>) -> (Code, Option<String>) {
+        let service = layer.layer(tower::service_fn(|request: Request<Body>| async move {
+            let mut response = Response::new(tonic::body::empty_body());
+            if let Some(actor) = request.headers().get(ACTOR_METADATA_KEY) {
+                response
+                    .headers_mut()
+                    .insert(ACTOR_METADATA_KEY, actor.clone());
+            }
+            Ok::<_, Infallible>(response)
+        }));
+
+        let mut request = Request::builder()
+            .uri(path)
+            .header(ACTOR_METADATA_KEY, "spoofed");
+        if let Some(token) = token {
+            request = request.header(AUTHORIZATION, format!("Bearer {}", token));
+        }
+        let response = service
+            .oneshot(request.body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+        let actor = response
+            .headers()
+            .get(ACTOR_METADATA_KEY)
+            .map(|value| value.to_str().unwrap().to_owned());
+        (get_status_code(response.headers()), actor)
+    }
+
+    #[tokio::test]
+    async fn test_auth_layer() {
+        assert_init_done().await;
+        ut_info!("start");
+
+        let layer = get_layer();
+        let token = jwt::tests::get_token("svc-scheduler", "arrow", 3600);
+
+        let (code, actor) = call(
+            &layer,
+            "/grpc.vertiport.service.RpcService/get_by_id",
+            Some(&token),
+        )
+        .await;
+        assert_eq!(code, Code::Ok);
+        assert_eq!(actor, Some(String::from("service:svc-scheduler")));
+
+        let (code, actor) = call(
+            &layer,
+            "/grpc.vertiport.service.RpcService/update",
+            Some(&token),
+        )
+        .await;
+        assert_eq!(code, Code::PermissionDenied);
+        assert_eq!(actor, None);
+
+        let (code, _) = call(&layer, "/grpc.vertiport.service.RpcService/get_by_id", None).await;
+        assert_eq!(code, Code::Unauthenticated);
+
+        let (code, _) = call(
+            &layer,
+            "/grpc.vertiport.service.RpcService/get_by_id",
+            Some("invalid"),
+        )
+        .await;
+        assert_eq!(code, Code::Unauthenticated);
+
+        // The health service can be called without authentication
+        let (code, actor) = call(&layer, "/grpc.health.v1.Health/Check", None).await;
+        assert_eq!(code, Code::Ok);
+        assert_eq!(actor, Some(String::from("unverified:spoofed")));
+
+        ut_info!("success");
+    }
+
+    #[tokio::test]
+    async fn test_auth_layer_disabled() {
+        assert_init_done().await;
+        ut_info!("start");
+
+        let layer = AuthLayer::from_config(&Config::default()).unwrap();
+        let (code, actor) = call(&layer, "/grpc.vertiport.service.RpcService/update", None).await;
+        assert_eq!(code, Code::Ok);
+        assert_eq!(actor, Some(String::from("unverified:spoofed")));
+
+        ut_info!("success");
+    }
+
+    #[tokio::test]
+    async fn test_get_bearer_token() {
+        assert_init_done().await;
+        ut_info!("start");
+
+        let mut headers = HeaderMap::new();
+        assert_eq!(get_bearer_token(&headers), None);
+
+        headers.insert(AUTHORIZATION, "Basic dXNlcjpwYXNz".parse().unwrap());
+        assert_eq!(get_bearer_token(&headers), None);
+
+        headers.insert(AUTHORIZATION, "Bearer token".parse().unwrap());
+        assert_eq!(get_bearer_token(&headers), Some("token"));
+
+        ut_info!("success");
+    }
+}
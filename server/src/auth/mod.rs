@@ -0,0 +1,512 @@
+//! Authentication and authorization
+//!
+//! Callers authenticate with a bearer token (JWT, see [`jwt::JwtValidator`]) provided in the
+//! `authorization` request metadata, or with the identity of their TLS client certificate.
+//! The token's subject (`sub` claim) or the certificate's common name identifies the caller
+//! ([`Identity`]). Subjects in [`Uuid`] format identify a `user`, any other subject identifies
+//! a service.
+//!
+//! Permissions are granted to `ACL` groups ([`Config::acl_permissions`]). Users get the
+//! permissions of the `ACL` groups they are linked to, services get the permissions of the
+//! `ACL` groups configured for them ([`Config::service_acl_groups`]).
+//! Group names and service names are matched case insensitive, with `-` and spaces read as `_`.
+
+#[macro_use]
+pub mod macros;
+pub mod jwt;
+pub mod layer;
+
+use crate::common::ArrErr;
+use crate::config::Config;
+use crate::grpc::server::group::GroupType;
+use crate::grpc::server::user_group::{self, RpcGroupLink};
+use crate::grpc::server::Id;
+
+use lib_common::uuid::Uuid;
+use std::collections::HashMap;
+use std::fmt::{self, Display};
+use std::str::FromStr;
+use tonic::{Code, Request, Status};
+
+/// Methods granted by the `read` permission
+const READ_METHODS: [&str; 9] = [
+    "get_by_id",
+    "search",
+    "search_stream",
+    "watch",
+    "get_linked_ids",
+    "get_linked",
+    "get_conflicts",
+    "get_availability",
+    "is_ready",
+];
+
+/// Methods granted by the `write` permission
+const WRITE_METHODS: [&str; 8] = [
+    "insert",
+    "insert_batch",
+    "update",
+    "delete",
+    "restore",
+    "link",
+    "replace_linked",
+    "unlink",
+];
+
+/// Authenticated caller of a gRPC request
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Identity {
+    /// user, identified by the id of its `user` record
+    User(Uuid),
+    /// service, identified by its name
+    Service(String),
+}
+
+impl Identity {
+    /// Returns the [`Identity`] for the provided subject
+    pub fn from_subject(subject: &str) -> Self {
+        match Uuid::parse_str(subject) {
+            Ok(id) => Identity::User(id),
+            Err(_) => Identity::Service(subject.to_owned()),
+        }
+    }
+}
+
+impl Display for Identity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Identity::User(id) => write!(f, "user:{}", id),
+            Identity::Service(name) => write!(f, "service:{}", name),
+        }
+    }
+}
+
+/// Permission to call one or more gRPC methods of a resource, configured as `<resource>:<method>`
+///
+/// The resource is the resource's table name, see [`get_resource`]. Both the resource and the
+/// method can be `*` to match any resource or method. The `read` and `write` methods match all
+/// methods reading or changing the resource's records.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Permission {
+    /// resource name or `*`
+    resource: String,
+    /// method name, `read`, `write` or `*`
+    method: String,
+}
+
+impl FromStr for Permission {
+    type Err = ArrErr;
+
+    fn from_str(permission: &str) -> Result<Self, Self::Err> {
+        match permission.trim().split_once(':') {
+            Some((resource, method)) if !resource.is_empty() && !method.is_empty() => Ok(Self {
+                resource: resource.to_owned(),
+                method: method.to_owned(),
+            }),
+            _ => Err(ArrErr::Error(format!(
+                "Invalid permission [{}], expected [<resource>:<method>].",
+                permission
+            ))),
+        }
+    }
+}
+
+impl Permission {
+    /// Returns `true` if the permission grants access to the provided resource method
+    pub fn matches(&self, resource: &str, method: &str) -> bool {
+        let method_matches = match self.method.as_str() {
+            "*" => true,
+            "read" => READ_METHODS.contains(&method),
+            "write" => WRITE_METHODS.contains(&method),
+            other => other == method,
+        };
+        (self.resource == "*" || self.resource == resource) && method_matches
+    }
+}
+
+/// Authenticates callers and checks their permissions
+#[derive(Debug)]
+pub struct Authorizer {
+    /// validator for bearer tokens, tokens are rejected if not set
+    jwt: Option<jwt::JwtValidator>,
+    /// permissions per (normalized) ACL group name
+    permissions: HashMap<String, Vec<Permission>>,
+    /// ACL group names per (normalized) service name
+    service_groups: HashMap<String, Vec<String>>,
+}
+
+impl Authorizer {
+    /// Creates a new authorizer using the provided token validator, permissions per ACL group
+    /// and ACL groups per service (both as comma separated lists)
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ArrErr`] if any of the provided permissions is invalid
+    pub fn new(
+        jwt: Option<jwt::JwtValidator>,
+        acl_permissions: &HashMap<String, String>,
+        service_acl_groups: &HashMap<String, String>,
+    ) -> Result<Self, ArrErr> {
+        let mut permissions = HashMap::new();
+        for (group, list) in acl_permissions {
+            let list = split_list(list)
+                .map(Permission::from_str)
+                .collect::<Result<Vec<Permission>, ArrErr>>()?;
+            permissions.insert(normalize_name(group), list);
+        }
+        let service_groups = service_acl_groups
+            .iter()
+            .map(|(service, list)| {
+                let groups = split_list(list).map(normalize_name).collect();
+                (normalize_name(service), groups)
+            })
+            .collect();
+
+        Ok(Self {
+            jwt,
+            permissions,
+            service_groups,
+        })
+    }
+
+    /// Creates a new authorizer using the provided [`Config`]
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ArrErr`] if the configured JWKS file could not be loaded
+    /// Returns [`ArrErr`] if any of the configured permissions is invalid
+    pub fn from_config(config: &Config) -> Result<Self, ArrErr> {
+        let jwt = match &config.auth_jwks_file {
+            Some(path) => Some(jwt::JwtValidator::from_file(
+                path,
+                config.auth_jwt_issuer.clone(),
+                config.auth_jwt_audience.clone(),
+            )?),
+            None => {
+                auth_warn!("No [auth_jwks_file] configured, bearer tokens will be rejected.");
+                None
+            }
+        };
+        Self::new(jwt, &config.acl_permissions, &config.service_acl_groups)
+    }
+
+    /// Returns the [`Identity`] of the caller, using the provided bearer token if any, or the
+    /// subject of the caller's client certificate otherwise.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Status`] with [`Code::Unauthenticated`] if no valid credentials were provided
+    pub fn authenticate(
+        &self,
+        token: Option<&str>,
+        peer_subject: Option<&str>,
+    ) -> Result<Identity, Status> {
+        match (token, peer_subject, &self.jwt) {
+            (Some(token), _, Some(jwt)) => match jwt.validate(token) {
+                Ok(subject) => Ok(Identity::from_subject(&subject)),
+                Err(e) => {
+                    auth_info!("Rejected bearer token: {}", e);
+                    Err(Status::unauthenticated("Invalid bearer token."))
+                }
+            },
+            (Some(_), _, None) => Err(Status::unauthenticated("Bearer tokens are not accepted.")),
+            (None, Some(subject), _) => Ok(Identity::from_subject(subject)),
+            (None, None, _) => Err(Status::unauthenticated(
+                "No bearer token or client certificate provided.",
+            )),
+        }
+    }
+
+    /// Checks if the provided [`Identity`] is allowed to call the method of the resource
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Status`] with [`Code::PermissionDenied`] if none of the caller's ACL groups
+    /// grant access to the resource method
+    /// Returns [`Status`] with [`Code::Internal`] if the caller's ACL groups could not be retrieved
+    pub async fn authorize(
+        &self,
+        identity: &Identity,
+        resource: &str,
+        method: &str,
+    ) -> Result<(), Status> {
+        let groups = match identity {
+            Identity::User(id) => get_user_acl_groups(id).await?,
+            Identity::Service(name) => self
+                .service_groups
+                .get(&normalize_name(name))
+                .cloned()
+                .unwrap_or_default(),
+        };
+        auth_debug!("[{}] is member of ACL groups [{:?}].", identity, groups);
+
+        if self.is_allowed(&groups, resource, method) {
+            return Ok(());
+        }
+        let error = format!(
+            "[{}] is not allowed to call [{}] for [{}].",
+            identity, method, resource
+        );
+        auth_info!("{}", error);
+        Err(Status::permission_denied(error))
+    }
+
+    /// Returns `true` if any of the provided ACL groups grants access to the resource method
+    pub fn is_allowed(&self, groups: &[String], resource: &str, method: &str) -> bool {
+        groups.iter().any(|group| {
+            self.permissions.get(group).map_or(false, |permissions| {
+                permissions
+                    .iter()
+                    .any(|permission| permission.matches(resource, method))
+            })
+        })
+    }
+}
+
+/// Returns the (normalized) names of the `ACL` groups linked to the provided user
+///
+/// # Errors
+///
+/// Returns [`Status`] with [`Code::PermissionDenied`] if the user does not exist
+/// Returns [`Status`] with [`Code::Internal`] if the user's groups could not be retrieved
+#[cfg(not(tarpaulin_include))]
+// no_coverage: (R5) Reads database records, should be part of integration tests
+async fn get_user_acl_groups(id: &Uuid) -> Result<Vec<String>, Status> {
+    let request = Request::new(Id {
+        id: id.to_string(),
+        include_archived: false,
+    });
+    let groups = user_group::GrpcServer::default()
+        .get_linked(request)
+        .await
+        .map_err(|e| match e.code() {
+            Code::NotFound => Status::permission_denied(format!("Unknown user [{}].", id)),
+            _ => {
+                auth_error!("Could not get groups for user [{}]: {}", id, e);
+                Status::internal("Could not determine permissions.")
+            }
+        })?
+        .into_inner();
+
+    Ok(groups
+        .list
+        .into_iter()
+        .filter_map(|group| group.data)
+        .filter(|data| data.group_type == GroupType::Acl as i32)
+        .map(|data| normalize_name(&data.name))
+        .collect())
+}
+
+/// Returns the resource name for the provided gRPC service name (`grpc.<package>.<Service>`).
+///
+/// The package name is used for all services except link services (`Rpc<Other>Link`), which
+/// use the name of the link table (`<package>_<other>`).
+pub fn get_resource(service: &str) -> String {
+    let package = service
+        .trim_start_matches("grpc.")
+        .split('.')
+        .next()
+        .unwrap_or_default();
+    let name = service.rsplit('.').next().unwrap_or_default();
+    match name
+        .strip_prefix("Rpc")
+        .and_then(|name| name.strip_suffix("Link"))
+    {
+        Some(other) if !other.is_empty() => format!("{}_{}", package, to_snake_case(other)),
+        _ => package.to_owned(),
+    }
+}
+
+/// Normalizes group and service names, so they can be matched with the (lowercase)
+/// environment variable names used for configuration
+pub fn normalize_name(name: &str) -> String {
+    name.trim().to_lowercase().replace(['-', ' '], "_")
+}
+
+/// Splits a comma separated list, skipping empty entries
+fn split_list(list: &str) -> impl Iterator<Item = &str> {
+    list.split(',')
+        .map(str::trim)
+        .filter(|entry| !entry.is_empty())
+}
+
+/// Converts a CamelCase name into snake_case
+fn to_snake_case(name: &str) -> String {
+    let mut snake = String::new();
+    for (index, c) in name.chars().enumerate() {
+        if c.is_uppercase() && index > 0 {
+            snake.push('_');
+        }
+        snake.extend(c.to_lowercase());
+    }
+    snake
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::*;
+
+    #[tokio::test]
+    async fn test_identity_from_subject() {
+        assert_init_done().await;
+        ut_info!("start");
+
+        let id = Uuid::new_v4();
+        let identity = Identity::from_subject(&id.to_string());
+        assert_eq!(identity, Identity::User(id));
+        assert_eq!(identity.to_string(), format!("user:{}", id));
+
+        let identity = Identity::from_subject("svc-scheduler");
+        assert_eq!(identity, Identity::Service(String::from("svc-scheduler")));
+        assert_eq!(identity.to_string(), "service:svc-scheduler");
+
+        ut_info!("success");
+    }
+
+    #[tokio::test]
+    async fn test_permission_matches() {
+        assert_init_done().await;
+        ut_info!("start");
+
+        let permission: Permission = "vertiport:read".parse().unwrap();
+        assert!(permission.matches("vertiport", "get_by_id"));
+        assert!(permission.matches("vertiport", "search"));
+        assert!(!permission.matches("vertiport", "update"));
+        assert!(!permission.matches("vertipad", "get_by_id"));
+
+        let permission: Permission = "*:write".parse().unwrap();
+        assert!(permission.matches("vertipad", "insert"));
+        assert!(permission.matches("user_group", "link"));
+        assert!(!permission.matches("vertipad", "search"));
+        assert!(!permission.matches("admin", "purge"));
+
+        let permission: Permission = "admin:purge".parse().unwrap();
+        assert!(permission.matches("admin", "purge"));
+
+        let permission: Permission = "*:*".parse().unwrap();
+        assert!(permission.matches("admin", "purge"));
+
+        assert!("vertiport".parse::<Permission>().is_err());
+        assert!(":read".parse::<Permission>().is_err());
+        assert!("vertiport:".parse::<Permission>().is_err());
+
+        ut_info!("success");
+    }
+
+    #[tokio::test]
+    async fn test_get_resource() {
+        assert_init_done().await;
+        ut_info!("start");
+
+        assert_eq!(
+            get_resource("grpc.vertiport.service.RpcService"),
+            "vertiport"
+        );
+        assert_eq!(get_resource("grpc.user.service.RpcGroupLink"), "user_group");
+        assert_eq!(get_resource("grpc.group.service.RpcUserLink"), "group_user");
+        assert_eq!(
+            get_resource("grpc.itinerary.service.RpcFlightPlanLink"),
+            "itinerary_flight_plan"
+        );
+        assert_eq!(
+            get_resource("grpc.flight_plan_parcel.service.RpcServiceLinked"),
+            "flight_plan_parcel"
+        );
+        assert_eq!(
+            get_resource("grpc.flight_plan.service.RpcSchedule"),
+            "flight_plan"
+        );
+        assert_eq!(get_resource("grpc.audit.RpcAudit"), "audit");
+        assert_eq!(get_resource("grpc.admin.service.RpcAdmin"), "admin");
+
+        ut_info!("success");
+    }
+
+    #[tokio::test]
+    async fn test_authenticate() {
+        assert_init_done().await;
+        ut_info!("start");
+
+        let jwt = jwt::JwtValidator::new(jwt::tests::get_jwks(), None, None);
+        let authorizer = Authorizer::new(Some(jwt), &HashMap::new(), &HashMap::new()).unwrap();
+
+        let token = jwt::tests::get_token("svc-scheduler", "arrow", 3600);
+        let result = authorizer.authenticate(Some(&token), None);
+        assert_eq!(
+            result.unwrap(),
+            Identity::Service(String::from("svc-scheduler"))
+        );
+
+        // The client certificate is used if no bearer token is provided
+        let result = authorizer.authenticate(None, Some("svc-compliance"));
+        assert_eq!(
+            result.unwrap(),
+            Identity::Service(String::from("svc-compliance"))
+        );
+
+        // An invalid token is rejected, even if a client certificate is provided
+        let result = authorizer.authenticate(Some("invalid"), Some("svc-compliance"));
+        assert_eq!(result.unwrap_err().code(), Code::Unauthenticated);
+
+        let result = authorizer.authenticate(None, None);
+        assert_eq!(result.unwrap_err().code(), Code::Unauthenticated);
+
+        // Bearer tokens are rejected if no JWKS is configured
+        let authorizer = Authorizer::new(None, &HashMap::new(), &HashMap::new()).unwrap();
+        let result = authorizer.authenticate(Some(&token), None);
+        assert_eq!(result.unwrap_err().code(), Code::Unauthenticated);
+
+        ut_info!("success");
+    }
+
+    #[tokio::test]
+    async fn test_authorize_service() {
+        assert_init_done().await;
+        ut_info!("start");
+
+        let acl_permissions = HashMap::from([
+            (
+                String::from("operators"),
+                String::from("vertiport:read, vertipad:*"),
+            ),
+            (
+                String::from("Fleet Managers"),
+                String::from("vehicle:write"),
+            ),
+        ]);
+        let service_acl_groups = HashMap::from([(
+            String::from("svc_scheduler"),
+            String::from("operators,fleet-managers"),
+        )]);
+        let authorizer = Authorizer::new(None, &acl_permissions, &service_acl_groups).unwrap();
+
+        let identity = Identity::Service(String::from("svc-scheduler"));
+        for (resource, method) in [
+            ("vertiport", "search"),
+            ("vertipad", "update"),
+            ("vehicle", "insert"),
+        ] {
+            let result = authorizer.authorize(&identity, resource, method).await;
+            ut_debug!("{:?}", result);
+            assert!(result.is_ok());
+        }
+        for (resource, method) in [("vertiport", "update"), ("vehicle", "get_by_id")] {
+            let result = authorizer.authorize(&identity, resource, method).await;
+            ut_debug!("{:?}", result);
+            assert_eq!(result.unwrap_err().code(), Code::PermissionDenied);
+        }
+
+        // Services without ACL groups are not allowed to call anything
+        let identity = Identity::Service(String::from("svc-unknown"));
+        let result = authorizer.authorize(&identity, "vertiport", "search").await;
+        assert_eq!(result.unwrap_err().code(), Code::PermissionDenied);
+
+        // Invalid permissions are rejected
+        let acl_permissions = HashMap::from([(String::from("operators"), String::from("read"))]);
+        let result = Authorizer::new(None, &acl_permissions, &service_acl_groups);
+        assert!(result.is_err());
+
+        ut_info!("success");
+    }
+}
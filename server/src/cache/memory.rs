@@ -0,0 +1,258 @@
+//! In-process LRU cache
+//!
+//! Each resource has its own LRU list, so frequently changing resources can't evict the values of
+//! other resources. Values are only visible to the current instance.
+
+use super::{ArrErr, Cache};
+
+use std::collections::{BTreeMap, HashMap};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// Cached value with its expiry time and position in the LRU list
+#[derive(Debug)]
+struct Entry {
+    value: Vec<u8>,
+    expires_at: Instant,
+    last_used: u64,
+}
+
+/// LRU list of a single resource
+#[derive(Debug, Default)]
+struct LruMap {
+    entries: HashMap<String, Entry>,
+    /// keys ordered by their last use, least recently used first
+    order: BTreeMap<u64, String>,
+    tick: u64,
+    /// bumped every time the resource's values are cleared
+    generation: u64,
+}
+
+impl LruMap {
+    fn next_tick(&mut self) -> u64 {
+        self.tick += 1;
+        self.tick
+    }
+
+    fn get(&mut self, key: &str) -> Option<Vec<u8>> {
+        let tick = self.next_tick();
+        let entry = self.entries.get_mut(key)?;
+        if entry.expires_at <= Instant::now() {
+            self.remove(key);
+            return None;
+        }
+        self.order.remove(&entry.last_used);
+        self.order.insert(tick, key.to_owned());
+        entry.last_used = tick;
+        Some(entry.value.clone())
+    }
+
+    fn set(&mut self, key: &str, value: Vec<u8>, expires_at: Instant, capacity: usize) {
+        let tick = self.next_tick();
+        self.remove(key);
+        while self.entries.len() >= capacity {
+            match self.order.pop_first() {
+                Some((_, oldest)) => {
+                    self.entries.remove(&oldest);
+                }
+                None => break,
+            }
+        }
+        self.order.insert(tick, key.to_owned());
+        self.entries.insert(
+            key.to_owned(),
+            Entry {
+                value,
+                expires_at,
+                last_used: tick,
+            },
+        );
+    }
+
+    fn remove(&mut self, key: &str) {
+        if let Some(entry) = self.entries.remove(key) {
+            self.order.remove(&entry.last_used);
+        }
+    }
+
+    fn clear(&mut self) {
+        *self = Self {
+            generation: self.generation + 1,
+            ..Self::default()
+        };
+    }
+}
+
+/// In-process [`Cache`] keeping the least recently used values up to the configured capacity
+#[derive(Debug)]
+pub struct MemoryCache {
+    ttl: Duration,
+    resources: Mutex<HashMap<String, LruMap>>,
+}
+
+impl MemoryCache {
+    /// Creates a new, empty cache. Values expire after the provided `ttl`.
+    pub fn new(ttl: Duration) -> Self {
+        Self {
+            ttl,
+            resources: Mutex::new(HashMap::new()),
+        }
+    }
+
+    fn lock(&self) -> Result<std::sync::MutexGuard<'_, HashMap<String, LruMap>>, ArrErr> {
+        self.resources.lock().map_err(|e| {
+            let error = format!("Could not lock memory cache: {}", e);
+            cache_error!("{}", error);
+            ArrErr::Error(error)
+        })
+    }
+}
+
+#[tonic::async_trait]
+impl Cache for MemoryCache {
+    async fn get(&self, resource: &str, key: &str) -> Result<Option<Vec<u8>>, ArrErr> {
+        Ok(self
+            .lock()?
+            .get_mut(resource)
+            .and_then(|values| values.get(key)))
+    }
+
+    async fn get_generation(&self, resource: &str) -> Result<u64, ArrErr> {
+        Ok(self
+            .lock()?
+            .get(resource)
+            .map_or(0, |values| values.generation))
+    }
+
+    async fn set(
+        &self,
+        resource: &str,
+        key: &str,
+        value: Vec<u8>,
+        capacity: usize,
+        generation: u64,
+    ) -> Result<(), ArrErr> {
+        let expires_at = Instant::now() + self.ttl;
+        let mut resources = self.lock()?;
+        let values = resources.entry(resource.to_owned()).or_default();
+        if values.generation != generation {
+            cache_debug!(
+                "Cache for [{}] has been cleared since generation [{}], not caching value [{}].",
+                resource,
+                generation,
+                key
+            );
+            return Ok(());
+        }
+        values.set(key, value, expires_at, capacity);
+        Ok(())
+    }
+
+    async fn clear(&self, resource: &str) -> Result<(), ArrErr> {
+        self.lock()?.entry(resource.to_owned()).or_default().clear();
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::*;
+
+    #[tokio::test]
+    async fn test_memory_cache_get_set() {
+        assert_init_done().await;
+        ut_info!("start");
+
+        let cache = MemoryCache::new(Duration::from_secs(60));
+        assert_eq!(cache.get("vertipad", "a").await.unwrap(), None);
+
+        cache.set("vertipad", "a", vec![1], 10, 0).await.unwrap();
+        cache.set("vertiport", "a", vec![2], 10, 0).await.unwrap();
+        assert_eq!(cache.get("vertipad", "a").await.unwrap(), Some(vec![1]));
+        assert_eq!(cache.get("vertiport", "a").await.unwrap(), Some(vec![2]));
+
+        cache.set("vertipad", "a", vec![3], 10, 0).await.unwrap();
+        assert_eq!(cache.get("vertipad", "a").await.unwrap(), Some(vec![3]));
+
+        ut_info!("success");
+    }
+
+    #[tokio::test]
+    async fn test_memory_cache_lru() {
+        assert_init_done().await;
+        ut_info!("start");
+
+        let cache = MemoryCache::new(Duration::from_secs(60));
+        cache.set("vertipad", "a", vec![1], 2, 0).await.unwrap();
+        cache.set("vertipad", "b", vec![2], 2, 0).await.unwrap();
+
+        // Using `a` makes `b` the least recently used value
+        assert!(cache.get("vertipad", "a").await.unwrap().is_some());
+        cache.set("vertipad", "c", vec![3], 2, 0).await.unwrap();
+
+        assert_eq!(cache.get("vertipad", "a").await.unwrap(), Some(vec![1]));
+        assert_eq!(cache.get("vertipad", "b").await.unwrap(), None);
+        assert_eq!(cache.get("vertipad", "c").await.unwrap(), Some(vec![3]));
+
+        ut_info!("success");
+    }
+
+    #[tokio::test]
+    async fn test_memory_cache_expiry() {
+        assert_init_done().await;
+        ut_info!("start");
+
+        let cache = MemoryCache::new(Duration::ZERO);
+        cache.set("vertipad", "a", vec![1], 10, 0).await.unwrap();
+        assert_eq!(cache.get("vertipad", "a").await.unwrap(), None);
+
+        ut_info!("success");
+    }
+
+    #[tokio::test]
+    async fn test_memory_cache_clear() {
+        assert_init_done().await;
+        ut_info!("start");
+
+        let cache = MemoryCache::new(Duration::from_secs(60));
+        cache.set("vertipad", "a", vec![1], 10, 0).await.unwrap();
+        cache.set("vertiport", "a", vec![2], 10, 0).await.unwrap();
+
+        cache.clear("vertipad").await.unwrap();
+        assert_eq!(cache.get("vertipad", "a").await.unwrap(), None);
+        assert_eq!(cache.get("vertiport", "a").await.unwrap(), Some(vec![2]));
+
+        ut_info!("success");
+    }
+
+    #[tokio::test]
+    async fn test_memory_cache_generation() {
+        assert_init_done().await;
+        ut_info!("start");
+
+        let cache = MemoryCache::new(Duration::from_secs(60));
+        let generation = cache.get_generation("vertipad").await.unwrap();
+        cache.clear("vertipad").await.unwrap();
+        assert_eq!(
+            cache.get_generation("vertipad").await.unwrap(),
+            generation + 1
+        );
+        assert_eq!(cache.get_generation("vertiport").await.unwrap(), 0);
+
+        // Values read before the cache was cleared may be stale and are not cached
+        cache
+            .set("vertipad", "a", vec![1], 10, generation)
+            .await
+            .unwrap();
+        assert_eq!(cache.get("vertipad", "a").await.unwrap(), None);
+
+        cache
+            .set("vertipad", "a", vec![2], 10, generation + 1)
+            .await
+            .unwrap();
+        assert_eq!(cache.get("vertipad", "a").await.unwrap(), Some(vec![2]));
+
+        ut_info!("success");
+    }
+}
@@ -0,0 +1,320 @@
+//! Read-through cache
+//!
+//! Records returned by `get_by_id` and the ids returned by `get_linked_ids` can be cached per
+//! resource, by configuring a cache size for the resource's table ([`Config::cache_size`]).
+//! Two backends are available ([`Config::cache_backend`]), an in-process LRU cache
+//! ([`memory::MemoryCache`]) and a Redis cache which can be shared by multiple instances
+//! ([`redis::RedisCache`]). Values are cached as encoded protobuf messages.
+//!
+//! All cached values of a resource are removed after one of its records has been changed, see
+//! [`invalidate`]. Cached values expire after [`Config::cache_ttl_secs`], so changes made by other
+//! instances will be picked up when using the in-process cache.
+//!
+//! Each invalidation bumps the resource's generation. Callers read the generation
+//! ([`get_generation`]) before reading from the database, values are only cached if the
+//! generation is unchanged, so data read before a change is never cached after its invalidation.
+//!
+//! Cache errors are logged but never returned, the database will be used instead.
+
+#[macro_use]
+pub mod macros;
+pub mod memory;
+pub mod redis;
+
+use crate::common::ArrErr;
+use crate::config::Config;
+use crate::resources::base::Resource;
+
+use lib_common::time::{DateTime, Utc};
+use lib_common::uuid::Uuid;
+use once_cell::sync::OnceCell;
+use prost::Message;
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::time::Duration;
+
+/// Available cache backends
+#[derive(Debug, Deserialize, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum CacheBackend {
+    /// Caching disabled
+    #[default]
+    None,
+    /// In-process LRU cache, see [`memory::MemoryCache`]
+    Memory,
+    /// Redis cache, see [`redis::RedisCache`]
+    Redis,
+}
+
+/// Generic cache backend storing encoded values per resource
+#[tonic::async_trait]
+pub trait Cache: Send + Sync {
+    /// Returns the value cached for the provided resource and key, if any
+    async fn get(&self, resource: &str, key: &str) -> Result<Option<Vec<u8>>, ArrErr>;
+    /// Returns the current generation of the provided resource, which changes every time the
+    /// resource's values are cleared
+    async fn get_generation(&self, resource: &str) -> Result<u64, ArrErr>;
+    /// Caches the provided value for the resource and key, unless the resource's values have been
+    /// cleared since the provided `generation` was read.
+    /// No more than `capacity` values should be kept for the resource.
+    async fn set(
+        &self,
+        resource: &str,
+        key: &str,
+        value: Vec<u8>,
+        capacity: usize,
+        generation: u64,
+    ) -> Result<(), ArrErr>;
+    /// Removes all cached values of the provided resource and bumps its generation
+    async fn clear(&self, resource: &str) -> Result<(), ArrErr>;
+}
+
+/// Record cached by `get_by_id`
+#[derive(Clone, PartialEq, Message)]
+pub struct CachedObject {
+    /// the encoded gRPC `Object`
+    #[prost(bytes = "vec", tag = "1")]
+    pub object: Vec<u8>,
+    /// the record's `updated_at` value as RFC 3339 [`String`], if any
+    #[prost(string, optional, tag = "2")]
+    pub updated_at: Option<String>,
+}
+
+/// Cache backend and cache sizes per resource
+struct ResourceCache {
+    backend: Box<dyn Cache>,
+    sizes: HashMap<String, usize>,
+}
+
+static CACHE: OnceCell<ResourceCache> = OnceCell::new();
+
+/// Initializes the cache using the provided [`Config`].
+/// Caching stays disabled if no cache backend is configured.
+///
+/// # Errors
+///
+/// Returns [`ArrErr`] if the `redis` backend is configured without a `redis_url`
+/// Returns [`ArrErr`] if no connection could be made to the Redis server
+#[cfg(not(tarpaulin_include))]
+// no_coverage: (R5) Will be part of integration tests, coverage report will need to be merged to show.
+pub async fn init(config: &Config) -> Result<(), ArrErr> {
+    let ttl = Duration::from_secs(config.cache_ttl_secs);
+    let backend: Box<dyn Cache> = match config.cache_backend {
+        CacheBackend::None => {
+            cache_info!("No cache backend configured, caching disabled.");
+            return Ok(());
+        }
+        CacheBackend::Memory => Box::new(memory::MemoryCache::new(ttl)),
+        CacheBackend::Redis => match &config.redis_url {
+            Some(url) => Box::new(redis::RedisCache::connect(url, ttl).await?),
+            None => {
+                let error = "No [redis_url] configured for the [redis] cache backend.";
+                cache_error!("{}", error);
+                return Err(ArrErr::Error(error.to_owned()));
+            }
+        },
+    };
+
+    cache_info!(
+        "Using [{:?}] cache backend with cache sizes [{:?}].",
+        config.cache_backend,
+        config.cache_size
+    );
+    let cache = ResourceCache {
+        backend,
+        sizes: config.cache_size.clone(),
+    };
+    if CACHE.set(cache).is_err() {
+        cache_warn!("Cache has already been initialized.");
+    }
+    Ok(())
+}
+
+/// Returns the cache backend and the configured cache size for the provided resource,
+/// if caching is enabled for the resource
+fn get_cache(resource: &str) -> Option<(&'static dyn Cache, usize)> {
+    let cache = CACHE.get()?;
+    match cache.sizes.get(resource) {
+        Some(size) if *size > 0 => Some((cache.backend.as_ref(), *size)),
+        _ => None,
+    }
+}
+
+/// Returns the value cached for the provided resource and key.
+///
+/// Returns [`None`] if caching is disabled for the resource, if no value is cached or if the
+/// cached value could not be read.
+pub async fn get<M: Message + Default>(resource: &str, key: &str) -> Option<M> {
+    let (cache, _) = get_cache(resource)?;
+    let value = match cache.get(resource, key).await {
+        Ok(value) => value?,
+        Err(e) => {
+            cache_warn!("Could not get cached [{}] value [{}]: {}", resource, key, e);
+            return None;
+        }
+    };
+    match M::decode(value.as_slice()) {
+        Ok(value) => {
+            cache_debug!("Found cached [{}] value [{}].", resource, key);
+            Some(value)
+        }
+        Err(e) => {
+            cache_warn!(
+                "Could not decode cached [{}] value [{}]: {}",
+                resource,
+                key,
+                e
+            );
+            None
+        }
+    }
+}
+
+/// Returns the current generation of the provided resource, to be passed to [`set`] or
+/// [`set_object`] once the value has been read from the database.
+///
+/// Returns [`None`] if caching is disabled for the resource or if the generation could not be read.
+pub async fn get_generation(resource: &str) -> Option<u64> {
+    let (cache, _) = get_cache(resource)?;
+    match cache.get_generation(resource).await {
+        Ok(generation) => Some(generation),
+        Err(e) => {
+            cache_warn!("Could not get [{}] cache generation: {}", resource, e);
+            None
+        }
+    }
+}
+
+/// Caches the provided value for the resource and key, if caching is enabled for the resource
+/// and the resource has not been invalidated since the provided `generation` was read.
+///
+/// Nothing is cached if no generation is provided.
+pub async fn set<M: Message>(resource: &str, key: &str, value: &M, generation: Option<u64>) {
+    if let (Some(generation), Some((cache, size))) = (generation, get_cache(resource)) {
+        if let Err(e) = cache
+            .set(resource, key, value.encode_to_vec(), size, generation)
+            .await
+        {
+            cache_warn!("Could not cache [{}] value [{}]: {}", resource, key, e);
+        }
+    }
+}
+
+/// Returns the gRPC `Object` cached for the provided resource and key, with the record's
+/// `updated_at` value if any.
+///
+/// Returns [`None`] if caching is disabled for the resource, if no object is cached or if the
+/// cached object could not be read.
+pub async fn get_object<O: Message + Default>(
+    resource: &str,
+    key: &str,
+) -> Option<(O, Option<DateTime<Utc>>)> {
+    let cached = get::<CachedObject>(resource, key).await?;
+    let object = match O::decode(cached.object.as_slice()) {
+        Ok(object) => object,
+        Err(e) => {
+            cache_warn!(
+                "Could not decode cached [{}] object [{}]: {}",
+                resource,
+                key,
+                e
+            );
+            return None;
+        }
+    };
+    let updated_at = cached
+        .updated_at
+        .and_then(|value| DateTime::parse_from_rfc3339(&value).ok())
+        .map(|value| value.with_timezone(&Utc));
+    Some((object, updated_at))
+}
+
+/// Caches the provided gRPC `Object` with the record's `updated_at` value for the resource and
+/// key, if caching is enabled for the resource and the resource has not been invalidated since
+/// the provided `generation` was read (see [`set`])
+pub async fn set_object<O: Message>(
+    resource: &str,
+    key: &str,
+    object: &O,
+    updated_at: Option<DateTime<Utc>>,
+    generation: Option<u64>,
+) {
+    if generation.is_none() || get_cache(resource).is_none() {
+        return;
+    }
+    let cached = CachedObject {
+        object: object.encode_to_vec(),
+        updated_at: updated_at.map(|value| value.to_rfc3339()),
+    };
+    set(resource, key, &cached, generation).await;
+}
+
+/// Removes all cached values of the provided resource and of the resources depending on it
+/// (see [`Resource::get_cache_dependents`]).
+///
+/// Should be called after any of the resource's records have been changed.
+pub async fn invalidate<V: Resource>() {
+    invalidate_resource(&V::get_psql_table()).await;
+    for dependent in V::get_cache_dependents() {
+        invalidate_resource(&dependent).await;
+    }
+}
+
+/// Removes all cached values of the provided resource (table name)
+pub async fn invalidate_resource(resource: &str) {
+    if let Some((cache, _)) = get_cache(resource) {
+        cache_debug!("Clearing cached [{}] values.", resource);
+        if let Err(e) = cache.clear(resource).await {
+            cache_error!("Could not clear cached [{}] values: {}", resource, e);
+        }
+    }
+}
+
+/// Returns the cache key used for the `get_by_id` value of the provided id
+pub fn get_object_key(id: &Uuid, include_archived: bool) -> String {
+    match include_archived {
+        true => format!("id:{}:archived", id),
+        false => format!("id:{}", id),
+    }
+}
+
+/// Returns the cache key used for the `get_linked_ids` value of the provided id
+pub fn get_linked_key(id: &Uuid) -> String {
+    format!("linked:{}", id)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::*;
+
+    #[tokio::test]
+    async fn test_cache_keys() {
+        assert_init_done().await;
+        ut_info!("start");
+
+        let id = Uuid::new_v4();
+        assert_eq!(get_object_key(&id, false), format!("id:{}", id));
+        assert_eq!(get_object_key(&id, true), format!("id:{}:archived", id));
+        assert_eq!(get_linked_key(&id), format!("linked:{}", id));
+
+        ut_info!("success");
+    }
+
+    #[tokio::test]
+    async fn test_cache_disabled() {
+        assert_init_done().await;
+        ut_info!("start");
+
+        // No cache sizes are configured for unit tests
+        let object = CachedObject {
+            object: vec![1, 2, 3],
+            updated_at: None,
+        };
+        set("vertipad", "id:1", &object, Some(0)).await;
+        assert_eq!(get_generation("vertipad").await, None);
+        assert_eq!(get::<CachedObject>("vertipad", "id:1").await, None);
+
+        ut_info!("success");
+    }
+}
@@ -0,0 +1,370 @@
+//! Redis cache
+//!
+//! The values of each resource are stored in a single Redis hash, so all values of a resource can
+//! be removed at once. The hash expires after the configured ttl, counting from the moment its
+//! first value was cached. Values are shared by all instances using the same Redis server.
+//!
+//! The generation of each resource is stored in a separate counter, which is incremented before
+//! the resource's hash is removed. Values are only set while the counter still has the generation
+//! read before the value was fetched from the database.
+
+use super::{ArrErr, Cache};
+
+use redis::aio::ConnectionManager;
+use redis::AsyncCommands;
+use std::time::Duration;
+
+/// Prefix used for the Redis keys of all cached resources
+const KEY_PREFIX: &str = "svc-storage:cache:";
+/// Prefix used for the Redis keys of the generation counters of all cached resources
+const GENERATION_KEY_PREFIX: &str = "svc-storage:cache-generation:";
+
+/// Lua script setting a hash field (`KEYS[1]`, `ARGV[1]`) to the provided value (`ARGV[2]`) if
+/// the counter (`KEYS[2]`) still has the expected value (`ARGV[3]`), returns `1` if the field has
+/// been set
+const HSET_IF_COUNTER_SCRIPT: &str = r#"
+if (redis.call("GET", KEYS[2]) or "0") ~= ARGV[3] then
+    return 0
+end
+redis.call("HSET", KEYS[1], ARGV[1], ARGV[2])
+return 1
+"#;
+
+/// Redis commands used by the [`RedisCache`]
+#[tonic::async_trait]
+pub trait RedisConnection: Send + Sync {
+    /// Returns the value of the provided hash field, if any
+    async fn hget(&self, key: &str, field: &str) -> Result<Option<Vec<u8>>, ArrErr>;
+    /// Sets the value of the provided hash field if the provided counter still has the expected
+    /// value. Returns `true` if the field has been set.
+    async fn hset_if_counter(
+        &self,
+        key: &str,
+        field: &str,
+        value: Vec<u8>,
+        counter_key: &str,
+        counter: u64,
+    ) -> Result<bool, ArrErr>;
+    /// Returns `true` if the provided hash field exists
+    async fn hexists(&self, key: &str, field: &str) -> Result<bool, ArrErr>;
+    /// Returns the number of fields in the provided hash
+    async fn hlen(&self, key: &str) -> Result<usize, ArrErr>;
+    /// Removes the provided key
+    async fn del(&self, key: &str) -> Result<(), ArrErr>;
+    /// Sets the expiry of the provided key in seconds
+    async fn expire(&self, key: &str, seconds: usize) -> Result<(), ArrErr>;
+    /// Returns the value of the provided counter, `0` if it doesn't exist
+    async fn get_counter(&self, key: &str) -> Result<u64, ArrErr>;
+    /// Increments the provided counter
+    async fn incr(&self, key: &str) -> Result<(), ArrErr>;
+}
+
+// The [`AsyncCommands`] functions need a mutable connection, a [`ConnectionManager`] can be cloned
+// cheaply to get one.
+#[tonic::async_trait]
+impl RedisConnection for ConnectionManager {
+    async fn hget(&self, key: &str, field: &str) -> Result<Option<Vec<u8>>, ArrErr> {
+        Ok(AsyncCommands::hget(&mut self.clone(), key, field).await?)
+    }
+    async fn hset_if_counter(
+        &self,
+        key: &str,
+        field: &str,
+        value: Vec<u8>,
+        counter_key: &str,
+        counter: u64,
+    ) -> Result<bool, ArrErr> {
+        Ok(redis::Script::new(HSET_IF_COUNTER_SCRIPT)
+            .key(key)
+            .key(counter_key)
+            .arg(field)
+            .arg(value)
+            .arg(counter)
+            .invoke_async(&mut self.clone())
+            .await?)
+    }
+    async fn hexists(&self, key: &str, field: &str) -> Result<bool, ArrErr> {
+        Ok(AsyncCommands::hexists(&mut self.clone(), key, field).await?)
+    }
+    async fn hlen(&self, key: &str) -> Result<usize, ArrErr> {
+        Ok(AsyncCommands::hlen(&mut self.clone(), key).await?)
+    }
+    async fn del(&self, key: &str) -> Result<(), ArrErr> {
+        Ok(AsyncCommands::del(&mut self.clone(), key).await?)
+    }
+    async fn expire(&self, key: &str, seconds: usize) -> Result<(), ArrErr> {
+        Ok(AsyncCommands::expire(&mut self.clone(), key, seconds).await?)
+    }
+    async fn get_counter(&self, key: &str) -> Result<u64, ArrErr> {
+        let counter: Option<u64> = AsyncCommands::get(&mut self.clone(), key).await?;
+        Ok(counter.unwrap_or(0))
+    }
+    async fn incr(&self, key: &str) -> Result<(), ArrErr> {
+        let _: u64 = AsyncCommands::incr(&mut self.clone(), key, 1).await?;
+        Ok(())
+    }
+}
+
+/// [`Cache`] storing values on a Redis server
+///
+/// New values are not cached once the configured capacity of a resource has been reached, until
+/// the resource's values have been cleared or have expired.
+#[derive(Debug)]
+pub struct RedisCache<C = ConnectionManager> {
+    connection: C,
+    ttl: Duration,
+}
+
+impl RedisCache<ConnectionManager> {
+    /// Connects to the Redis server at the provided url. Values expire after the provided `ttl`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ArrErr`] if the url is invalid or no connection could be made
+    #[cfg(not(tarpaulin_include))]
+    // no_coverage: (R5) Will be part of integration tests, coverage report will need to be merged to show.
+    pub async fn connect(url: &str, ttl: Duration) -> Result<Self, ArrErr> {
+        cache_info!("Connecting to Redis server.");
+        let client = redis::Client::open(url)?;
+        let connection = client.get_tokio_connection_manager().await?;
+        Ok(Self::new(connection, ttl))
+    }
+}
+
+impl<C: RedisConnection> RedisCache<C> {
+    /// Creates a new cache using the provided connection. Values expire after the provided `ttl`.
+    pub fn new(connection: C, ttl: Duration) -> Self {
+        Self { connection, ttl }
+    }
+}
+
+/// Returns the Redis key of the hash holding the provided resource's values
+fn get_resource_key(resource: &str) -> String {
+    format!("{}{}", KEY_PREFIX, resource)
+}
+
+/// Returns the Redis key of the counter holding the provided resource's generation
+fn get_generation_key(resource: &str) -> String {
+    format!("{}{}", GENERATION_KEY_PREFIX, resource)
+}
+
+#[tonic::async_trait]
+impl<C: RedisConnection> Cache for RedisCache<C> {
+    async fn get(&self, resource: &str, key: &str) -> Result<Option<Vec<u8>>, ArrErr> {
+        self.connection.hget(&get_resource_key(resource), key).await
+    }
+
+    async fn get_generation(&self, resource: &str) -> Result<u64, ArrErr> {
+        self.connection
+            .get_counter(&get_generation_key(resource))
+            .await
+    }
+
+    async fn set(
+        &self,
+        resource: &str,
+        key: &str,
+        value: Vec<u8>,
+        capacity: usize,
+        generation: u64,
+    ) -> Result<(), ArrErr> {
+        let resource_key = get_resource_key(resource);
+        let len = self.connection.hlen(&resource_key).await?;
+        if len >= capacity && !self.connection.hexists(&resource_key, key).await? {
+            cache_debug!(
+                "Cache for [{}] is full, not caching value [{}].",
+                resource,
+                key
+            );
+            return Ok(());
+        }
+
+        let generation_key = get_generation_key(resource);
+        if !self
+            .connection
+            .hset_if_counter(&resource_key, key, value, &generation_key, generation)
+            .await?
+        {
+            cache_debug!(
+                "Cache for [{}] has been cleared since generation [{}], not caching value [{}].",
+                resource,
+                generation,
+                key
+            );
+            return Ok(());
+        }
+        if len == 0 {
+            self.connection
+                .expire(&resource_key, self.ttl.as_secs().max(1) as usize)
+                .await?;
+        }
+        Ok(())
+    }
+
+    async fn clear(&self, resource: &str) -> Result<(), ArrErr> {
+        // The generation is incremented first, so values read before can't be set after the
+        // hash has been removed
+        self.connection.incr(&get_generation_key(resource)).await?;
+        self.connection.del(&get_resource_key(resource)).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::*;
+    use std::collections::HashMap;
+    use std::sync::Mutex;
+
+    /// Local stand-in for a Redis server
+    #[derive(Debug, Default)]
+    struct LocalRedis {
+        hashes: Mutex<HashMap<String, HashMap<String, Vec<u8>>>>,
+        expiry: Mutex<HashMap<String, usize>>,
+        counters: Mutex<HashMap<String, u64>>,
+    }
+
+    #[tonic::async_trait]
+    impl RedisConnection for LocalRedis {
+        async fn hget(&self, key: &str, field: &str) -> Result<Option<Vec<u8>>, ArrErr> {
+            let hashes = self.hashes.lock().unwrap();
+            Ok(hashes.get(key).and_then(|hash| hash.get(field)).cloned())
+        }
+        async fn hset_if_counter(
+            &self,
+            key: &str,
+            field: &str,
+            value: Vec<u8>,
+            counter_key: &str,
+            counter: u64,
+        ) -> Result<bool, ArrErr> {
+            let counters = self.counters.lock().unwrap();
+            if counters.get(counter_key).copied().unwrap_or(0) != counter {
+                return Ok(false);
+            }
+            let mut hashes = self.hashes.lock().unwrap();
+            hashes
+                .entry(key.to_owned())
+                .or_default()
+                .insert(field.to_owned(), value);
+            Ok(true)
+        }
+        async fn hexists(&self, key: &str, field: &str) -> Result<bool, ArrErr> {
+            let hashes = self.hashes.lock().unwrap();
+            Ok(hashes
+                .get(key)
+                .map_or(false, |hash| hash.contains_key(field)))
+        }
+        async fn hlen(&self, key: &str) -> Result<usize, ArrErr> {
+            let hashes = self.hashes.lock().unwrap();
+            Ok(hashes.get(key).map_or(0, |hash| hash.len()))
+        }
+        async fn del(&self, key: &str) -> Result<(), ArrErr> {
+            self.hashes.lock().unwrap().remove(key);
+            self.expiry.lock().unwrap().remove(key);
+            Ok(())
+        }
+        async fn expire(&self, key: &str, seconds: usize) -> Result<(), ArrErr> {
+            self.expiry.lock().unwrap().insert(key.to_owned(), seconds);
+            Ok(())
+        }
+        async fn get_counter(&self, key: &str) -> Result<u64, ArrErr> {
+            let counters = self.counters.lock().unwrap();
+            Ok(counters.get(key).copied().unwrap_or(0))
+        }
+        async fn incr(&self, key: &str) -> Result<(), ArrErr> {
+            *self
+                .counters
+                .lock()
+                .unwrap()
+                .entry(key.to_owned())
+                .or_default() += 1;
+            Ok(())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_redis_cache_get_set() {
+        assert_init_done().await;
+        ut_info!("start");
+
+        let cache = RedisCache::new(LocalRedis::default(), Duration::from_secs(60));
+        assert_eq!(cache.get("vertipad", "a").await.unwrap(), None);
+
+        cache.set("vertipad", "a", vec![1], 10, 0).await.unwrap();
+        cache.set("vertiport", "a", vec![2], 10, 0).await.unwrap();
+        assert_eq!(cache.get("vertipad", "a").await.unwrap(), Some(vec![1]));
+        assert_eq!(cache.get("vertiport", "a").await.unwrap(), Some(vec![2]));
+
+        // The expiry is set once, when the first value is cached
+        let expiry = cache.connection.expiry.lock().unwrap().clone();
+        assert_eq!(expiry.get("svc-storage:cache:vertipad"), Some(&60));
+        assert_eq!(expiry.get("svc-storage:cache:vertiport"), Some(&60));
+
+        ut_info!("success");
+    }
+
+    #[tokio::test]
+    async fn test_redis_cache_capacity() {
+        assert_init_done().await;
+        ut_info!("start");
+
+        let cache = RedisCache::new(LocalRedis::default(), Duration::from_secs(60));
+        cache.set("vertipad", "a", vec![1], 2, 0).await.unwrap();
+        cache.set("vertipad", "b", vec![2], 2, 0).await.unwrap();
+        cache.set("vertipad", "c", vec![3], 2, 0).await.unwrap();
+        assert_eq!(cache.get("vertipad", "c").await.unwrap(), None);
+
+        // Existing values can still be replaced
+        cache.set("vertipad", "a", vec![4], 2, 0).await.unwrap();
+        assert_eq!(cache.get("vertipad", "a").await.unwrap(), Some(vec![4]));
+
+        ut_info!("success");
+    }
+
+    #[tokio::test]
+    async fn test_redis_cache_clear() {
+        assert_init_done().await;
+        ut_info!("start");
+
+        let cache = RedisCache::new(LocalRedis::default(), Duration::from_secs(60));
+        cache.set("vertipad", "a", vec![1], 10, 0).await.unwrap();
+        cache.set("vertiport", "a", vec![2], 10, 0).await.unwrap();
+
+        cache.clear("vertipad").await.unwrap();
+        assert_eq!(cache.get("vertipad", "a").await.unwrap(), None);
+        assert_eq!(cache.get("vertiport", "a").await.unwrap(), Some(vec![2]));
+
+        ut_info!("success");
+    }
+
+    #[tokio::test]
+    async fn test_redis_cache_generation() {
+        assert_init_done().await;
+        ut_info!("start");
+
+        let cache = RedisCache::new(LocalRedis::default(), Duration::from_secs(60));
+        let generation = cache.get_generation("vertipad").await.unwrap();
+        cache.clear("vertipad").await.unwrap();
+        assert_eq!(
+            cache.get_generation("vertipad").await.unwrap(),
+            generation + 1
+        );
+        assert_eq!(cache.get_generation("vertiport").await.unwrap(), 0);
+
+        // Values read before the cache was cleared may be stale and are not cached
+        cache
+            .set("vertipad", "a", vec![1], 10, generation)
+            .await
+            .unwrap();
+        assert_eq!(cache.get("vertipad", "a").await.unwrap(), None);
+
+        cache
+            .set("vertipad", "a", vec![2], 10, generation + 1)
+            .await
+            .unwrap();
+        assert_eq!(cache.get("vertipad", "a").await.unwrap(), Some(vec![2]));
+
+        ut_info!("success");
+    }
+}
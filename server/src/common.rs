@@ -3,6 +3,7 @@
 //! Commonly used libraries, functions and statics, made public for easy use in modules.
 
 use config::ConfigError;
+use lib_common::time::{DateTime, Utc};
 use std::sync::atomic::{AtomicBool, Ordering};
 use tokio::task::JoinError;
 
@@ -51,4 +52,24 @@ pub enum ArrErr {
     #[error("error: {0}")]
     /// return new [`anyhow::Error`] with calling params
     AnyhowError(#[from] anyhow::Error),
+
+    #[error("redis error: {0}")]
+    /// return new [`redis::RedisError`] with calling params
+    RedisError(#[from] redis::RedisError),
+
+    #[error("version conflict: record was last updated at {0}")]
+    /// return new version conflict error with the current `updated_at` value of the record
+    VersionConflict(DateTime<Utc>),
+
+    #[error("unique violation: {0}")]
+    /// return new unique violation error with the conflicting field(s)
+    UniqueViolation(String),
+
+    #[error("timeslot conflict: {0:?}")]
+    /// return new timeslot conflict error with a validation error for each conflicting reservation
+    TimeslotConflict(Vec<crate::grpc::server::ValidationError>),
+
+    #[error("state conflict: {0:?}")]
+    /// return new state conflict error with a validation error for each state changed by a concurrent update
+    StateConflict(Vec<crate::grpc::server::ValidationError>),
 }
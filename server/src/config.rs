@@ -2,10 +2,12 @@
 //!
 //! Define and implement config options for module
 
+use crate::cache::CacheBackend;
 use anyhow::Result;
 use config::{ConfigError, Environment};
 use dotenv::dotenv;
 use serde::Deserialize;
+use std::collections::HashMap;
 
 /// struct holding configuration options
 #[derive(Debug, Deserialize, Clone)]
@@ -23,8 +25,61 @@ pub struct Config {
     pub db_client_key: Option<String>,
     /// port number to listen on for our gRPC server
     pub docker_port_grpc: u16,
+    /// optional path to the certificate (chain) used by the gRPC server.
+    /// The gRPC server will accept plaintext connections only if not set.
+    pub grpc_tls_cert: Option<String>,
+    /// optional path to the private key of the gRPC server certificate
+    pub grpc_tls_key: Option<String>,
+    /// optional path to the ca certificate used to verify gRPC client certificates (mTLS).
+    /// Client certificates will not be requested if not set.
+    pub grpc_tls_client_ca: Option<String>,
+    /// interval in seconds used to check the gRPC TLS files for changes, `0` disables reloading
+    pub grpc_tls_reload_interval_secs: u64,
+    /// port number to listen on for our metrics server
+    pub docker_port_metrics: u16,
     /// path to log configuration YAML file
     pub log_config: String,
+    /// optional endpoint of the OpenTelemetry collector receiving traces (OTLP over gRPC).
+    /// Traces will not be exported if not set.
+    pub otlp_endpoint: Option<String>,
+    /// interval in seconds used to check the database connection for the gRPC health status,
+    /// raised to `1` if set to `0`
+    pub health_check_interval_secs: u64,
+    #[serde(default)]
+    /// retention period in days per resource (table name) before archived records will be purged.
+    /// Resources without a retention period will never be purged.
+    pub purge_retention_days: HashMap<String, u32>,
+    #[serde(default)]
+    /// backend used for the read-through cache, caching is disabled if not set
+    pub cache_backend: CacheBackend,
+    /// optional url of the Redis server used by the `redis` cache backend
+    pub redis_url: Option<String>,
+    #[serde(default)]
+    /// maximum number of cached entries per resource (table name).
+    /// Resources without a cache size will not be cached.
+    pub cache_size: HashMap<String, usize>,
+    /// number of seconds cached entries are kept before they expire
+    pub cache_ttl_secs: u64,
+    #[serde(default)]
+    /// boolean requiring callers to authenticate and be authorized for each gRPC request
+    pub auth_enabled: bool,
+    /// optional path to the JWKS file containing the keys used to validate bearer tokens.
+    /// Bearer tokens will be rejected if not set.
+    pub auth_jwks_file: Option<String>,
+    /// optional issuer (`iss` claim) bearer tokens should have been issued by
+    pub auth_jwt_issuer: Option<String>,
+    /// optional audience (`aud` claim) bearer tokens should have been issued for
+    pub auth_jwt_audience: Option<String>,
+    #[serde(default)]
+    /// comma separated list of permissions (`<resource>:<method>`) per ACL group name
+    pub acl_permissions: HashMap<String, String>,
+    #[serde(default)]
+    /// comma separated list of ACL group names per service identity
+    pub service_acl_groups: HashMap<String, String>,
+    #[serde(default)]
+    /// boolean enabling the admin gRPC service (purge). The service is only registered if
+    /// authentication is enabled as well.
+    pub admin_enabled: bool,
 }
 
 impl Default for Config {
@@ -39,12 +94,31 @@ impl Config {
     pub fn new() -> Self {
         Config {
             docker_port_grpc: 50051,
+            grpc_tls_cert: None,
+            grpc_tls_key: None,
+            grpc_tls_client_ca: None,
+            grpc_tls_reload_interval_secs: 60,
+            docker_port_metrics: 9090,
             log_config: String::from("log4rs.yaml"),
+            otlp_endpoint: None,
+            health_check_interval_secs: 5,
             pg: deadpool_postgres::Config::new(),
             use_tls: true,
             db_ca_cert: "".to_string(),
             db_client_cert: None,
             db_client_key: None,
+            purge_retention_days: HashMap::new(),
+            cache_backend: CacheBackend::None,
+            redis_url: None,
+            cache_size: HashMap::new(),
+            cache_ttl_secs: 300,
+            auth_enabled: false,
+            auth_jwks_file: None,
+            auth_jwt_issuer: None,
+            auth_jwt_audience: None,
+            acl_permissions: HashMap::new(),
+            service_acl_groups: HashMap::new(),
+            admin_enabled: false,
         }
     }
 
@@ -56,8 +130,18 @@ impl Config {
 
         config::Config::builder()
             .set_default("docker_port_grpc", default_config.docker_port_grpc)?
+            .set_default("docker_port_metrics", default_config.docker_port_metrics)?
             .set_default("log_config", default_config.log_config)?
+            .set_default(
+                "health_check_interval_secs",
+                default_config.health_check_interval_secs,
+            )?
             .set_default("use_tls", default_config.use_tls)?
+            .set_default("cache_ttl_secs", default_config.cache_ttl_secs)?
+            .set_default(
+                "grpc_tls_reload_interval_secs",
+                default_config.grpc_tls_reload_interval_secs,
+            )?
             .add_source(Environment::default().separator("__"))
             .build()?
             .try_deserialize()
@@ -67,6 +151,7 @@ impl Config {
 #[cfg(test)]
 mod tests {
     use super::Config;
+    use crate::cache::CacheBackend;
     use crate::test_util::assert_init_done;
 
     #[tokio::test]
@@ -77,8 +162,23 @@ mod tests {
         let config = Config::default();
 
         assert_eq!(config.docker_port_grpc, 50051);
+        assert_eq!(config.docker_port_metrics, 9090);
+        assert_eq!(config.grpc_tls_cert, None);
+        assert_eq!(config.grpc_tls_client_ca, None);
+        assert_eq!(config.grpc_tls_reload_interval_secs, 60);
         assert_eq!(config.log_config, String::from("log4rs.yaml"));
+        assert_eq!(config.otlp_endpoint, None);
         assert_eq!(config.use_tls, true);
+        assert_eq!(config.health_check_interval_secs, 5);
+        assert!(config.purge_retention_days.is_empty());
+        assert_eq!(config.cache_backend, CacheBackend::None);
+        assert!(config.cache_size.is_empty());
+        assert_eq!(config.cache_ttl_secs, 300);
+        assert_eq!(config.auth_enabled, false);
+        assert_eq!(config.auth_jwks_file, None);
+        assert!(config.acl_permissions.is_empty());
+        assert!(config.service_acl_groups.is_empty());
+        assert_eq!(config.admin_enabled, false);
 
         ut_info!("Success.");
     }
@@ -89,19 +189,82 @@ mod tests {
         ut_info!("Start.");
 
         std::env::set_var("DOCKER_PORT_GRPC", "6789");
+        std::env::set_var("DOCKER_PORT_METRICS", "9876");
         std::env::set_var("LOG_CONFIG", "config_file.yaml");
+        std::env::set_var("OTLP_ENDPOINT", "http://otel-collector:4317");
+        std::env::set_var("PURGE_RETENTION_DAYS__USER", "30");
+        std::env::set_var("CACHE_BACKEND", "memory");
+        std::env::set_var("CACHE_SIZE__VERTIPAD", "1000");
+        std::env::set_var("AUTH_ENABLED", "true");
+        std::env::set_var("AUTH_JWKS_FILE", "/etc/svc-storage/jwks.json");
+        std::env::set_var("ACL_PERMISSIONS__OPERATORS", "vertiport:read,flight_plan:*");
+        std::env::set_var("SERVICE_ACL_GROUPS__SVC_SCHEDULER", "operators");
+        std::env::set_var("GRPC_TLS_CERT", "/etc/svc-storage/tls/server.crt");
+        std::env::set_var("GRPC_TLS_KEY", "/etc/svc-storage/tls/server.key");
+        std::env::set_var("GRPC_TLS_CLIENT_CA", "/etc/svc-storage/tls/ca.crt");
+        std::env::set_var("GRPC_TLS_RELOAD_INTERVAL_SECS", "10");
+        std::env::set_var("ADMIN_ENABLED", "true");
 
         let config = Config::try_from_env();
 
         // Reset env to proper defaults
         std::env::set_var("DOCKER_PORT_GRPC", "50051");
+        std::env::remove_var("DOCKER_PORT_METRICS");
         std::env::set_var("LOG_CONFIG", "log4rs.yaml");
+        std::env::remove_var("OTLP_ENDPOINT");
+        std::env::remove_var("PURGE_RETENTION_DAYS__USER");
+        std::env::remove_var("CACHE_BACKEND");
+        std::env::remove_var("CACHE_SIZE__VERTIPAD");
+        std::env::remove_var("AUTH_ENABLED");
+        std::env::remove_var("AUTH_JWKS_FILE");
+        std::env::remove_var("ACL_PERMISSIONS__OPERATORS");
+        std::env::remove_var("SERVICE_ACL_GROUPS__SVC_SCHEDULER");
+        std::env::remove_var("GRPC_TLS_CERT");
+        std::env::remove_var("GRPC_TLS_KEY");
+        std::env::remove_var("GRPC_TLS_CLIENT_CA");
+        std::env::remove_var("GRPC_TLS_RELOAD_INTERVAL_SECS");
+        std::env::remove_var("ADMIN_ENABLED");
 
         assert!(config.is_ok());
         let config = config.unwrap();
 
         assert_eq!(config.docker_port_grpc, 6789);
+        assert_eq!(config.docker_port_metrics, 9876);
         assert_eq!(config.log_config, String::from("config_file.yaml"));
+        assert_eq!(
+            config.otlp_endpoint,
+            Some(String::from("http://otel-collector:4317"))
+        );
+        assert_eq!(config.purge_retention_days.get("user"), Some(&30));
+        assert_eq!(config.cache_backend, CacheBackend::Memory);
+        assert_eq!(config.cache_size.get("vertipad"), Some(&1000));
+        assert_eq!(config.auth_enabled, true);
+        assert_eq!(
+            config.auth_jwks_file,
+            Some(String::from("/etc/svc-storage/jwks.json"))
+        );
+        assert_eq!(
+            config.acl_permissions.get("operators"),
+            Some(&String::from("vertiport:read,flight_plan:*"))
+        );
+        assert_eq!(
+            config.service_acl_groups.get("svc_scheduler"),
+            Some(&String::from("operators"))
+        );
+        assert_eq!(
+            config.grpc_tls_cert,
+            Some(String::from("/etc/svc-storage/tls/server.crt"))
+        );
+        assert_eq!(
+            config.grpc_tls_key,
+            Some(String::from("/etc/svc-storage/tls/server.key"))
+        );
+        assert_eq!(
+            config.grpc_tls_client_ca,
+            Some(String::from("/etc/svc-storage/tls/ca.crt"))
+        );
+        assert_eq!(config.grpc_tls_reload_interval_secs, 10);
+        assert_eq!(config.admin_enabled, true);
 
         ut_info!("Success.");
     }
@@ -6,11 +6,13 @@ use std::str::FromStr;
 use tokio_postgres::Row;
 use tonic::{Code, Request, Response, Status};
 
+use super::server::audit::AuditAction;
 use super::server::*;
 use super::GrpcDataObjectType;
+use crate::cache;
 use crate::postgres::linked_resource::PsqlType;
 use crate::postgres::simple_resource::PsqlType as PsqlSimpleType;
-use crate::postgres::PsqlSearch;
+use crate::postgres::{psql_readiness, PsqlSearch};
 use crate::resources::base::linked_resource::{LinkedResource, ObjectType};
 use crate::resources::base::simple_resource::SimpleResource;
 use crate::resources::base::Resource;
@@ -189,6 +191,7 @@ where
                 resource.try_get_uuid()?,
             )]),
             None,
+            AuditAction::Unlink,
         )
         .await
         .map_err(|e| {
@@ -221,6 +224,7 @@ where
     /// Returns a [`tonic`] gRCP [`Response`] containing an object of provided type `[Self::OtherList]`.
     ///
     /// The existence of the provided resource `id` will be validated first.
+    /// Archived linked records will only be returned if `include_archived` is set for the provided [`Id`].
     ///
     /// # Errors
     ///
@@ -235,9 +239,11 @@ where
         Self: Send + 'async_trait,
     {
         let id: Id = request.into_inner();
+        let include_archived = id.include_archived;
         let ids = Self::_get_linked(id).await?;
         let other_id_field = Self::OtherResourceObject::try_get_id_field()?;
-        let filter = AdvancedSearchFilter::search_in(other_id_field, ids);
+        let filter =
+            AdvancedSearchFilter::search_in(other_id_field, ids).include_archived(include_archived);
 
         match Self::OtherResourceObject::advanced_search(filter).await {
             Ok(rows) => Ok(Response::new(rows.try_into()?)),
@@ -247,22 +253,32 @@ where
 
     /// Internal function used for `generic_get_linked_ids` and `generic_get_linked`
     ///
+    /// The linked ids are cached if caching is enabled for the link table, see [`crate::cache`].
     async fn _get_linked(id: Id) -> Result<Vec<String>, Status> {
         let resource: Self::ResourceObject = id.clone().into();
-        Self::ResourceObject::get_by_id(&resource.try_get_uuid()?)
-            .await
-            .map_err(|e| {
-                grpc_error!(
-                    "No [{}] found for specified uuid [{:?}]: {}",
-                    Self::ResourceObject::get_psql_table(),
-                    id.id,
-                    e
-                );
-                Status::new(
-                    Code::NotFound,
-                    "Could not find any resource for the provided id",
-                )
-            })?;
+        let uuid = resource.try_get_uuid()?;
+
+        let link_table = Self::LinkedResourceObject::get_psql_table();
+        let key = cache::get_linked_key(&uuid);
+        if let Some(linked) = cache::get::<IdList>(&link_table, &key).await {
+            return Ok(linked.ids);
+        }
+
+        // Read before the linked ids, so they won't be cached if the links change meanwhile
+        let generation = cache::get_generation(&link_table).await;
+
+        Self::ResourceObject::get_by_id(&uuid).await.map_err(|e| {
+            grpc_error!(
+                "No [{}] found for specified uuid [{:?}]: {}",
+                Self::ResourceObject::get_psql_table(),
+                id.id,
+                e
+            );
+            Status::new(
+                Code::NotFound,
+                "Could not find any resource for the provided id",
+            )
+        })?;
 
         let id_field = Self::ResourceObject::try_get_id_field().map_err(|e| {
             grpc_error!(
@@ -292,15 +308,25 @@ where
         for row in rows {
             ids.push(row.get::<&str, Uuid>(other_id_field.as_str()).to_string());
         }
+        cache::set(&link_table, &key, &IdList { ids: ids.clone() }, generation).await;
         Ok(ids)
     }
 
     /// Returns ready:true when service is available
+    ///
+    /// The service is considered available when the database can serve queries.
     async fn generic_is_ready(
         &self,
         _request: Request<ReadyRequest>,
     ) -> Result<Response<ReadyResponse>, Status> {
-        let response = ReadyResponse { ready: true };
+        let ready = match psql_readiness().await {
+            Ok(_) => true,
+            Err(e) => {
+                grpc_warn!("Service not ready, database is unavailable: {}", e);
+                false
+            }
+        };
+        let response = ReadyResponse { ready };
         Ok(Response::new(response))
     }
 }
@@ -38,15 +38,21 @@ macro_rules! impl_grpc_link_service {
             /// # Errors
             ///
             /// Returns [`tonic::Status`] with [`tonic::Code::NotFound`] if the provided `id` is not found in the database.
+            #[tracing::instrument(skip_all, fields(otel.name = %format!("{}/link", self.get_name()), otel.kind = "server"))]
             async fn link(
                 &self,
                 request: tonic::Request<$link_other_resource>,
             ) -> Result<tonic::Response<()>, tonic::Status> {
                 grpc_info!("{} server.", self.get_name());
                 grpc_debug!("request: {:?}", request);
+                $crate::grpc::server::trace_context::set_parent_from_metadata(request.metadata());
+                let actor = $crate::grpc::get_actor(&request);
                 let data: $link_other_resource = request.into_inner();
-                self.generic_link(data.id.clone(), data.get_other_ids().try_into()?, false)
-                    .await
+                $crate::postgres::audit::with_actor(
+                    actor,
+                    self.generic_link(data.id.clone(), data.get_other_ids().try_into()?, false),
+                )
+                .await
             }
 
             #[doc = concat!("Takes an [`", stringify!($link_other_resource),"`] to replace the provided ",stringify!($other_resource)," linked ids in the database.")]
@@ -54,15 +60,21 @@ macro_rules! impl_grpc_link_service {
             /// # Errors
             ///
             /// Returns [`tonic::Status`] with [`tonic::Code::NotFound`] if the provided `id` is not found in the database.
+            #[tracing::instrument(skip_all, fields(otel.name = %format!("{}/replace_linked", self.get_name()), otel.kind = "server"))]
             async fn replace_linked(
                 &self,
                 request: tonic::Request<$link_other_resource>,
             ) -> Result<tonic::Response<()>, tonic::Status> {
                 grpc_info!("{} server.", self.get_name());
                 grpc_debug!("request: {:?}", request);
+                $crate::grpc::server::trace_context::set_parent_from_metadata(request.metadata());
+                let actor = $crate::grpc::get_actor(&request);
                 let data: $link_other_resource = request.into_inner();
-                self.generic_link(data.id.clone(), data.get_other_ids().try_into()?, true)
-                    .await
+                $crate::postgres::audit::with_actor(
+                    actor,
+                    self.generic_link(data.id.clone(), data.get_other_ids().try_into()?, true),
+                )
+                .await
             }
 
             #[doc = concat!("Takes an [`Id`] to unlink all ",stringify!($other_resource)," linked ids in the database.")]
@@ -70,10 +82,13 @@ macro_rules! impl_grpc_link_service {
             /// # Errors
             ///
             /// Returns [`tonic::Status`] with [`tonic::Code::NotFound`] if the provided `id` is not found in the database.
+            #[tracing::instrument(skip_all, fields(otel.name = %format!("{}/unlink", self.get_name()), otel.kind = "server"))]
             async fn unlink(&self, request: tonic::Request<Id>) -> Result<tonic::Response<()>, tonic::Status> {
                 grpc_info!("{} server.", self.get_name());
                 grpc_debug!("request: {:?}", request);
-                self.generic_unlink(request).await
+                $crate::grpc::server::trace_context::set_parent_from_metadata(request.metadata());
+                let actor = $crate::grpc::get_actor(&request);
+                $crate::postgres::audit::with_actor(actor, self.generic_unlink(request)).await
             }
 
             #[doc = concat!("Takes an [`Id`] to get all ",stringify!($other_resource)," linked ids from the database.")]
@@ -81,12 +96,14 @@ macro_rules! impl_grpc_link_service {
             /// # Errors
             ///
             /// Returns [`tonic::Status`] with [`tonic::Code::NotFound`] if the provided `id` is not found in the database.
+            #[tracing::instrument(skip_all, fields(otel.name = %format!("{}/get_linked_ids", self.get_name()), otel.kind = "server"))]
             async fn get_linked_ids(
                 &self,
                 request: tonic::Request<Id>,
             ) -> Result<tonic::Response<IdList>, tonic::Status> {
                 grpc_info!("{} server.", self.get_name());
                 grpc_debug!("request: {:?}", request);
+                $crate::grpc::server::trace_context::set_parent_from_metadata(request.metadata());
                 self.generic_get_linked_ids(request).await
             }
 
@@ -95,22 +112,26 @@ macro_rules! impl_grpc_link_service {
             /// # Errors
             ///
             /// Returns [`tonic::Status`] with [`tonic::Code::NotFound`] if the provided `id` is not found in the database.
+            #[tracing::instrument(skip_all, fields(otel.name = %format!("{}/get_linked", self.get_name()), otel.kind = "server"))]
             async fn get_linked(
                 &self,
                 request: tonic::Request<Id>,
             ) -> Result<tonic::Response<$other_resource::List>, tonic::Status> {
                 grpc_info!("{} server.", self.get_name());
                 grpc_debug!("request: {:?}", request);
+                $crate::grpc::server::trace_context::set_parent_from_metadata(request.metadata());
                 self.generic_get_linked(request).await
             }
 
             /// Returns ready:true when service is available
+            #[tracing::instrument(skip_all, fields(otel.name = %format!("{}/is_ready", self.get_name()), otel.kind = "server"))]
             async fn is_ready(
                 &self,
                 request: tonic::Request<ReadyRequest>,
             ) -> Result<tonic::Response<ReadyResponse>, tonic::Status> {
                 grpc_debug!("{} server.", self.get_name());
                 grpc_debug!("request: {:?}", request);
+                $crate::grpc::server::trace_context::set_parent_from_metadata(request.metadata());
                 self.generic_is_ready(request).await
             }
         }
@@ -350,9 +371,13 @@ macro_rules! impl_grpc_link_service {
                         }
                         Ok(tonic::Response::new($other_resource::List {
                             list: other_resource_list,
+                            ..Default::default()
                         }))
                     }
-                    _ => Ok(tonic::Response::new($other_resource::List { list: vec![] })),
+                    _ => Ok(tonic::Response::new($other_resource::List {
+                        list: vec![],
+                        ..Default::default()
+                    })),
                 }
             }
 
@@ -49,7 +49,8 @@ macro_rules! grpc_server_simple_service_mod {
         /// Provides basic insert/ update/ get / delete functionality and a more advanced search function.
         ///
         pub mod $resource {
-            #![allow(unused_qualifications)]
+            // tonic names the stream type for `search_stream` after the rpc: `search_streamStream`
+            #![allow(unused_qualifications, non_camel_case_types)]
             use super::{
                 AdvancedSearchFilter, Deserialize, GrpcSimpleService, Id, ReadyRequest,
                 ReadyResponse, ResourceObject, Serialize,
@@ -110,7 +111,8 @@ macro_rules! grpc_server_simple_service_linked_mod {
         /// Provides basic insert/ update/ get / delete functionality and a more advanced search function.
         ///
         pub mod $linked_resource {
-            #![allow(unused_qualifications)]
+            // tonic names the stream type for `search_stream` after the rpc: `search_streamStream`
+            #![allow(unused_qualifications, non_camel_case_types)]
             use super::{
                 $other_resource, $resource, AdvancedSearchFilter, Deserialize,
                 GrpcSimpleServiceLinked, Id, IdList, Ids, ReadyRequest, ReadyResponse,
@@ -12,6 +12,22 @@ macro_rules! impl_grpc_simple_service {
             }
         }
 
+        lazy_static::lazy_static! {
+            /// Broadcast channel used to send change notifications to all `watch` streams
+            pub static ref CHANGE_SENDER: tokio::sync::broadcast::Sender<$crate::grpc::ChangeNotification<Object>> =
+                tokio::sync::broadcast::channel($crate::grpc::WATCH_CHANNEL_CAPACITY).0;
+        }
+
+        impl From<$crate::grpc::ChangeNotification<Object>> for ChangeEvent {
+            fn from(notification: $crate::grpc::ChangeNotification<Object>) -> Self {
+                ChangeEvent {
+                    change_type: notification.change_type as i32,
+                    id: notification.id,
+                    object: notification.object,
+                }
+            }
+        }
+
         impl GrpcSimpleService for GrpcServer {
             type ResourceObject = ResourceObject<Data>;
             type Data = Data;
@@ -19,6 +35,12 @@ macro_rules! impl_grpc_simple_service {
             type UpdateObject = UpdateObject;
             type List = List;
             type Response = Response;
+            type InsertBatchRequest = InsertBatchRequest;
+            type ChangeEvent = ChangeEvent;
+
+            fn get_change_sender() -> &'static tokio::sync::broadcast::Sender<$crate::grpc::ChangeNotification<Object>> {
+                &CHANGE_SENDER
+            }
         }
 
         #[tonic::async_trait]
@@ -39,7 +61,7 @@ macro_rules! impl_grpc_simple_service {
             ///     let server = GrpcServer::default();
             ///
             ///     let id = "53acfe06-dd9b-42e8-8cb4-12a2fb2fa693".to_string();
-            ///     let result = match server.get_by_id(tonic::Request::new(Id { id })).await
+            ///     let result = match server.get_by_id(tonic::Request::new(Id { id, include_archived: false })).await
             ///     {
             ///         Ok(res) => res.into_inner(),
             ///         Err(e) => {
@@ -51,12 +73,14 @@ macro_rules! impl_grpc_simple_service {
             ///     Ok(result)
             /// }
             /// ```
+            #[tracing::instrument(skip_all, fields(otel.name = %format!("{}/get_by_id", self.get_name()), otel.kind = "server"))]
             async fn get_by_id(
                 &self,
                 request: tonic::Request<Id>,
             ) -> Result<tonic::Response<<Self as GrpcSimpleService>::Object>, tonic::Status> {
                 grpc_info!("{} server.", self.get_name());
                 grpc_debug!("request: {:?}", request);
+                $crate::grpc::server::trace_context::set_parent_from_metadata(request.metadata());
                 self.generic_get_by_id(request).await
             }
             /// Takes an [`AdvancedSearchFilter`] object to search the database with the provided values.
@@ -93,14 +117,58 @@ macro_rules! impl_grpc_simple_service {
             ///     Ok(())
             /// }
             /// ```
+            #[tracing::instrument(skip_all, fields(otel.name = %format!("{}/search", self.get_name()), otel.kind = "server"))]
             async fn search(
                 &self,
                 request: tonic::Request<AdvancedSearchFilter>,
             ) -> Result<tonic::Response<<Self as GrpcSimpleService>::List>, tonic::Status> {
                 grpc_info!("{} server.", self.get_name());
                 grpc_debug!("request: {:?}", request);
+                $crate::grpc::server::trace_context::set_parent_from_metadata(request.metadata());
                 self.generic_search(request).await
             }
+            /// Server streaming response type for the search_stream method.
+            type search_streamStream = $crate::grpc::GrpcSearchStream<<Self as GrpcSimpleService>::Object>;
+            #[doc = concat!("Returns a [`tonic`] gRCP [`Response`] containing a stream of ", stringify!($resource), " [`Object`] messages")]
+            ///
+            /// Takes an [`AdvancedSearchFilter`] object to search the database with the provided values.
+            /// Results are sent one at a time, so large result sets don't need to fit in a single message.
+            ///
+            /// # Errors
+            ///
+            /// Returns [`Status`] with [`tonic::Code::Internal`] if any error is returned from the db search.
+            /// The stream yields a [`Status`] with [`tonic::Code::Internal`] if a row could not be converted into an [`Object`].
+            #[tracing::instrument(skip_all, fields(otel.name = %format!("{}/search_stream", self.get_name()), otel.kind = "server"))]
+            async fn search_stream(
+                &self,
+                request: tonic::Request<AdvancedSearchFilter>,
+            ) -> Result<tonic::Response<Self::search_streamStream>, tonic::Status> {
+                grpc_info!("{} server.", self.get_name());
+                grpc_debug!("request: {:?}", request);
+                $crate::grpc::server::trace_context::set_parent_from_metadata(request.metadata());
+                self.generic_search_stream(request).await
+            }
+            /// Server streaming response type for the watch method.
+            type watchStream = $crate::grpc::GrpcWatchStream<<Self as GrpcSimpleService>::ChangeEvent>;
+            #[doc = concat!("Returns a [`tonic`] gRCP [`Response`] containing a stream of ", stringify!($resource), " [`ChangeEvent`] messages")]
+            ///
+            /// Takes an [`AdvancedSearchFilter`] object to only receive events for records matching the provided values.
+            /// An event is sent for every insert, update and delete made after the stream has been opened.
+            ///
+            /// # Errors
+            ///
+            /// The stream yields a [`Status`] with [`tonic::Code::DataLoss`] and closes if the client could not keep up with the change events.
+            /// The stream yields a [`Status`] with [`tonic::Code::Internal`] and closes if the filter could not be applied.
+            #[tracing::instrument(skip_all, fields(otel.name = %format!("{}/watch", self.get_name()), otel.kind = "server"))]
+            async fn watch(
+                &self,
+                request: tonic::Request<AdvancedSearchFilter>,
+            ) -> Result<tonic::Response<Self::watchStream>, tonic::Status> {
+                grpc_info!("{} server.", self.get_name());
+                grpc_debug!("request: {:?}", request);
+                $crate::grpc::server::trace_context::set_parent_from_metadata(request.metadata());
+                self.generic_watch(request).await
+            }
             #[doc = concat!("Takes a ", stringify!($resource), " [`Data`] object to create a new ", stringify!($resource), " with the provided data.")]
             ///
             /// A new [`Uuid`](lib_common::uuid::Uuid) will be generated by the database and returned as `id` as part of the returned [`Object`].
@@ -132,17 +200,61 @@ macro_rules! impl_grpc_simple_service {
             ///     Ok(())
             /// }
             /// ```
+            #[tracing::instrument(skip_all, fields(otel.name = %format!("{}/insert", self.get_name()), otel.kind = "server"))]
             async fn insert(
                 &self,
                 request: tonic::Request<<Self as GrpcSimpleService>::Data>,
             ) -> Result<tonic::Response<<Self as GrpcSimpleService>::Response>, tonic::Status> {
                 grpc_info!("{} server.", self.get_name());
                 grpc_debug!("request: {:?}", request);
-                self.generic_insert(request).await
+                $crate::grpc::server::trace_context::set_parent_from_metadata(request.metadata());
+                let actor = $crate::grpc::get_actor(&request);
+                $crate::postgres::audit::with_actor(actor, self.generic_insert(request)).await
+            }
+            #[doc = concat!("Takes an [`InsertBatchRequest`] with a list of ", stringify!($resource), " [`Data`] objects to insert at once.")]
+            ///
+            /// Every item will be validated separately. All valid items will be inserted using a single database transaction.
+            /// If `all_or_nothing` is set, nothing will be inserted when any of the items fails validation.
+            /// Returns an [`InsertBatchResult`](crate::resources::InsertBatchResult) for each provided item, in the same order.
+            ///
+            /// # Examples
+            /// ```
+            #[doc = concat!("use svc_storage::resources::", stringify!($resource), "::{InsertBatchRequest, GrpcServer, RpcService};")]
+            #[doc = concat!("use svc_storage::resources::", stringify!($resource), "::mock;")]
+            ///
+            /// async fn example() -> Result<(), tonic::Status> {
+            ///     let server = GrpcServer::default();
+            ///
+            ///     let result = match server.insert_batch(tonic::Request::new(InsertBatchRequest {
+            ///         list: vec![mock::get_data_obj(), mock::get_data_obj()],
+            ///         all_or_nothing: true,
+            ///     })).await
+            ///     {
+            ///         Ok(res) => res.into_inner(),
+            ///         Err(e) => {
+            ///             return Err(e);
+            ///         },
+            ///     };
+            ///     log::debug!("{:?}", result);
+            ///
+            ///     Ok(())
+            /// }
+            /// ```
+            #[tracing::instrument(skip_all, fields(otel.name = %format!("{}/insert_batch", self.get_name()), otel.kind = "server"))]
+            async fn insert_batch(
+                &self,
+                request: tonic::Request<<Self as GrpcSimpleService>::InsertBatchRequest>,
+            ) -> Result<tonic::Response<$crate::grpc::server::InsertBatchResponse>, tonic::Status> {
+                grpc_info!("{} server.", self.get_name());
+                grpc_debug!("request: {:?}", request);
+                $crate::grpc::server::trace_context::set_parent_from_metadata(request.metadata());
+                let actor = $crate::grpc::get_actor(&request);
+                $crate::postgres::audit::with_actor(actor, self.generic_insert_batch(request)).await
             }
             #[doc = concat!("Takes a ", stringify!($resource), " [`UpdateObject`] to update the resource with new data in the database")]
             ///
             /// A field mask can be provided to restrict updates to specific fields.
+            /// Unknown fields in the mask will be returned as validation errors.
             /// Returns the updated [`Response`] on success.
             ///
             /// # Examples
@@ -157,6 +269,7 @@ macro_rules! impl_grpc_simple_service {
             ///     let result = match server.update(tonic::Request::new(UpdateObject {
             ///         id: "54acfe06-dd9b-42e8-8cb4-12a2fb2fa693".to_string(),
             ///         data: Some(mock::get_data_obj()),
+            ///         expected_updated_at: None,
             ///         mask: None
             ///     })).await
             ///     {
@@ -170,26 +283,29 @@ macro_rules! impl_grpc_simple_service {
             ///     Ok(())
             /// }
             /// ```
+            #[tracing::instrument(skip_all, fields(otel.name = %format!("{}/update", self.get_name()), otel.kind = "server"))]
             async fn update(
                 &self,
                 request: tonic::Request<<Self as GrpcSimpleService>::UpdateObject>,
             ) -> Result<tonic::Response<<Self as GrpcSimpleService>::Response>, tonic::Status> {
                 grpc_info!("{} server.", self.get_name());
                 grpc_debug!("request: {:?}", request);
-                self.generic_update(request).await
+                $crate::grpc::server::trace_context::set_parent_from_metadata(request.metadata());
+                let actor = $crate::grpc::get_actor(&request);
+                $crate::postgres::audit::with_actor(actor, self.generic_update(request)).await
             }
-            #[doc = concat!("Takes an [`Id`] to set the matching ", stringify!($resource), " record as deleted in the database.")]
+            #[doc = concat!("Takes a [`DeleteRequest`] to set the matching ", stringify!($resource), " record as deleted in the database.")]
             ///
             /// # Examples
             /// ```
-            /// use svc_storage::resources::Id;
+            /// use svc_storage::resources::DeleteRequest;
             #[doc = concat!("use svc_storage::resources::", stringify!($resource), "::{GrpcServer, RpcService};")]
             ///
             /// async fn example() -> Result<(), tonic::Status> {
             ///     let server = GrpcServer::default();
             ///
             ///     let id = "53acfe06-dd9b-42e8-8cb4-12a2fb2fa693".to_string();
-            ///     let result = match server.delete(tonic::Request::new(Id { id })).await
+            ///     let result = match server.delete(tonic::Request::new(DeleteRequest { id, expected_updated_at: None })).await
             ///     {
             ///         Ok(res) => res.into_inner(),
             ///         Err(e) => {
@@ -201,18 +317,63 @@ macro_rules! impl_grpc_simple_service {
             ///     Ok(())
             /// }
             /// ```
-            async fn delete(&self, request: tonic::Request<Id>) -> Result<tonic::Response<()>, tonic::Status> {
+            #[tracing::instrument(skip_all, fields(otel.name = %format!("{}/delete", self.get_name()), otel.kind = "server"))]
+            async fn delete(&self, request: tonic::Request<$crate::grpc::server::DeleteRequest>) -> Result<tonic::Response<()>, tonic::Status> {
+                grpc_info!("{} server.", self.get_name());
+                grpc_debug!("request: {:?}", request);
+                $crate::grpc::server::trace_context::set_parent_from_metadata(request.metadata());
+                let actor = $crate::grpc::get_actor(&request);
+                $crate::postgres::audit::with_actor(actor, self.generic_delete(request)).await
+            }
+            #[doc = concat!("Takes an [`Id`] to restore the matching archived ", stringify!($resource), " record by clearing its `deleted_at` value.")]
+            ///
+            /// # Errors
+            ///
+            /// Returns [`Status`] with [`tonic::Code::NotFound`] if no record is returned from the database
+            /// Returns [`Status`] with [`tonic::Code::FailedPrecondition`] if the record is not archived
+            /// Returns [`Status`] with [`tonic::Code::AlreadyExists`] if restoring the record would violate a unique constraint
+            ///
+            /// # Examples
+            /// ```
+            /// use svc_storage::resources::Id;
+            #[doc = concat!("use svc_storage::resources::", stringify!($resource), "::{Object, GrpcServer, RpcService};")]
+            ///
+            /// async fn example() -> Result<Object, tonic::Status> {
+            ///     let server = GrpcServer::default();
+            ///
+            ///     let id = "53acfe06-dd9b-42e8-8cb4-12a2fb2fa693".to_string();
+            ///     let result = match server.restore(tonic::Request::new(Id { id, include_archived: false })).await
+            ///     {
+            ///         Ok(res) => res.into_inner(),
+            ///         Err(e) => {
+            ///             return Err(e);
+            ///         },
+            ///     };
+            ///     log::debug!("{:?}", result);
+            ///
+            ///     Ok(result)
+            /// }
+            /// ```
+            #[tracing::instrument(skip_all, fields(otel.name = %format!("{}/restore", self.get_name()), otel.kind = "server"))]
+            async fn restore(
+                &self,
+                request: tonic::Request<Id>,
+            ) -> Result<tonic::Response<<Self as GrpcSimpleService>::Object>, tonic::Status> {
                 grpc_info!("{} server.", self.get_name());
                 grpc_debug!("request: {:?}", request);
-                self.generic_delete(request).await
+                $crate::grpc::server::trace_context::set_parent_from_metadata(request.metadata());
+                let actor = $crate::grpc::get_actor(&request);
+                $crate::postgres::audit::with_actor(actor, self.generic_restore(request)).await
             }
             /// Returns ready:true when service is available
+            #[tracing::instrument(skip_all, fields(otel.name = %format!("{}/is_ready", self.get_name()), otel.kind = "server"))]
             async fn is_ready(
                 &self,
                 request: tonic::Request<ReadyRequest>,
             ) -> Result<tonic::Response<ReadyResponse>, tonic::Status> {
                 grpc_debug!("{} server.", self.get_name());
                 grpc_debug!("request: {:?}", request);
+                $crate::grpc::server::trace_context::set_parent_from_metadata(request.metadata());
                 self.generic_is_ready(request).await
             }
         }
@@ -231,6 +392,24 @@ macro_rules! impl_grpc_simple_service {
             }
         }
 
+        lazy_static::lazy_static! {
+            /// Broadcast channel used to send change notifications to all `watch` streams
+            pub static ref CHANGE_SENDER: tokio::sync::broadcast::Sender<$crate::grpc::ChangeNotification<Object>> =
+                tokio::sync::broadcast::channel($crate::grpc::WATCH_CHANNEL_CAPACITY).0;
+            /// In memory archived data used for mock restore implementation
+            pub static ref ARCHIVED_DATA: futures::lock::Mutex<Vec<Object>> = futures::lock::Mutex::new(Vec::new());
+        }
+
+        impl From<$crate::grpc::ChangeNotification<Object>> for ChangeEvent {
+            fn from(notification: $crate::grpc::ChangeNotification<Object>) -> Self {
+                ChangeEvent {
+                    change_type: notification.change_type as i32,
+                    id: notification.id,
+                    object: notification.object,
+                }
+            }
+        }
+
         impl GrpcSimpleService for GrpcServer {
             type ResourceObject = ResourceObject<Data>;
             type Data = Data;
@@ -238,6 +417,12 @@ macro_rules! impl_grpc_simple_service {
             type UpdateObject = UpdateObject;
             type List = List;
             type Response = Response;
+            type InsertBatchRequest = InsertBatchRequest;
+            type ChangeEvent = ChangeEvent;
+
+            fn get_change_sender() -> &'static tokio::sync::broadcast::Sender<$crate::grpc::ChangeNotification<Object>> {
+                &CHANGE_SENDER
+            }
         }
 
         #[tonic::async_trait]
@@ -275,7 +460,10 @@ macro_rules! impl_grpc_simple_service {
                 if filters.len() == 0 {
                     grpc_debug!("(MOCK) no filters provided, returning all.");
                     return Ok(tonic::Response::new(List {
-                        list
+                        total_count: Some(list.len() as i64),
+                        list,
+                        has_more: false,
+                        next_cursor: None,
                     }));
                 }
 
@@ -285,44 +473,8 @@ macro_rules! impl_grpc_simple_service {
                 }
                 grpc_debug!("(MOCK) unfiltered serialized objects: {:?}", unfiltered);
 
-                let mut collected: Vec<serde_json::Value> = vec![];
-                for filter in filters {
-                    let operator: $crate::grpc::server::PredicateOperator =
-                        match $crate::grpc::server::PredicateOperator::try_from(filter.predicate_operator) {
-                            Ok(val) => val,
-                            Err(e) => {
-                                return Err(tonic::Status::internal(format!(
-                                            "Can't convert i32 [{}] into PredicateOperator Enum value: {}",
-                                            filter.predicate_operator, e
-                                )));
-                            }
-                        };
-
-                    match filter.comparison_operator {
-                        Some(comparison_operator) => match $crate::grpc::server::ComparisonOperator::try_from(comparison_operator) {
-                            Ok(comparison_operator) => match comparison_operator {
-                                $crate::grpc::server::ComparisonOperator::And => {
-                                    let unfiltered = collected.clone();
-                                    collected = vec![];
-                                    $crate::grpc::server::search::filter_for_operator(&filter.search_field, &filter.search_value, &unfiltered, &mut collected, operator)
-                                        .map_err(|e| tonic::Status::internal(format!("Could not get filtered values for provided filter: {}", e)))?
-                                }
-                                $crate::grpc::server::ComparisonOperator::Or => {
-                                    $crate::grpc::server::search::filter_for_operator(&filter.search_field, &filter.search_value, &unfiltered, &mut collected, operator)
-                                        .map_err(|e| tonic::Status::internal(format!("Could not get filtered values for provided filter: {}", e)))?
-                                }
-                            }
-                            Err(e) => {
-                                return Err(tonic::Status::internal(format!(
-                                            "Can't convert i32 [{}] into ComparisonOperator Enum value: {}",
-                                            comparison_operator, e
-                                )));
-                            }
-                        },
-                        None => $crate::grpc::server::search::filter_for_operator(&filter.search_field, &filter.search_value, &unfiltered, &mut collected, operator)
-                            .map_err(|e| tonic::Status::internal(format!("Could not get filtered values for provided filter: {}", e)))?
-                    };
-                }
+                let collected: Vec<serde_json::Value> = $crate::grpc::server::search::filter_for_options(&filters, &unfiltered)
+                    .map_err(|e| tonic::Status::internal(format!("Could not get filtered values for provided filter: {}", e)))?;
 
                 let mut filtered: Vec<<Self as GrpcSimpleService>::Object> = vec![];
                 for val in collected.iter() {
@@ -332,11 +484,77 @@ macro_rules! impl_grpc_simple_service {
                     );
                 }
                 let response = List {
-                    list: filtered
+                    total_count: Some(filtered.len() as i64),
+                    list: filtered,
+                    has_more: false,
+                    next_cursor: None,
                 };
                 Ok(tonic::Response::new(response))
             }
 
+            type search_streamStream = $crate::grpc::GrpcSearchStream<<Self as GrpcSimpleService>::Object>;
+            async fn search_stream(
+                &self,
+                request: tonic::Request<AdvancedSearchFilter>,
+            ) -> Result<tonic::Response<Self::search_streamStream>, tonic::Status> {
+                grpc_warn!("(MOCK) {} server.", self.get_name());
+                let list = self.search(request).await?.into_inner().list;
+                Ok(tonic::Response::new(Box::pin(futures::stream::iter(
+                    list.into_iter().map(Ok::<_, tonic::Status>),
+                ))))
+            }
+
+            type watchStream = $crate::grpc::GrpcWatchStream<<Self as GrpcSimpleService>::ChangeEvent>;
+            async fn watch(
+                &self,
+                request: tonic::Request<AdvancedSearchFilter>,
+            ) -> Result<tonic::Response<Self::watchStream>, tonic::Status> {
+                grpc_warn!("(MOCK) {} server.", self.get_name());
+                grpc_debug!("(MOCK) request: {:?}", request);
+                let filters = request.into_inner().filters;
+                let receiver = CHANGE_SENDER.subscribe();
+                let stream = futures::stream::unfold(Some(receiver), move |receiver| {
+                    let filters = filters.clone();
+                    async move {
+                        let mut receiver = receiver?;
+                        loop {
+                            let notification = match receiver.recv().await {
+                                Ok(notification) => notification,
+                                Err(tokio::sync::broadcast::error::RecvError::Lagged(count)) => {
+                                    let error = format!("Watch stream could not keep up, [{}] change events have been dropped", count);
+                                    return Some((Err(tonic::Status::data_loss(error)), None));
+                                }
+                                Err(tokio::sync::broadcast::error::RecvError::Closed) => return None,
+                            };
+                            if filters.len() == 0 {
+                                return Some((Ok(notification.into()), Some(receiver)));
+                            }
+
+                            let object = match &notification.object {
+                                Some(object) => object,
+                                None => continue,
+                            };
+                            let value = match serde_json::to_value(object) {
+                                Ok(value) => value,
+                                Err(e) => {
+                                    let error = format!("Could not convert [{:?}] to json value: {}", object, e);
+                                    return Some((Err(tonic::Status::internal(error)), None));
+                                }
+                            };
+                            match $crate::grpc::server::search::filter_for_options(&filters, &vec![value]) {
+                                Ok(collected) if collected.len() > 0 => return Some((Ok(notification.into()), Some(receiver))),
+                                Ok(_) => continue,
+                                Err(e) => {
+                                    let error = format!("Could not get filtered values for provided filter: {}", e);
+                                    return Some((Err(tonic::Status::internal(error)), None));
+                                }
+                            }
+                        }
+                    }
+                });
+                Ok(tonic::Response::new(Box::pin(stream)))
+            }
+
             async fn insert(
                 &self,
                 request: tonic::Request<<Self as GrpcSimpleService>::Data>,
@@ -348,6 +566,7 @@ macro_rules! impl_grpc_simple_service {
                 let object = Object {
                     id: lib_common::uuid::Uuid::new_v4().to_string(),
                     data: Some(data),
+                    deleted_at: None,
                 };
                 let response = Response {
                     object: Some(object.clone()),
@@ -357,6 +576,46 @@ macro_rules! impl_grpc_simple_service {
                     })
                 };
                 mem_data.push(object.clone());
+                let _ = CHANGE_SENDER.send($crate::grpc::ChangeNotification {
+                    change_type: $crate::grpc::server::ChangeType::Insert,
+                    id: object.id.clone(),
+                    object: Some(object),
+                });
+                Ok(tonic::Response::new(response))
+            }
+
+            async fn insert_batch(
+                &self,
+                request: tonic::Request<<Self as GrpcSimpleService>::InsertBatchRequest>,
+            ) -> Result<tonic::Response<$crate::grpc::server::InsertBatchResponse>, tonic::Status> {
+                grpc_warn!("(MOCK) {} server.", self.get_name());
+                grpc_debug!("(MOCK) request: {:?}", request);
+                let mut mem_data = MEM_DATA.lock().await;
+                let mut results = vec![];
+                for data in request.into_inner().list {
+                    let object = Object {
+                        id: lib_common::uuid::Uuid::new_v4().to_string(),
+                        data: Some(data),
+                        deleted_at: None,
+                    };
+                    results.push($crate::grpc::server::InsertBatchResult {
+                        id: Some(object.id.clone()),
+                        validation_result: Some($crate::grpc::server::ValidationResult {
+                            success: true,
+                            errors: Vec::new()
+                        })
+                    });
+                    mem_data.push(object.clone());
+                    let _ = CHANGE_SENDER.send($crate::grpc::ChangeNotification {
+                        change_type: $crate::grpc::server::ChangeType::Insert,
+                        id: object.id.clone(),
+                        object: Some(object),
+                    });
+                }
+                let response = $crate::grpc::server::InsertBatchResponse {
+                    inserted_count: results.len() as i32,
+                    results,
+                };
                 Ok(tonic::Response::new(response))
             }
 
@@ -371,11 +630,34 @@ macro_rules! impl_grpc_simple_service {
                 let mut list = MEM_DATA.lock().await;
                 for object in &mut *list {
                     if object.id == id {
-                        object.data = Some(
-                            Data {
-                                ..update.data.clone().ok_or(tonic::Status::invalid_argument("No update data given."))?
+                        let data = match update.data.clone() {
+                            Some(data) => data,
+                            None => {
+                                let error = format!("No data provided for update with id: {}", id);
+                                grpc_error!("(MOCK) {}", error);
+                                return Err(tonic::Status::cancelled(error));
                             }
-                        );
+                        };
+                        let mut current = serde_json::to_value(object.data.clone().unwrap_or_default())
+                            .map_err(|e| tonic::Status::internal(format!("Could not convert [{:?}] to json value: {}", object.data, e)))?;
+                        let new = serde_json::to_value(data.clone())
+                            .map_err(|e| tonic::Status::internal(format!("Could not convert [{:?}] to json value: {}", data, e)))?;
+                        let validation_result = $crate::grpc::apply_stub_update(&mut current, &new, update.mask.as_ref(), stringify!($resource))?;
+                        if !validation_result.success {
+                            grpc_warn!("(MOCK) Validation errors returned from update function.");
+                            return Ok(tonic::Response::new(Response {
+                                object: None,
+                                validation_result: Some(validation_result),
+                            }));
+                        }
+                        let data: Data = serde_json::from_value(current.clone())
+                            .map_err(|e| tonic::Status::internal(format!("Could not convert [{:?}] to Data from json value: {}", current, e)))?;
+                        object.data = Some(data);
+                        let _ = CHANGE_SENDER.send($crate::grpc::ChangeNotification {
+                            change_type: $crate::grpc::server::ChangeType::Update,
+                            id: object.id.clone(),
+                            object: Some(object.clone()),
+                        });
 
                         let response = Response {
                             object: Some(object.clone()),
@@ -393,17 +675,92 @@ macro_rules! impl_grpc_simple_service {
 
             async fn delete(
                 &self,
-                request: tonic::Request<Id>,
+                request: tonic::Request<$crate::grpc::server::DeleteRequest>,
             ) -> Result<tonic::Response<()>, tonic::Status> {
                 grpc_warn!("(MOCK) {} server.", self.get_name());
                 grpc_debug!("(MOCK) request: {:?}", request);
                 let delete = request.into_inner();
                 let id = delete.id;
                 let mut list = MEM_DATA.lock().await;
+                if let Some(object) = list.iter().find(|object| object.id == id) {
+                    let _ = CHANGE_SENDER.send($crate::grpc::ChangeNotification {
+                        change_type: $crate::grpc::server::ChangeType::Delete,
+                        id: id.clone(),
+                        object: Some(object.clone()),
+                    });
+                    // Keep archived records around so they can be restored
+                    if <ResourceObject<Data> as $crate::resources::base::Resource>::get_definition().has_field("deleted_at") {
+                        ARCHIVED_DATA.lock().await.push(object.clone());
+                    }
+                }
                 list.retain(|object| object.id != id);
                 Ok(tonic::Response::new(()))
             }
 
+            async fn restore(
+                &self,
+                request: tonic::Request<Id>,
+            ) -> Result<tonic::Response<<Self as GrpcSimpleService>::Object>, tonic::Status> {
+                grpc_warn!("(MOCK) {} server.", self.get_name());
+                grpc_debug!("(MOCK) request: {:?}", request);
+                let id = request.into_inner().id;
+
+                if !<ResourceObject<Data> as $crate::resources::base::Resource>::get_definition().has_field("deleted_at") {
+                    let error = format!(
+                        "Restore is not supported for [{}], resource can not be archived.",
+                        stringify!($resource)
+                    );
+                    grpc_info!("(MOCK) {}", error);
+                    return Err(tonic::Status::unimplemented(error));
+                }
+
+                let mut list = MEM_DATA.lock().await;
+                if list.iter().any(|object| object.id == id) {
+                    let error = format!(
+                        "Restore refused for [{}] with id [{}], record is not archived.",
+                        stringify!($resource),
+                        id
+                    );
+                    grpc_info!("(MOCK) {}", error);
+                    return Err(tonic::Status::failed_precondition(error));
+                }
+
+                let mut archived = ARCHIVED_DATA.lock().await;
+                let index = match archived.iter().position(|object| object.id == id) {
+                    Some(index) => index,
+                    None => {
+                        grpc_error!("(MOCK) No [{}] found for specified uuid [{}].", stringify!($resource), id);
+                        return Err(tonic::Status::not_found("Could not find any resource for the provided id"));
+                    }
+                };
+
+                let mut others: Vec<serde_json::Value> = vec![];
+                for val in list.iter() {
+                    others.push(serde_json::to_value(val).map_err(|e| tonic::Status::internal(format!("Could not convert [{:?}] to json value: {}", val, e)))?);
+                }
+                let restored = serde_json::to_value(&archived[index])
+                    .map_err(|e| tonic::Status::internal(format!("Could not convert [{:?}] to json value: {}", archived[index], e)))?;
+                let unique_fields = <ResourceObject<Data> as $crate::resources::base::Resource>::get_unique_fields();
+                if let Some(fields) = $crate::grpc::server::search::find_unique_violation(&restored, &others, &unique_fields) {
+                    let error = format!(
+                        "Restore refused for [{}], a record with the same [{}] value(s) already exists.",
+                        stringify!($resource),
+                        fields
+                    );
+                    grpc_info!("(MOCK) {}", error);
+                    return Err(tonic::Status::already_exists(error));
+                }
+
+                let object = archived.remove(index);
+                list.push(object.clone());
+                let _ = CHANGE_SENDER.send($crate::grpc::ChangeNotification {
+                    change_type: $crate::grpc::server::ChangeType::Update,
+                    id: id.clone(),
+                    object: Some(object.clone()),
+                });
+                Ok(tonic::Response::new(object))
+            }
+
             async fn is_ready(
                 &self,
                 request: tonic::Request<ReadyRequest>,
@@ -35,22 +35,27 @@ macro_rules! impl_grpc_simple_service_linked {
             /// # Errors
             ///
             /// Returns [`tonic::Status`] with [`tonic::Code::NotFound`] if the provided `id` is not found in the database.
+            #[tracing::instrument(skip_all, fields(otel.name = %format!("{}/unlink", self.get_name()), otel.kind = "server"))]
             async fn unlink(&self, request: tonic::Request<Id>) -> Result<tonic::Response<()>, tonic::Status> {
                 grpc_info!("{} server.", self.get_name());
                 grpc_debug!("request: {:?}", request);
-                self.generic_unlink(request).await
+                $crate::grpc::server::trace_context::set_parent_from_metadata(request.metadata());
+                let actor = $crate::grpc::get_actor(&request);
+                $crate::postgres::audit::with_actor(actor, self.generic_unlink(request)).await
             }
             #[doc = concat!("Takes an [`Id`] to get all ",stringify!($other_resource)," linked ids from the database.")]
             ///
             /// # Errors
             ///
             /// Returns [`tonic::Status`] with [`tonic::Code::NotFound`] if the provided `id` is not found in the database.
+            #[tracing::instrument(skip_all, fields(otel.name = %format!("{}/get_linked_ids", self.get_name()), otel.kind = "server"))]
             async fn get_linked_ids(
                 &self,
                 request: tonic::Request<Id>,
             ) -> Result<tonic::Response<IdList>, tonic::Status> {
                 grpc_info!("{} server.", self.get_name());
                 grpc_debug!("request: {:?}", request);
+                $crate::grpc::server::trace_context::set_parent_from_metadata(request.metadata());
                 self.generic_get_linked_ids(request).await
             }
             #[doc = concat!("Takes an [`Id`] to get all ",stringify!($other_resource)," linked objects from the database.")]
@@ -58,12 +63,14 @@ macro_rules! impl_grpc_simple_service_linked {
             /// # Errors
             ///
             /// Returns [`tonic::Status`] with [`tonic::Code::NotFound`] if the provided `id` is not found in the database.
+            #[tracing::instrument(skip_all, fields(otel.name = %format!("{}/get_linked", self.get_name()), otel.kind = "server"))]
             async fn get_linked(
                 &self,
                 request: tonic::Request<Id>,
             ) -> Result<tonic::Response<<Self as GrpcSimpleServiceLinked>::OtherList>, tonic::Status> {
                 grpc_info!("{} server.", self.get_name());
                 grpc_debug!("request: {:?}", request);
+                $crate::grpc::server::trace_context::set_parent_from_metadata(request.metadata());
                 self.generic_get_linked(request).await
             }
             #[doc = concat!("Returns a [`tonic`] gRCP [`Response`] containing an ", stringify!($linked_resource), " [`Object`]")]
@@ -106,12 +113,14 @@ macro_rules! impl_grpc_simple_service_linked {
             ///     Ok(result)
             /// }
             /// ```
+            #[tracing::instrument(skip_all, fields(otel.name = %format!("{}/get_by_id", self.get_name()), otel.kind = "server"))]
             async fn get_by_id(
                 &self,
                 request: tonic::Request<Ids>,
             ) -> Result<tonic::Response<<Self as GrpcSimpleServiceLinked>::LinkedObject>, tonic::Status> {
                 grpc_info!("{} server.", self.get_name());
                 grpc_debug!("request: {:?}", request);
+                $crate::grpc::server::trace_context::set_parent_from_metadata(request.metadata());
                 self.generic_get_by_id(request).await
             }
             /// Takes an [`AdvancedSearchFilter`] object to search the database with the provided values.
@@ -148,14 +157,37 @@ macro_rules! impl_grpc_simple_service_linked {
             ///     Ok(())
             /// }
             /// ```
+            #[tracing::instrument(skip_all, fields(otel.name = %format!("{}/search", self.get_name()), otel.kind = "server"))]
             async fn search(
                 &self,
                 request: tonic::Request<AdvancedSearchFilter>,
             ) -> Result<tonic::Response<<Self as GrpcSimpleServiceLinked>::LinkedRowDataList>, tonic::Status> {
                 grpc_info!("{} server.", self.get_name());
                 grpc_debug!("request: {:?}", request);
+                $crate::grpc::server::trace_context::set_parent_from_metadata(request.metadata());
                 self.generic_search(request).await
             }
+            /// Server streaming response type for the search_stream method.
+            type search_streamStream = $crate::grpc::GrpcSearchStream<<Self as GrpcSimpleServiceLinked>::LinkedRowData>;
+            #[doc = concat!("Returns a [`tonic`] gRCP [`Response`] containing a stream of ", stringify!($linked_resource), " [`RowData`] messages")]
+            ///
+            /// Takes an [`AdvancedSearchFilter`] object to search the database with the provided values.
+            /// Results are sent one at a time, so large result sets don't need to fit in a single message.
+            ///
+            /// # Errors
+            ///
+            /// Returns [`Status`] with [`tonic::Code::Internal`] if any error is returned from the db search.
+            /// The stream yields a [`Status`] with [`tonic::Code::Internal`] if a row could not be converted into a [`RowData`].
+            #[tracing::instrument(skip_all, fields(otel.name = %format!("{}/search_stream", self.get_name()), otel.kind = "server"))]
+            async fn search_stream(
+                &self,
+                request: tonic::Request<AdvancedSearchFilter>,
+            ) -> Result<tonic::Response<Self::search_streamStream>, tonic::Status> {
+                grpc_info!("{} server.", self.get_name());
+                grpc_debug!("request: {:?}", request);
+                $crate::grpc::server::trace_context::set_parent_from_metadata(request.metadata());
+                self.generic_search_stream(request).await
+            }
             #[doc = concat!("Takes a ", stringify!($linked_resource), " [`Data`] object to create a new ", stringify!($linked_resource), " with the provided data.")]
             ///
             /// A new [`Uuid`](lib_common::uuid::Uuid) will be generated by the database and returned as `id` as part of the returned [`Object`].
@@ -187,17 +219,21 @@ macro_rules! impl_grpc_simple_service_linked {
             ///     Ok(())
             /// }
             /// ```
+            #[tracing::instrument(skip_all, fields(otel.name = %format!("{}/insert", self.get_name()), otel.kind = "server"))]
             async fn insert(
                 &self,
                 request: tonic::Request<<Self as GrpcSimpleServiceLinked>::LinkedRowData>,
             ) -> Result<tonic::Response<Response>, tonic::Status> {
                 grpc_info!("{} server.", self.get_name());
                 grpc_debug!("request: {:?}", request);
-                self.generic_insert(request).await
+                $crate::grpc::server::trace_context::set_parent_from_metadata(request.metadata());
+                let actor = $crate::grpc::get_actor(&request);
+                $crate::postgres::audit::with_actor(actor, self.generic_insert(request)).await
             }
             #[doc = concat!("Takes a ", stringify!($linked_resource), " [`UpdateObject`] to update the resource with new data in the database")]
             ///
             /// A field mask can be provided to restrict updates to specific fields.
+            /// Unknown fields in the mask will be returned as validation errors.
             /// Returns the updated [`Response`] on success.
             ///
             /// # Examples
@@ -222,6 +258,7 @@ macro_rules! impl_grpc_simple_service_linked {
             ///             },
             ///         ],
             ///         data: Some(mock::get_data_obj()),
+            ///         expected_updated_at: None,
             ///         mask: None
             ///     })).await
             ///     {
@@ -235,19 +272,22 @@ macro_rules! impl_grpc_simple_service_linked {
             ///     Ok(())
             /// }
             /// ```
+            #[tracing::instrument(skip_all, fields(otel.name = %format!("{}/update", self.get_name()), otel.kind = "server"))]
             async fn update(
                 &self,
                 request: tonic::Request<<Self as GrpcSimpleServiceLinked>::LinkedUpdateObject>,
             ) -> Result<tonic::Response<<Self as GrpcSimpleServiceLinked>::LinkedResponse>, tonic::Status> {
                 grpc_info!("{} server.", self.get_name());
                 grpc_debug!("request: {:?}", request);
-                self.generic_update(request).await
+                $crate::grpc::server::trace_context::set_parent_from_metadata(request.metadata());
+                let actor = $crate::grpc::get_actor(&request);
+                $crate::postgres::audit::with_actor(actor, self.generic_update(request)).await
             }
-            #[doc = concat!("Takes an [`Id`] to set the matching ", stringify!($linked_resource), " record as deleted in the database.")]
+            #[doc = concat!("Takes a [`LinkedDeleteRequest`] to set the matching ", stringify!($linked_resource), " record as deleted in the database.")]
             ///
             /// # Examples
             /// ```
-            /// use svc_storage::resources::{FieldValue, Ids};
+            /// use svc_storage::resources::{FieldValue, LinkedDeleteRequest};
             #[doc = concat!("use svc_storage::resources::", stringify!($linked_resource), "::{GrpcServer, RpcServiceLinked};")]
             ///
             /// async fn example() -> Result<(), tonic::Status> {
@@ -255,7 +295,7 @@ macro_rules! impl_grpc_simple_service_linked {
             ///
             ///     let id1 = "53acfe06-dd9b-42e8-8cb4-12a2fb2fa693";
             ///     let id2 = "279f750f-712f-4c3b-8c92-331b79ffa7f9";
-            ///     let result = match server.delete(tonic::Request::new(Ids {
+            ///     let result = match server.delete(tonic::Request::new(LinkedDeleteRequest {
             ///         ids: vec![
             ///             FieldValue {
             ///                 field: String::from("field_1"),
@@ -265,7 +305,8 @@ macro_rules! impl_grpc_simple_service_linked {
             ///                 field: String::from("field_2"),
             ///                 value: String::from(id2)
             ///             },
-            ///         ]
+            ///         ],
+            ///         expected_updated_at: None,
             ///     })).await
             ///     {
             ///         Ok(res) => res.into_inner(),
@@ -278,18 +319,23 @@ macro_rules! impl_grpc_simple_service_linked {
             ///     Ok(())
             /// }
             /// ```
-            async fn delete(&self, request: tonic::Request<Ids>) -> Result<tonic::Response<()>, tonic::Status> {
+            #[tracing::instrument(skip_all, fields(otel.name = %format!("{}/delete", self.get_name()), otel.kind = "server"))]
+            async fn delete(&self, request: tonic::Request<$crate::grpc::server::LinkedDeleteRequest>) -> Result<tonic::Response<()>, tonic::Status> {
                 grpc_info!("{} server.", self.get_name());
                 grpc_debug!("request: {:?}", request);
-                self.generic_delete(request).await
+                $crate::grpc::server::trace_context::set_parent_from_metadata(request.metadata());
+                let actor = $crate::grpc::get_actor(&request);
+                $crate::postgres::audit::with_actor(actor, self.generic_delete(request)).await
             }
             /// Returns ready:true when service is available
+            #[tracing::instrument(skip_all, fields(otel.name = %format!("{}/is_ready", self.get_name()), otel.kind = "server"))]
             async fn is_ready(
                 &self,
                 request: tonic::Request<ReadyRequest>,
             ) -> Result<tonic::Response<ReadyResponse>, tonic::Status> {
                 grpc_debug!("{} server.", self.get_name());
                 grpc_debug!("request: {:?}", request);
+                $crate::grpc::server::trace_context::set_parent_from_metadata(request.metadata());
                 self.generic_is_ready(request).await
             }
         }
@@ -397,7 +443,10 @@ macro_rules! impl_grpc_simple_service_linked {
                     grpc_error!("(MOCK) {}", error);
                     return Err(tonic::Status::not_found(error));
                 }
-                Ok(tonic::Response::new($other_resource::List { list: other_resource_list }))
+                Ok(tonic::Response::new($other_resource::List {
+                    list: other_resource_list,
+                    ..Default::default()
+                }))
             }
             async fn get_by_id(
                 &self,
@@ -479,7 +528,10 @@ macro_rules! impl_grpc_simple_service_linked {
                 if filters.len() == 0 {
                     grpc_debug!("(MOCK) no filters provided, returning all.");
                     return Ok(tonic::Response::new(RowDataList {
-                        list
+                        total_count: Some(list.len() as i64),
+                        list,
+                        has_more: false,
+                        next_cursor: None,
                     }));
                 }
 
@@ -517,45 +569,8 @@ macro_rules! impl_grpc_simple_service_linked {
                 }
                 grpc_debug!("(MOCK) unfiltered serialized objects: {:?}", unfiltered);
 
-                let mut collected: Vec<serde_json::Value> = vec![];
-                for filter in filters {
-                    let operator: $crate::grpc::server::PredicateOperator =
-                        match $crate::grpc::server::PredicateOperator::try_from(filter.predicate_operator) {
-                            Ok(val) => val,
-                            Err(e) => {
-                                return Err(tonic::Status::internal(format!(
-                                    "Can't convert i32 [{}] into PredicateOperator Enum value: {}",
-                                    filter.predicate_operator, e
-                                )));
-                            }
-                        };
-
-
-                    match filter.comparison_operator {
-                        Some(comparison_operator) => match $crate::grpc::server::ComparisonOperator::try_from(comparison_operator) {
-                            Ok(comparison_operator) => match comparison_operator {
-                                $crate::grpc::server::ComparisonOperator::And => {
-                                    let unfiltered = collected.clone();
-                                    collected = vec![];
-                                    $crate::grpc::server::search::filter_for_operator(&filter.search_field, &filter.search_value, &unfiltered, &mut collected, operator)
-                                        .map_err(|e| tonic::Status::internal(format!("Could not get filtered values for provided filter: {}", e)))?
-                                }
-                                $crate::grpc::server::ComparisonOperator::Or => {
-                                    $crate::grpc::server::search::filter_for_operator(&filter.search_field, &filter.search_value, &unfiltered, &mut collected, operator)
-                                        .map_err(|e| tonic::Status::internal(format!("Could not get filtered values for provided filter: {}", e)))?
-                                }
-                            }
-                            Err(e) => {
-                                return Err(tonic::Status::internal(format!(
-                                    "Can't convert i32 [{}] into ComparisonOperator Enum value: {}",
-                                    comparison_operator, e
-                                )));
-                            }
-                        },
-                        None => $crate::grpc::server::search::filter_for_operator(&filter.search_field, &filter.search_value, &unfiltered, &mut collected, operator)
-                            .map_err(|e| tonic::Status::internal(format!("Could not get filtered values for provided filter: {}", e)))?
-                    };
-                }
+                let collected: Vec<serde_json::Value> = $crate::grpc::server::search::filter_for_options(&filters, &unfiltered)
+                    .map_err(|e| tonic::Status::internal(format!("Could not get filtered values for provided filter: {}", e)))?;
 
                 let mut filtered: Vec<<Self as GrpcSimpleServiceLinked>::LinkedRowData> = vec![];
                 for val in collected.iter() {
@@ -574,10 +589,24 @@ macro_rules! impl_grpc_simple_service_linked {
                     filtered.push(row_data);
                 }
                 let response = RowDataList {
-                    list: filtered
+                    total_count: Some(filtered.len() as i64),
+                    list: filtered,
+                    has_more: false,
+                    next_cursor: None,
                 };
                 Ok(tonic::Response::new(response))
             }
+            type search_streamStream = $crate::grpc::GrpcSearchStream<<Self as GrpcSimpleServiceLinked>::LinkedRowData>;
+            async fn search_stream(
+                &self,
+                request: tonic::Request<AdvancedSearchFilter>,
+            ) -> Result<tonic::Response<Self::search_streamStream>, tonic::Status> {
+                grpc_warn!("(MOCK) {} server.", self.get_name());
+                let list = self.search(request).await?.into_inner().list;
+                Ok(tonic::Response::new(Box::pin(futures::stream::iter(
+                    list.into_iter().map(Ok::<_, tonic::Status>),
+                ))))
+            }
             async fn insert(
                 &self,
                 request: tonic::Request<<Self as GrpcSimpleServiceLinked>::LinkedRowData>,
@@ -654,31 +683,45 @@ macro_rules! impl_grpc_simple_service_linked {
                     for row_data in &mut *linked_resource_list {
                         if row_data.[<$resource _id>] == resource_id && row_data.[<$other_resource _id>] == other_resource_id {
                             grpc_debug!("(MOCK) found row_data matching ids: {:?}", row_data);
-                            let mut row_data_serialized = serde_json::to_value(request.data.clone())
-                                .map_err(|e| tonic::Status::internal(format!("Could not convert [{:?}] to json value: {}", request.data.clone(), e)))?
-                                .as_object_mut()
-                                .ok_or(tonic::Status::internal("Could not convert json data to mutable object."))?
-                                .to_owned();
-                            for id in &ids {
-                                row_data_serialized.insert(id.field.clone(), serde_json::Value::String(id.value.clone()));
+                            let data = match request.data.clone() {
+                                Some(data) => data,
+                                None => {
+                                    let error = format!("No data provided for update with ids: {:?}", ids);
+                                    grpc_error!("(MOCK) {}", error);
+                                    return Err(tonic::Status::cancelled(error));
+                                }
+                            };
+                            let mut row_data_serialized = serde_json::to_value(row_data.clone())
+                                .map_err(|e| tonic::Status::internal(format!("Could not convert [{:?}] to json value: {}", row_data, e)))?;
+                            let new = serde_json::to_value(data.clone())
+                                .map_err(|e| tonic::Status::internal(format!("Could not convert [{:?}] to json value: {}", data, e)))?;
+                            let validation_result = $crate::grpc::apply_stub_update(&mut row_data_serialized, &new, request.mask.as_ref(), stringify!($linked_resource))?;
+                            if !validation_result.success {
+                                grpc_warn!("(MOCK) Validation errors returned from update function.");
+                                return Ok(tonic::Response::new(Response {
+                                    object: None,
+                                    validation_result: Some(validation_result),
+                                }));
                             }
                             grpc_debug!("(MOCK) serialized new row_data: {:?}", row_data_serialized);
-                            let new_row_data: <Self as GrpcSimpleServiceLinked>::LinkedRowData = serde_json::from_value(serde_json::Value::Object(row_data_serialized.clone()))
+                            let new_row_data: <Self as GrpcSimpleServiceLinked>::LinkedRowData = serde_json::from_value(row_data_serialized.clone())
                                 .map_err(|e| tonic::Status::internal(format!("Could not convert [{:?}] to RowData from json value: {}", row_data_serialized, e)))?;
                             grpc_debug!("(MOCK) new row_data: {:?}", new_row_data);
 
-                            //let _ = std::mem::replace(&mut row_data, &mut new_row_data);
+                            // The id fields are not part of the Data object, remove them to get the updated data
+                            row_data_serialized.as_object_mut()
+                                .ok_or(tonic::Status::internal("Could not convert json data to mutable object."))?
+                                .retain(|key, _| key != id_field && key != other_id_field);
+                            let new_data: <Self as GrpcSimpleServiceLinked>::LinkedData = serde_json::from_value(row_data_serialized.clone())
+                                .map_err(|e| tonic::Status::internal(format!("Could not convert [{:?}] to Data from json value: {}", row_data_serialized, e)))?;
                             *row_data = new_row_data;
 
                             let response = Response {
                                 object: Some(Object {
                                     ids,
-                                    data: request.data
-                                }),
-                                validation_result: Some($crate::grpc::server::ValidationResult {
-                                    success: true,
-                                    errors: Vec::new(),
+                                    data: Some(new_data),
                                 }),
+                                validation_result: Some(validation_result),
                             };
 
                             return Ok(tonic::Response::new(response));
@@ -689,7 +732,7 @@ macro_rules! impl_grpc_simple_service_linked {
             }
             async fn delete(
                 &self,
-                request: tonic::Request<Ids>,
+                request: tonic::Request<$crate::grpc::server::LinkedDeleteRequest>,
             ) -> Result<tonic::Response<()>, tonic::Status> {
                 grpc_warn!("(MOCK) {} server.", self.get_name());
                 grpc_debug!("(MOCK) request: {:?}", request);
@@ -8,6 +8,7 @@ pub mod macros;
 pub mod tests;
 
 pub mod server;
+pub mod tls;
 
 mod link_service;
 mod simple_service;
@@ -19,12 +20,18 @@ pub use simple_service::GrpcSimpleService;
 pub use simple_service_linked::GrpcSimpleServiceLinked;
 
 use anyhow::Error;
+use futures::Stream;
+use lib_common::time::{DateTime, Utc};
 use prost_wkt_types::Timestamp;
+use std::pin::Pin;
 use std::time::SystemTime;
 use std::{fmt::Debug, vec};
 use tokio::runtime::{Handle, Runtime};
+use tokio_postgres::Row;
 use tonic::Status;
 
+use crate::postgres::PagingInfo;
+use crate::resources::base::Resource;
 use server::geo_types::{GeoLineStringZ, GeoPointZ, GeoPolygonZ};
 
 /// gRPC field types
@@ -107,6 +114,167 @@ pub trait GrpcDataObjectType: prost::Message + Clone {
     fn get_field_value(&self, key: &str) -> Result<GrpcField, ArrErr>;
 }
 
+/// Stream of search results returned by the `search_stream` gRPC services
+pub type GrpcSearchStream<T> = Pin<Box<dyn Stream<Item = Result<T, Status>> + Send>>;
+
+/// Stream of change events returned by the `watch` gRPC services
+pub type GrpcWatchStream<T> = Pin<Box<dyn Stream<Item = Result<T, Status>> + Send>>;
+
+/// Number of change notifications kept for each resource's `watch` streams.
+/// Streams which fall behind more than this number of notifications will be closed.
+pub const WATCH_CHANNEL_CAPACITY: usize = 1024;
+
+/// Change notification sent to all `watch` streams of a resource
+#[derive(Debug, Clone)]
+pub struct ChangeNotification<T> {
+    /// type of change
+    pub change_type: server::ChangeType,
+    /// id of the changed record
+    pub id: String,
+    /// the record's current data, if it could still be found
+    pub object: Option<T>,
+}
+
+/// Provides function to set paging information on gRPC search result `List` objects
+pub trait GrpcSearchResultList {
+    /// set the total number of matching records, if more records are available and the cursor for the next page
+    fn set_paging_info(&mut self, paging_info: PagingInfo);
+}
+
+/// Provides function to get the items of gRPC `InsertBatchRequest` objects
+pub trait GrpcInsertBatchRequest<T> {
+    /// returns the list of items to insert and the `all_or_nothing` flag
+    fn into_parts(self) -> (Vec<T>, bool);
+}
+
+/// Response metadata key containing the current `updated_at` value of a record.
+/// This value can be provided as `expected_updated_at` for optimistic concurrency checks.
+pub const UPDATED_AT_METADATA_KEY: &str = "x-updated-at";
+
+/// Returns the `updated_at` value of the provided [`Row`], if the resource has one
+pub(crate) fn get_updated_at(row: &Row) -> Option<DateTime<Utc>> {
+    row.try_get::<&str, DateTime<Utc>>("updated_at").ok()
+}
+
+/// Adds the provided `updated_at` value to the response metadata using the [`UPDATED_AT_METADATA_KEY`]
+pub(crate) fn set_updated_at_metadata<T>(
+    response: &mut tonic::Response<T>,
+    updated_at: Option<DateTime<Utc>>,
+) {
+    if let Some(updated_at) = updated_at {
+        if let Ok(value) = updated_at.to_rfc3339().parse() {
+            response
+                .metadata_mut()
+                .insert(UPDATED_AT_METADATA_KEY, value);
+        }
+    }
+}
+
+/// Returns a [`Status`] with [`Code::InvalidArgument`](tonic::Code::InvalidArgument) if an
+/// `expected_updated_at` value is provided for a resource without an `updated_at` field
+pub(crate) fn check_expected_updated_at<R: Resource>(
+    expected_updated_at: &Option<Timestamp>,
+) -> Result<(), Status> {
+    if expected_updated_at.is_some() && !R::get_definition().has_field("updated_at") {
+        let error = format!(
+            "Resource [{}] does not support [expected_updated_at].",
+            R::get_psql_table()
+        );
+        grpc_warn!("{}", error);
+        return Err(Status::invalid_argument(error));
+    }
+    Ok(())
+}
+
+/// Applies the provided update `data` to the `current` json object, the way the database
+/// backend applies partial updates. Used by the `stub_backends` service implementations.
+///
+/// Only the fields listed in the `mask` are copied, all fields are copied if no mask paths are
+/// provided. Paths can be provided with or without the `data.` prefix.
+/// A failed [`ValidationResult`](server::ValidationResult) is returned, without changing
+/// `current`, if the mask contains fields which are not part of the `data`.
+#[cfg(feature = "stub_backends")]
+pub fn apply_stub_update(
+    current: &mut serde_json::Value,
+    data: &serde_json::Value,
+    mask: Option<&prost_types::FieldMask>,
+    table: &str,
+) -> Result<server::ValidationResult, Status> {
+    let (current, data) = match (current.as_object_mut(), data.as_object()) {
+        (Some(current), Some(data)) => (current, data),
+        _ => return Err(Status::internal("Could not convert json data to object.")),
+    };
+
+    let paths: Vec<&str> = match mask {
+        Some(mask) if !mask.paths.is_empty() => mask
+            .paths
+            .iter()
+            .map(|path| path.strip_prefix("data.").unwrap_or(path))
+            .collect(),
+        _ => data.keys().map(String::as_str).collect(),
+    };
+    let errors: Vec<server::ValidationError> = paths
+        .iter()
+        .filter(|path| !data.contains_key(**path))
+        .map(|path| server::ValidationError {
+            field: path.to_string(),
+            error: format!(
+                "Unknown field [{}] provided in update mask for table [{}].",
+                path, table
+            ),
+        })
+        .collect();
+    if !errors.is_empty() {
+        return Ok(server::ValidationResult {
+            success: false,
+            errors,
+        });
+    }
+
+    for path in paths {
+        if let Some(value) = data.get(path) {
+            current.insert(path.to_owned(), value.clone());
+        }
+    }
+    Ok(server::ValidationResult {
+        success: true,
+        errors: vec![],
+    })
+}
+
+/// Request metadata key containing the actor performing the request, recorded in the audit log
+pub const ACTOR_METADATA_KEY: &str = "x-actor";
+
+/// Returns the actor provided in the request metadata using the [`ACTOR_METADATA_KEY`], or
+/// [`UNKNOWN_ACTOR`](crate::postgres::audit::UNKNOWN_ACTOR) if no actor was provided
+///
+/// The actor is set by the [`AuthLayer`](crate::auth::layer::AuthLayer) for authenticated requests,
+/// actors of other requests are prefixed with [`UNVERIFIED_ACTOR_PREFIX`](crate::auth::layer::UNVERIFIED_ACTOR_PREFIX).
+pub(crate) fn get_actor<T>(request: &tonic::Request<T>) -> String {
+    request
+        .metadata()
+        .get(ACTOR_METADATA_KEY)
+        .and_then(|value| value.to_str().ok())
+        .filter(|actor| !actor.is_empty())
+        .map(String::from)
+        .unwrap_or_else(|| crate::postgres::audit::UNKNOWN_ACTOR.to_owned())
+}
+
+/// Returns a [`Status`] with [`Code::FailedPrecondition`](tonic::Code::FailedPrecondition)
+/// containing the record's current `updated_at` value, both in the message and in the
+/// [`UPDATED_AT_METADATA_KEY`] metadata field
+pub(crate) fn version_conflict_status(current: &DateTime<Utc>) -> Status {
+    let current = current.to_rfc3339();
+    let mut status = Status::failed_precondition(format!(
+        "The record has been modified, current updated_at: {}",
+        current
+    ));
+    if let Ok(value) = current.parse() {
+        status.metadata_mut().insert(UPDATED_AT_METADATA_KEY, value);
+    }
+    status
+}
+
 impl From<ArrErr> for Status {
     fn from(err: ArrErr) -> Self {
         // These errors come from modules like Postgres, where you
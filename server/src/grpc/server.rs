@@ -1,12 +1,22 @@
 //! gRPC server implementation
+use super::tls::{self, TlsFiles, TlsReloader};
 use super::GrpcSimpleService;
 use super::GrpcSimpleServiceLinked;
+use crate::auth::layer::{AuthLayer, HEALTH_SERVICE};
 use crate::config::Config;
+use crate::metrics::layer::GrpcMetricsLayer;
+use crate::postgres::psql_readiness;
 use crate::resources::base::ResourceObject;
 use crate::shutdown_signal;
 use serde::{Deserialize, Serialize};
 use std::net::SocketAddr;
+use std::time::Duration;
+use tokio::net::TcpListener;
+use tokio::time::MissedTickBehavior;
+use tonic::server::NamedService;
 use tonic::transport::Server;
+use tonic_health::server::HealthReporter;
+use tonic_health::ServingStatus;
 
 // include gRPC generic structs
 include!("../../../out/grpc/grpc.rs");
@@ -22,6 +32,7 @@ grpc_server_simple_service_mod!(parcel_scan);
 grpc_server_simple_service_mod!(scanner);
 grpc_server_simple_service_mod!(user);
 grpc_server_simple_service_mod!(vehicle);
+grpc_server_simple_service_mod!(vehicle_model);
 grpc_server_simple_service_mod!(vertipad);
 grpc_server_simple_service_mod!(vertiport);
 
@@ -46,6 +57,501 @@ pub mod itinerary_flight_plan {
     );
 }
 
+/// Module to expose the schedule service implementation for flight_plan
+pub mod flight_plan_schedule {
+    use super::calendar::{get_free_intervals, Interval, Schedule};
+    pub use super::flight_plan::rpc_schedule_server::*;
+    use super::flight_plan::{
+        AvailabilityRequest, AvailabilityResponse, ConflictsRequest, List, TimeInterval,
+        VertipadAvailability,
+    };
+    use super::{vertipad, vertiport, ReadyRequest, ReadyResponse};
+    use crate::common::ArrErr;
+    use lib_common::time::{DateTime, Utc};
+    use lib_common::uuid::Uuid;
+    use prost_wkt_types::Timestamp;
+    use tonic::{Request, Response, Status};
+
+    /// Implementation of flight_plan schedule gRPC endpoints
+    #[derive(Clone, Default, Debug, Copy)]
+    pub struct GrpcServer {}
+
+    #[cfg(not(tarpaulin_include))]
+    // no_coverage: (R5) Reads database records, should be part of integration tests
+    #[tonic::async_trait]
+    impl RpcSchedule for GrpcServer {
+        async fn get_conflicts(
+            &self,
+            request: Request<ConflictsRequest>,
+        ) -> Result<Response<List>, Status> {
+            grpc_info!("flight_plan_schedule server.");
+            grpc_debug!("request: {:?}", request);
+
+            let request = request.into_inner();
+            let vertipad_id = parse_id(&request.vertipad_id)?;
+            let exclude_id = match &request.exclude_id {
+                Some(id) => Some(parse_id(id)?),
+                None => None,
+            };
+            let timeslot =
+                parse_interval(request.timeslot_start, request.timeslot_end, "timeslot")?;
+
+            let list = find_conflicts(&vertipad_id, &timeslot, exclude_id.as_ref()).await?;
+            Ok(Response::new(list))
+        }
+
+        async fn get_availability(
+            &self,
+            request: Request<AvailabilityRequest>,
+        ) -> Result<Response<AvailabilityResponse>, Status> {
+            grpc_info!("flight_plan_schedule server.");
+            grpc_debug!("request: {:?}", request);
+
+            let request = request.into_inner();
+            let interval = parse_interval(request.time_start, request.time_end, "time")?;
+            let (vertiport, vertipads) = match (&request.vertipad_id, &request.vertiport_id) {
+                (Some(vertipad_id), None) => {
+                    let vertipad = find_vertipad(&parse_id(vertipad_id)?).await?;
+                    let vertiport = match &vertipad.data {
+                        Some(data) => find_vertiport(&data.vertiport_id).await?,
+                        None => None,
+                    };
+                    (vertiport, vec![vertipad])
+                }
+                (None, Some(vertiport_id)) => {
+                    let vertiport_id = parse_id(vertiport_id)?;
+                    let vertiport = match find_vertiport(&vertiport_id.to_string()).await? {
+                        Some(vertiport) => vertiport,
+                        None => {
+                            let error = format!("No vertiport found for id [{}].", vertiport_id);
+                            grpc_info!("{}", error);
+                            return Err(Status::not_found(error));
+                        }
+                    };
+                    (Some(vertiport), find_vertipads(&vertiport_id).await?)
+                }
+                _ => {
+                    return Err(Status::invalid_argument(
+                        "Either a vertipad_id or a vertiport_id should be provided.",
+                    ))
+                }
+            };
+
+            let mut list = vec![];
+            for vertipad in vertipads {
+                let vertipad_id = Uuid::parse_str(&vertipad.id).map_err(ArrErr::from)?;
+                let flight_plans = find_conflicts(&vertipad_id, &interval, None).await?;
+                let free = get_vertipad_availability(
+                    &interval,
+                    &vertipad,
+                    vertiport.as_ref(),
+                    &flight_plans,
+                )?;
+                list.push(VertipadAvailability {
+                    vertipad_id: vertipad.id,
+                    vertiport_id: vertipad
+                        .data
+                        .map(|data| data.vertiport_id)
+                        .unwrap_or_default(),
+                    free: free
+                        .into_iter()
+                        .map(|(start, end)| TimeInterval {
+                            start: Some(start.into()),
+                            end: Some(end.into()),
+                        })
+                        .collect(),
+                });
+            }
+
+            Ok(Response::new(AvailabilityResponse { list }))
+        }
+
+        async fn is_ready(
+            &self,
+            _request: Request<ReadyRequest>,
+        ) -> Result<Response<ReadyResponse>, Status> {
+            grpc_info!("flight_plan_schedule server.");
+
+            #[cfg(feature = "stub_backends")]
+            let ready = true;
+            #[cfg(not(feature = "stub_backends"))]
+            let ready = match crate::postgres::psql_readiness().await {
+                Ok(_) => true,
+                Err(e) => {
+                    grpc_warn!("Service not ready, database is unavailable: {}", e);
+                    false
+                }
+            };
+            Ok(Response::new(ReadyResponse { ready }))
+        }
+    }
+
+    /// Returns the free intervals of the provided vertipad within the requested interval
+    ///
+    /// A vertipad is not available if it is disabled, if its vertiport could not be found, during
+    /// the events of the vertipad's and vertiport's schedules and during the timeslots reserved by
+    /// the provided flight plans.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Status`] with [`Code::Internal`](tonic::Code::Internal) if a schedule could not be parsed
+    fn get_vertipad_availability(
+        interval: &Interval,
+        vertipad: &vertipad::Object,
+        vertiport: Option<&vertiport::Data>,
+        flight_plans: &List,
+    ) -> Result<Vec<Interval>, Status> {
+        let (data, vertiport) = match (&vertipad.data, vertiport) {
+            (Some(data), Some(vertiport)) if data.enabled => (data, vertiport),
+            _ => return Ok(vec![]),
+        };
+
+        let mut busy = vec![];
+        for (resource, id, calendar) in [
+            ("vertipad", &vertipad.id, data.get_calendar()),
+            ("vertiport", &data.vertiport_id, vertiport.get_calendar()),
+        ] {
+            let calendar = calendar.map_err(|e| {
+                let error = format!("Could not parse schedule of [{}] [{}]", resource, id);
+                grpc_error!("{}: {}", error, e);
+                Status::internal(error)
+            })?;
+            busy.append(&mut calendar.get_busy_intervals(interval));
+        }
+
+        for flight_plan in flight_plans
+            .list
+            .iter()
+            .filter_map(|object| object.data.as_ref())
+        {
+            for (vertipad_id, start, end) in [
+                (
+                    &flight_plan.origin_vertipad_id,
+                    &flight_plan.origin_timeslot_start,
+                    &flight_plan.origin_timeslot_end,
+                ),
+                (
+                    &flight_plan.target_vertipad_id,
+                    &flight_plan.target_timeslot_start,
+                    &flight_plan.target_timeslot_end,
+                ),
+            ] {
+                if let (true, Some(start), Some(end)) = (*vertipad_id == vertipad.id, start, end) {
+                    busy.push((start.clone().into(), end.clone().into()));
+                }
+            }
+        }
+
+        Ok(get_free_intervals(interval, busy))
+    }
+
+    /// Returns all flight plans reserving the provided vertipad during (part of) the provided interval
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Status`] with [`Code::Internal`](tonic::Code::Internal) if any error is returned from a db call
+    #[cfg(not(tarpaulin_include))]
+    // no_coverage: (R5) Reads database records, should be part of integration tests
+    async fn find_conflicts(
+        vertipad_id: &Uuid,
+        interval: &Interval,
+        exclude_id: Option<&Uuid>,
+    ) -> Result<List, Status> {
+        let (start, end) = *interval;
+
+        #[cfg(feature = "stub_backends")]
+        let list = {
+            let overlaps = |pad_id: &str,
+                            slot_start: &Option<Timestamp>,
+                            slot_end: &Option<Timestamp>| {
+                match (slot_start, slot_end) {
+                    (Some(slot_start), Some(slot_end)) => {
+                        pad_id == vertipad_id.to_string()
+                            && DateTime::<Utc>::from(slot_start.clone()) < end
+                            && DateTime::<Utc>::from(slot_end.clone()) > start
+                    }
+                    _ => false,
+                }
+            };
+            let list = super::flight_plan::MEM_DATA
+                .lock()
+                .await
+                .iter()
+                .filter(|object| {
+                    exclude_id.map_or(true, |id| object.id != id.to_string())
+                        && object.deleted_at.is_none()
+                })
+                .filter(|object| match &object.data {
+                    Some(data) => {
+                        data.flight_status != super::flight_plan::FlightStatus::Cancelled as i32
+                            && (overlaps(
+                                &data.origin_vertipad_id,
+                                &data.origin_timeslot_start,
+                                &data.origin_timeslot_end,
+                            ) || overlaps(
+                                &data.target_vertipad_id,
+                                &data.target_timeslot_start,
+                                &data.target_timeslot_end,
+                            ))
+                    }
+                    None => false,
+                })
+                .cloned()
+                .collect();
+            List {
+                list,
+                ..Default::default()
+            }
+        };
+        #[cfg(not(feature = "stub_backends"))]
+        let list: List = {
+            use crate::resources::base::ResourceObject;
+
+            let rows = crate::postgres::timeslot::get_conflicts::<
+                ResourceObject<super::flight_plan::Data>,
+            >(vertipad_id, &start, &end, exclude_id)
+            .await
+            .map_err(|e| {
+                let error = "Something went wrong trying to retrieve values from the database";
+                grpc_error!("{} for [flight_plan] conflicts: {}", error, e);
+                Status::internal(error)
+            })?;
+            rows.try_into()?
+        };
+
+        Ok(list)
+    }
+
+    /// Returns the (non archived) vertipad matching the provided id
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Status`] with [`Code::NotFound`](tonic::Code::NotFound) if no vertipad could be found
+    /// Returns [`Status`] with [`Code::Internal`](tonic::Code::Internal) if any error is returned from a db call
+    #[cfg(not(tarpaulin_include))]
+    // no_coverage: (R5) Reads database records, should be part of integration tests
+    async fn find_vertipad(id: &Uuid) -> Result<vertipad::Object, Status> {
+        #[cfg(feature = "stub_backends")]
+        let vertipad = super::vertipad::MEM_DATA
+            .lock()
+            .await
+            .iter()
+            .find(|object| object.id == id.to_string() && object.deleted_at.is_none())
+            .cloned();
+        #[cfg(not(feature = "stub_backends"))]
+        let vertipad = {
+            use crate::postgres::simple_resource::PsqlType;
+            use crate::resources::base::ResourceObject;
+
+            match ResourceObject::<vertipad::Data>::find_by_id(id, false).await? {
+                Some(row) => Some(vertipad::Object::try_from(row)?),
+                None => None,
+            }
+        };
+
+        vertipad.ok_or_else(|| {
+            let error = format!("No vertipad found for id [{}].", id);
+            grpc_info!("{}", error);
+            Status::not_found(error)
+        })
+    }
+
+    /// Returns all (non archived) vertipads of the provided vertiport
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Status`] with [`Code::Internal`](tonic::Code::Internal) if any error is returned from a db call
+    #[cfg(not(tarpaulin_include))]
+    // no_coverage: (R5) Reads database records, should be part of integration tests
+    async fn find_vertipads(vertiport_id: &Uuid) -> Result<Vec<vertipad::Object>, Status> {
+        #[cfg(feature = "stub_backends")]
+        let vertipads = super::vertipad::MEM_DATA
+            .lock()
+            .await
+            .iter()
+            .filter(|object| {
+                object.deleted_at.is_none()
+                    && object
+                        .data
+                        .as_ref()
+                        .map_or(false, |data| data.vertiport_id == vertiport_id.to_string())
+            })
+            .cloned()
+            .collect();
+        #[cfg(not(feature = "stub_backends"))]
+        let vertipads = {
+            use crate::postgres::PsqlSearch;
+            use crate::resources::base::ResourceObject;
+
+            let filter = super::AdvancedSearchFilter::search_equals(
+                String::from("vertiport_id"),
+                vertiport_id.to_string(),
+            );
+            let list: vertipad::List = ResourceObject::<vertipad::Data>::advanced_search(filter)
+                .await?
+                .try_into()?;
+            list.list
+        };
+
+        Ok(vertipads)
+    }
+
+    /// Returns the data of the (non archived) vertiport matching the provided id, if found
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Status`] with [`Code::Internal`](tonic::Code::Internal) if any error is returned from a db call
+    #[cfg(not(tarpaulin_include))]
+    // no_coverage: (R5) Reads database records, should be part of integration tests
+    async fn find_vertiport(id: &str) -> Result<Option<vertiport::Data>, Status> {
+        #[cfg(feature = "stub_backends")]
+        let vertiport = super::vertiport::MEM_DATA
+            .lock()
+            .await
+            .iter()
+            .find(|object| object.id == id && object.deleted_at.is_none())
+            .and_then(|object| object.data.clone());
+        #[cfg(not(feature = "stub_backends"))]
+        let vertiport = {
+            use crate::postgres::simple_resource::PsqlType;
+            use crate::resources::base::ResourceObject;
+
+            let id = Uuid::parse_str(id).map_err(ArrErr::from)?;
+            match ResourceObject::<vertiport::Data>::find_by_id(&id, false).await? {
+                Some(row) => Some(vertiport::Data::try_from(row)?),
+                None => None,
+            }
+        };
+
+        Ok(vertiport)
+    }
+
+    /// Parses the provided start and end [`Timestamp`]s into an [`Interval`]
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Status`] with [`Code::InvalidArgument`](tonic::Code::InvalidArgument) if either
+    /// value is missing, or if the start is not before the end
+    fn parse_interval(
+        start: Option<Timestamp>,
+        end: Option<Timestamp>,
+        name: &str,
+    ) -> Result<Interval, Status> {
+        let (start, end): (DateTime<Utc>, DateTime<Utc>) = match (start, end) {
+            (Some(start), Some(end)) => (start.into(), end.into()),
+            _ => {
+                return Err(Status::invalid_argument(format!(
+                    "Both {}_start and {}_end should be provided.",
+                    name, name
+                )))
+            }
+        };
+        if start >= end {
+            return Err(Status::invalid_argument(format!(
+                "The provided {}_start should be before {}_end.",
+                name, name
+            )));
+        }
+        Ok((start, end))
+    }
+
+    /// Parses the provided id [`String`] into a [`Uuid`], returning an `INVALID_ARGUMENT` [`Status`] on failure
+    fn parse_id(id: &str) -> Result<Uuid, Status> {
+        Uuid::parse_str(id).map_err(|e| {
+            let error = format!("Could not convert provided id [{}] into uuid: {}", id, e);
+            grpc_info!("{}", error);
+            Status::invalid_argument(error)
+        })
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::super::flight_plan;
+        use super::*;
+        use lib_common::time::{Duration, TimeZone};
+
+        #[tokio::test]
+        async fn test_get_vertipad_availability() {
+            crate::test_util::assert_init_done().await;
+            ut_info!("start");
+
+            // Monday March 6th 2023
+            let time = |hour: i64| {
+                Utc.with_ymd_and_hms(2023, 3, 6, 0, 0, 0).unwrap() + Duration::hours(hour)
+            };
+            let interval = (time(6), time(20));
+
+            let vertiport_id = Uuid::new_v4().to_string();
+            let vertiport = vertiport::Data {
+                schedule: Some(String::from(
+                    "DTSTART:20230306T180000Z;DURATION:PT14H\nRRULE:FREQ=DAILY",
+                )),
+                ..vertiport::mock::get_data_obj()
+            };
+            let mut vertipad = vertipad::Object {
+                id: Uuid::new_v4().to_string(),
+                data: Some(vertipad::Data {
+                    vertiport_id: vertiport_id.clone(),
+                    enabled: true,
+                    schedule: Some(String::from("DTSTART:20230306T120000Z;DURATION:PT1H")),
+                    ..vertipad::mock::get_data_obj()
+                }),
+                deleted_at: None,
+            };
+            let flight_plans = flight_plan::List {
+                list: vec![flight_plan::Object {
+                    id: Uuid::new_v4().to_string(),
+                    data: Some(flight_plan::Data {
+                        origin_vertipad_id: vertipad.id.clone(),
+                        origin_timeslot_start: Some(time(9).into()),
+                        origin_timeslot_end: Some(time(10).into()),
+                        target_vertipad_id: Uuid::new_v4().to_string(),
+                        target_timeslot_start: Some(time(10).into()),
+                        target_timeslot_end: Some(time(11).into()),
+                        ..flight_plan::mock::get_data_obj()
+                    }),
+                    deleted_at: None,
+                }],
+                ..Default::default()
+            };
+
+            let result =
+                get_vertipad_availability(&interval, &vertipad, Some(&vertiport), &flight_plans);
+            ut_debug!("{:?}", result);
+            assert_eq!(
+                result.unwrap(),
+                vec![
+                    (time(6), time(9)),
+                    (time(10), time(12)),
+                    (time(13), time(18))
+                ]
+            );
+
+            // A vertipad without an existing vertiport is not available
+            let result = get_vertipad_availability(&interval, &vertipad, None, &flight_plans);
+            assert_eq!(result.unwrap(), vec![]);
+
+            // A malformed schedule results in an internal error
+            if let Some(data) = vertipad.data.as_mut() {
+                data.schedule = Some(String::from("DTSTART:invalid"));
+            }
+            let result =
+                get_vertipad_availability(&interval, &vertipad, Some(&vertiport), &flight_plans);
+            assert_eq!(result.unwrap_err().code(), tonic::Code::Internal);
+
+            // A disabled vertipad is not available
+            if let Some(data) = vertipad.data.as_mut() {
+                data.enabled = false;
+            }
+            let result =
+                get_vertipad_availability(&interval, &vertipad, Some(&vertiport), &flight_plans);
+            assert_eq!(result.unwrap(), vec![]);
+
+            ut_info!("success");
+        }
+    }
+}
+
 grpc_server_group_service_mod!(user);
 grpc_server_group_service_mod!(vehicle);
 grpc_server_group_service_mod!(vertiport);
@@ -56,6 +562,36 @@ pub mod search {
     include!("../../../includes/search.rs");
 }
 
+/// Provide schedule (calendar) parsing and time interval helpers
+///
+/// # Examples
+/// ```
+/// use lib_common::time::{TimeZone, Utc};
+/// use svc_storage::resources::calendar::Calendar;
+///
+/// // Unavailable outside of working hours (8AM - 6PM) on workdays, and during the weekend
+/// let calendar: Calendar = "\
+/// DTSTART:20221020T180000Z;DURATION:PT14H
+/// RRULE:FREQ=WEEKLY;BYDAY=MO,TU,WE,TH,FR
+/// DTSTART:20221022T000000Z;DURATION:PT24H
+/// RRULE:FREQ=WEEKLY;BYDAY=SA,SU"
+///     .parse()
+///     .unwrap();
+///
+/// let start = Utc.with_ymd_and_hms(2023, 3, 7, 0, 0, 0).unwrap();
+/// let end = Utc.with_ymd_and_hms(2023, 3, 8, 0, 0, 0).unwrap();
+/// let busy = calendar.get_busy_intervals(&(start, end));
+/// assert_eq!(busy.len(), 2);
+/// ```
+pub mod calendar {
+    include!("../../../includes/calendar.rs");
+}
+
+/// Provide W3C trace context propagation through gRPC metadata
+pub mod trace_context {
+    include!("../../../includes/trace_context.rs");
+}
+
 /// Provide geo types and conversions
 pub mod geo_types {
     use serde::{Deserialize, Serialize};
@@ -64,6 +600,207 @@ pub mod geo_types {
     include!("../../../includes/geo_types.rs");
 }
 
+/// Provide admin service for maintenance tasks
+pub mod admin {
+    use std::collections::HashMap;
+    use tonic::{Request, Response, Status};
+
+    include!("../../../out/grpc/server/grpc.admin.service.rs");
+    pub use rpc_admin_server::*;
+
+    /// Implementation of admin gRPC endpoints
+    #[derive(Clone, Default, Debug)]
+    pub struct GrpcServer {
+        /// retention period in days per resource, used to purge archived records
+        pub purge_retention_days: HashMap<String, u32>,
+    }
+
+    impl GrpcServer {
+        /// Create a new admin server using the provided purge retention periods
+        pub fn new(purge_retention_days: HashMap<String, u32>) -> Self {
+            Self {
+                purge_retention_days,
+            }
+        }
+    }
+
+    #[cfg(not(tarpaulin_include))]
+    // no_coverage: (R5) Permanently deletes data, should be part of integration tests
+    #[tonic::async_trait]
+    impl RpcAdmin for GrpcServer {
+        async fn purge(&self, _request: Request<()>) -> Result<Response<PurgeResponse>, Status> {
+            grpc_info!("Purge requested.");
+
+            #[cfg(feature = "stub_backends")]
+            let results: Vec<crate::postgres::purge::PurgeResult> = vec![];
+            #[cfg(not(feature = "stub_backends"))]
+            let results = crate::postgres::purge::purge(&self.purge_retention_days)
+                .await
+                .map_err(|e| {
+                    let error = format!("Could not purge archived records: {}", e);
+                    grpc_error!("{}", error);
+                    Status::internal(error)
+                })?;
+
+            let purged = results
+                .into_iter()
+                .map(|result| PurgedRecords {
+                    resource: result.resource,
+                    ids: result.ids.iter().map(|id| id.to_string()).collect(),
+                })
+                .collect();
+            Ok(Response::new(PurgeResponse { purged }))
+        }
+    }
+}
+
+/// Provide audit service to read the audit log
+pub mod audit {
+    use super::{AdvancedSearchFilter, Deserialize, Id, ReadyRequest, ReadyResponse, Serialize};
+    use tonic::{Code, Request, Response, Status};
+
+    include!("../../../out/grpc/server/grpc.audit.rs");
+    pub use rpc_audit_server::*;
+
+    /// Implementation of audit gRPC endpoints
+    #[derive(Clone, Default, Debug, Copy)]
+    pub struct GrpcServer {}
+
+    #[cfg(not(tarpaulin_include))]
+    // no_coverage: (R5) Reads database records, should be part of integration tests
+    #[tonic::async_trait]
+    impl RpcAudit for GrpcServer {
+        async fn get_by_id(&self, request: Request<Id>) -> Result<Response<Object>, Status> {
+            grpc_info!("audit server.");
+            grpc_debug!("request: {:?}", request);
+
+            #[cfg(feature = "stub_backends")]
+            let row: Option<tokio_postgres::Row> = None;
+            #[cfg(not(feature = "stub_backends"))]
+            let row = {
+                use crate::postgres::simple_resource::PsqlType;
+                use crate::resources::base::ResourceObject;
+
+                let id = lib_common::uuid::Uuid::try_from(request.into_inner())?;
+                ResourceObject::<Data>::find_by_id(&id, true)
+                    .await
+                    .map_err(|e| {
+                        grpc_error!("Could not get audit log entry [{}]: {}", id, e);
+                        Status::new(Code::Internal, "Could not get resource for the provided id")
+                    })?
+            };
+
+            match row {
+                Some(row) => Ok(Response::new(row.try_into()?)),
+                None => Err(Status::new(
+                    Code::NotFound,
+                    "Could not find any resource for the provided id",
+                )),
+            }
+        }
+
+        async fn search(
+            &self,
+            request: Request<AdvancedSearchFilter>,
+        ) -> Result<Response<List>, Status> {
+            grpc_info!("audit server.");
+            grpc_debug!("request: {:?}", request);
+
+            #[cfg(feature = "stub_backends")]
+            let list = List::default();
+            #[cfg(not(feature = "stub_backends"))]
+            let list = {
+                use crate::grpc::GrpcSearchResultList;
+                use crate::postgres::PsqlSearch;
+                use crate::resources::base::ResourceObject;
+
+                let filter = request.into_inner();
+                let rows = ResourceObject::<Data>::advanced_search(filter.clone())
+                    .await
+                    .map_err(|e| {
+                        let error =
+                            "Something went wrong trying to retrieve values from the database";
+                        grpc_error!("{} for [audit_log]: {}", error, e);
+                        Status::new(Code::Internal, error)
+                    })?;
+                let paging_info = ResourceObject::<Data>::get_search_paging_info(&filter, &rows)
+                    .await
+                    .map_err(|e| {
+                        let error =
+                            "Something went wrong trying to determine the paging information";
+                        grpc_error!("{} for [audit_log]: {}", error, e);
+                        Status::new(Code::Internal, error)
+                    })?;
+
+                let mut list: List = rows.try_into()?;
+                list.set_paging_info(paging_info);
+                list
+            };
+
+            Ok(Response::new(list))
+        }
+
+        async fn is_ready(
+            &self,
+            _request: Request<ReadyRequest>,
+        ) -> Result<Response<ReadyResponse>, Status> {
+            grpc_info!("audit server.");
+
+            #[cfg(feature = "stub_backends")]
+            let ready = true;
+            #[cfg(not(feature = "stub_backends"))]
+            let ready = match crate::postgres::psql_readiness().await {
+                Ok(_) => true,
+                Err(e) => {
+                    grpc_warn!("Service not ready, database is unavailable: {}", e);
+                    false
+                }
+            };
+            Ok(Response::new(ReadyResponse { ready }))
+        }
+    }
+}
+
+/// Returns the admin service if enabled by [`Config::admin_enabled`].
+///
+/// The admin service permanently removes data, so it will not be registered if authentication
+/// is disabled.
+fn get_admin_service(config: &Config) -> Option<admin::RpcAdminServer<admin::GrpcServer>> {
+    if !config.admin_enabled {
+        grpc_info!("Admin service disabled.");
+        return None;
+    }
+    if !config.auth_enabled {
+        grpc_warn!("Admin service enabled, but authentication is disabled. Refusing to register the admin service.");
+        return None;
+    }
+
+    Some(admin::RpcAdminServer::new(admin::GrpcServer::new(
+        config.purge_retention_days.clone(),
+    )))
+}
+
+/// Minimal interval used to check the database connection for the gRPC health status
+const MIN_HEALTH_CHECK_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Returns the interval used to check the database connection, configured by
+/// [`Config::health_check_interval_secs`].
+///
+/// Intervals shorter than [`MIN_HEALTH_CHECK_INTERVAL`] are raised to the minimal interval.
+fn get_health_check_interval(config: &Config) -> Duration {
+    let interval = Duration::from_secs(config.health_check_interval_secs);
+    if interval < MIN_HEALTH_CHECK_INTERVAL {
+        grpc_warn!(
+            "Health check interval of [{}] seconds is too short, using [{}] seconds instead.",
+            config.health_check_interval_secs,
+            MIN_HEALTH_CHECK_INTERVAL.as_secs()
+        );
+        return MIN_HEALTH_CHECK_INTERVAL;
+    }
+
+    interval
+}
+
 /// Starts the grpc servers for this microservice using the provided configuration
 ///
 /// # Examples
@@ -92,82 +829,47 @@ pub async fn grpc_server(config: Config, shutdown_rx: Option<tokio::sync::onesho
         }
     };
 
-    let (mut health_reporter, health_service) = tonic_health::server::health_reporter();
-    health_reporter
-        .set_serving::<adsb::RpcServiceServer<adsb::GrpcServer>>()
-        .await;
-    health_reporter
-        .set_serving::<flight_plan::RpcServiceServer<flight_plan::GrpcServer>>()
-        .await;
-    health_reporter
-        .set_serving::<flight_plan_parcel::RpcServiceLinkedServer<flight_plan_parcel::GrpcServer>>()
-        .await;
-    health_reporter
-        .set_serving::<group::RpcServiceServer<group::GrpcServer>>()
-        .await;
-    health_reporter
-        .set_serving::<group_user::RpcUserLinkServer<group_user::GrpcServer>>()
-        .await;
-    health_reporter
-        .set_serving::<group_vehicle::RpcVehicleLinkServer<group_vehicle::GrpcServer>>()
-        .await;
-    health_reporter
-        .set_serving::<group_vertipad::RpcVertipadLinkServer<group_vertipad::GrpcServer>>()
-        .await;
-    health_reporter
-        .set_serving::<group_vertiport::RpcVertiportLinkServer<group_vertiport::GrpcServer>>()
-        .await;
-    health_reporter
-        .set_serving::<itinerary::RpcServiceServer<itinerary::GrpcServer>>()
-        .await;
-    health_reporter
-        .set_serving::<itinerary_flight_plan::RpcFlightPlanLinkServer<itinerary_flight_plan::GrpcServer>>()
-        .await;
-    health_reporter
-        .set_serving::<parcel::RpcServiceServer<parcel::GrpcServer>>()
-        .await;
-    health_reporter
-        .set_serving::<parcel_scan::RpcServiceServer<parcel_scan::GrpcServer>>()
-        .await;
-    health_reporter
-        .set_serving::<pilot::RpcServiceServer<pilot::GrpcServer>>()
-        .await;
-    health_reporter
-        .set_serving::<scanner::RpcServiceServer<scanner::GrpcServer>>()
-        .await;
-    health_reporter
-        .set_serving::<user::RpcServiceServer<user::GrpcServer>>()
-        .await;
-    health_reporter
-        .set_serving::<user_group::RpcGroupLinkServer<user_group::GrpcServer>>()
-        .await;
-    health_reporter
-        .set_serving::<vehicle::RpcServiceServer<vehicle::GrpcServer>>()
-        .await;
-    health_reporter
-        .set_serving::<vehicle_group::RpcGroupLinkServer<vehicle_group::GrpcServer>>()
-        .await;
-    health_reporter
-        .set_serving::<vertipad::RpcServiceServer<vertipad::GrpcServer>>()
-        .await;
-    health_reporter
-        .set_serving::<vertipad_group::RpcGroupLinkServer<vertipad_group::GrpcServer>>()
-        .await;
-    health_reporter
-        .set_serving::<vertiport::RpcServiceServer<vertiport::GrpcServer>>()
-        .await;
-    health_reporter
-        .set_serving::<vertiport_group::RpcGroupLinkServer<vertiport_group::GrpcServer>>()
-        .await;
+    let auth_layer = match AuthLayer::from_config(&config) {
+        Ok(auth_layer) => auth_layer,
+        Err(e) => {
+            grpc_error!("Failed to configure authentication: {}", e);
+            return;
+        }
+    };
+
+    let tls_reloader = match TlsFiles::from_config(&config)
+        .and_then(|files| files.map(TlsReloader::new).transpose())
+    {
+        Ok(tls_reloader) => tls_reloader,
+        Err(e) => {
+            grpc_error!("Failed to configure TLS: {}", e);
+            return;
+        }
+    };
+
+    let (health_reporter, health_service) = tonic_health::server::health_reporter();
+    let health_check_task = tokio::spawn(health_check(
+        health_reporter,
+        get_health_check_interval(&config),
+    ));
 
     //start server
     grpc_info!("Starting gRPC services on: {}.", full_grpc_addr);
-    match Server::builder()
+    let router = Server::builder()
+        .layer(GrpcMetricsLayer::new(
+            get_service_names().into_iter().chain([HEALTH_SERVICE]),
+        ))
+        .layer(auth_layer)
         .add_service(health_service)
+        .add_optional_service(get_admin_service(&config))
+        .add_service(audit::RpcAuditServer::new(audit::GrpcServer::default()))
         .add_service(adsb::RpcServiceServer::new(adsb::GrpcServer::default()))
         .add_service(flight_plan::RpcServiceServer::new(
             flight_plan::GrpcServer::default(),
         ))
+        .add_service(flight_plan_schedule::RpcScheduleServer::new(
+            flight_plan_schedule::GrpcServer::default(),
+        ))
         .add_service(flight_plan_parcel::RpcServiceLinkedServer::new(
             flight_plan_parcel::GrpcServer::default(),
         ))
@@ -208,6 +910,9 @@ pub async fn grpc_server(config: Config, shutdown_rx: Option<tokio::sync::onesho
         .add_service(vehicle_group::RpcGroupLinkServer::new(
             vehicle_group::GrpcServer::default(),
         ))
+        .add_service(vehicle_model::RpcServiceServer::new(
+            vehicle_model::GrpcServer::default(),
+        ))
         .add_service(vertipad::RpcServiceServer::new(
             vertipad::GrpcServer::default(),
         ))
@@ -219,15 +924,122 @@ pub async fn grpc_server(config: Config, shutdown_rx: Option<tokio::sync::onesho
         ))
         .add_service(vertiport_group::RpcGroupLinkServer::new(
             vertiport_group::GrpcServer::default(),
-        ))
-        .serve_with_shutdown(full_grpc_addr, shutdown_signal("grpc", shutdown_rx))
-        .await
-    {
+        ));
+
+    let result = match tls_reloader {
+        Some(tls_reloader) => {
+            let listener = match TcpListener::bind(full_grpc_addr).await {
+                Ok(listener) => listener,
+                Err(e) => {
+                    grpc_error!("Could not bind gRPC address [{}]: {}", full_grpc_addr, e);
+                    health_check_task.abort();
+                    return;
+                }
+            };
+            let reload_task = tokio::spawn(
+                tls_reloader
+                    .clone()
+                    .watch(Duration::from_secs(config.grpc_tls_reload_interval_secs)),
+            );
+            let result = router
+                .serve_with_incoming_shutdown(
+                    tls::incoming(listener, tls_reloader),
+                    shutdown_signal("grpc", shutdown_rx),
+                )
+                .await;
+            reload_task.abort();
+            result
+        }
+        None => {
+            router
+                .serve_with_shutdown(full_grpc_addr, shutdown_signal("grpc", shutdown_rx))
+                .await
+        }
+    };
+    match result {
         Ok(_) => grpc_info!("gRPC server running at: {}.", full_grpc_addr),
         Err(e) => {
             grpc_error!("Could not start gRPC server: {}", e);
         }
     };
+
+    health_check_task.abort();
+}
+
+/// Returns the names of all gRPC services provided by this server
+fn get_service_names() -> Vec<&'static str> {
+    vec![
+        <admin::RpcAdminServer<admin::GrpcServer> as NamedService>::NAME,
+        <audit::RpcAuditServer<audit::GrpcServer> as NamedService>::NAME,
+        <adsb::RpcServiceServer<adsb::GrpcServer> as NamedService>::NAME,
+        <flight_plan::RpcServiceServer<flight_plan::GrpcServer> as NamedService>::NAME,
+        <flight_plan_schedule::RpcScheduleServer<flight_plan_schedule::GrpcServer> as NamedService>::NAME,
+        <flight_plan_parcel::RpcServiceLinkedServer<flight_plan_parcel::GrpcServer> as NamedService>::NAME,
+        <group::RpcServiceServer<group::GrpcServer> as NamedService>::NAME,
+        <group_user::RpcUserLinkServer<group_user::GrpcServer> as NamedService>::NAME,
+        <group_vehicle::RpcVehicleLinkServer<group_vehicle::GrpcServer> as NamedService>::NAME,
+        <group_vertipad::RpcVertipadLinkServer<group_vertipad::GrpcServer> as NamedService>::NAME,
+        <group_vertiport::RpcVertiportLinkServer<group_vertiport::GrpcServer> as NamedService>::NAME,
+        <itinerary::RpcServiceServer<itinerary::GrpcServer> as NamedService>::NAME,
+        <itinerary_flight_plan::RpcFlightPlanLinkServer<itinerary_flight_plan::GrpcServer> as NamedService>::NAME,
+        <parcel::RpcServiceServer<parcel::GrpcServer> as NamedService>::NAME,
+        <parcel_scan::RpcServiceServer<parcel_scan::GrpcServer> as NamedService>::NAME,
+        <pilot::RpcServiceServer<pilot::GrpcServer> as NamedService>::NAME,
+        <scanner::RpcServiceServer<scanner::GrpcServer> as NamedService>::NAME,
+        <user::RpcServiceServer<user::GrpcServer> as NamedService>::NAME,
+        <user_group::RpcGroupLinkServer<user_group::GrpcServer> as NamedService>::NAME,
+        <vehicle::RpcServiceServer<vehicle::GrpcServer> as NamedService>::NAME,
+        <vehicle_group::RpcGroupLinkServer<vehicle_group::GrpcServer> as NamedService>::NAME,
+        <vehicle_model::RpcServiceServer<vehicle_model::GrpcServer> as NamedService>::NAME,
+        <vertipad::RpcServiceServer<vertipad::GrpcServer> as NamedService>::NAME,
+        <vertipad_group::RpcGroupLinkServer<vertipad_group::GrpcServer> as NamedService>::NAME,
+        <vertiport::RpcServiceServer<vertiport::GrpcServer> as NamedService>::NAME,
+        <vertiport_group::RpcGroupLinkServer<vertiport_group::GrpcServer> as NamedService>::NAME,
+    ]
+}
+
+/// Sets the health status of all gRPC services provided by this server
+async fn set_health_status(health_reporter: &mut HealthReporter, status: ServingStatus) {
+    for service_name in get_service_names() {
+        health_reporter
+            .set_service_status(service_name, status)
+            .await;
+    }
+}
+
+/// Periodically checks if the database can be reached and updates the health status of all
+/// gRPC services accordingly. Services will be reported as `NOT_SERVING` as long as the
+/// database is unavailable.
+#[cfg(not(tarpaulin_include))]
+// no_coverage: (R5) Runs for the lifetime of the server, should be part of integration tests
+async fn health_check(mut health_reporter: HealthReporter, interval: Duration) {
+    let mut interval = tokio::time::interval(interval);
+    interval.set_missed_tick_behavior(MissedTickBehavior::Delay);
+
+    let mut serving: Option<bool> = None;
+    loop {
+        interval.tick().await;
+
+        let ready = match psql_readiness().await {
+            Ok(_) => true,
+            Err(e) => {
+                grpc_error!("Database readiness check failed: {}", e);
+                false
+            }
+        };
+        if serving == Some(ready) {
+            continue;
+        }
+
+        if ready {
+            grpc_info!("Database is available, setting all services to SERVING.");
+            set_health_status(&mut health_reporter, ServingStatus::Serving).await;
+        } else {
+            grpc_warn!("Database is unavailable, setting all services to NOT_SERVING.");
+            set_health_status(&mut health_reporter, ServingStatus::NotServing).await;
+        }
+        serving = Some(ready);
+    }
 }
 
 #[cfg(test)]
@@ -248,6 +1060,13 @@ mod tests {
         ut_debug!("{:?}", result);
         assert!(result.is_ok());
 
+        let result = imp
+            .generic_is_ready(tonic::Request::new(ReadyRequest {}))
+            .await;
+        ut_debug!("{:?}", result);
+        assert!(result.is_ok());
+        assert!(result.unwrap().into_inner().ready);
+
         let result = imp.generic_insert(tonic::Request::new(data)).await;
         ut_debug!("{:?}", result);
         assert!(result.is_ok());
@@ -257,4 +1076,61 @@ mod tests {
 
         ut_info!("success")
     }
+
+    #[tokio::test]
+    async fn test_get_service_names() {
+        crate::test_util::assert_init_done().await;
+        ut_info!("start");
+
+        let mut service_names = super::get_service_names();
+        let num_services = service_names.len();
+        assert_eq!(num_services, 26);
+
+        // Every service should only be reported once
+        service_names.sort();
+        service_names.dedup();
+        assert_eq!(service_names.len(), num_services);
+
+        ut_info!("success")
+    }
+
+    #[tokio::test]
+    async fn test_get_admin_service() {
+        crate::test_util::assert_init_done().await;
+        ut_info!("start");
+
+        let mut config = crate::config::Config::default();
+        assert!(super::get_admin_service(&config).is_none());
+
+        // The admin service should never be available without authentication
+        config.admin_enabled = true;
+        assert!(super::get_admin_service(&config).is_none());
+
+        config.auth_enabled = true;
+        assert!(super::get_admin_service(&config).is_some());
+
+        ut_info!("success")
+    }
+
+    #[tokio::test]
+    async fn test_get_health_check_interval() {
+        crate::test_util::assert_init_done().await;
+        ut_info!("start");
+
+        let mut config = crate::config::Config::default();
+        config.health_check_interval_secs = 10;
+        assert_eq!(
+            super::get_health_check_interval(&config),
+            std::time::Duration::from_secs(10)
+        );
+
+        // A zero interval would make the health check panic
+        config.health_check_interval_secs = 0;
+        assert_eq!(
+            super::get_health_check_interval(&config),
+            super::MIN_HEALTH_CHECK_INTERVAL
+        );
+
+        ut_info!("success")
+    }
 }
@@ -1,13 +1,21 @@
 //! Grpc Simple resource Traits
 
+use futures::StreamExt;
+use lib_common::uuid::Uuid;
 use std::marker::PhantomData;
+use tokio::sync::broadcast::{self, error::RecvError};
 use tokio_postgres::Row;
 use tonic::{Code, Request, Response, Status};
 
 use super::server::*;
-use super::GrpcDataObjectType;
+use super::{
+    check_expected_updated_at, get_updated_at, set_updated_at_metadata, version_conflict_status,
+    ArrErr, ChangeNotification, GrpcDataObjectType, GrpcInsertBatchRequest, GrpcSearchResultList,
+    GrpcSearchStream, GrpcWatchStream,
+};
+use crate::cache;
 use crate::postgres::simple_resource::{PsqlObjectType, PsqlType};
-use crate::postgres::PsqlSearch;
+use crate::postgres::{psql_readiness, PsqlSearch};
 use crate::resources::base::simple_resource::{GenericResourceResult, ObjectType, SimpleResource};
 use crate::resources::base::Resource;
 
@@ -30,13 +38,25 @@ where
         + Sync
         + Send,
     <Self as GrpcSimpleService>::Data: GrpcDataObjectType + TryFrom<Row>,
-    <Self as GrpcSimpleService>::List: TryFrom<Vec<Row>>,
-    <Self as GrpcSimpleService>::Object: From<Self::ResourceObject>,
+    <Self as GrpcSimpleService>::List: TryFrom<Vec<Row>> + GrpcSearchResultList,
+    <Self as GrpcSimpleService>::Object: From<Self::ResourceObject>
+        + TryFrom<Row>
+        + prost::Message
+        + serde::Serialize
+        + Default
+        + Clone
+        + Send
+        + Sync
+        + 'static,
     <Self as GrpcSimpleService>::UpdateObject: Send,
     <Self as GrpcSimpleService>::Response:
         From<GenericResourceResult<Self::ResourceObject, Self::Data>>,
-    Status:
-        From<<Self::Data as TryFrom<Row>>::Error> + From<<Self::List as TryFrom<Vec<Row>>>::Error>,
+    <Self as GrpcSimpleService>::InsertBatchRequest: GrpcInsertBatchRequest<Self::Data> + Send,
+    <Self as GrpcSimpleService>::ChangeEvent:
+        From<ChangeNotification<Self::Object>> + Send + 'static,
+    Status: From<<Self::Data as TryFrom<Row>>::Error>
+        + From<<Self::Object as TryFrom<Row>>::Error>
+        + From<<Self::List as TryFrom<Vec<Row>>>::Error>,
 {
     /// The type expected for the [`Self::ResourceObject<Self::Data>`] type. Must implement;
     /// [`ObjectType<Self::Data>`], [`PsqlType`], [`PsqlSearch`],
@@ -45,49 +65,212 @@ where
     type ResourceObject;
     /// The type expected for `Data` structs. Must implement; [`GrpcDataObjectType`], `TryFrom<[Row]>`
     type Data;
-    /// The type expected for `Object` structs. Must implement; `From<[Self::ResourceObject]>`
+    /// The type expected for `Object` structs. Must implement; `From<[Self::ResourceObject]>`, `TryFrom<[Row]>`, [`prost::Message`], [`serde::Serialize`], [`Default`], [`Clone`], [`Send`], [`Sync`]
     type Object;
     /// The type expected for `UpdateObject` structs. Must implement; [`Send`]
     type UpdateObject;
-    /// The type expected for `List` structs. Must implement `TryFrom<[Vec<Row>]>`
+    /// The type expected for `List` structs. Must implement `TryFrom<[Vec<Row>]>`, [`GrpcSearchResultList`]
     type List;
     /// The type expected for `Response` structs. Must implement; `From<[GenericResourceResult<Self::ResourceObject, Self::Data>]>`
     type Response;
+    /// The type expected for `InsertBatchRequest` structs. Must implement; [`GrpcInsertBatchRequest<Self::Data>`], [`Send`]
+    type InsertBatchRequest;
+    /// The type expected for `ChangeEvent` structs. Must implement; `From<[ChangeNotification<Self::Object>]>`, [`Send`]
+    type ChangeEvent;
+
+    /// Returns the broadcast channel used to send [`ChangeNotification`]s to all `watch` streams of the resource
+    fn get_change_sender() -> &'static broadcast::Sender<ChangeNotification<Self::Object>>;
+
+    /// Sends a [`ChangeNotification`] for the record with the provided id to all `watch` streams.
+    ///
+    /// The record's current data will be included if the record can still be found in the database.
+    /// Nothing will be sent if there are no active `watch` streams.
+    async fn publish_change(change_type: ChangeType, id: &Uuid) {
+        let sender = Self::get_change_sender();
+        if sender.receiver_count() == 0 {
+            return;
+        }
+
+        let object = Self::get_change_object(id).await;
+        // Sending only fails if all receivers have been dropped in the meantime
+        let _ = sender.send(ChangeNotification {
+            change_type,
+            id: id.to_string(),
+            object,
+        });
+    }
+
+    /// Returns the record with the provided id as [`Self::Object`] to be included in a
+    /// [`ChangeNotification`], archived records included.
+    ///
+    /// Returns [`None`] if no active `watch` streams exist, or if the record could not be found or converted.
+    async fn get_change_object(id: &Uuid) -> Option<Self::Object> {
+        if Self::get_change_sender().receiver_count() == 0 {
+            return None;
+        }
+
+        match Self::ResourceObject::find_by_id(id, true).await {
+            Ok(Some(row)) => match Self::Object::try_from(row) {
+                Ok(object) => Some(object),
+                Err(e) => {
+                    grpc_warn!(
+                        "Could not convert [{}] row for change notification: {}",
+                        Self::ResourceObject::get_psql_table(),
+                        Status::from(e)
+                    );
+                    None
+                }
+            },
+            Ok(None) => None,
+            Err(e) => {
+                grpc_warn!(
+                    "Could not get [{}] with id [{}] for change notification: {}",
+                    Self::ResourceObject::get_psql_table(),
+                    id,
+                    e
+                );
+                None
+            }
+        }
+    }
+
+    /// Sends a [`ChangeType::Delete`] [`ChangeNotification`] to all `watch` streams for each of the
+    /// provided rows, which have been removed from the database (purged for example).
+    fn publish_removed(removed: Vec<(Uuid, Row)>) {
+        for (id, row) in removed {
+            Self::publish_row(ChangeType::Delete, &id, row);
+        }
+    }
+
+    /// Sends a [`ChangeNotification`] for the provided row to all `watch` streams, without
+    /// reading the record from the database again.
+    ///
+    /// Nothing will be sent if there are no active `watch` streams.
+    fn publish_row(change_type: ChangeType, id: &Uuid, row: Row) {
+        let sender = Self::get_change_sender();
+        if sender.receiver_count() == 0 {
+            return;
+        }
+
+        let object = match Self::Object::try_from(row) {
+            Ok(object) => Some(object),
+            Err(e) => {
+                grpc_warn!(
+                    "Could not convert [{}] row for change notification: {}",
+                    Self::ResourceObject::get_psql_table(),
+                    Status::from(e)
+                );
+                None
+            }
+        };
+        // Sending only fails if all receivers have been dropped in the meantime
+        let _ = sender.send(ChangeNotification {
+            change_type,
+            id: id.to_string(),
+            object,
+        });
+    }
+
+    /// Checks if the record of the provided [`ChangeNotification`] matches the given filter.
+    ///
+    /// The filter is applied in memory to the object included in the notification, combining the
+    /// filter options the same way as the `search` query does. Geo operators can't be applied in
+    /// memory and are rejected. Notifications without an object will only match an empty filter.
+    /// Archived objects only match if `include_archived` is set, unless the change is a delete.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ArrErr`] if the object could not be converted to json or if the filter is invalid.
+    fn change_matches_filter(
+        filter: &AdvancedSearchFilter,
+        notification: &ChangeNotification<Self::Object>,
+    ) -> Result<bool, ArrErr> {
+        if filter.filters.is_empty() {
+            return Ok(true);
+        }
+
+        let object = match &notification.object {
+            Some(object) => object,
+            None => return Ok(false),
+        };
+        let value = serde_json::to_value(object)
+            .map_err(|e| ArrErr::Error(format!("Could not convert object to json value: {}", e)))?;
+
+        // Deleted records are archived, but we still want to report their deletion
+        let include_archived =
+            filter.include_archived || notification.change_type == ChangeType::Delete;
+        if !include_archived && !value["deleted_at"].is_null() {
+            return Ok(false);
+        }
+
+        let collected =
+            search::filter_for_options(&filter.filters, &vec![value]).map_err(ArrErr::Error)?;
+        Ok(!collected.is_empty())
+    }
 
     /// Returns a [`tonic`] gRCP [`Response`] containing an object of provided type [`Self::Object`].
     /// `Self::Object` will contain the record data found for the provided [`Id`].
     ///
+    /// Archived records will only be returned if `include_archived` is set for the provided [`Id`],
+    /// in which case the object's `deleted_at` field will be set.
+    ///
+    /// Objects are cached if caching is enabled for the resource, see [`crate::cache`].
+    ///
     /// # Errors
     ///
     /// Returns [`Status`] with [`Code::NotFound`] if no record is returned from the database.  
+    /// Returns [`Status`] with [`Code::Internal`] if any error is returned from a db call.  
     /// Returns [`Status`] with [`Code::Internal`] if the provided Id can not be converted to a [`lib_common::uuid::Uuid`].  
     /// Returns [`Status`] with [`Code::Internal`] if the resulting [`Row`] data could not be converted into [`Self::Object`].  
+    ///
+    /// The record's current `updated_at` value, if any, is returned in the [`UPDATED_AT_METADATA_KEY`](super::UPDATED_AT_METADATA_KEY) response metadata.
     async fn generic_get_by_id(
         &self,
         request: Request<Id>,
     ) -> Result<Response<Self::Object>, Status> {
         let id: Id = request.into_inner();
-        let mut resource: Self::ResourceObject = id.clone().into();
+        let resource: Self::ResourceObject = id.clone().into();
+        let uuid = resource.try_get_uuid()?;
 
-        let data: Self::Data = Self::ResourceObject::get_by_id(&resource.try_get_uuid()?)
+        let table = Self::ResourceObject::get_psql_table();
+        let key = cache::get_object_key(&uuid, id.include_archived);
+        if let Some((object, updated_at)) = cache::get_object(&table, &key).await {
+            let mut response = Response::new(object);
+            set_updated_at_metadata(&mut response, updated_at);
+            return Ok(response);
+        }
+
+        // Read before the record, so the object won't be cached if the record changes meanwhile
+        let generation = cache::get_generation(&table).await;
+        let row = Self::ResourceObject::find_by_id(&uuid, id.include_archived)
             .await
             .map_err(|e| {
                 grpc_error!(
-                    "No [{}] found for specified uuid [{:?}]: {}",
+                    "Could not get [{}] for specified uuid [{:?}]: {}",
                     Self::ResourceObject::get_psql_table(),
                     id.id,
                     e
                 );
+                Status::new(Code::Internal, "Could not get resource for the provided id")
+            })?
+            .ok_or_else(|| {
+                grpc_error!(
+                    "No [{}] found for specified uuid [{:?}].",
+                    Self::ResourceObject::get_psql_table(),
+                    id.id
+                );
                 Status::new(
                     Code::NotFound,
                     "Could not find any resource for the provided id",
                 )
-            })?
-            .try_into()?;
-
-        resource.set_data(data);
+            })?;
+        let updated_at = get_updated_at(&row);
+        let object: Self::Object = row.try_into()?;
+        cache::set_object(&table, &key, &object, updated_at, generation).await;
 
-        Ok(Response::new(resource.into()))
+        let mut response = Response::new(object);
+        set_updated_at_metadata(&mut response, updated_at);
+        Ok(response)
     }
     /// Returns a [`tonic`] gRCP [`Response`] containing an object of provided type [`Self::Object`].
     /// `Self::Object`(TryFrom\<Vec\<Row\>\>) will contain all records found in the database using the the provided [`AdvancedSearchFilter`].
@@ -104,7 +287,52 @@ where
         request: Request<AdvancedSearchFilter>,
     ) -> Result<Response<Self::List>, Status> {
         let filter: AdvancedSearchFilter = request.into_inner();
-        let rows = Self::ResourceObject::advanced_search(filter)
+        let rows = Self::ResourceObject::advanced_search(filter.clone())
+            .await
+            .map_err(|e| {
+                let error = "Something went wrong trying to retrieve values from the database";
+                grpc_error!(
+                    "{} for [{}]: {}",
+                    error,
+                    Self::ResourceObject::get_psql_table(),
+                    e
+                );
+                Status::new(Code::Internal, error)
+            })?;
+
+        let paging_info = Self::ResourceObject::get_search_paging_info(&filter, &rows)
+            .await
+            .map_err(|e| {
+                let error = "Something went wrong trying to determine the paging information";
+                grpc_error!(
+                    "{} for [{}]: {}",
+                    error,
+                    Self::ResourceObject::get_psql_table(),
+                    e
+                );
+                Status::new(Code::Internal, error)
+            })?;
+
+        let mut list: Self::List = rows.try_into()?;
+        list.set_paging_info(paging_info);
+        Ok(Response::new(list))
+    }
+
+    /// Returns a [`tonic`] gRCP [`Response`] containing a stream of objects of provided type [`Self::Object`].
+    /// The stream will yield all records found in the database using the the provided [`AdvancedSearchFilter`].
+    ///
+    /// Rows are converted and sent one at a time, so the result set is never held in memory as a whole.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Status`] with [`Code::Internal`] if any error is returned from the db search result.  
+    /// The stream yields a [`Status`] with [`Code::Internal`] if a [`Row`] could not be read or converted into [`Self::Object`].  
+    async fn generic_search_stream(
+        &self,
+        request: Request<AdvancedSearchFilter>,
+    ) -> Result<Response<GrpcSearchStream<Self::Object>>, Status> {
+        let filter: AdvancedSearchFilter = request.into_inner();
+        let rows = Self::ResourceObject::advanced_search_stream(filter)
             .await
             .map_err(|e| {
                 let error = "Something went wrong trying to retrieve values from the database";
@@ -117,7 +345,71 @@ where
                 Status::new(Code::Internal, error)
             })?;
 
-        Ok(Response::new(rows.try_into()?))
+        let stream =
+            rows.map(|row| -> Result<Self::Object, Status> { Ok(Self::Object::try_from(row?)?) });
+        Ok(Response::new(Box::pin(stream)))
+    }
+
+    /// Returns a [`tonic`] gRCP [`Response`] containing a stream of change events of provided type [`Self::ChangeEvent`].
+    /// The stream will yield an event for every insert, update and delete of a record matching the provided [`AdvancedSearchFilter`].
+    ///
+    /// Only changes made after the stream has been opened are sent. The stream stays open until the client disconnects.
+    ///
+    /// # Errors
+    ///
+    /// The stream yields a [`Status`] with [`Code::DataLoss`] if the client could not keep up and change events have been dropped.  
+    /// The stream yields a [`Status`] with [`Code::Internal`] if the filter could not be applied.  
+    async fn generic_watch(
+        &self,
+        request: Request<AdvancedSearchFilter>,
+    ) -> Result<Response<GrpcWatchStream<Self::ChangeEvent>>, Status>
+    where
+        Self: 'static,
+    {
+        let filter: AdvancedSearchFilter = request.into_inner();
+        let receiver = Self::get_change_sender().subscribe();
+
+        // The stream ends after an error, by setting the receiver to `None`
+        let stream = futures::stream::unfold(Some(receiver), move |receiver| {
+            let filter = filter.clone();
+            async move {
+                let mut receiver = receiver?;
+                loop {
+                    let notification = match receiver.recv().await {
+                        Ok(notification) => notification,
+                        Err(RecvError::Lagged(count)) => {
+                            let error = format!(
+                                "Watch stream could not keep up, [{}] change events have been dropped",
+                                count
+                            );
+                            grpc_warn!(
+                                "{} for [{}].",
+                                error,
+                                Self::ResourceObject::get_psql_table()
+                            );
+                            return Some((Err(Status::data_loss(error)), None));
+                        }
+                        Err(RecvError::Closed) => return None,
+                    };
+
+                    match Self::change_matches_filter(&filter, &notification) {
+                        Ok(true) => return Some((Ok(notification.into()), Some(receiver))),
+                        Ok(false) => continue,
+                        Err(e) => {
+                            let error = "Something went wrong trying to apply the watch filter";
+                            grpc_error!(
+                                "{} for [{}]: {}",
+                                error,
+                                Self::ResourceObject::get_psql_table(),
+                                e
+                            );
+                            return Some((Err(Status::new(Code::Internal, error)), None));
+                        }
+                    }
+                }
+            }
+        });
+        Ok(Response::new(Box::pin(stream)))
     }
 
     /// Returns a [`tonic`] gRCP [`Response`] containing an object of provided type [`Self::Object`].
@@ -140,22 +432,25 @@ where
         grpc_debug!("Inserting with data {:?}", data);
 
         let mut resource: Self::ResourceObject = data.clone().into();
-        let (id, validation_result) = Self::ResourceObject::create(&resource.try_get_data()?)
-            .await
-            .map_err(|e| {
-                let error = "Insert failed, we got an error from the database";
-                grpc_error!(
-                    "{} for [{}]: {}",
-                    error,
-                    Self::ResourceObject::get_psql_table(),
-                    e
-                );
-                Status::new(Code::Internal, error)
-            })?;
+        let (row, validation_result) =
+            Self::ResourceObject::create_returning(&resource.try_get_data()?)
+                .await
+                .map_err(|e| {
+                    let error = "Insert failed, we got an error from the database";
+                    grpc_error!(
+                        "{} for [{}]: {}",
+                        error,
+                        Self::ResourceObject::get_psql_table(),
+                        e
+                    );
+                    Status::new(Code::Internal, error)
+                })?;
 
         if validation_result.success {
-            if let Some(id) = id {
+            if let Some(row) = row {
+                let id: Uuid = row.get(&*Self::ResourceObject::try_get_id_field()?);
                 resource.set_id(id.to_string());
+                Self::publish_row(ChangeType::Insert, &id, row);
             } else {
                 grpc_error!(
                     "No id returned from insert [{}] function.",
@@ -183,16 +478,82 @@ where
         }
     }
 
+    /// Returns a [`tonic`] gRCP [`Response`] containing an [`InsertBatchResponse`] with an [`InsertBatchResult`] for each provided item.
+    ///
+    /// Every item will be validated separately before insert.  
+    /// All valid items will be inserted using a single database transaction. A new UUID will be generated by the database
+    /// for each inserted item and returned as `id` as part of the item's [`InsertBatchResult`].  
+    /// If `all_or_nothing` is set, nothing will be inserted when any of the items fails validation.  
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Status`] with [`Code::Internal`] if any error is returned from a db call, no items will be inserted in that case.
+    ///
+    async fn generic_insert_batch(
+        &self,
+        request: Request<Self::InsertBatchRequest>,
+    ) -> Result<Response<InsertBatchResponse>, Status> {
+        let (list, all_or_nothing) = request.into_inner().into_parts();
+        grpc_debug!(
+            "Inserting [{}] items with all_or_nothing [{}]",
+            list.len(),
+            all_or_nothing
+        );
+
+        let results = Self::ResourceObject::create_batch_returning(&list, all_or_nothing)
+            .await
+            .map_err(|e| {
+                let error = "Batch insert failed, we got an error from the database";
+                grpc_error!(
+                    "{} for [{}]: {}",
+                    error,
+                    Self::ResourceObject::get_psql_table(),
+                    e
+                );
+                Status::new(Code::Internal, error)
+            })?;
+
+        let id_field = Self::ResourceObject::try_get_id_field()?;
+        let mut inserted_count = 0;
+        let mut items = vec![];
+        for (row, validation_result) in results {
+            let id = match row {
+                Some(row) => {
+                    let id: Uuid = row.get(&*id_field);
+                    inserted_count += 1;
+                    Self::publish_row(ChangeType::Insert, &id, row);
+                    Some(id.to_string())
+                }
+                None => None,
+            };
+            items.push(InsertBatchResult {
+                id,
+                validation_result: Some(validation_result),
+            });
+        }
+
+        Ok(Response::new(InsertBatchResponse {
+            results: items,
+            inserted_count,
+        }))
+    }
+
     /// Returns a [`tonic`] gRCP [`Response`] containing an object of provided type [`Self::Object`].
     /// `Self::Response`(From<GenericResourceResult<Self::ResourceObject, Self::Data>>) will contain the updated record after saving the provided data [`Self::Data`].
     ///
     /// The given data will be validated before insert.
     /// Any errors found during validation will be added to the [`ValidationResult`](crate::resources::ValidationResult).
     /// A field [`prost_types::FieldMask`] can be provided to restrict updates to specific fields.
+    /// Only the fields listed in the mask will be validated and written to the database.
+    /// An `expected_updated_at` value can be provided to only apply the update if the record's
+    /// current `updated_at` value matches. The new `updated_at` value is returned in the
+    /// [`UPDATED_AT_METADATA_KEY`](super::UPDATED_AT_METADATA_KEY) response metadata.
     ///
     /// # Errors
     ///
     /// Returns [`Status`] with [`Code::Cancelled`] if the [`Request`] doesn't contain any data.  
+    /// Returns [`Status`] with [`Code::InvalidArgument`] if an `expected_updated_at` value is provided for a resource without an `updated_at` field.  
+    /// Returns [`Status`] with [`Code::FailedPrecondition`] if the provided `expected_updated_at` does not match the record's `updated_at` value.  
     /// Returns [`Status`] with [`Code::Internal`] if any error is returned from a db call.  
     /// Returns [`Status`] with [`Code::Internal`] if the provided Id can not be converted to a [`lib_common::uuid::Uuid`].  
     /// Returns [`Status`] with [`Code::Internal`] if the resulting [`Row`] data could not be converted into [`Self::Data`].  
@@ -204,8 +565,12 @@ where
         let req: Self::ResourceObject = request.into_inner().into();
         let id: Id = Id {
             id: req.try_get_id()?,
+            include_archived: false,
         };
         let mut resource: Self::ResourceObject = id.into();
+        resource.set_mask(req.get_mask());
+        check_expected_updated_at::<Self::ResourceObject>(&req.get_expected_updated_at())?;
+        resource.set_expected_updated_at(req.get_expected_updated_at());
 
         let data = match req.get_data() {
             Some(data) => data,
@@ -217,6 +582,14 @@ where
         };
 
         let (data, validation_result) = resource.update(&data).await.map_err(|e| {
+            if let ArrErr::VersionConflict(current) = &e {
+                grpc_info!(
+                    "Update refused for [{}], record has been modified: {}",
+                    Self::ResourceObject::get_psql_table(),
+                    e
+                );
+                return version_conflict_status(current);
+            }
             let error = "Update failed, we got an error from the database";
             grpc_error!(
                 "{} for [{}]: {}",
@@ -228,13 +601,17 @@ where
         })?;
 
         if let Some(data) = data {
+            let updated_at = get_updated_at(&data);
             resource.set_data(data.try_into()?);
+            Self::publish_change(ChangeType::Update, &resource.try_get_uuid()?).await;
             let result = GenericResourceResult {
                 phantom: PhantomData,
                 validation_result,
                 resource: Some(resource),
             };
-            Ok(Response::new(result.into()))
+            let mut response = Response::new(result.into());
+            set_updated_at_metadata(&mut response, updated_at);
+            Ok(response)
         } else {
             let error = "Validation errors returned from update function.";
             grpc_warn!("{}", error);
@@ -249,16 +626,46 @@ where
         }
     }
 
-    /// Takes an [`Id`] to set the matching database record as deleted in the database.
+    /// Takes a [`DeleteRequest`] to set the matching database record as deleted in the database.
+    ///
+    /// An `expected_updated_at` value can be provided to only delete the record if its
+    /// current `updated_at` value matches.
     ///
     /// # Errors
     ///
     /// Returns [`Status`] with [`Code::NotFound`] if no record is returned from the database.  
+    /// Returns [`Status`] with [`Code::InvalidArgument`] if an `expected_updated_at` value is provided for a resource without an `updated_at` field.  
+    /// Returns [`Status`] with [`Code::FailedPrecondition`] if the provided `expected_updated_at` does not match the record's `updated_at` value.  
     /// Returns [`Status`] with [`Code::Internal`] if any error is returned from a db call.  
-    async fn generic_delete(&self, request: Request<Id>) -> Result<Response<()>, Status> {
-        let id: Id = request.into_inner();
-        let resource: Self::ResourceObject = id.into();
+    async fn generic_delete(
+        &self,
+        request: Request<DeleteRequest>,
+    ) -> Result<Response<()>, Status> {
+        let req: DeleteRequest = request.into_inner();
+        check_expected_updated_at::<Self::ResourceObject>(&req.expected_updated_at)?;
+        let mut resource: Self::ResourceObject = Id {
+            id: req.id,
+            include_archived: false,
+        }
+        .into();
+        resource.set_expected_updated_at(req.expected_updated_at);
+        let id = resource.try_get_uuid()?;
+        // Records without a `deleted_at` field are removed, so get them first to include them in the change notification
+        let archivable = Self::ResourceObject::get_definition().has_field("deleted_at");
+        let removed = if archivable {
+            None
+        } else {
+            Self::get_change_object(&id).await
+        };
         resource.delete().await.map_err(|e| {
+            if let ArrErr::VersionConflict(current) = &e {
+                grpc_info!(
+                    "Delete refused for [{}], record has been modified: {}",
+                    Self::ResourceObject::get_psql_table(),
+                    e
+                );
+                return version_conflict_status(current);
+            }
             let error = "Delete failed, we got an error from the database";
             grpc_error!(
                 "{} for [{}]: {}",
@@ -268,16 +675,134 @@ where
             );
             Status::new(Code::Internal, error)
         })?;
+        if archivable {
+            Self::publish_change(ChangeType::Delete, &id).await;
+        } else {
+            // Sending only fails if there are no active `watch` streams
+            let _ = Self::get_change_sender().send(ChangeNotification {
+                change_type: ChangeType::Delete,
+                id: id.to_string(),
+                object: removed,
+            });
+        }
 
         Ok(Response::new(()))
     }
 
+    /// Takes an [`Id`] to restore the matching archived database record by clearing its `deleted_at` value.
+    /// Returns a [`tonic`] gRCP [`Response`] containing the restored object of provided type [`Self::Object`].
+    ///
+    /// The resource's unique fields will be checked against all non archived records before restoring.
+    /// The record's new `updated_at` value, if any, is returned in the [`UPDATED_AT_METADATA_KEY`](super::UPDATED_AT_METADATA_KEY) response metadata.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Status`] with [`Code::Unimplemented`] if the resource does not have a `deleted_at` field.  
+    /// Returns [`Status`] with [`Code::NotFound`] if no record is returned from the database.  
+    /// Returns [`Status`] with [`Code::FailedPrecondition`] if the record is not archived.  
+    /// Returns [`Status`] with [`Code::AlreadyExists`] if restoring the record would violate a unique constraint or result in conflicting timeslot reservations.  
+    /// Returns [`Status`] with [`Code::Internal`] if any error is returned from a db call.  
+    /// Returns [`Status`] with [`Code::Internal`] if the provided Id can not be converted to a [`lib_common::uuid::Uuid`].  
+    /// Returns [`Status`] with [`Code::Internal`] if the resulting [`Row`] data could not be converted into [`Self::Data`].  
+    async fn generic_restore(
+        &self,
+        request: Request<Id>,
+    ) -> Result<Response<Self::Object>, Status> {
+        let id: Id = request.into_inner();
+        let mut resource: Self::ResourceObject = id.clone().into();
+
+        if !Self::ResourceObject::get_definition().has_field("deleted_at") {
+            let error = format!(
+                "Restore is not supported for [{}], resource can not be archived.",
+                Self::ResourceObject::get_psql_table()
+            );
+            grpc_info!("{}", error);
+            return Err(Status::unimplemented(error));
+        }
+
+        Self::ResourceObject::get_by_id(&resource.try_get_uuid()?)
+            .await
+            .map_err(|e| {
+                grpc_error!(
+                    "No [{}] found for specified uuid [{:?}]: {}",
+                    Self::ResourceObject::get_psql_table(),
+                    id.id,
+                    e
+                );
+                Status::new(
+                    Code::NotFound,
+                    "Could not find any resource for the provided id",
+                )
+            })?;
+
+        if !resource.is_archived().await {
+            let error = format!(
+                "Restore refused for [{}] with id [{}], record is not archived.",
+                Self::ResourceObject::get_psql_table(),
+                id.id
+            );
+            grpc_info!("{}", error);
+            return Err(Status::failed_precondition(error));
+        }
+
+        let row = resource.restore().await.map_err(|e| {
+            if let ArrErr::UniqueViolation(fields) = &e {
+                let error = format!(
+                    "Restore refused for [{}], a record with the same [{}] value(s) already exists.",
+                    Self::ResourceObject::get_psql_table(),
+                    fields
+                );
+                grpc_info!("{}", error);
+                return Status::already_exists(error);
+            }
+            if let ArrErr::TimeslotConflict(errors) = &e {
+                let conflicts: Vec<String> = errors
+                    .iter()
+                    .map(|error| format!("{}: {}", error.field, error.error))
+                    .collect();
+                let error = format!(
+                    "Restore refused for [{}], conflicting timeslot reservations found: {}",
+                    Self::ResourceObject::get_psql_table(),
+                    conflicts.join(" ")
+                );
+                grpc_info!("{}", error);
+                return Status::already_exists(error);
+            }
+            let error = "Restore failed, we got an error from the database";
+            grpc_error!(
+                "{} for [{}]: {}",
+                error,
+                Self::ResourceObject::get_psql_table(),
+                e
+            );
+            Status::new(Code::Internal, error)
+        })?;
+        let updated_at = get_updated_at(&row);
+        let data: Self::Data = row.try_into()?;
+
+        resource.set_data(data);
+        Self::publish_change(ChangeType::Update, &resource.try_get_uuid()?).await;
+
+        let mut response = Response::new(resource.into());
+        set_updated_at_metadata(&mut response, updated_at);
+        Ok(response)
+    }
+
     /// Returns ready:true when service is available
+    ///
+    /// The service is considered available when the database can serve queries.
     async fn generic_is_ready(
         &self,
         _request: Request<ReadyRequest>,
     ) -> Result<Response<ReadyResponse>, Status> {
-        let response = ReadyResponse { ready: true };
+        let ready = match psql_readiness().await {
+            Ok(_) => true,
+            Err(e) => {
+                grpc_warn!("Service not ready, database is unavailable: {}", e);
+                false
+            }
+        };
+        let response = ReadyResponse { ready };
         Ok(Response::new(response))
     }
 }
@@ -3,15 +3,21 @@
 use std::collections::HashMap;
 use std::marker::PhantomData;
 
+use futures::StreamExt;
 use lib_common::uuid::Uuid;
 use tokio_postgres::Row;
 use tonic::{Code, Request, Response, Status};
 
+use super::server::audit::AuditAction;
 use super::server::*;
-use super::GrpcDataObjectType;
+use super::{
+    check_expected_updated_at, get_updated_at, set_updated_at_metadata, version_conflict_status,
+    ArrErr, GrpcDataObjectType, GrpcSearchResultList, GrpcSearchStream,
+};
+use crate::cache;
 use crate::postgres::simple_resource::PsqlType as PsqlSimpleType;
 use crate::postgres::simple_resource_linked::{PsqlObjectType, PsqlType};
-use crate::postgres::PsqlSearch;
+use crate::postgres::{psql_readiness, PsqlSearch};
 use crate::resources::base::simple_resource::SimpleResource;
 use crate::resources::base::simple_resource_linked::{GenericResourceResult, SimpleResourceLinked};
 use crate::resources::base::ObjectType;
@@ -42,9 +48,10 @@ where
         + Sync
         + Send,
     <Self as GrpcSimpleServiceLinked>::LinkedData: GrpcDataObjectType + TryFrom<Row>,
-    <Self as GrpcSimpleServiceLinked>::LinkedRowData: GrpcDataObjectType + TryFrom<Row>,
+    <Self as GrpcSimpleServiceLinked>::LinkedRowData:
+        GrpcDataObjectType + TryFrom<Row> + Send + 'static,
     <Self as GrpcSimpleServiceLinked>::LinkedList: TryFrom<Vec<Row>>,
-    <Self as GrpcSimpleServiceLinked>::LinkedRowDataList: TryFrom<Vec<Row>>,
+    <Self as GrpcSimpleServiceLinked>::LinkedRowDataList: TryFrom<Vec<Row>> + GrpcSearchResultList,
     <Self as GrpcSimpleServiceLinked>::LinkedObject: From<Self::LinkedResourceObject>,
     <Self as GrpcSimpleServiceLinked>::LinkedUpdateObject: Send,
     <Self as GrpcSimpleServiceLinked>::LinkedResponse:
@@ -73,6 +80,7 @@ where
     <Self as GrpcSimpleServiceLinked>::OtherData: GrpcDataObjectType + TryFrom<Row>,
     <Self as GrpcSimpleServiceLinked>::OtherList: TryFrom<Vec<Row>>,
     Status: From<<Self::LinkedData as TryFrom<Row>>::Error>
+        + From<<Self::LinkedRowData as TryFrom<Row>>::Error>
         + From<<Self::LinkedRowDataList as TryFrom<Vec<Row>>>::Error>
         + From<<Self::OtherList as TryFrom<Vec<Row>>>::Error>,
 {
@@ -85,7 +93,7 @@ where
     /// Must implement; [`GrpcDataObjectType`], `TryFrom<[Row]>`
     type LinkedData;
     /// The type expected for the RowData struct of the linked resource.
-    /// Must implement; `TryFrom<[Row]>`
+    /// Must implement; `TryFrom<[Row]>`, [`Send`]
     type LinkedRowData;
     /// The type expected for the Object struct of the linked resource.
     /// Must implement; `From<[Self::LinkedResourceObject]>`
@@ -97,7 +105,7 @@ where
     /// Must implement; `TryFrom<[Vec<Row>]>`
     type LinkedList;
     /// The type expected for the RowDataList struct of the linked resource.
-    /// Must implement; `TryFrom<[Vec<Row>]>`
+    /// Must implement; `TryFrom<[Vec<Row>]>`, [`GrpcSearchResultList`]
     type LinkedRowDataList;
     /// The type expected for the Response struct of the linked resource.
     /// Must implement; `TryFrom<[Vec<Row>]>`
@@ -132,6 +140,8 @@ where
     /// Returns [`Status`] with [`Code::NotFound`] if no record is returned from the database.  
     /// Returns [`Status`] with [`Code::Internal`] if the provided Ids can not be converted to valid [`lib_common::uuid::Uuid`]s.  
     /// Returns [`Status`] with [`Code::Internal`] if the resulting [`Row`] data could not be converted into [`Self::LinkedObject`].
+    ///
+    /// The record's current `updated_at` value, if any, is returned in the [`UPDATED_AT_METADATA_KEY`](super::UPDATED_AT_METADATA_KEY) response metadata.
     async fn generic_get_by_id(
         &self,
         request: Request<Ids>,
@@ -139,26 +149,28 @@ where
         let id: Ids = request.into_inner();
         let mut resource: Self::LinkedResourceObject = id.clone().into();
 
-        let data: Self::LinkedData =
-            Self::LinkedResourceObject::get_for_ids(&id.clone().try_into()?)
-                .await
-                .map_err(|e| {
-                    grpc_error!(
-                        "No [{}] found for specified uuids [{:?}]: {}",
-                        Self::ResourceObject::get_psql_table(),
-                        id,
-                        e
-                    );
-                    Status::new(
-                        Code::NotFound,
-                        "Could not find any resource for the provided id",
-                    )
-                })?
-                .try_into()?;
+        let row = Self::LinkedResourceObject::get_for_ids(&id.clone().try_into()?)
+            .await
+            .map_err(|e| {
+                grpc_error!(
+                    "No [{}] found for specified uuids [{:?}]: {}",
+                    Self::ResourceObject::get_psql_table(),
+                    id,
+                    e
+                );
+                Status::new(
+                    Code::NotFound,
+                    "Could not find any resource for the provided id",
+                )
+            })?;
+        let updated_at = get_updated_at(&row);
+        let data: Self::LinkedData = row.try_into()?;
 
         resource.set_data(data);
 
-        Ok(Response::new(resource.into()))
+        let mut response = Response::new(resource.into());
+        set_updated_at_metadata(&mut response, updated_at);
+        Ok(response)
     }
 
     /// Returns a [`tonic`] gRCP [`Response`] containing an object of provided type [`Self::LinkedRowDataList`].
@@ -176,7 +188,7 @@ where
         request: Request<AdvancedSearchFilter>,
     ) -> Result<Response<Self::LinkedRowDataList>, Status> {
         let filter: AdvancedSearchFilter = request.into_inner();
-        let rows = Self::LinkedResourceObject::advanced_search(filter)
+        let rows = Self::LinkedResourceObject::advanced_search(filter.clone())
             .await
             .map_err(|e| {
                 let error = "Something went wrong trying to retrieve values from the database";
@@ -189,7 +201,55 @@ where
                 Status::new(Code::Internal, error)
             })?;
 
-        Ok(Response::new(rows.try_into()?))
+        let paging_info = Self::LinkedResourceObject::get_search_paging_info(&filter, &rows)
+            .await
+            .map_err(|e| {
+                let error = "Something went wrong trying to determine the paging information";
+                grpc_error!(
+                    "{} for [{}]: {}",
+                    error,
+                    Self::ResourceObject::get_psql_table(),
+                    e
+                );
+                Status::new(Code::Internal, error)
+            })?;
+
+        let mut list: Self::LinkedRowDataList = rows.try_into()?;
+        list.set_paging_info(paging_info);
+        Ok(Response::new(list))
+    }
+
+    /// Returns a [`tonic`] gRCP [`Response`] containing a stream of objects of provided type [`Self::LinkedRowData`].
+    /// The stream will yield all records found in the database using the the provided [`AdvancedSearchFilter`].
+    ///
+    /// Rows are converted and sent one at a time, so the result set is never held in memory as a whole.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Status`] with [`Code::Internal`] if any error is returned from the db search result.  
+    /// The stream yields a [`Status`] with [`Code::Internal`] if a [`Row`] could not be read or converted into [`Self::LinkedRowData`].  
+    async fn generic_search_stream(
+        &self,
+        request: Request<AdvancedSearchFilter>,
+    ) -> Result<Response<GrpcSearchStream<Self::LinkedRowData>>, Status> {
+        let filter: AdvancedSearchFilter = request.into_inner();
+        let rows = Self::LinkedResourceObject::advanced_search_stream(filter)
+            .await
+            .map_err(|e| {
+                let error = "Something went wrong trying to retrieve values from the database";
+                grpc_error!(
+                    "{} for [{}]: {}",
+                    error,
+                    Self::ResourceObject::get_psql_table(),
+                    e
+                );
+                Status::new(Code::Internal, error)
+            })?;
+
+        let stream = rows.map(|row| -> Result<Self::LinkedRowData, Status> {
+            Ok(Self::LinkedRowData::try_from(row?)?)
+        });
+        Ok(Response::new(Box::pin(stream)))
     }
 
     /// Returns an empty [`tonic`] gRCP [`Response`] on success
@@ -228,6 +288,7 @@ where
                 resource.try_get_uuid()?,
             )]),
             None,
+            AuditAction::Unlink,
         )
         .await
         .map_err(|e| {
@@ -264,6 +325,7 @@ where
     /// Returns a [`tonic`] gRCP [`Response`] containing an object of provided type [`Self::OtherList`].
     ///
     /// The existence of the provided resource `id` will be validated first.
+    /// Archived linked records will only be returned if `include_archived` is set for the provided [`Id`].
     ///
     /// X: `ResourceObject<other::Data>` Resource type of 'other' resource being linked
     /// Y: `other::Data` Data type of 'other' resource being linked
@@ -283,9 +345,11 @@ where
         Self: Send + 'async_trait,
     {
         let id: Id = request.into_inner();
+        let include_archived = id.include_archived;
         let ids = Self::_get_linked(id).await?;
         let other_id_field = Self::OtherResourceObject::try_get_id_field()?;
-        let filter = AdvancedSearchFilter::search_in(other_id_field, ids);
+        let filter =
+            AdvancedSearchFilter::search_in(other_id_field, ids).include_archived(include_archived);
 
         let rows = Self::OtherResourceObject::advanced_search(filter).await?;
 
@@ -294,23 +358,32 @@ where
 
     /// Internal function used for `generic_get_linked_ids` and `generic_get_linked`
     ///
+    /// The linked ids are cached if caching is enabled for the link table, see [`crate::cache`].
     async fn _get_linked(id: Id) -> Result<Vec<String>, Status> {
         let resource: Self::ResourceObject = id.clone().into();
+        let uuid = resource.try_get_uuid()?;
 
-        Self::ResourceObject::get_by_id(&resource.try_get_uuid()?)
-            .await
-            .map_err(|e| {
-                grpc_error!(
-                    "No [{}] found for specified uuid [{:?}]: {}",
-                    Self::ResourceObject::get_psql_table(),
-                    id.id,
-                    e
-                );
-                Status::new(
-                    Code::NotFound,
-                    "Could not find any resource for the provided id",
-                )
-            })?;
+        let link_table = Self::LinkedResourceObject::get_psql_table();
+        let key = cache::get_linked_key(&uuid);
+        if let Some(linked) = cache::get::<IdList>(&link_table, &key).await {
+            return Ok(linked.ids);
+        }
+
+        // Read before the linked ids, so they won't be cached if the links change meanwhile
+        let generation = cache::get_generation(&link_table).await;
+
+        Self::ResourceObject::get_by_id(&uuid).await.map_err(|e| {
+            grpc_error!(
+                "No [{}] found for specified uuid [{:?}]: {}",
+                Self::ResourceObject::get_psql_table(),
+                id.id,
+                e
+            );
+            Status::new(
+                Code::NotFound,
+                "Could not find any resource for the provided id",
+            )
+        })?;
 
         let id_field = Self::ResourceObject::try_get_id_field().map_err(|e| {
             grpc_error!(
@@ -339,6 +412,7 @@ where
         for row in rows {
             ids.push(row.get::<&str, Uuid>(other_id_field.as_str()).to_string());
         }
+        cache::set(&link_table, &key, &IdList { ids: ids.clone() }, generation).await;
         Ok(ids)
     }
 
@@ -401,10 +475,16 @@ where
     /// The given data will be validated before insert.
     /// Any errors found during validation will be added to the [`ValidationResult`](crate::resources::ValidationResult).
     /// A field [`prost_types::FieldMask`] can be provided to restrict updates to specific fields.
+    /// Only the fields listed in the mask will be validated and written to the database.
+    /// An `expected_updated_at` value can be provided to only apply the update if the record's
+    /// current `updated_at` value matches. The new `updated_at` value is returned in the
+    /// [`UPDATED_AT_METADATA_KEY`](super::UPDATED_AT_METADATA_KEY) response metadata.
     ///
     /// # Errors
     ///
     /// Returns [`Status`] with [`Code::Cancelled`] if the [`Request`] doesn't contain any data.  
+    /// Returns [`Status`] with [`Code::InvalidArgument`] if an `expected_updated_at` value is provided for a resource without an `updated_at` field.  
+    /// Returns [`Status`] with [`Code::FailedPrecondition`] if the provided `expected_updated_at` does not match the record's `updated_at` value.  
     /// Returns [`Status`] with [`Code::Internal`] if any error is returned from a db call.  
     /// Returns [`Status`] with [`Code::Internal`] if the provided Ids can not be converted to valid [`lib_common::uuid::Uuid`]s.  
     /// Returns [`Status`] with [`Code::Internal`] if the resulting [`Row`] data could not be converted into [`Self::ResourceData`].  
@@ -414,6 +494,9 @@ where
         request: Request<Self::LinkedUpdateObject>,
     ) -> Result<Response<Self::LinkedResponse>, Status> {
         let mut resource: Self::LinkedResourceObject = request.into_inner().into();
+        check_expected_updated_at::<Self::LinkedResourceObject>(
+            &resource.get_expected_updated_at(),
+        )?;
 
         let data = match resource.get_data() {
             Some(data) => data,
@@ -428,6 +511,14 @@ where
         };
 
         let (data, validation_result) = resource.update(&data).await.map_err(|e| {
+            if let ArrErr::VersionConflict(current) = &e {
+                grpc_info!(
+                    "Update refused for [{}], record has been modified: {}",
+                    Self::LinkedResourceObject::get_psql_table(),
+                    e
+                );
+                return version_conflict_status(current);
+            }
             let error = "Update failed, we got an error from the database";
             grpc_error!(
                 "{} for [{}]: {}",
@@ -439,13 +530,16 @@ where
         })?;
 
         if let Some(data) = data {
+            let updated_at = get_updated_at(&data);
             resource.set_data(data.try_into()?);
             let result = GenericResourceResult {
                 phantom: PhantomData,
                 validation_result,
                 resource: Some(resource),
             };
-            Ok(Response::new(result.into()))
+            let mut response = Response::new(result.into());
+            set_updated_at_metadata(&mut response, updated_at);
+            Ok(response)
         } else {
             let error = "Validation errors returned from update function.";
             grpc_warn!("{}", error);
@@ -460,15 +554,33 @@ where
         }
     }
 
-    /// Takes an [`Id`] to set the matching database record as deleted in the database.
+    /// Takes a [`LinkedDeleteRequest`] to set the matching database record as deleted in the database.
+    ///
+    /// An `expected_updated_at` value can be provided to only delete the record if its
+    /// current `updated_at` value matches.
     ///
     /// # Errors
     ///
+    /// Returns [`Status`] with [`Code::InvalidArgument`] if an `expected_updated_at` value is provided for a resource without an `updated_at` field.  
+    /// Returns [`Status`] with [`Code::FailedPrecondition`] if the provided `expected_updated_at` does not match the record's `updated_at` value.  
     /// Returns [`Status`] with [`Code::Internal`] if any error is returned from a db call.  
-    async fn generic_delete(&self, request: Request<Ids>) -> Result<Response<()>, Status> {
-        let id: Ids = request.into_inner();
-        let resource: Self::LinkedResourceObject = id.into();
+    async fn generic_delete(
+        &self,
+        request: Request<LinkedDeleteRequest>,
+    ) -> Result<Response<()>, Status> {
+        let req: LinkedDeleteRequest = request.into_inner();
+        check_expected_updated_at::<Self::LinkedResourceObject>(&req.expected_updated_at)?;
+        let mut resource: Self::LinkedResourceObject = Ids { ids: req.ids }.into();
+        resource.set_expected_updated_at(req.expected_updated_at);
         resource.delete().await.map_err(|e| {
+            if let ArrErr::VersionConflict(current) = &e {
+                grpc_info!(
+                    "Delete refused for [{}], record has been modified: {}",
+                    Self::LinkedResourceObject::get_psql_table(),
+                    e
+                );
+                return version_conflict_status(current);
+            }
             let error = "Delete failed, we got an error from the database";
             grpc_error!(
                 "{} for [{}]: {}",
@@ -483,11 +595,20 @@ where
     }
 
     /// Returns ready:true when service is available
+    ///
+    /// The service is considered available when the database can serve queries.
     async fn generic_is_ready(
         &self,
         _request: Request<ReadyRequest>,
     ) -> Result<Response<ReadyResponse>, Status> {
-        let response = ReadyResponse { ready: true };
+        let ready = match psql_readiness().await {
+            Ok(_) => true,
+            Err(e) => {
+                grpc_warn!("Service not ready, database is unavailable: {}", e);
+                false
+            }
+        };
+        let response = ReadyResponse { ready };
         Ok(Response::new(response))
     }
 }
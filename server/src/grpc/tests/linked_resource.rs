@@ -103,9 +103,11 @@ pub async fn generate_test_data() -> (resources::linked::List, resources::resour
     (
         resources::linked::List {
             list: linked_objects,
+            ..Default::default()
         },
         resources::resource::List {
             list: resource_objects,
+            ..Default::default()
         },
     )
 }
@@ -131,7 +133,10 @@ pub async fn check_linked_ids(
     num_expected: usize,
 ) {
     let result = server
-        .get_linked_ids(tonic::Request::new(Id { id: id.to_owned() }))
+        .get_linked_ids(tonic::Request::new(Id {
+            id: id.to_owned(),
+            include_archived: false,
+        }))
         .await;
     ut_debug!("Got linked: {:?}", result);
 
@@ -152,7 +157,10 @@ pub async fn check_linked_ids(
 /// check linked for id
 pub async fn check_linked(server: &GrpcServer, id: &str, existing: &resources::linked::List) {
     let result = server
-        .get_linked(tonic::Request::new(Id { id: id.to_owned() }))
+        .get_linked(tonic::Request::new(Id {
+            id: id.to_owned(),
+            include_archived: false,
+        }))
         .await;
     ut_debug!("Got linked: {:?}", result);
 
@@ -166,7 +174,10 @@ pub async fn check_linked(server: &GrpcServer, id: &str, existing: &resources::l
 /// Remove all links for given id
 pub async fn check_unlink(server: &GrpcServer, id: &str) {
     let result = server
-        .unlink(tonic::Request::new(Id { id: id.to_owned() }))
+        .unlink(tonic::Request::new(Id {
+            id: id.to_owned(),
+            include_archived: false,
+        }))
         .await;
 
     ut_debug!("{:?}", result);
@@ -28,6 +28,11 @@ pub mod resource {
             &self,
             request: tonic::Request<Data>,
         ) -> Result<tonic::Response<Response>, tonic::Status>;
+        /// insert multiple resources at once
+        async fn insert_batch(
+            &self,
+            request: tonic::Request<InsertBatchRequest>,
+        ) -> Result<tonic::Response<crate::resources::InsertBatchResponse>, tonic::Status>;
         /// update resource
         async fn update(
             &self,
@@ -36,13 +41,38 @@ pub mod resource {
         /// delete resource
         async fn delete(
             &self,
-            request: tonic::Request<Id>,
+            request: tonic::Request<crate::resources::DeleteRequest>,
         ) -> Result<tonic::Response<()>, tonic::Status>;
+        /// restore archived resource
+        async fn restore(
+            &self,
+            request: tonic::Request<Id>,
+        ) -> Result<tonic::Response<Object>, tonic::Status>;
         /// search resource
         async fn search(
             &self,
             request: tonic::Request<AdvancedSearchFilter>,
         ) -> Result<tonic::Response<List>, tonic::Status>;
+        /// stream type for search_stream
+        #[allow(non_camel_case_types)]
+        type search_streamStream: futures::Stream<Item = Result<Object, tonic::Status>>
+            + Send
+            + 'static;
+        /// search resource, streaming the results
+        async fn search_stream(
+            &self,
+            request: tonic::Request<AdvancedSearchFilter>,
+        ) -> Result<tonic::Response<Self::search_streamStream>, tonic::Status>;
+        /// stream type for watch
+        #[allow(non_camel_case_types)]
+        type watchStream: futures::Stream<Item = Result<ChangeEvent, tonic::Status>>
+            + Send
+            + 'static;
+        /// watch resource changes
+        async fn watch(
+            &self,
+            request: tonic::Request<AdvancedSearchFilter>,
+        ) -> Result<tonic::Response<Self::watchStream>, tonic::Status>;
         /// check if service is ready
         async fn is_ready(
             &self,
@@ -88,6 +118,11 @@ pub mod linked {
             &self,
             request: tonic::Request<Data>,
         ) -> Result<tonic::Response<Response>, tonic::Status>;
+        /// insert multiple resources at once
+        async fn insert_batch(
+            &self,
+            request: tonic::Request<InsertBatchRequest>,
+        ) -> Result<tonic::Response<crate::resources::InsertBatchResponse>, tonic::Status>;
         /// update resource
         async fn update(
             &self,
@@ -96,13 +131,38 @@ pub mod linked {
         /// delete resource
         async fn delete(
             &self,
-            request: tonic::Request<Id>,
+            request: tonic::Request<crate::resources::DeleteRequest>,
         ) -> Result<tonic::Response<()>, tonic::Status>;
+        /// restore archived resource
+        async fn restore(
+            &self,
+            request: tonic::Request<Id>,
+        ) -> Result<tonic::Response<Object>, tonic::Status>;
         /// search resource
         async fn search(
             &self,
             request: tonic::Request<AdvancedSearchFilter>,
         ) -> Result<tonic::Response<List>, tonic::Status>;
+        /// stream type for search_stream
+        #[allow(non_camel_case_types)]
+        type search_streamStream: futures::Stream<Item = Result<Object, tonic::Status>>
+            + Send
+            + 'static;
+        /// search resource, streaming the results
+        async fn search_stream(
+            &self,
+            request: tonic::Request<AdvancedSearchFilter>,
+        ) -> Result<tonic::Response<Self::search_streamStream>, tonic::Status>;
+        /// stream type for watch
+        #[allow(non_camel_case_types)]
+        type watchStream: futures::Stream<Item = Result<ChangeEvent, tonic::Status>>
+            + Send
+            + 'static;
+        /// watch resource changes
+        async fn watch(
+            &self,
+            request: tonic::Request<AdvancedSearchFilter>,
+        ) -> Result<tonic::Response<Self::watchStream>, tonic::Status>;
         /// check if service is ready
         async fn is_ready(
             &self,
@@ -140,7 +200,18 @@ async fn test_simple_service_scenario() {
     // Check if we can insert a new message
     let new_object = insert_one(&server).await;
 
-    test_not_deleted(&server, 1).await;
+    // Check if we can insert multiple messages at once
+    let batch_ids = test_insert_batch(&server, 3).await;
+    for id in &batch_ids {
+        let _object_from_db = get_by_id(&server, id).await;
+    }
+
+    test_not_deleted(&server, 4).await;
+    test_search_stream(&server, 4).await;
+
+    // Check if we receive change events for new messages
+    test_watch(&server).await;
+    test_watch_matches_search(&server).await;
 
     // Check if we can get a single message based on their id
     let _object_from_db = get_by_id(&server, &new_object.id).await;
@@ -148,8 +219,14 @@ async fn test_simple_service_scenario() {
     // Check if we can update the newly inserted message with new data
     test_update_one(&server, &new_object.id, new_object.data.unwrap()).await;
 
+    // Check if unknown fields in the update mask are refused
+    test_update_unknown_mask_field(&server, &new_object.id).await;
+
     // Check if we can delete the message
     test_delete_one(&server, &new_object.id).await;
+
+    // Check if we can restore the deleted message, but only once
+    test_restore_one(&server, &new_object.id).await;
 }
 
 #[tokio::test]
@@ -194,6 +271,9 @@ async fn test_simple_service_linked_scenario() {
     )
     .await;
 
+    // Check if only the fields provided in the mask will be updated
+    test_update_masked(&server, &resource_list.list[0].id, &linked_list.list[0].id).await;
+
     // Check if we can delete the message
     test_delete_one(&server, &resource_list.list[0].id, &linked_list.list[0].id).await;
 
@@ -293,6 +373,50 @@ async fn test_from_arrerr_to_status() {
     ut_info!("success");
 }
 
+#[tokio::test]
+async fn test_version_conflict_status() {
+    assert_init_done().await;
+    ut_info!("start");
+
+    let current = lib_common::time::Utc::now();
+    let status = version_conflict_status(&current);
+    assert_eq!(status.code(), tonic::Code::FailedPrecondition);
+    assert!(status.message().contains(&current.to_rfc3339()));
+    assert_eq!(
+        status
+            .metadata()
+            .get(UPDATED_AT_METADATA_KEY)
+            .and_then(|value| value.to_str().ok()),
+        Some(current.to_rfc3339().as_str())
+    );
+
+    ut_info!("success");
+}
+
+#[tokio::test]
+async fn test_check_expected_updated_at() {
+    assert_init_done().await;
+    ut_info!("start");
+
+    use crate::resources::base::ResourceObject;
+    type Versioned = ResourceObject<crate::resources::simple_resource::Data>;
+    type Unversioned = ResourceObject<crate::resources::simple_resource_linked_no_archive::Data>;
+
+    let expected_updated_at: Option<Timestamp> = Some(lib_common::time::Utc::now().into());
+
+    // Resources with an `updated_at` field accept an expected version
+    let result = check_expected_updated_at::<Versioned>(&expected_updated_at);
+    assert!(result.is_ok());
+
+    // Resources without an `updated_at` field can not be versioned
+    let result = check_expected_updated_at::<Unversioned>(&expected_updated_at);
+    assert_eq!(result.unwrap_err().code(), tonic::Code::InvalidArgument);
+    let result = check_expected_updated_at::<Unversioned>(&None);
+    assert!(result.is_ok());
+
+    ut_info!("success");
+}
+
 #[tokio::test]
 async fn test_from_grpc_field_to_bytes() {
     assert_init_done().await;
@@ -2,6 +2,8 @@
 
 use crate::grpc::GrpcSimpleService;
 pub use crate::resources::test_util::simple_resource::*;
+use crate::resources::DeleteRequest;
+use futures::TryStreamExt;
 use lib_common::time::{Timestamp, Utc};
 
 /// Dummy rpc service trait used to test grpc server implementation
@@ -17,6 +19,11 @@ pub trait RpcService: Send + Sync + 'static {
         &self,
         request: tonic::Request<Data>,
     ) -> Result<tonic::Response<Response>, tonic::Status>;
+    /// insert multiple resources at once
+    async fn insert_batch(
+        &self,
+        request: tonic::Request<InsertBatchRequest>,
+    ) -> Result<tonic::Response<crate::resources::InsertBatchResponse>, tonic::Status>;
     /// update resource
     async fn update(
         &self,
@@ -25,13 +32,34 @@ pub trait RpcService: Send + Sync + 'static {
     /// delete resource
     async fn delete(
         &self,
-        request: tonic::Request<Id>,
+        request: tonic::Request<DeleteRequest>,
     ) -> Result<tonic::Response<()>, tonic::Status>;
+    /// restore archived resource
+    async fn restore(
+        &self,
+        request: tonic::Request<Id>,
+    ) -> Result<tonic::Response<Object>, tonic::Status>;
     /// search resource
     async fn search(
         &self,
         request: tonic::Request<AdvancedSearchFilter>,
     ) -> Result<tonic::Response<List>, tonic::Status>;
+    /// stream type for search_stream
+    #[allow(non_camel_case_types)]
+    type search_streamStream: futures::Stream<Item = Result<Object, tonic::Status>> + Send + 'static;
+    /// search resource, streaming the results
+    async fn search_stream(
+        &self,
+        request: tonic::Request<AdvancedSearchFilter>,
+    ) -> Result<tonic::Response<Self::search_streamStream>, tonic::Status>;
+    /// stream type for watch
+    #[allow(non_camel_case_types)]
+    type watchStream: futures::Stream<Item = Result<ChangeEvent, tonic::Status>> + Send + 'static;
+    /// watch resource changes
+    async fn watch(
+        &self,
+        request: tonic::Request<AdvancedSearchFilter>,
+    ) -> Result<tonic::Response<Self::watchStream>, tonic::Status>;
     /// check if service is ready
     async fn is_ready(
         &self,
@@ -76,9 +104,44 @@ pub async fn insert_one(server: &GrpcServer) -> Object {
     response.object.unwrap()
 }
 
+/// Insert multiple objects at once
+pub async fn test_insert_batch(server: &GrpcServer, count: usize) -> Vec<String> {
+    let mut list = vec![];
+    for _ in 0..count {
+        list.push(get_valid_data(
+            Uuid::new_v4(),
+            Uuid::new_v4(),
+            Some(Utc::now().into()),
+            Some(Utc::now().into()),
+        ));
+    }
+
+    let result = server
+        .insert_batch(tonic::Request::new(InsertBatchRequest {
+            list,
+            all_or_nothing: true,
+        }))
+        .await;
+    assert!(result.is_ok(), "Expected 'Ok' but got {:?}", result);
+
+    let response = result.unwrap().into_inner();
+    assert_eq!(response.inserted_count as usize, count);
+    assert_eq!(response.results.len(), count);
+
+    let mut ids = vec![];
+    for result in response.results {
+        assert!(result.validation_result.unwrap().success);
+        ids.push(result.id.unwrap());
+    }
+    ids
+}
+
 /// Get object for id
 pub async fn get_by_id(server: &GrpcServer, id: &str) -> Object {
-    let id: Id = Id { id: id.to_owned() };
+    let id: Id = Id {
+        id: id.to_owned(),
+        include_archived: false,
+    };
     let result = server.get_by_id(tonic::Request::new(id)).await;
     assert!(result.is_ok(), "Expected 'Ok' but got {:?}", result);
 
@@ -99,6 +162,117 @@ pub async fn test_not_deleted(server: &GrpcServer, min_expected: usize) {
     assert!(list.list.len() >= min_expected);
 }
 
+/// stream all objects from the server
+pub async fn test_search_stream(server: &GrpcServer, min_expected: usize) {
+    let message_filter = AdvancedSearchFilter::search_is_null("deleted_at".to_owned());
+
+    ut_info!("Starting streaming search");
+    let result = server
+        .search_stream(tonic::Request::new(message_filter))
+        .await;
+    assert!(result.is_ok(), "Expected 'Ok' but got an error");
+
+    let objects: Vec<Object> = result.unwrap().into_inner().try_collect().await.unwrap();
+    assert!(objects.len() >= min_expected);
+}
+
+/// watch for changes and check if an event is received for a newly inserted object
+pub async fn test_watch(server: &GrpcServer) {
+    ut_info!("Starting watch");
+    let result = server
+        .watch(tonic::Request::new(AdvancedSearchFilter::default()))
+        .await;
+    assert!(result.is_ok(), "Expected 'Ok' but got an error");
+    let mut stream = result.unwrap().into_inner();
+
+    let object = insert_one(server).await;
+    let event = tokio::time::timeout(std::time::Duration::from_secs(5), async {
+        while let Some(event) = stream.try_next().await.unwrap() {
+            if event.id == object.id {
+                return Some(event);
+            }
+        }
+        None
+    })
+    .await;
+    assert!(event.is_ok(), "Timeout waiting for change event");
+
+    let event = event.unwrap();
+    assert!(
+        event.is_some(),
+        "Stream closed before receiving change event"
+    );
+
+    let event = event.unwrap();
+    assert_eq!(
+        event.change_type,
+        crate::resources::ChangeType::Insert as i32
+    );
+    assert_eq!(event.object.map(|object| object.id), Some(object.id));
+}
+
+/// watch with a filter combining `AND` and `OR` and check if the same objects are matched as
+/// returned by a search using the same filter
+pub async fn test_watch_matches_search(server: &GrpcServer) {
+    let tag = Uuid::new_v4().to_string();
+    let a = format!("{}_a", tag);
+    let b = format!("{}_b", tag);
+
+    // `string = a OR string = b AND bool = false` should be evaluated as
+    // `string = a OR (string = b AND bool = false)`
+    let filter = AdvancedSearchFilter::search_equals(String::from("string"), a.clone())
+        .or_equals(String::from("string"), b.clone())
+        .and_equals(String::from("bool"), String::from("false"));
+
+    ut_info!("Starting filtered watch");
+    let result = server.watch(tonic::Request::new(filter.clone())).await;
+    assert!(result.is_ok(), "Expected 'Ok' but got an error");
+    let mut stream = result.unwrap().into_inner();
+
+    let mut inserted = vec![];
+    for (string, bool) in [(&a, true), (&b, true), (&b, false)] {
+        let mut data = get_valid_data(
+            Uuid::new_v4(),
+            Uuid::new_v4(),
+            Some(Utc::now().into()),
+            Some(Utc::now().into()),
+        );
+        data.string = string.clone();
+        data.bool = bool;
+
+        let result = server.insert(tonic::Request::new(data)).await;
+        assert!(result.is_ok(), "Expected 'Ok' but got {:?}", result);
+        inserted.push(result.unwrap().into_inner().object.unwrap().id);
+    }
+
+    let result = server.search(tonic::Request::new(filter)).await;
+    assert!(result.is_ok(), "Expected 'Ok' but got {:?}", result);
+    let mut searched: Vec<String> = result
+        .unwrap()
+        .into_inner()
+        .list
+        .into_iter()
+        .map(|object| object.id)
+        .filter(|id| inserted.contains(id))
+        .collect();
+    searched.sort();
+    assert_eq!(searched.len(), 2);
+
+    // Collect events until no more events are received
+    let mut watched: Vec<String> = vec![];
+    while let Ok(Some(event)) =
+        tokio::time::timeout(std::time::Duration::from_secs(2), stream.try_next())
+            .await
+            .map(|event| event.unwrap())
+    {
+        if inserted.contains(&event.id) {
+            watched.push(event.id);
+        }
+    }
+    watched.sort();
+    assert_eq!(watched, searched);
+}
+
 pub async fn test_update_one(server: &GrpcServer, id: &str, data: Data) {
     // Create a timestamp with nanos set to zero since the database doesn't provide any nanos
     // This will make it easier to compare the result later
@@ -127,6 +301,7 @@ pub async fn test_update_one(server: &GrpcServer, id: &str, data: Data) {
         .update(tonic::Request::new(UpdateObject {
             id: id.to_owned(),
             data: Some(new_data.clone()),
+            expected_updated_at: None,
             mask: Some(prost_types::FieldMask {
                 paths: vec![
                     String::from("bool"),
@@ -155,10 +330,63 @@ pub async fn test_update_one(server: &GrpcServer, id: &str, data: Data) {
     assert_eq!(data, new_data);
 }
 
+/// Update with a mask containing an unknown field should be refused with a failed validation result
+pub async fn test_update_unknown_mask_field(server: &GrpcServer, id: &str) {
+    let current = get_by_id(server, id).await.data.unwrap();
+    let mut new_data = current.clone();
+    new_data.string = String::from("updated with unknown mask field");
+
+    let result = server
+        .update(tonic::Request::new(UpdateObject {
+            id: id.to_owned(),
+            data: Some(new_data),
+            expected_updated_at: None,
+            mask: Some(prost_types::FieldMask {
+                paths: vec![String::from("data.string"), String::from("unknown_field")],
+            }),
+        }))
+        .await;
+    assert!(result.is_ok(), "Expected 'Ok' but got {:?}", result);
+    let response: Response = result.unwrap().into_inner();
+    assert!(response.object.is_none());
+    let validation_result = response.validation_result.unwrap();
+    assert!(!validation_result.success);
+    assert_eq!(validation_result.errors.len(), 1);
+    assert_eq!(validation_result.errors[0].field, "unknown_field");
+
+    // Nothing should have been changed
+    let data: Data = get_by_id(server, id).await.data.unwrap();
+    assert_eq!(data, current);
+}
+
 /// Delete for given id
 pub async fn test_delete_one(server: &GrpcServer, id: &str) {
-    let id: Id = Id { id: id.to_owned() };
+    let request = DeleteRequest {
+        id: id.to_owned(),
+        expected_updated_at: None,
+    };
+
+    let result = server.delete(tonic::Request::new(request)).await;
+    assert!(result.is_ok(), "Expected 'Ok' but got {:?}", result);
+}
 
-    let result = server.delete(tonic::Request::new(id)).await;
+/// Restore for given (archived) id, restoring a non archived record should be refused
+pub async fn test_restore_one(server: &GrpcServer, id: &str) {
+    let result = server
+        .restore(tonic::Request::new(Id {
+            id: id.to_owned(),
+            include_archived: false,
+        }))
+        .await;
     assert!(result.is_ok(), "Expected 'Ok' but got {:?}", result);
+    assert_eq!(result.unwrap().into_inner().id, id);
+
+    let result = server
+        .restore(tonic::Request::new(Id {
+            id: id.to_owned(),
+            include_archived: false,
+        }))
+        .await;
+    assert!(result.is_err());
+    assert_eq!(result.unwrap_err().code(), tonic::Code::FailedPrecondition);
 }
@@ -3,6 +3,7 @@
 use crate::grpc::GrpcSimpleServiceLinked;
 use crate::resources;
 use crate::resources::test_util::simple_resource_linked_no_archive::*;
+use crate::resources::LinkedDeleteRequest;
 use lib_common::time::Utc;
 
 use super::linked;
@@ -43,13 +44,23 @@ pub trait RpcServiceLinked: Send + Sync + 'static {
     /// delete resource
     async fn delete(
         &self,
-        request: tonic::Request<Ids>,
+        request: tonic::Request<LinkedDeleteRequest>,
     ) -> Result<tonic::Response<()>, tonic::Status>;
     /// search resource
     async fn search(
         &self,
         request: tonic::Request<AdvancedSearchFilter>,
     ) -> Result<tonic::Response<RowDataList>, tonic::Status>;
+    /// stream type for search_stream
+    #[allow(non_camel_case_types)]
+    type search_streamStream: futures::Stream<Item = Result<RowData, tonic::Status>>
+        + Send
+        + 'static;
+    /// search resource, streaming the results
+    async fn search_stream(
+        &self,
+        request: tonic::Request<AdvancedSearchFilter>,
+    ) -> Result<tonic::Response<Self::search_streamStream>, tonic::Status>;
     /// check if service is ready
     async fn is_ready(
         &self,
@@ -119,9 +130,11 @@ pub async fn generate_test_data() -> (resources::linked::List, resources::simple
     (
         resources::linked::List {
             list: linked_objects,
+            ..Default::default()
         },
         resources::simple_resource::List {
             list: simple_resource_objects,
+            ..Default::default()
         },
     )
 }
@@ -150,7 +163,10 @@ pub async fn check_linked_ids(
     num_expected: usize,
 ) {
     let result = server
-        .get_linked_ids(tonic::Request::new(Id { id: id.to_owned() }))
+        .get_linked_ids(tonic::Request::new(Id {
+            id: id.to_owned(),
+            include_archived: false,
+        }))
         .await;
     ut_debug!("Got linked: {:?}", result);
 
@@ -176,7 +192,10 @@ pub async fn check_linked_ids(
 /// check linked for id
 pub async fn check_linked(server: &GrpcServer, id: &str, existing: &resources::linked::List) {
     let result = server
-        .get_linked(tonic::Request::new(Id { id: id.to_owned() }))
+        .get_linked(tonic::Request::new(Id {
+            id: id.to_owned(),
+            include_archived: false,
+        }))
         .await;
     ut_debug!("Got linked: {:?}", result);
 
@@ -190,7 +209,10 @@ pub async fn check_linked(server: &GrpcServer, id: &str, existing: &resources::l
 // Remove all links for given id
 pub async fn check_unlink(server: &GrpcServer, id: &str) {
     let result = server
-        .unlink(tonic::Request::new(Id { id: id.to_owned() }))
+        .unlink(tonic::Request::new(Id {
+            id: id.to_owned(),
+            include_archived: false,
+        }))
         .await;
     assert!(result.is_ok(), "Expected 'Ok' but got {:?}", result);
 
@@ -252,6 +274,7 @@ pub async fn test_update_one(server: &GrpcServer, resource_id: &str, linked_id:
         .update(tonic::Request::new(UpdateObject {
             ids,
             data: Some(new_data.clone()),
+            expected_updated_at: None,
             mask: Some(prost_types::FieldMask {
                 paths: vec![String::from("test_bool"), String::from("test_string")],
             }),
@@ -268,7 +291,7 @@ pub async fn test_update_one(server: &GrpcServer, resource_id: &str, linked_id:
 
 /// Delete for given id
 pub async fn test_delete_one(server: &GrpcServer, resource_id: &str, linked_id: &str) {
-    let ids = Ids {
+    let request = LinkedDeleteRequest {
         ids: vec![
             FieldValue {
                 field: String::from("simple_resource_id"),
@@ -279,8 +302,9 @@ pub async fn test_delete_one(server: &GrpcServer, resource_id: &str, linked_id:
                 value: linked_id.to_owned(),
             },
         ],
+        expected_updated_at: None,
     };
 
-    let result = server.delete(tonic::Request::new(ids)).await;
+    let result = server.delete(tonic::Request::new(request)).await;
     assert!(result.is_ok(), "Expected 'Ok' but got {:?}", result);
 }
@@ -0,0 +1,520 @@
+//! TLS for the gRPC server
+//!
+//! The gRPC server accepts TLS connections if both [`Config::grpc_tls_cert`] and
+//! [`Config::grpc_tls_key`] are configured. Client certificates are required and verified
+//! against [`Config::grpc_tls_client_ca`] if set (mTLS), which makes the client certificate's
+//! common name available for authentication, see [`crate::auth::layer`].
+//!
+//! The configured files are checked for changes every
+//! [`Config::grpc_tls_reload_interval_secs`], so renewed certificates will be used for new
+//! connections without restarting the server. The current configuration is kept if the changed
+//! files could not be loaded. Reloading is disabled if the interval is set to `0`.
+
+use crate::common::ArrErr;
+use crate::config::Config;
+
+use futures::Stream;
+use std::fmt;
+use std::fs::{self, File};
+use std::io::{self, BufReader};
+use std::sync::{Arc, RwLock};
+use std::time::{Duration, SystemTime};
+use tokio::net::{TcpListener, TcpStream};
+use tokio::sync::mpsc;
+use tokio::time::MissedTickBehavior;
+use tokio_rustls::rustls::server::AllowAnyAuthenticatedClient;
+use tokio_rustls::rustls::{Certificate, PrivateKey, RootCertStore, ServerConfig};
+use tokio_rustls::server::TlsStream;
+use tokio_rustls::TlsAcceptor;
+
+/// Maximum number of connections which completed the TLS handshake, waiting to be served
+const ACCEPT_BACKLOG: usize = 128;
+
+/// Time to wait before accepting new connections after an error
+const ACCEPT_ERROR_DELAY: Duration = Duration::from_millis(100);
+
+/// Time a client gets to complete the TLS handshake before the connection is closed
+const HANDSHAKE_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Paths of the files used to configure TLS for the gRPC server
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TlsFiles {
+    /// path to the certificate (chain) of the server
+    pub cert: String,
+    /// path to the private key of the server certificate
+    pub key: String,
+    /// optional path to the ca certificate used to verify client certificates
+    pub client_ca: Option<String>,
+}
+
+impl TlsFiles {
+    /// Returns the TLS files configured in the provided [`Config`], if any
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ArrErr`] if only one of `grpc_tls_cert` and `grpc_tls_key` is set
+    /// Returns [`ArrErr`] if `grpc_tls_client_ca` is set without a server certificate
+    pub fn from_config(config: &Config) -> Result<Option<Self>, ArrErr> {
+        match (&config.grpc_tls_cert, &config.grpc_tls_key) {
+            (Some(cert), Some(key)) => Ok(Some(Self {
+                cert: cert.clone(),
+                key: key.clone(),
+                client_ca: config.grpc_tls_client_ca.clone(),
+            })),
+            (None, None) if config.grpc_tls_client_ca.is_none() => Ok(None),
+            (None, None) => Err(ArrErr::Error(String::from(
+                "[grpc_tls_client_ca] requires [grpc_tls_cert] and [grpc_tls_key] to be set.",
+            ))),
+            _ => Err(ArrErr::Error(String::from(
+                "Both [grpc_tls_cert] and [grpc_tls_key] must be set to enable TLS.",
+            ))),
+        }
+    }
+
+    /// Loads the certificates and key, returning the resulting server configuration
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ArrErr`] if any of the files could not be read or doesn't contain valid PEM data
+    /// Returns [`ArrErr`] if the key doesn't match the certificate
+    pub fn load(&self) -> Result<ServerConfig, ArrErr> {
+        let certs = read_certs(&self.cert)?;
+        let key = read_key(&self.key)?;
+
+        let builder = ServerConfig::builder().with_safe_defaults();
+        let builder = match &self.client_ca {
+            Some(client_ca) => {
+                let mut roots = RootCertStore::empty();
+                for cert in read_certs(client_ca)? {
+                    roots.add(&cert).map_err(|e| {
+                        ArrErr::Error(format!("Invalid ca certificate in [{}]: {}", client_ca, e))
+                    })?;
+                }
+                builder.with_client_cert_verifier(AllowAnyAuthenticatedClient::new(roots).boxed())
+            }
+            None => builder.with_no_client_auth(),
+        };
+
+        let mut config = builder.with_single_cert(certs, key).map_err(|e| {
+            ArrErr::Error(format!(
+                "Invalid certificate [{}] or key [{}]: {}",
+                self.cert, self.key, e
+            ))
+        })?;
+        // gRPC requires HTTP/2
+        config.alpn_protocols = vec![b"h2".to_vec()];
+        Ok(config)
+    }
+
+    /// Returns the last modification time of each file, [`None`] for files which can't be read
+    fn modified(&self) -> Vec<Option<SystemTime>> {
+        let mut paths = vec![&self.cert, &self.key];
+        if let Some(client_ca) = &self.client_ca {
+            paths.push(client_ca);
+        }
+        paths
+            .into_iter()
+            .map(|path| fs::metadata(path).and_then(|meta| meta.modified()).ok())
+            .collect()
+    }
+}
+
+/// TLS server configuration which is reloaded when the configured files change
+#[derive(Clone)]
+pub struct TlsReloader {
+    files: TlsFiles,
+    config: Arc<RwLock<Arc<ServerConfig>>>,
+}
+
+impl fmt::Debug for TlsReloader {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("TlsReloader")
+            .field("files", &self.files)
+            .finish_non_exhaustive()
+    }
+}
+
+impl TlsReloader {
+    /// Creates a new reloader, loading the provided files
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ArrErr`] if the files could not be loaded, see [`TlsFiles::load`]
+    pub fn new(files: TlsFiles) -> Result<Self, ArrErr> {
+        let config = files.load()?;
+        match &files.client_ca {
+            Some(client_ca) => grpc_info!(
+                "Loaded TLS certificate [{}], client certificates will be verified using [{}].",
+                files.cert,
+                client_ca
+            ),
+            None => grpc_info!("Loaded TLS certificate [{}].", files.cert),
+        }
+        Ok(Self {
+            files,
+            config: Arc::new(RwLock::new(Arc::new(config))),
+        })
+    }
+
+    /// Returns an acceptor using the current server configuration
+    pub fn acceptor(&self) -> TlsAcceptor {
+        let config = match self.config.read() {
+            Ok(config) => config.clone(),
+            Err(e) => e.into_inner().clone(),
+        };
+        TlsAcceptor::from(config)
+    }
+
+    /// Checks the files for changes every `interval`, reloading the server configuration when
+    /// they did. Runs until the task is aborted.
+    ///
+    /// Returns immediately if the `interval` is zero, which disables reloading.
+    pub async fn watch(self, interval: Duration) {
+        if interval.is_zero() {
+            grpc_info!("TLS reload interval is 0, reloading TLS configuration disabled.");
+            return;
+        }
+        let mut interval = tokio::time::interval(interval);
+        interval.set_missed_tick_behavior(MissedTickBehavior::Delay);
+
+        let mut modified = self.files.modified();
+        loop {
+            interval.tick().await;
+            self.reload_if_changed(&mut modified);
+        }
+    }
+
+    /// Reloads the server configuration if any of the files' modification times differs from
+    /// the provided `modified` times, which are updated.
+    /// Returns `true` if a new configuration has been loaded.
+    fn reload_if_changed(&self, modified: &mut Vec<Option<SystemTime>>) -> bool {
+        let current = self.files.modified();
+        if current == *modified {
+            return false;
+        }
+        // Only retry after the next change, the files might still be in the process of being
+        // replaced.
+        *modified = current;
+
+        let config = match self.files.load() {
+            Ok(config) => Arc::new(config),
+            Err(e) => {
+                grpc_error!("Could not reload TLS configuration, keeping current: {}", e);
+                return false;
+            }
+        };
+        match self.config.write() {
+            Ok(mut current) => *current = config,
+            Err(e) => *e.into_inner() = config,
+        }
+        grpc_info!("Reloaded TLS certificate [{}].", self.files.cert);
+        true
+    }
+}
+
+/// Returns a stream of TLS connections accepted on the provided listener, which can be passed
+/// to [`tonic::transport::server::Router::serve_with_incoming_shutdown`].
+///
+/// Each handshake is done in its own task, so slow clients don't block other connections.
+/// Connections which fail the handshake are logged and closed.
+/// Accepting connections stops when the returned stream is dropped.
+pub fn incoming(
+    listener: TcpListener,
+    reloader: TlsReloader,
+) -> impl Stream<Item = Result<TlsStream<TcpStream>, io::Error>> {
+    let (sender, receiver) = mpsc::channel(ACCEPT_BACKLOG);
+    tokio::spawn(accept(listener, reloader, sender));
+
+    futures::stream::unfold(receiver, |mut receiver| async move {
+        receiver.recv().await.map(|stream| (Ok(stream), receiver))
+    })
+}
+
+/// Accepts connections on the listener, sending each connection which completed the TLS
+/// handshake to the provided `sender`
+async fn accept(
+    listener: TcpListener,
+    reloader: TlsReloader,
+    sender: mpsc::Sender<TlsStream<TcpStream>>,
+) {
+    loop {
+        let (stream, addr) = tokio::select! {
+            _ = sender.closed() => return,
+            result = listener.accept() => match result {
+                Ok(connection) => connection,
+                Err(e) => {
+                    grpc_warn!("Could not accept connection: {}", e);
+                    // Prevent a busy loop if the error persists, for example when running out
+                    // of file descriptors
+                    tokio::time::sleep(ACCEPT_ERROR_DELAY).await;
+                    continue;
+                }
+            },
+        };
+
+        let acceptor = reloader.acceptor();
+        let sender = sender.clone();
+        tokio::spawn(async move {
+            match tokio::time::timeout(HANDSHAKE_TIMEOUT, acceptor.accept(stream)).await {
+                Ok(Ok(stream)) => {
+                    // The receiver has been dropped if the server has been shut down
+                    let _ = sender.send(stream).await;
+                }
+                Ok(Err(e)) => grpc_warn!("TLS handshake with [{}] failed: {}", addr, e),
+                Err(_) => grpc_warn!("TLS handshake with [{}] timed out.", addr),
+            }
+        });
+    }
+}
+
+/// Reads all PEM encoded certificates from the provided file
+fn read_certs(path: &str) -> Result<Vec<Certificate>, ArrErr> {
+    let mut reader = open(path)?;
+    let certs = rustls_pemfile::certs(&mut reader).map_err(|e| {
+        ArrErr::Error(format!(
+            "Unable to read certificates from [{}]: {}",
+            path, e
+        ))
+    })?;
+    if certs.is_empty() {
+        return Err(ArrErr::Error(format!(
+            "No certificates found in [{}].",
+            path
+        )));
+    }
+    Ok(certs.into_iter().map(Certificate).collect())
+}
+
+/// Reads the first PEM encoded private key (PKCS#8, PKCS#1 or SEC1) from the provided file
+fn read_key(path: &str) -> Result<PrivateKey, ArrErr> {
+    let mut reader = open(path)?;
+    loop {
+        let item = rustls_pemfile::read_one(&mut reader).map_err(|e| {
+            ArrErr::Error(format!("Unable to read private key from [{}]: {}", path, e))
+        })?;
+        match item {
+            Some(rustls_pemfile::Item::PKCS8Key(key))
+            | Some(rustls_pemfile::Item::RSAKey(key))
+            | Some(rustls_pemfile::Item::ECKey(key)) => return Ok(PrivateKey(key)),
+            Some(_) => continue,
+            None => {
+                return Err(ArrErr::Error(format!(
+                    "No private key found in [{}].",
+                    path
+                )))
+            }
+        }
+    }
+}
+
+/// Opens the provided file for reading
+fn open(path: &str) -> Result<BufReader<File>, ArrErr> {
+    File::open(path)
+        .map(BufReader::new)
+        .map_err(|e| ArrErr::Error(format!("Unable to open [{}]: {}", path, e)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::*;
+    use futures::StreamExt;
+    use openssl::asn1::Asn1Time;
+    use openssl::bn::BigNum;
+    use openssl::hash::MessageDigest;
+    use openssl::nid::Nid;
+    use openssl::pkey::PKey;
+    use openssl::rsa::Rsa;
+    use openssl::x509::extension::SubjectAlternativeName;
+    use openssl::x509::{X509Builder, X509NameBuilder};
+    use std::path::PathBuf;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio_rustls::rustls::{ClientConfig, ServerName};
+    use tokio_rustls::TlsConnector;
+
+    /// Returns a PEM encoded self-signed certificate and key for `localhost`
+    fn get_cert(serial: u32) -> (String, String) {
+        let key = PKey::from_rsa(Rsa::generate(2048).unwrap()).unwrap();
+        let mut name = X509NameBuilder::new().unwrap();
+        name.append_entry_by_nid(Nid::COMMONNAME, "localhost")
+            .unwrap();
+        let name = name.build();
+
+        let mut builder = X509Builder::new().unwrap();
+        builder.set_version(2).unwrap();
+        builder
+            .set_serial_number(&BigNum::from_u32(serial).unwrap().to_asn1_integer().unwrap())
+            .unwrap();
+        builder.set_subject_name(&name).unwrap();
+        builder.set_issuer_name(&name).unwrap();
+        builder.set_pubkey(&key).unwrap();
+        builder
+            .set_not_before(&Asn1Time::days_from_now(0).unwrap())
+            .unwrap();
+        builder
+            .set_not_after(&Asn1Time::days_from_now(1).unwrap())
+            .unwrap();
+        let san = SubjectAlternativeName::new()
+            .dns("localhost")
+            .build(&builder.x509v3_context(None, None))
+            .unwrap();
+        builder.append_extension(san).unwrap();
+        builder.sign(&key, MessageDigest::sha256()).unwrap();
+
+        (
+            String::from_utf8(builder.build().to_pem().unwrap()).unwrap(),
+            String::from_utf8(key.private_key_to_pem_pkcs8().unwrap()).unwrap(),
+        )
+    }
+
+    /// Writes a new certificate and key to a temporary directory named after the test
+    fn get_files(test: &str) -> TlsFiles {
+        let dir: PathBuf = std::env::temp_dir().join(format!("svc-storage-tls-{}", test));
+        fs::create_dir_all(&dir).unwrap();
+        let files = TlsFiles {
+            cert: dir.join("server.crt").to_string_lossy().into_owned(),
+            key: dir.join("server.key").to_string_lossy().into_owned(),
+            client_ca: None,
+        };
+        let (cert, key) = get_cert(1);
+        fs::write(&files.cert, cert).unwrap();
+        fs::write(&files.key, key).unwrap();
+        files
+    }
+
+    #[tokio::test]
+    async fn test_tls_files_from_config() {
+        assert_init_done().await;
+        ut_info!("start");
+
+        let mut config = Config::default();
+        assert_eq!(TlsFiles::from_config(&config).unwrap(), None);
+
+        config.grpc_tls_cert = Some(String::from("server.crt"));
+        assert!(TlsFiles::from_config(&config).is_err());
+
+        config.grpc_tls_key = Some(String::from("server.key"));
+        config.grpc_tls_client_ca = Some(String::from("ca.crt"));
+        assert_eq!(
+            TlsFiles::from_config(&config).unwrap(),
+            Some(TlsFiles {
+                cert: String::from("server.crt"),
+                key: String::from("server.key"),
+                client_ca: Some(String::from("ca.crt")),
+            })
+        );
+
+        config.grpc_tls_cert = None;
+        config.grpc_tls_key = None;
+        assert!(TlsFiles::from_config(&config).is_err());
+
+        ut_info!("success");
+    }
+
+    #[tokio::test]
+    async fn test_tls_files_load() {
+        assert_init_done().await;
+        ut_info!("start");
+
+        let mut files = get_files("load");
+        let config = files.load().unwrap();
+        assert_eq!(config.alpn_protocols, vec![b"h2".to_vec()]);
+
+        // Client certificates can be verified using the self-signed certificate as ca
+        files.client_ca = Some(files.cert.clone());
+        assert!(files.load().is_ok());
+
+        // The key doesn't contain a certificate
+        files.client_ca = Some(files.key.clone());
+        assert!(files.load().is_err());
+
+        files.client_ca = None;
+        files.key = String::from("/does/not/exist.key");
+        assert!(files.load().is_err());
+
+        ut_info!("success");
+    }
+
+    #[tokio::test]
+    async fn test_tls_reloader() {
+        assert_init_done().await;
+        ut_info!("start");
+
+        let files = get_files("reload");
+        let reloader = TlsReloader::new(files.clone()).unwrap();
+        let mut modified = files.modified();
+        assert!(!reloader.reload_if_changed(&mut modified));
+
+        // A new certificate is loaded once the files have been changed
+        let (cert, key) = get_cert(2);
+        fs::write(&files.cert, cert).unwrap();
+        fs::write(&files.key, key).unwrap();
+        let later = SystemTime::now() + Duration::from_secs(1);
+        File::options()
+            .write(true)
+            .open(&files.cert)
+            .unwrap()
+            .set_modified(later)
+            .unwrap();
+        assert!(reloader.reload_if_changed(&mut modified));
+        assert!(!reloader.reload_if_changed(&mut modified));
+
+        // The current configuration is kept if the changed files are invalid
+        let current = reloader.config.read().unwrap().clone();
+        fs::write(&files.key, "invalid").unwrap();
+        File::options()
+            .write(true)
+            .open(&files.key)
+            .unwrap()
+            .set_modified(later + Duration::from_secs(1))
+            .unwrap();
+        assert!(!reloader.reload_if_changed(&mut modified));
+        assert!(Arc::ptr_eq(&current, &reloader.config.read().unwrap()));
+
+        // A zero interval disables reloading instead of panicking
+        reloader.watch(Duration::ZERO).await;
+
+        ut_info!("success");
+    }
+
+    #[tokio::test]
+    async fn test_incoming() {
+        assert_init_done().await;
+        ut_info!("start");
+
+        let files = get_files("incoming");
+        let reloader = TlsReloader::new(files.clone()).unwrap();
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let mut incoming = Box::pin(incoming(listener, reloader));
+
+        let mut roots = RootCertStore::empty();
+        for cert in read_certs(&files.cert).unwrap() {
+            roots.add(&cert).unwrap();
+        }
+        let mut client_config = ClientConfig::builder()
+            .with_safe_defaults()
+            .with_root_certificates(roots)
+            .with_no_client_auth();
+        client_config.alpn_protocols = vec![b"h2".to_vec()];
+        let connector = TlsConnector::from(Arc::new(client_config));
+
+        let client = tokio::spawn(async move {
+            let stream = TcpStream::connect(addr).await.unwrap();
+            let domain = ServerName::try_from("localhost").unwrap();
+            let mut stream = connector.connect(domain, stream).await.unwrap();
+            stream.write_all(b"ping").await.unwrap();
+            stream.flush().await.unwrap();
+            stream
+        });
+
+        let mut stream = incoming.next().await.unwrap().unwrap();
+        assert_eq!(stream.get_ref().1.alpn_protocol(), Some(&b"h2"[..]));
+        let mut buf = [0u8; 4];
+        stream.read_exact(&mut buf).await.unwrap();
+        assert_eq!(&buf, b"ping");
+        client.await.unwrap();
+
+        ut_info!("success");
+    }
+}
@@ -4,11 +4,15 @@
 #[macro_use]
 pub mod test_util;
 
+pub mod auth;
+pub mod cache;
 pub mod common;
 pub mod config;
 pub mod grpc;
+pub mod metrics;
 pub mod postgres;
 pub mod resources;
+pub mod telemetry;
 
 pub use crate::config::Config;
 pub use clap::Parser;
@@ -25,6 +29,12 @@ pub struct Cli {
     /// Indicates if we should rebuild the database. If not found, defaults to false
     #[arg(long)]
     pub rebuild_psql: Option<bool>,
+    /// Indicates if we should run pending database migrations. If not found, defaults to false
+    #[arg(long)]
+    pub migrate: Option<bool>,
+    /// Indicates if we should purge archived records which exceeded their retention period. If not found, defaults to false
+    #[arg(long)]
+    pub purge: Option<bool>,
 }
 impl Copy for Cli {}
 
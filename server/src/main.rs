@@ -20,8 +20,13 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
 
     info!("(main) Server startup.");
 
+    // Traces will only be exported if an OTLP endpoint is configured
+    telemetry::init(&config)?;
+
     // Allow options for psql init or and/ or recreation
     // locally: cargo run -- --init-psql true
+    // or to apply pending migrations: cargo run -- --migrate true
+    // or to purge expired archived records: cargo run -- --purge true
     let args = Cli::parse();
     if let Some(rebuild_psql) = args.rebuild_psql {
         if rebuild_psql {
@@ -38,12 +43,37 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
             info!("(main) PSQL Database creation completed.");
         }
     }
+    if let Some(migrate) = args.migrate {
+        if migrate {
+            info!("(main) Found argument [migrate]. Running database migrations now...");
+            #[cfg(not(feature = "stub_backends"))]
+            postgres::migrations::migrate().await?;
+            info!("(main) PSQL Database migrations completed.");
+        }
+    }
+    if let Some(purge) = args.purge {
+        if purge {
+            info!("(main) Found argument [purge]. Purging archived records now...");
+            #[cfg(not(feature = "stub_backends"))]
+            postgres::purge::purge(&config.purge_retention_days).await?;
+            info!("(main) PSQL purge completed.");
+        }
+    }
+
+    // Caching is disabled if no cache backend is configured
+    cache::init(&config).await?;
+
+    // Start metrics server
+    tokio::spawn(metrics::server::metrics_server(config.clone(), None));
 
     // Start GRPC Server
     tokio::spawn(grpc::server::grpc_server(config, None)).await?;
 
     info!("(main) Server shutdown.");
 
+    // Make sure all remaining spans are exported before shutdown
+    telemetry::shutdown();
+
     // Make sure all log message are written/ displayed before shutdown
     log::logger().flush();
 
@@ -0,0 +1,287 @@
+//! gRPC request metrics
+//!
+//! Provides a [`tower::Layer`] which can be added to the tonic server to record the number of
+//! requests, the request latency and the returned gRPC status codes per service and method.
+//!
+//! Only the services registered with the layer are used as label values, requests to other
+//! services or to methods not provided by a registered service are recorded as [`UNKNOWN_LABEL`],
+//! so clients can't create an unbounded number of metrics.
+
+use super::{GRPC_REQUESTS, GRPC_REQUEST_DURATION, GRPC_RESPONSES};
+
+use futures::future::BoxFuture;
+use hyper::{HeaderMap, Request, Response};
+use std::collections::HashSet;
+use std::sync::Arc;
+use std::task::{Context, Poll};
+use std::time::Instant;
+use tonic::Code;
+use tower::{Layer, Service};
+
+/// Service and method label used for requests to unknown services or methods
+pub const UNKNOWN_LABEL: &str = "unknown";
+
+/// [`Layer`] wrapping gRPC services with [`GrpcMetrics`]
+///
+/// # Examples
+/// ```
+/// use svc_storage::metrics::layer::GrpcMetricsLayer;
+/// pub async fn server() {
+///     let (_, health_service) = tonic_health::server::health_reporter();
+///     tonic::transport::Server::builder()
+///         .layer(GrpcMetricsLayer::new(["grpc.health.v1.Health"]))
+///         .add_service(health_service)
+///         .serve("0.0.0.0:50051".parse().unwrap());
+/// }
+/// ```
+#[derive(Debug, Clone)]
+pub struct GrpcMetricsLayer {
+    services: Arc<HashSet<String>>,
+}
+
+impl GrpcMetricsLayer {
+    /// Creates a new layer for the provided service names (see
+    /// [`NamedService::NAME`](tonic::server::NamedService::NAME)).
+    /// Requests to any other service will be recorded as [`UNKNOWN_LABEL`].
+    pub fn new<'a>(services: impl IntoIterator<Item = &'a str>) -> Self {
+        Self {
+            services: Arc::new(services.into_iter().map(String::from).collect()),
+        }
+    }
+}
+
+impl<S> Layer<S> for GrpcMetricsLayer {
+    type Service = GrpcMetrics<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        GrpcMetrics {
+            inner,
+            services: self.services.clone(),
+        }
+    }
+}
+
+/// [`Service`] recording metrics for each gRPC request passed to the inner service
+///
+/// The latency is measured until the response headers are returned. Streaming responses are
+/// counted with the status code found in their headers, which will be `Ok` unless the request
+/// failed before the stream was started.
+///
+/// Requests are recorded once the response headers are returned, since tonic only reports unknown
+/// methods of a registered service by responding with [`Code::Unimplemented`].
+#[derive(Debug, Clone)]
+pub struct GrpcMetrics<S> {
+    inner: S,
+    services: Arc<HashSet<String>>,
+}
+
+impl<S, ReqBody, ResBody> Service<Request<ReqBody>> for GrpcMetrics<S>
+where
+    S: Service<Request<ReqBody>, Response = Response<ResBody>>,
+    S::Future: Send + 'static,
+{
+    type Response = S::Response;
+    type Error = S::Error;
+    type Future = BoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, request: Request<ReqBody>) -> Self::Future {
+        let (service, method) = split_path(request.uri().path());
+        let (service, method) = match self.services.contains(&service) {
+            true => (service, method),
+            false => (String::from(UNKNOWN_LABEL), String::from(UNKNOWN_LABEL)),
+        };
+        let start = Instant::now();
+
+        let future = self.inner.call(request);
+        Box::pin(async move {
+            let response = future.await;
+            let duration = start.elapsed().as_secs_f64();
+
+            let code = match &response {
+                Ok(response) => get_status_code(response.headers()),
+                Err(_) => Code::Unknown,
+            };
+            let method = match code {
+                Code::Unimplemented => String::from(UNKNOWN_LABEL),
+                _ => method,
+            };
+            let labels = [service.as_str(), method.as_str()];
+            GRPC_REQUESTS.with_label_values(&labels).inc();
+            GRPC_REQUEST_DURATION
+                .with_label_values(&labels)
+                .observe(duration);
+
+            let code = format!("{:?}", code);
+            GRPC_RESPONSES
+                .with_label_values(&[service.as_str(), method.as_str(), code.as_str()])
+                .inc();
+            response
+        })
+    }
+}
+
+/// Splits a gRPC request path (`/{service}/{method}`) into its service and method name
+pub(crate) fn split_path(path: &str) -> (String, String) {
+    match path.trim_start_matches('/').split_once('/') {
+        Some((service, method)) => (service.to_owned(), method.to_owned()),
+        None => (path.trim_start_matches('/').to_owned(), String::from("")),
+    }
+}
+
+/// Returns the gRPC status code found in the provided response headers.
+/// Tonic only adds a `grpc-status` header to responses if the request failed, the status code
+/// of successful responses is sent in the trailers, so [`Code::Ok`] is returned if no header
+/// is found.
+pub(crate) fn get_status_code(headers: &HeaderMap) -> Code {
+    match headers.get("grpc-status") {
+        Some(value) => match value.to_str().ok().and_then(|value| value.parse().ok()) {
+            Some(code) => Code::from_i32(code),
+            None => Code::Unknown,
+        },
+        None => Code::Ok,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::*;
+    use hyper::Body;
+    use std::convert::Infallible;
+    use tower::ServiceExt;
+
+    #[tokio::test]
+    async fn test_split_path() {
+        assert_init_done().await;
+        ut_info!("start");
+
+        assert_eq!(
+            split_path("/grpc.vertipad.RpcService/get_by_id"),
+            (
+                String::from("grpc.vertipad.RpcService"),
+                String::from("get_by_id")
+            )
+        );
+        assert_eq!(
+            split_path("/unknown"),
+            (String::from("unknown"), String::from(""))
+        );
+
+        ut_info!("success");
+    }
+
+    #[tokio::test]
+    async fn test_get_status_code() {
+        assert_init_done().await;
+        ut_info!("start");
+
+        let mut headers = HeaderMap::new();
+        assert_eq!(get_status_code(&headers), Code::Ok);
+
+        headers.insert("grpc-status", "5".parse().unwrap());
+        assert_eq!(get_status_code(&headers), Code::NotFound);
+
+        headers.insert("grpc-status", "invalid".parse().unwrap());
+        assert_eq!(get_status_code(&headers), Code::Unknown);
+
+        ut_info!("success");
+    }
+
+    #[tokio::test]
+    async fn test_grpc_metrics_layer() {
+        assert_init_done().await;
+        ut_info!("start");
+
+        let service = GrpcMetricsLayer::new(["grpc.test.RpcService"]).layer(tower::service_fn(
+            |_: Request<Body>| async {
+                let response = Response::builder()
+                    .header("grpc-status", "5")
+                    .body(Body::empty())
+                    .unwrap();
+                Ok::<_, Infallible>(response)
+            },
+        ));
+        let request = Request::builder()
+            .uri("/grpc.test.RpcService/test_grpc_metrics_layer")
+            .body(Body::empty())
+            .unwrap();
+        assert!(service.oneshot(request).await.is_ok());
+
+        let labels = ["grpc.test.RpcService", "test_grpc_metrics_layer"];
+        assert_eq!(GRPC_REQUESTS.with_label_values(&labels).get(), 1);
+        assert_eq!(
+            GRPC_REQUEST_DURATION
+                .with_label_values(&labels)
+                .get_sample_count(),
+            1
+        );
+        assert_eq!(
+            GRPC_RESPONSES
+                .with_label_values(&[labels[0], labels[1], "NotFound"])
+                .get(),
+            1
+        );
+
+        ut_info!("success");
+    }
+
+    #[tokio::test]
+    async fn test_grpc_metrics_layer_unknown() {
+        assert_init_done().await;
+        ut_info!("start");
+
+        let layer = GrpcMetricsLayer::new(["grpc.test.RpcUnknownService"]);
+        let service = tower::service_fn(|request: Request<Body>| async move {
+            // Tonic responds with `Unimplemented` to unknown paths
+            let status = match request.uri().path() {
+                "/grpc.test.RpcUnknownService/known" => "0",
+                _ => "12",
+            };
+            let response = Response::builder()
+                .header("grpc-status", status)
+                .body(Body::empty())
+                .unwrap();
+            Ok::<_, Infallible>(response)
+        });
+        for path in [
+            "/grpc.test.RpcUnknownService/known",
+            "/grpc.test.RpcUnknownService/random_method",
+            "/grpc.random.Service/random_method",
+            "/grpc.random.Service/other_method",
+        ] {
+            let request = Request::builder().uri(path).body(Body::empty()).unwrap();
+            let service = layer.layer(service.clone());
+            assert!(service.oneshot(request).await.is_ok());
+        }
+
+        let known = ["grpc.test.RpcUnknownService", "known"];
+        assert_eq!(GRPC_REQUESTS.with_label_values(&known).get(), 1);
+        let unknown_method = ["grpc.test.RpcUnknownService", UNKNOWN_LABEL];
+        assert_eq!(GRPC_REQUESTS.with_label_values(&unknown_method).get(), 1);
+        assert_eq!(
+            GRPC_RESPONSES
+                .with_label_values(&[unknown_method[0], unknown_method[1], "Unimplemented"])
+                .get(),
+            1
+        );
+        // Other tests may call unknown services as well
+        assert!(
+            GRPC_REQUESTS
+                .with_label_values(&[UNKNOWN_LABEL, UNKNOWN_LABEL])
+                .get()
+                >= 2
+        );
+        assert_eq!(
+            GRPC_REQUESTS
+                .with_label_values(&["grpc.random.Service", "random_method"])
+                .get(),
+            0
+        );
+
+        ut_info!("success");
+    }
+}
@@ -0,0 +1,192 @@
+//! Prometheus metrics
+//!
+//! Collects gRPC request metrics ([`layer::GrpcMetricsLayer`]), database connection pool
+//! statistics and database query latencies per table. The collected metrics are exposed in the
+//! Prometheus text format on the `/metrics` endpoint of the metrics server
+//! ([`server::metrics_server`]), listening on [`Config::docker_port_metrics`](crate::Config::docker_port_metrics).
+
+#[macro_use]
+pub mod macros;
+pub mod layer;
+pub mod server;
+
+use crate::common::ArrErr;
+
+use once_cell::sync::Lazy;
+use prometheus::core::Collector;
+use prometheus::{
+    Encoder, HistogramOpts, HistogramTimer, HistogramVec, IntCounterVec, IntGauge, Opts, Registry,
+    TextEncoder,
+};
+
+/// Registry holding all metrics of this service
+static REGISTRY: Lazy<Registry> = Lazy::new(Registry::new);
+
+/// Number of received gRPC requests per service and method
+pub(crate) static GRPC_REQUESTS: Lazy<IntCounterVec> = Lazy::new(|| {
+    register(IntCounterVec::new(
+        Opts::new(
+            "svc_storage_grpc_requests_total",
+            "Number of received gRPC requests",
+        ),
+        &["service", "method"],
+    ))
+});
+
+/// Number of gRPC responses per service, method and gRPC status code
+pub(crate) static GRPC_RESPONSES: Lazy<IntCounterVec> = Lazy::new(|| {
+    register(IntCounterVec::new(
+        Opts::new(
+            "svc_storage_grpc_responses_total",
+            "Number of gRPC responses by status code",
+        ),
+        &["service", "method", "code"],
+    ))
+});
+
+/// gRPC request latency in seconds per service and method
+pub(crate) static GRPC_REQUEST_DURATION: Lazy<HistogramVec> = Lazy::new(|| {
+    register(HistogramVec::new(
+        HistogramOpts::new(
+            "svc_storage_grpc_request_duration_seconds",
+            "gRPC request latency in seconds",
+        ),
+        &["service", "method"],
+    ))
+});
+
+/// Database query latency in seconds per table and operation
+static DB_QUERY_DURATION: Lazy<HistogramVec> = Lazy::new(|| {
+    register(HistogramVec::new(
+        HistogramOpts::new(
+            "svc_storage_db_query_duration_seconds",
+            "Database query latency in seconds",
+        ),
+        &["table", "operation"],
+    ))
+});
+
+/// Current number of connections in the database connection pool
+static DB_POOL_SIZE: Lazy<IntGauge> = Lazy::new(|| {
+    register(IntGauge::new(
+        "svc_storage_db_pool_size",
+        "Current number of connections in the database connection pool",
+    ))
+});
+
+/// Number of idle connections in the database connection pool
+static DB_POOL_AVAILABLE: Lazy<IntGauge> = Lazy::new(|| {
+    register(IntGauge::new(
+        "svc_storage_db_pool_available",
+        "Number of idle connections in the database connection pool",
+    ))
+});
+
+/// Number of requests waiting for a database connection
+static DB_POOL_WAITING: Lazy<IntGauge> = Lazy::new(|| {
+    register(IntGauge::new(
+        "svc_storage_db_pool_waiting",
+        "Number of requests waiting for a database connection",
+    ))
+});
+
+/// Registers the provided metric with the service's [`Registry`]
+///
+/// Metric definitions are static, so an invalid definition is a programming error.
+fn register<M: Collector + Clone + 'static>(metric: Result<M, prometheus::Error>) -> M {
+    let metric = metric.expect("(register) invalid metric definition");
+    if let Err(e) = REGISTRY.register(Box::new(metric.clone())) {
+        metrics_error!("Could not register metric: {}", e);
+    }
+    metric
+}
+
+/// Starts a timer recording the latency of a database query for the provided table and
+/// operation. The latency is recorded once the returned timer is dropped.
+///
+/// # Examples
+/// ```
+/// use svc_storage::metrics::query_timer;
+/// async fn example() {
+///     let _timer = query_timer("vertipad", "get_by_id");
+///     // execute query
+/// }
+/// ```
+pub fn query_timer(table: &str, operation: &str) -> HistogramTimer {
+    DB_QUERY_DURATION
+        .with_label_values(&[table, operation])
+        .start_timer()
+}
+
+/// Updates the database connection pool gauges with the pool's current status.
+/// All gauges will be `0` if the pool has not been initialized yet.
+fn update_pool_metrics() {
+    let (size, available, waiting) = match crate::postgres::get_psql_pool_status() {
+        Some(status) => (status.size, status.available, status.waiting),
+        None => (0, 0, 0),
+    };
+    DB_POOL_SIZE.set(size as i64);
+    DB_POOL_AVAILABLE.set(available as i64);
+    DB_POOL_WAITING.set(waiting as i64);
+}
+
+/// Returns all collected metrics in the Prometheus text format
+///
+/// # Errors
+///
+/// Returns [`ArrErr`] if the metrics could not be encoded
+pub fn gather() -> Result<String, ArrErr> {
+    update_pool_metrics();
+
+    let mut buffer = vec![];
+    TextEncoder::new()
+        .encode(&REGISTRY.gather(), &mut buffer)
+        .map_err(|e| {
+            let error = format!("Could not encode metrics: {}", e);
+            metrics_error!("{}", error);
+            ArrErr::Error(error)
+        })?;
+    String::from_utf8(buffer).map_err(|e| {
+        let error = format!("Could not encode metrics: {}", e);
+        metrics_error!("{}", error);
+        ArrErr::Error(error)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::*;
+
+    #[tokio::test]
+    async fn test_query_timer() {
+        assert_init_done().await;
+        ut_info!("start");
+
+        let histogram = DB_QUERY_DURATION.with_label_values(&["test_table", "test_query_timer"]);
+        assert_eq!(histogram.get_sample_count(), 0);
+
+        {
+            let _timer = query_timer("test_table", "test_query_timer");
+        }
+        assert_eq!(histogram.get_sample_count(), 1);
+
+        ut_info!("success");
+    }
+
+    #[tokio::test]
+    async fn test_gather() {
+        assert_init_done().await;
+        ut_info!("start");
+
+        drop(query_timer("test_table", "test_gather"));
+        let metrics = gather().unwrap();
+        assert!(metrics.contains("svc_storage_db_query_duration_seconds"));
+        assert!(metrics.contains(r#"operation="test_gather""#));
+        assert!(metrics.contains("svc_storage_db_pool_size"));
+        assert!(metrics.contains("svc_storage_db_pool_available"));
+        assert!(metrics.contains("svc_storage_db_pool_waiting"));
+
+        ut_info!("success");
+    }
+}
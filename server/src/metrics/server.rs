@@ -0,0 +1,126 @@
+//! Metrics HTTP server
+//!
+//! Serves the collected metrics on the `/metrics` endpoint so they can be scraped by Prometheus.
+
+use crate::config::Config;
+use crate::shutdown_signal;
+
+use hyper::header::CONTENT_TYPE;
+use hyper::service::{make_service_fn, service_fn};
+use hyper::{Body, Method, Request, Response, Server, StatusCode};
+use prometheus::TEXT_FORMAT;
+use std::convert::Infallible;
+use std::net::SocketAddr;
+
+/// Returns the collected metrics for `GET /metrics`, `404 Not Found` for any other request
+async fn handle_request(request: Request<Body>) -> Result<Response<Body>, Infallible> {
+    metrics_debug!("{} {}", request.method(), request.uri().path());
+
+    let response = match (request.method(), request.uri().path()) {
+        (&Method::GET, "/metrics") => match super::gather() {
+            Ok(metrics) => Response::builder()
+                .header(CONTENT_TYPE, TEXT_FORMAT)
+                .body(Body::from(metrics)),
+            Err(_) => Response::builder()
+                .status(StatusCode::INTERNAL_SERVER_ERROR)
+                .body(Body::empty()),
+        },
+        _ => Response::builder()
+            .status(StatusCode::NOT_FOUND)
+            .body(Body::empty()),
+    };
+
+    match response {
+        Ok(response) => Ok(response),
+        Err(e) => {
+            metrics_error!("Could not build response: {}", e);
+            let mut response = Response::new(Body::empty());
+            *response.status_mut() = StatusCode::INTERNAL_SERVER_ERROR;
+            Ok(response)
+        }
+    }
+}
+
+/// Starts the metrics server for this microservice using the provided configuration
+///
+/// # Examples
+/// ```
+/// use svc_storage::metrics::server::metrics_server;
+/// use svc_storage::Config;
+/// async fn example() -> Result<(), tokio::task::JoinError> {
+///     let config = Config::default();
+///     tokio::spawn(metrics_server(config, None)).await
+/// }
+/// ```
+#[cfg(not(tarpaulin_include))]
+// no_coverage: (R5) Can not be tested in unittest, should be part of integration
+// tests
+pub async fn metrics_server(
+    config: Config,
+    shutdown_rx: Option<tokio::sync::oneshot::Receiver<()>>,
+) {
+    metrics_debug!("entry.");
+
+    let metrics_port = config.docker_port_metrics;
+    let full_metrics_addr: SocketAddr = match format!("[::]:{}", metrics_port).parse() {
+        Ok(addr) => addr,
+        Err(e) => {
+            metrics_error!("Failed to parse metrics address: {}", e);
+            return;
+        }
+    };
+
+    let make_service =
+        make_service_fn(|_| async { Ok::<_, Infallible>(service_fn(handle_request)) });
+
+    metrics_info!("Starting metrics server on: {}.", full_metrics_addr);
+    let server = match Server::try_bind(&full_metrics_addr) {
+        Ok(builder) => builder.serve(make_service),
+        Err(e) => {
+            metrics_error!("Could not start metrics server: {}", e);
+            return;
+        }
+    };
+    match server
+        .with_graceful_shutdown(shutdown_signal("metrics", shutdown_rx))
+        .await
+    {
+        Ok(_) => metrics_info!("Metrics server stopped."),
+        Err(e) => {
+            metrics_error!("Metrics server error: {}", e);
+        }
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::*;
+
+    #[tokio::test]
+    async fn test_handle_request() {
+        assert_init_done().await;
+        ut_info!("start");
+
+        let request = Request::builder()
+            .uri("/metrics")
+            .body(Body::empty())
+            .unwrap();
+        let response = handle_request(request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(response.headers().get(CONTENT_TYPE).unwrap(), TEXT_FORMAT);
+
+        let body = hyper::body::to_bytes(response.into_body()).await.unwrap();
+        let body = String::from_utf8(body.to_vec()).unwrap();
+        assert!(body.contains("svc_storage_db_pool_size"));
+
+        let request = Request::builder()
+            .uri("/unknown")
+            .body(Body::empty())
+            .unwrap();
+        let response = handle_request(request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+
+        ut_info!("success");
+    }
+}
@@ -0,0 +1,434 @@
+//! Audit log of all mutations
+//!
+//! Every insert, update, delete, restore, link and unlink is recorded in the [`AUDIT_LOG_TABLE`]
+//! within the same transaction as the mutation itself, so no change can be committed without its
+//! audit log entry. Each entry contains the actor performing the mutation (see [`with_actor`]) and
+//! a JSON diff with the `before` and `after` values of all changed fields.
+//!
+//! The audit log is append only, entries are never removed by this service. Once a record is
+//! purged, the values recorded in its entries are redacted (see [`redact_entries`]) so no personal
+//! data is kept after the retention period.
+
+use super::{ArrErr, PsqlField};
+use crate::grpc::server::audit::AuditAction;
+use crate::resources::base::ResourceDefinition;
+
+use deadpool_postgres::Transaction;
+use lib_common::uuid::Uuid;
+use serde_json::{json, Map, Value};
+use std::collections::HashMap;
+use std::future::Future;
+
+/// Name of the audit log table
+pub const AUDIT_LOG_TABLE: &str = "audit_log";
+
+/// Actor recorded for mutations which are not performed on behalf of a request
+pub const UNKNOWN_ACTOR: &str = "unknown";
+
+/// Name of the column containing the row as JSON text, see [`get_row_json_column`]
+pub const ROW_JSON_COLUMN: &str = "audit_row";
+
+/// Maximum number of audit log entries written by a single statement
+const MAX_ENTRIES_PER_STATEMENT: usize = 10000;
+
+/// Value recorded in place of the redacted values of purged records
+pub const REDACTED_VALUE: &str = "[redacted]";
+
+tokio::task_local! {
+    /// Actor performing the mutations of the current task
+    static ACTOR: String;
+}
+
+/// Runs the provided future, recording the given actor for all mutations performed by it
+pub async fn with_actor<F: Future>(actor: String, future: F) -> F::Output {
+    ACTOR.scope(actor, future).await
+}
+
+/// Returns the actor set for the current task by [`with_actor`], or [`UNKNOWN_ACTOR`] if none was set
+pub fn current_actor() -> String {
+    ACTOR
+        .try_with(|actor| actor.clone())
+        .unwrap_or_else(|_| UNKNOWN_ACTOR.to_owned())
+}
+
+/// Fetches all rows matching the provided ids as JSON objects, using the provided transaction
+///
+/// # Errors
+///
+/// Returns [`ArrErr`] Database Error if database query execution failed
+/// Returns [`ArrErr`] if a row could not be converted into a JSON object
+#[cfg(not(tarpaulin_include))]
+// no_coverage: (R5) Is part of integration tests, coverage report will need to be merged to show.
+pub async fn get_rows_json(
+    transaction: &Transaction<'_>,
+    table: &str,
+    ids: &HashMap<String, Uuid>,
+) -> Result<Vec<Value>, ArrErr> {
+    let mut params: Vec<&PsqlField> = vec![];
+    let mut keys: Vec<String> = vec![];
+    for (id_field, value) in ids {
+        keys.push(format!(r#"t."{}" = ${}"#, id_field, params.len() + 1));
+        params.push(value);
+    }
+
+    let mut select_sql = format!(
+        r#"SELECT row_to_json(t.*)::TEXT AS "row" FROM "{}" AS t"#,
+        table
+    );
+    if !keys.is_empty() {
+        select_sql.push_str(&format!(" WHERE {}", keys.join(" AND ")));
+    }
+    psql_debug!("[{}].", select_sql);
+    psql_debug!("[{:?}].", &params);
+
+    let rows = transaction.query(&select_sql, &params).await?;
+    rows.iter()
+        .map(|row| get_row_json(table, row.get("row")))
+        .collect()
+}
+
+/// Returns the SQL expression selecting the provided table's row as JSON text, which can be
+/// added to a `RETURNING` clause to get the audit log values of the mutated rows right away.
+/// The value can be converted using [`get_row_json`].
+pub fn get_row_json_column(table: &str) -> String {
+    format!(
+        r#"row_to_json("{}".*)::TEXT AS "{}""#,
+        table, ROW_JSON_COLUMN
+    )
+}
+
+/// Converts the JSON text of a row, as selected by [`get_rows_json`] or [`get_row_json_column`],
+/// into a JSON object
+///
+/// # Errors
+///
+/// Returns [`ArrErr`] if the text could not be converted into a JSON object
+pub fn get_row_json(table: &str, row: &str) -> Result<Value, ArrErr> {
+    serde_json::from_str(row).map_err(|e| {
+        let error = format!("Could not convert row of [{}] into JSON: {}", table, e);
+        psql_error!("{}", error);
+        ArrErr::Error(error)
+    })
+}
+
+/// Writes an audit log entry for a single mutated row, using the provided transaction
+///
+/// The ids of the row are read from the `after` value, or from the `before` value if the row has
+/// been deleted, using the id columns of the provided [`ResourceDefinition`].
+///
+/// # Errors
+///
+/// Returns [`ArrErr`] if neither a `before` nor an `after` value is provided
+/// Returns [`ArrErr`] if the row's ids could not be found or converted into a [`Uuid`]
+/// Returns [`ArrErr`] Database Error if database query execution failed
+#[cfg(not(tarpaulin_include))]
+// no_coverage: (R5) Is part of integration tests, coverage report will need to be merged to show.
+pub async fn write_entry(
+    transaction: &Transaction<'_>,
+    definition: &ResourceDefinition,
+    action: AuditAction,
+    before: Option<&Value>,
+    after: Option<&Value>,
+) -> Result<(), ArrErr> {
+    write_entries(transaction, definition, action, &[(before, after)]).await
+}
+
+/// Writes an audit log entry for each of the provided `(before, after)` row values, using
+/// multi-row inserts within the provided transaction. See [`write_entry`].
+///
+/// # Errors
+///
+/// Returns [`ArrErr`] if neither a `before` nor an `after` value is provided for any of the rows
+/// Returns [`ArrErr`] if a row's ids could not be found or converted into a [`Uuid`]
+/// Returns [`ArrErr`] Database Error if database query execution failed
+#[cfg(not(tarpaulin_include))]
+// no_coverage: (R5) Is part of integration tests, coverage report will need to be merged to show.
+pub async fn write_entries(
+    transaction: &Transaction<'_>,
+    definition: &ResourceDefinition,
+    action: AuditAction,
+    rows: &[(Option<&Value>, Option<&Value>)],
+) -> Result<(), ArrErr> {
+    let action_name = action.as_str_name();
+    let actor = current_actor();
+
+    let mut entries: Vec<(Uuid, Option<Uuid>, String)> = vec![];
+    for (before, after) in rows {
+        let row = match after.or(*before) {
+            Some(row) => row,
+            None => {
+                let error = format!(
+                    "No row values provided for [{}] audit log entry of [{}].",
+                    action_name, definition.psql_table
+                );
+                psql_error!("{}", error);
+                return Err(ArrErr::Error(error));
+            }
+        };
+
+        let mut ids = vec![];
+        for id_col in definition.psql_id_cols.iter().take(2) {
+            ids.push(get_row_id(row, id_col)?);
+        }
+        let resource_id = match ids.first() {
+            Some(id) => *id,
+            None => {
+                let error = format!(
+                    "No id column configured for table {}",
+                    definition.psql_table
+                );
+                psql_error!("{}", error);
+                return Err(ArrErr::Error(error));
+            }
+        };
+        psql_info!(
+            "Recording [{}] of [{}] [{}] by [{}] in audit log.",
+            action_name,
+            definition.psql_table,
+            resource_id,
+            actor
+        );
+        entries.push((
+            resource_id,
+            ids.get(1).copied(),
+            get_diff(*before, *after).to_string(),
+        ));
+    }
+
+    for chunk in entries.chunks(MAX_ENTRIES_PER_STATEMENT) {
+        // The resource, action and actor are the same for all entries
+        let mut params: Vec<&PsqlField> = vec![&definition.psql_table, &action_name, &actor];
+        let mut values = vec![];
+        for (resource_id, linked_id, diff) in chunk {
+            let index = params.len();
+            values.push(format!(
+                "($1, ${}, ${}, $2, $3, ${})",
+                index + 1,
+                index + 2,
+                index + 3
+            ));
+            params.push(resource_id);
+            params.push(linked_id);
+            params.push(diff);
+        }
+
+        let insert_sql = format!(
+            r#"INSERT INTO "{}" ("resource", "resource_id", "linked_id", "action", "actor", "diff") VALUES {}"#,
+            AUDIT_LOG_TABLE,
+            values.join(", ")
+        );
+        psql_debug!("[{}].", insert_sql);
+        transaction.execute(&insert_sql, &params[..]).await?;
+    }
+    Ok(())
+}
+
+/// Redacts the values of all audit log entries recorded for the record with the provided id,
+/// including entries of link tables referencing the record, using the provided transaction.
+///
+/// The field names and id values are kept, so the entries still show which fields have been
+/// changed. All other values are replaced by [`REDACTED_VALUE`], see [`redact_diff`].
+/// Returns the number of redacted entries.
+///
+/// # Errors
+///
+/// Returns [`ArrErr`] if an entry's diff could not be converted into a JSON object
+/// Returns [`ArrErr`] Database Error if database query execution failed
+#[cfg(not(tarpaulin_include))]
+// no_coverage: (R5) Is part of integration tests, coverage report will need to be merged to show.
+pub async fn redact_entries(transaction: &Transaction<'_>, id: &Uuid) -> Result<u64, ArrErr> {
+    let select_sql = format!(
+        r#"SELECT "audit_log_id", "diff" FROM "{}" WHERE "resource_id" = $1 OR "linked_id" = $1"#,
+        AUDIT_LOG_TABLE
+    );
+    psql_debug!("[{}].", select_sql);
+    let update_sql = format!(
+        r#"UPDATE "{}" SET "diff" = $1 WHERE "audit_log_id" = $2"#,
+        AUDIT_LOG_TABLE
+    );
+    psql_debug!("[{}].", update_sql);
+
+    let mut count = 0;
+    for row in transaction.query(&select_sql, &[id]).await? {
+        let entry_id: Uuid = row.get("audit_log_id");
+        let diff: Value = serde_json::from_str(row.get("diff")).map_err(|e| {
+            let error = format!(
+                "Could not convert audit log entry [{}] into JSON: {}",
+                entry_id, e
+            );
+            psql_error!("{}", error);
+            ArrErr::Error(error)
+        })?;
+
+        let redacted = redact_diff(&diff);
+        if redacted != diff {
+            transaction
+                .execute(&update_sql, &[&redacted.to_string(), &entry_id])
+                .await?;
+            count += 1;
+        }
+    }
+
+    psql_info!(
+        "Redacted [{}] audit log entries of purged record [{}].",
+        count,
+        id
+    );
+    Ok(count)
+}
+
+/// Returns the provided diff (see [`get_diff`]) with all values replaced by [`REDACTED_VALUE`],
+/// except for id values (fields ending with `_id`) and `null` values.
+pub fn redact_diff(diff: &Value) -> Value {
+    let redact = |values: &Value| match values.as_object() {
+        Some(fields) => Value::Object(
+            fields
+                .iter()
+                .map(|(field, value)| {
+                    let value = if field.ends_with("_id") || value.is_null() {
+                        value.clone()
+                    } else {
+                        Value::String(REDACTED_VALUE.to_owned())
+                    };
+                    (field.clone(), value)
+                })
+                .collect(),
+        ),
+        None => values.clone(),
+    };
+
+    json!({
+        "before": redact(&diff["before"]),
+        "after": redact(&diff["after"]),
+    })
+}
+
+/// Returns a JSON object with the `before` and `after` values of all fields which differ between
+/// the provided row values. Fields with equal values are left out.
+///
+/// `before` is `null` for inserted rows and `after` is `null` for deleted rows.
+pub fn get_diff(before: Option<&Value>, after: Option<&Value>) -> Value {
+    let empty = Map::new();
+    let before_fields = before.and_then(Value::as_object).unwrap_or(&empty);
+    let after_fields = after.and_then(Value::as_object).unwrap_or(&empty);
+
+    let mut before_diff = Map::new();
+    for (field, value) in before_fields {
+        if after_fields.get(field) != Some(value) {
+            before_diff.insert(field.clone(), value.clone());
+        }
+    }
+    let mut after_diff = Map::new();
+    for (field, value) in after_fields {
+        if before_fields.get(field) != Some(value) {
+            after_diff.insert(field.clone(), value.clone());
+        }
+    }
+
+    json!({
+        "before": before.map(|_| Value::Object(before_diff)),
+        "after": after.map(|_| Value::Object(after_diff)),
+    })
+}
+
+/// Returns the [`Uuid`] value of the provided id column from a JSON row value
+fn get_row_id(row: &Value, id_col: &str) -> Result<Uuid, ArrErr> {
+    match row.get(id_col).and_then(Value::as_str) {
+        Some(id) => Ok(Uuid::parse_str(id)?),
+        None => {
+            let error = format!("No [{}] value found in row [{}].", id_col, row);
+            psql_error!("{}", error);
+            Err(ArrErr::Error(error))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::*;
+
+    #[tokio::test]
+    async fn test_with_actor() {
+        assert_init_done().await;
+        ut_info!("start");
+
+        assert_eq!(current_actor(), UNKNOWN_ACTOR);
+        let actor = with_actor(String::from("operator"), async { current_actor() }).await;
+        assert_eq!(actor, "operator");
+        assert_eq!(current_actor(), UNKNOWN_ACTOR);
+
+        ut_info!("success");
+    }
+
+    #[tokio::test]
+    async fn test_get_diff() {
+        assert_init_done().await;
+        ut_info!("start");
+
+        let before = json!({"id": "1", "name": "old", "status": "ACTIVE"});
+        let after = json!({"id": "1", "name": "new", "status": "ACTIVE"});
+
+        assert_eq!(
+            get_diff(Some(&before), Some(&after)),
+            json!({"before": {"name": "old"}, "after": {"name": "new"}})
+        );
+        assert_eq!(
+            get_diff(None, Some(&after)),
+            json!({"before": null, "after": after})
+        );
+        assert_eq!(
+            get_diff(Some(&before), None),
+            json!({"before": before, "after": null})
+        );
+        assert_eq!(
+            get_diff(Some(&before), Some(&before)),
+            json!({"before": {}, "after": {}})
+        );
+
+        ut_info!("success");
+    }
+
+    #[tokio::test]
+    async fn test_redact_diff() {
+        assert_init_done().await;
+        ut_info!("start");
+
+        let id = Uuid::new_v4().to_string();
+        let diff = json!({
+            "before": {"user_id": id, "email": "old@example.com", "deleted_at": null},
+            "after": {"user_id": id, "email": "new@example.com", "deleted_at": "2023-01-01"},
+        });
+        assert_eq!(
+            redact_diff(&diff),
+            json!({
+                "before": {"user_id": id, "email": REDACTED_VALUE, "deleted_at": null},
+                "after": {"user_id": id, "email": REDACTED_VALUE, "deleted_at": REDACTED_VALUE},
+            })
+        );
+
+        let diff = json!({"before": null, "after": {"user_id": id, "age": 42}});
+        assert_eq!(
+            redact_diff(&diff),
+            json!({"before": null, "after": {"user_id": id, "age": REDACTED_VALUE}})
+        );
+        assert_eq!(redact_diff(&redact_diff(&diff)), redact_diff(&diff));
+
+        ut_info!("success");
+    }
+
+    #[tokio::test]
+    async fn test_get_row_id() {
+        assert_init_done().await;
+        ut_info!("start");
+
+        let id = Uuid::new_v4();
+        let row = json!({"vertipad_id": id.to_string(), "name": "pad"});
+
+        assert_eq!(get_row_id(&row, "vertipad_id").unwrap(), id);
+        assert!(get_row_id(&row, "vertiport_id").is_err());
+        assert!(get_row_id(&row, "name").is_err());
+
+        ut_info!("success");
+    }
+}
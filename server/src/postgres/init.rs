@@ -22,6 +22,7 @@ pub async fn create_db() -> Result<(), ArrErr> {
     ResourceObject::<vertiport_group::Data>::init_table().await?;
     ResourceObject::<vertipad::Data>::init_table().await?;
     ResourceObject::<vertipad_group::Data>::init_table().await?;
+    ResourceObject::<vehicle_model::Data>::init_table().await?;
     ResourceObject::<vehicle::Data>::init_table().await?;
     ResourceObject::<vehicle_group::Data>::init_table().await?;
     ResourceObject::<pilot::Data>::init_table().await?;
@@ -33,32 +34,54 @@ pub async fn create_db() -> Result<(), ArrErr> {
     ResourceObject::<flight_plan_parcel::Data>::init_table().await?;
     ResourceObject::<scanner::Data>::init_table().await?;
     ResourceObject::<parcel_scan::Data>::init_table().await?;
+    ResourceObject::<audit::Data>::init_table().await?;
     Ok(())
 }
 
+/// Calls the provided macro with all resources, in the order their tables should be dropped.
+/// Referencing tables are listed before the tables they reference.
+///
+/// Each resource is prefixed with its kind (`simple`, `link` or `audit`), so the callback can
+/// decide which resources to handle.
+#[macro_export]
+macro_rules! with_resources_in_drop_order {
+    ($callback:ident) => {
+        $callback! {
+            audit audit,
+            simple parcel_scan,
+            simple scanner,
+            link flight_plan_parcel,
+            simple parcel,
+            link itinerary_flight_plan,
+            simple itinerary,
+            simple flight_plan,
+            simple adsb,
+            simple pilot,
+            link vehicle_group,
+            simple vehicle,
+            simple vehicle_model,
+            link vertipad_group,
+            simple vertipad,
+            link vertiport_group,
+            simple vertiport,
+            link user_group,
+            simple user,
+            simple group,
+        }
+    };
+}
+
 /// If we want to recreate the database tables created by this module, we will want to drop the existing tables first.
 /// This function makes sure the tables will be dropped in the correct order
 pub async fn drop_db() -> Result<(), ArrErr> {
     psql_warn!("Dropping database tables.");
     // Drop our tables (in the correct order)
-    ResourceObject::<parcel_scan::Data>::drop_table().await?;
-    ResourceObject::<scanner::Data>::drop_table().await?;
-    ResourceObject::<flight_plan_parcel::Data>::drop_table().await?;
-    ResourceObject::<parcel::Data>::drop_table().await?;
-    ResourceObject::<itinerary_flight_plan::Data>::drop_table().await?;
-    ResourceObject::<itinerary::Data>::drop_table().await?;
-    ResourceObject::<flight_plan::Data>::drop_table().await?;
-    ResourceObject::<adsb::Data>::drop_table().await?;
-    ResourceObject::<pilot::Data>::drop_table().await?;
-    ResourceObject::<vehicle_group::Data>::drop_table().await?;
-    ResourceObject::<vehicle::Data>::drop_table().await?;
-    ResourceObject::<vertipad_group::Data>::drop_table().await?;
-    ResourceObject::<vertipad::Data>::drop_table().await?;
-    ResourceObject::<vertiport_group::Data>::drop_table().await?;
-    ResourceObject::<vertiport::Data>::drop_table().await?;
-    ResourceObject::<user_group::Data>::drop_table().await?;
-    ResourceObject::<user::Data>::drop_table().await?;
-    ResourceObject::<group::Data>::drop_table().await?;
+    macro_rules! drop_tables {
+        ($($kind:ident $resource:ident),+ $(,)?) => {
+            $(ResourceObject::<$resource::Data>::drop_table().await?;)+
+        };
+    }
+    crate::with_resources_in_drop_order!(drop_tables);
     Ok(())
 }
 
@@ -2,15 +2,19 @@
 //!
 
 use super::get_psql_client;
-use super::{ArrErr, PsqlField};
+use super::{audit, ArrErr, PsqlField};
+use crate::cache;
+use crate::grpc::server::audit::AuditAction;
 use crate::grpc::GrpcDataObjectType;
 use crate::postgres::PsqlFieldSend;
 use crate::resources::base::linked_resource::*;
+use crate::telemetry;
 
 use deadpool_postgres::Transaction;
 use lib_common::uuid::Uuid;
 use std::collections::HashMap;
 use std::vec;
+use tracing::{field::Empty, instrument};
 
 /// Generic PostgreSQL trait to provide wrappers for common `LinkedResource` functions
 #[cfg(not(tarpaulin_include))]
@@ -24,83 +28,57 @@ where
     /// Generic delete for ids function to delete rows for the provided key fields
     /// Since this is a linked resource, the id is expected to be given as a [Vec\<FieldValuePair\>]
     /// to specify the id_column / value pairs to match
+    /// Each deleted row will be recorded in the audit log using the provided [`AuditAction`].
     /// An optional transaction handle can be provided, which will be used if present.
     /// This function will not commit, so the caller is responsible for committing the transaction when done.
+    #[instrument(skip_all, err, fields(db.system = "postgresql", db.sql.table = %Self::get_psql_table(), db.statement = Empty))]
     async fn delete_for_ids(
         ids: HashMap<String, Uuid>,
         transaction: Option<&Transaction>,
+        action: AuditAction,
     ) -> Result<(), ArrErr> {
         psql_debug!("Start [{:?}].", ids);
         let definition = Self::get_definition();
 
-        let mut params: Vec<Box<PsqlFieldSend>> = vec![];
-        let mut query = format!(r#"DELETE FROM "{}""#, definition.get_psql_table());
-        let mut search_operator = "WHERE";
-        let mut next_param_index: i32 = 1;
-
-        for (field, value) in ids.clone() {
-            if Self::has_id_col(&field) {
-                query.push_str(&format!(
-                    r#" {} "{}" = ${}"#,
-                    search_operator, field, next_param_index
-                ));
-                params.push(Box::new(value));
-                search_operator = "AND";
-                next_param_index += 1;
-            }
-        }
+        let ids: HashMap<String, Uuid> = ids
+            .into_iter()
+            .filter(|(field, _)| Self::has_id_col(field))
+            .collect();
 
         psql_info!(
             "Deleting rows for table [{}]. uuids: {:?}",
             definition.psql_table,
             ids
         );
-        psql_debug!("[{}].", &query);
-        psql_debug!("[{:?}].", &params);
 
-        let mut ref_params: Vec<&PsqlField> = vec![];
-        for field in params.iter() {
-            ref_params.push(field.as_ref());
-        }
-
-        // TODO(R5): Move this to 2 separate functions which can be used in other places as well
-        match transaction {
-            Some(client) => {
-                let stmt = client.prepare_cached(&query).await?;
-                match client.execute(&stmt, &ref_params[..]).await {
-                    Ok(rows) => {
-                        psql_debug!(
-                            "Removed [{}] entries from [{}].",
-                            rows,
-                            definition.get_psql_table()
-                        );
-                        Ok(())
-                    }
-                    Err(e) => Err(e.into()),
-                }
+        let rows = match transaction {
+            Some(transaction) => {
+                super::queries::delete_for_ids(transaction, &definition, &ids, action).await?
             }
             None => {
-                let client = get_psql_client().await?;
-                let stmt = client.prepare_cached(&query).await?;
-                match client.execute(&stmt, &ref_params[..]).await {
-                    Ok(rows) => {
-                        psql_debug!(
-                            "Removed [{}] entries from [{}].",
-                            rows,
-                            definition.get_psql_table()
-                        );
-                        Ok(())
-                    }
-                    Err(e) => Err(e.into()),
-                }
+                let mut client = get_psql_client().await?;
+                let transaction = client.transaction().await?;
+                let rows =
+                    super::queries::delete_for_ids(&transaction, &definition, &ids, action).await?;
+                transaction.commit().await?;
+                cache::invalidate::<Self>().await;
+                rows
             }
-        }
+        };
+        psql_debug!(
+            "Removed [{}] entries from [{}].",
+            rows,
+            definition.get_psql_table()
+        );
+        Ok(())
     }
 
     /// Generic link function based on resource definition and provided [`Vec\<FieldValuePair\>`].
     /// If there are key/ value pairs provided in the `replace` [`HashMap\<String, Uuid\>`], all values for the given key pair will be dropped first.
     /// An UPSERT query will be used to insert a new row, or update an existing one if the primary key already exists.
     /// This function uses a transaction, making sure we're able to insert the new values before committing any changes.
+    /// All removed and inserted rows will be recorded in the audit log within the same transaction.
+    #[instrument(skip_all, err, fields(db.system = "postgresql", db.sql.table = %Self::get_psql_table(), db.statement = Empty))]
     async fn link_ids(
         ids: Vec<HashMap<String, Uuid>>,
         replace: HashMap<String, Uuid>,
@@ -111,14 +89,18 @@ where
         let mut client = get_psql_client().await?;
         let transaction = client.transaction().await?;
 
-        if !replace.is_empty() {
-            Self::delete_for_ids(replace, Some(&transaction)).await?
-        }
+        let action = if replace.is_empty() {
+            AuditAction::Link
+        } else {
+            Self::delete_for_ids(replace, Some(&transaction), AuditAction::ReplaceLinked).await?;
+            AuditAction::ReplaceLinked
+        };
 
         for entry in ids {
             let mut params: Vec<Box<PsqlFieldSend>> = vec![];
             let mut inserts = vec![];
             let mut fields = vec![];
+            let mut row_ids = HashMap::new();
             let mut next_param_index = 1;
 
             for (field, value) in entry {
@@ -126,6 +108,7 @@ where
                     fields.push(field.clone());
                     inserts.push(format!("${}", next_param_index));
                     params.push(Box::new(value));
+                    row_ids.insert(field, value);
                     next_param_index += 1;
                 }
             }
@@ -146,12 +129,22 @@ where
             for field in params.iter() {
                 ref_params.push(field.as_ref());
             }
-            transaction
+            telemetry::record_statement(insert_sql);
+            let num_rows = transaction
                 .execute(insert_sql, &ref_params[..])
                 .await
                 .map_err(ArrErr::from)?;
+
+            // Existing links are left untouched, so only record newly inserted rows
+            if num_rows > 0 {
+                let after =
+                    audit::get_rows_json(&transaction, &definition.psql_table, &row_ids).await?;
+                audit::write_entry(&transaction, &definition, action, None, after.first()).await?;
+            }
         }
-        transaction.commit().await.map_err(ArrErr::from)
+        transaction.commit().await?;
+        cache::invalidate::<Self>().await;
+        Ok(())
     }
 }
 
@@ -167,6 +160,7 @@ where
     T: GrpcDataObjectType,
 {
     /// delete database record from the database using the Object's primary key
+    #[instrument(skip_all, err, fields(db.system = "postgresql", db.sql.table = %Self::get_psql_table(), db.statement = Empty))]
     async fn delete(&self) -> Result<(), ArrErr> {
         psql_debug!("Start [{:?}].", self.try_get_uuids());
         let definition = Self::get_definition();
@@ -178,37 +172,17 @@ where
             ids
         );
 
-        let mut params: Vec<Box<PsqlFieldSend>> = vec![];
-        let mut query = format!(r#"DELETE FROM "{}""#, definition.psql_table);
-        let mut next_param_index: i32 = 1;
-
-        let mut search_operator = "WHERE";
-        for (field, value) in ids {
-            query.push_str(&format!(
-                r#" {} "{}" = ${}"#,
-                search_operator, field, next_param_index
-            ));
-            params.push(Box::new(value));
-            search_operator = "AND";
-            next_param_index += 1;
-        }
-        psql_debug!("[{}].", query);
-        psql_debug!("[{:?}].", &params);
-
-        let client = get_psql_client().await?;
-        let stmt = client.prepare_cached(&query).await?;
-
         psql_info!("Removing entry from table [{}].", definition.psql_table);
 
-        let mut ref_params: Vec<&PsqlField> = vec![];
-        for field in params.iter() {
-            ref_params.push(field.as_ref());
-        }
-
-        match client.execute(&stmt, &ref_params[..]).await {
+        let mut client = get_psql_client().await?;
+        let transaction = client.transaction().await?;
+        match super::queries::delete_for_ids(&transaction, &definition, &ids, AuditAction::Unlink)
+            .await
+        {
             Ok(num_rows) => {
                 if num_rows == 1 {
-                    //TODO(R5): flush shared memcache for this resource when memcache is implemented
+                    transaction.commit().await?;
+                    cache::invalidate::<Self>().await;
                     Ok(())
                 } else {
                     let error = format!(
@@ -220,7 +194,7 @@ where
                     Err(ArrErr::Error(error))
                 }
             }
-            Err(e) => Err(e.into()),
+            Err(e) => Err(e),
         }
     }
 }
@@ -0,0 +1,640 @@
+//! Versioned database schema migrations
+//!
+//! Each resource starts with a baseline step (version `0`) creating the table and its indices.
+//! Additional steps can be provided by the resource through [`Resource::get_migrations`](crate::resources::base::Resource::get_migrations).
+//! Applied steps are recorded in the [`MIGRATIONS_TABLE`] together with a checksum of their
+//! queries, so we can detect steps that have been changed after they were applied.
+
+use super::init::PsqlInitResource;
+use super::{get_psql_client, ArrErr, PsqlFieldType};
+use crate::grpc::server::*;
+use crate::resources::base::{ResourceDefinition, ResourceObject};
+
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+use std::fmt;
+
+/// Name of the table used to keep track of applied migrations
+pub const MIGRATIONS_TABLE: &str = "schema_migrations";
+
+/// Version reserved for the baseline step of each resource.
+/// The baseline is derived from the current [`ResourceDefinition`], so its checksum is not verified.
+pub const BASELINE_VERSION: i64 = 0;
+
+/// A single migration step for a resource
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Migration {
+    /// Version of the step, must be unique and incrementing per resource
+    pub version: i64,
+    /// Short description of the step, stored in the migrations table
+    pub description: String,
+    /// Queries to execute, they will be run in order in a single transaction
+    pub queries: Vec<String>,
+}
+
+impl Migration {
+    /// Create a new migration step
+    pub fn new(version: i64, description: &str, queries: Vec<String>) -> Self {
+        Self {
+            version,
+            description: description.to_owned(),
+            queries,
+        }
+    }
+
+    /// Returns the SHA-256 checksum (hex encoded) of the step's queries
+    pub fn checksum(&self) -> String {
+        let mut hasher = Sha256::new();
+        for query in &self.queries {
+            hasher.update(query.trim().as_bytes());
+            hasher.update(b";\n");
+        }
+        format!("{:x}", hasher.finalize())
+    }
+}
+
+/// Column information as found in the database's `information_schema`
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TableColumn {
+    /// column name
+    pub name: String,
+    /// `udt_name` of the column type
+    pub udt_name: String,
+    /// `true` if the column accepts `NULL` values
+    pub nullable: bool,
+}
+
+/// Difference found between a [`ResourceDefinition`] and the actual database table
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum SchemaDrift {
+    /// The table does not exist in the database
+    MissingTable {
+        /// table name
+        table: String,
+    },
+    /// The column is defined for the resource, but does not exist in the database
+    MissingColumn {
+        /// table name
+        table: String,
+        /// column name
+        column: String,
+    },
+    /// The column exists in the database, but is not defined for the resource
+    UnknownColumn {
+        /// table name
+        table: String,
+        /// column name
+        column: String,
+    },
+    /// The column type in the database does not match the resource definition
+    TypeMismatch {
+        /// table name
+        table: String,
+        /// column name
+        column: String,
+        /// expected `udt_name` based on the resource definition
+        expected: String,
+        /// `udt_name` found in the database
+        found: String,
+    },
+    /// The column `NOT NULL` constraint does not match the resource definition
+    NullableMismatch {
+        /// table name
+        table: String,
+        /// column name
+        column: String,
+        /// `true` if the column should accept `NULL` values based on the resource definition
+        expected_nullable: bool,
+    },
+}
+
+impl fmt::Display for SchemaDrift {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SchemaDrift::MissingTable { table } => {
+                write!(f, "table [{}] does not exist", table)
+            }
+            SchemaDrift::MissingColumn { table, column } => {
+                write!(f, "column [{}.{}] does not exist", table, column)
+            }
+            SchemaDrift::UnknownColumn { table, column } => {
+                write!(
+                    f,
+                    "column [{}.{}] is not part of the definition",
+                    table, column
+                )
+            }
+            SchemaDrift::TypeMismatch {
+                table,
+                column,
+                expected,
+                found,
+            } => write!(
+                f,
+                "column [{}.{}] has type [{}], expected [{}]",
+                table, column, found, expected
+            ),
+            SchemaDrift::NullableMismatch {
+                table,
+                column,
+                expected_nullable,
+            } => write!(
+                f,
+                "column [{}.{}] should {}accept NULL values",
+                table,
+                column,
+                if *expected_nullable { "" } else { "not " }
+            ),
+        }
+    }
+}
+
+/// Generic PostgreSQL trait to provide migration functions for `Resource` structs
+#[tonic::async_trait]
+pub trait PsqlMigrateResource
+where
+    Self: PsqlInitResource,
+{
+    /// Returns all migration steps for the resource, starting with the baseline step
+    fn get_all_migrations() -> Vec<Migration> {
+        let mut queries = vec![Self::_get_create_table_query()];
+        queries.append(&mut Self::get_table_indices());
+
+        let mut migrations = vec![Migration::new(BASELINE_VERSION, "baseline", queries)];
+        migrations.append(&mut Self::get_migrations());
+        migrations
+    }
+
+    /// Applies all pending migration steps for the resource.
+    ///
+    /// Returns the number of applied steps.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ArrErr`] if the migration steps are not ordered correctly
+    /// Returns [`ArrErr`] if the checksum of an applied step doesn't match the checksum of the step's queries
+    /// Returns [`ArrErr`] Database Error if any of the queries failed, the step will be rolled back
+    async fn migrate_table() -> Result<usize, ArrErr> {
+        let table = Self::get_psql_table();
+        let migrations = Self::get_all_migrations();
+        validate_migrations(&table, &migrations)?;
+
+        let applied = get_applied_migrations(&table).await?;
+        let mut count = 0;
+        for migration in migrations {
+            match applied.get(&migration.version) {
+                Some(checksum) => {
+                    if migration.version != BASELINE_VERSION && *checksum != migration.checksum() {
+                        let error = format!(
+                            "Checksum mismatch for migration [{}] version [{}], the step has been changed after it was applied.",
+                            table, migration.version
+                        );
+                        psql_error!("{}", error);
+                        return Err(ArrErr::Error(error));
+                    }
+                    psql_debug!(
+                        "Migration [{}] version [{}] already applied.",
+                        table,
+                        migration.version
+                    );
+                }
+                None => {
+                    apply_migration(&table, &migration).await?;
+                    count += 1;
+                }
+            }
+        }
+        Ok(count)
+    }
+
+    /// Compares the resource's [`ResourceDefinition`] with the table columns found in the database
+    async fn get_schema_drift() -> Result<Vec<SchemaDrift>, ArrErr> {
+        let definition = Self::get_definition();
+        let columns = get_table_columns(&definition.psql_table).await?;
+        Ok(get_definition_drift(&definition, &columns))
+    }
+}
+impl<T> PsqlMigrateResource for T where T: PsqlInitResource {}
+
+/// Runs all pending migrations for all resources, in the same order as
+/// [`create_db`](super::init::create_db), and logs any schema drift found afterwards.
+pub async fn migrate() -> Result<(), ArrErr> {
+    psql_info!("Running database migrations.");
+    init_migrations_table().await?;
+
+    let mut count = 0;
+    count += ResourceObject::<group::Data>::migrate_table().await?;
+    count += ResourceObject::<user::Data>::migrate_table().await?;
+    count += ResourceObject::<user_group::Data>::migrate_table().await?;
+    count += ResourceObject::<vertiport::Data>::migrate_table().await?;
+    count += ResourceObject::<vertiport_group::Data>::migrate_table().await?;
+    count += ResourceObject::<vertipad::Data>::migrate_table().await?;
+    count += ResourceObject::<vertipad_group::Data>::migrate_table().await?;
+    count += ResourceObject::<vehicle_model::Data>::migrate_table().await?;
+    count += ResourceObject::<vehicle::Data>::migrate_table().await?;
+    count += ResourceObject::<vehicle_group::Data>::migrate_table().await?;
+    count += ResourceObject::<pilot::Data>::migrate_table().await?;
+    count += ResourceObject::<adsb::Data>::migrate_table().await?;
+    count += ResourceObject::<flight_plan::Data>::migrate_table().await?;
+    count += ResourceObject::<itinerary::Data>::migrate_table().await?;
+    count += ResourceObject::<itinerary_flight_plan::Data>::migrate_table().await?;
+    count += ResourceObject::<parcel::Data>::migrate_table().await?;
+    count += ResourceObject::<flight_plan_parcel::Data>::migrate_table().await?;
+    count += ResourceObject::<scanner::Data>::migrate_table().await?;
+    count += ResourceObject::<parcel_scan::Data>::migrate_table().await?;
+    count += ResourceObject::<audit::Data>::migrate_table().await?;
+    psql_info!("Applied [{}] migration steps.", count);
+
+    let drift = get_schema_drift().await?;
+    if drift.is_empty() {
+        psql_info!("No schema drift found.");
+    }
+    for item in drift {
+        psql_warn!("Schema drift: {}.", item);
+    }
+
+    Ok(())
+}
+
+/// Compares all resource definitions with the actual database tables
+pub async fn get_schema_drift() -> Result<Vec<SchemaDrift>, ArrErr> {
+    let mut drift = vec![];
+    drift.append(&mut ResourceObject::<group::Data>::get_schema_drift().await?);
+    drift.append(&mut ResourceObject::<user::Data>::get_schema_drift().await?);
+    drift.append(&mut ResourceObject::<user_group::Data>::get_schema_drift().await?);
+    drift.append(&mut ResourceObject::<vertiport::Data>::get_schema_drift().await?);
+    drift.append(&mut ResourceObject::<vertiport_group::Data>::get_schema_drift().await?);
+    drift.append(&mut ResourceObject::<vertipad::Data>::get_schema_drift().await?);
+    drift.append(&mut ResourceObject::<vertipad_group::Data>::get_schema_drift().await?);
+    drift.append(&mut ResourceObject::<vehicle_model::Data>::get_schema_drift().await?);
+    drift.append(&mut ResourceObject::<vehicle::Data>::get_schema_drift().await?);
+    drift.append(&mut ResourceObject::<vehicle_group::Data>::get_schema_drift().await?);
+    drift.append(&mut ResourceObject::<pilot::Data>::get_schema_drift().await?);
+    drift.append(&mut ResourceObject::<adsb::Data>::get_schema_drift().await?);
+    drift.append(&mut ResourceObject::<flight_plan::Data>::get_schema_drift().await?);
+    drift.append(&mut ResourceObject::<itinerary::Data>::get_schema_drift().await?);
+    drift.append(&mut ResourceObject::<itinerary_flight_plan::Data>::get_schema_drift().await?);
+    drift.append(&mut ResourceObject::<parcel::Data>::get_schema_drift().await?);
+    drift.append(&mut ResourceObject::<flight_plan_parcel::Data>::get_schema_drift().await?);
+    drift.append(&mut ResourceObject::<scanner::Data>::get_schema_drift().await?);
+    drift.append(&mut ResourceObject::<parcel_scan::Data>::get_schema_drift().await?);
+    drift.append(&mut ResourceObject::<audit::Data>::get_schema_drift().await?);
+    Ok(drift)
+}
+
+/// Creates the [`MIGRATIONS_TABLE`] if it doesn't exist yet
+pub(crate) async fn init_migrations_table() -> Result<(), ArrErr> {
+    let query = format!(
+        r#"CREATE TABLE IF NOT EXISTS "{}" ("resource" TEXT NOT NULL, "version" BIGINT NOT NULL, "description" TEXT NOT NULL, "checksum" TEXT NOT NULL, "applied_at" TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT CURRENT_TIMESTAMP, PRIMARY KEY("resource", "version"))"#,
+        MIGRATIONS_TABLE
+    );
+    psql_debug!("[{}].", query);
+
+    let client = get_psql_client().await?;
+    client.execute(&query, &[]).await?;
+    Ok(())
+}
+
+/// Returns a [`HashMap`] with the applied migration versions and their checksums for the given resource
+async fn get_applied_migrations(resource: &str) -> Result<HashMap<i64, String>, ArrErr> {
+    let query = format!(
+        r#"SELECT "version", "checksum" FROM "{}" WHERE "resource" = $1"#,
+        MIGRATIONS_TABLE
+    );
+    psql_debug!("[{}].", query);
+
+    let client = get_psql_client().await?;
+    let rows = client.query(&query, &[&resource]).await?;
+    Ok(rows
+        .iter()
+        .map(|row| (row.get("version"), row.get("checksum")))
+        .collect())
+}
+
+/// Runs the migration's queries and records the migration in a single transaction
+async fn apply_migration(resource: &str, migration: &Migration) -> Result<(), ArrErr> {
+    psql_info!(
+        "Applying migration [{}] version [{}]: {}.",
+        resource,
+        migration.version,
+        migration.description
+    );
+
+    let mut client = get_psql_client().await?;
+    let transaction = client.transaction().await?;
+    for query in &migration.queries {
+        psql_debug!("[{}].", query);
+        if let Err(e) = transaction.execute(query.as_str(), &[]).await {
+            psql_error!(
+                "Failed to apply migration [{}] version [{}]: {}",
+                resource,
+                migration.version,
+                e
+            );
+            transaction.rollback().await?;
+            return Err(e.into());
+        }
+    }
+
+    let insert = format!(
+        r#"INSERT INTO "{}" ("resource", "version", "description", "checksum") VALUES ($1, $2, $3, $4)"#,
+        MIGRATIONS_TABLE
+    );
+    transaction
+        .execute(
+            &insert,
+            &[
+                &resource,
+                &migration.version,
+                &migration.description,
+                &migration.checksum(),
+            ],
+        )
+        .await?;
+    transaction.commit().await.map_err(ArrErr::from)
+}
+
+/// Returns the table columns as found in the `information_schema`
+async fn get_table_columns(table: &str) -> Result<Vec<TableColumn>, ArrErr> {
+    let query = r#"SELECT "column_name", "udt_name", "is_nullable" FROM information_schema.columns WHERE "table_schema" = current_schema() AND "table_name" = $1"#;
+    psql_debug!("[{}].", query);
+
+    let client = get_psql_client().await?;
+    let rows = client.query(query, &[&table]).await?;
+    Ok(rows
+        .iter()
+        .map(|row| TableColumn {
+            name: row.get("column_name"),
+            udt_name: row.get("udt_name"),
+            nullable: row.get::<&str, String>("is_nullable") == "YES",
+        })
+        .collect())
+}
+
+/// Makes sure the migration versions are unique and incrementing.
+/// Only the baseline step is allowed to use the [`BASELINE_VERSION`].
+fn validate_migrations(resource: &str, migrations: &[Migration]) -> Result<(), ArrErr> {
+    let mut last_version: Option<i64> = None;
+    for migration in migrations {
+        let valid = match last_version {
+            None => migration.version == BASELINE_VERSION,
+            Some(last) => migration.version > last,
+        };
+        if !valid {
+            let error = format!(
+                "Invalid migration version [{}] for [{}], versions should be unique and incrementing.",
+                migration.version, resource
+            );
+            psql_error!("{}", error);
+            return Err(ArrErr::Error(error));
+        }
+        last_version = Some(migration.version);
+    }
+    Ok(())
+}
+
+/// Returns the `udt_name` we expect to find in the `information_schema` for the given field type.
+/// Should match the types used by [`create_db`](super::init::create_db).
+fn get_expected_udt_name(field_type: &PsqlFieldType) -> String {
+    match *field_type {
+        PsqlFieldType::ANYENUM => String::from("text"),
+        PsqlFieldType::PATH | PsqlFieldType::POINT | PsqlFieldType::POLYGON => {
+            String::from("geometry")
+        }
+        PsqlFieldType::JSON => String::from("jsonb"),
+        PsqlFieldType::INT8_ARRAY => String::from("_int8"),
+        _ => field_type.name().to_lowercase(),
+    }
+}
+
+/// Compares the given [`ResourceDefinition`] with the provided table columns
+fn get_definition_drift(
+    definition: &ResourceDefinition,
+    columns: &[TableColumn],
+) -> Vec<SchemaDrift> {
+    let table = definition.get_psql_table();
+    if columns.is_empty() {
+        return vec![SchemaDrift::MissingTable { table }];
+    }
+
+    // All id columns are created as non nullable UUID columns
+    let mut expected: HashMap<String, (String, bool)> = definition
+        .fields
+        .iter()
+        .map(|(name, field)| {
+            (
+                name.clone(),
+                (
+                    get_expected_udt_name(&field.field_type),
+                    !field.is_mandatory(),
+                ),
+            )
+        })
+        .collect();
+    for id_col in definition.get_psql_id_cols() {
+        expected.insert(id_col, (String::from("uuid"), false));
+    }
+
+    let mut drift = vec![];
+    for column in columns {
+        match expected.remove(&column.name) {
+            Some((udt_name, nullable)) => {
+                if udt_name != column.udt_name {
+                    drift.push(SchemaDrift::TypeMismatch {
+                        table: table.clone(),
+                        column: column.name.clone(),
+                        expected: udt_name,
+                        found: column.udt_name.clone(),
+                    });
+                }
+                if nullable != column.nullable {
+                    drift.push(SchemaDrift::NullableMismatch {
+                        table: table.clone(),
+                        column: column.name.clone(),
+                        expected_nullable: nullable,
+                    });
+                }
+            }
+            None => drift.push(SchemaDrift::UnknownColumn {
+                table: table.clone(),
+                column: column.name.clone(),
+            }),
+        }
+    }
+
+    let mut missing: Vec<String> = expected.into_keys().collect();
+    missing.sort();
+    for column in missing {
+        drift.push(SchemaDrift::MissingColumn {
+            table: table.clone(),
+            column,
+        });
+    }
+
+    drift
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::resources::base::FieldDefinition;
+    use crate::test_util::*;
+
+    fn get_test_definition() -> ResourceDefinition {
+        ResourceDefinition {
+            psql_table: String::from("test"),
+            psql_id_cols: vec![String::from("test_id")],
+            fields: HashMap::from([
+                (
+                    String::from("name"),
+                    FieldDefinition::new(PsqlFieldType::TEXT, true),
+                ),
+                (
+                    String::from("status"),
+                    FieldDefinition::new(PsqlFieldType::ANYENUM, true),
+                ),
+                (
+                    String::from("location"),
+                    FieldDefinition::new(PsqlFieldType::POINT, false),
+                ),
+                (
+                    String::from("deleted_at"),
+                    FieldDefinition::new_internal(PsqlFieldType::TIMESTAMPTZ, false),
+                ),
+            ]),
+        }
+    }
+
+    fn get_column(name: &str, udt_name: &str, nullable: bool) -> TableColumn {
+        TableColumn {
+            name: String::from(name),
+            udt_name: String::from(udt_name),
+            nullable,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_migration_checksum() {
+        assert_init_done().await;
+        ut_info!("start");
+
+        let migration = Migration::new(
+            1,
+            "add column",
+            vec![String::from(
+                r#"ALTER TABLE "test" ADD COLUMN IF NOT EXISTS "name" TEXT"#,
+            )],
+        );
+        let same = Migration::new(
+            1,
+            "other description",
+            vec![String::from(
+                r#" ALTER TABLE "test" ADD COLUMN IF NOT EXISTS "name" TEXT "#,
+            )],
+        );
+        let changed = Migration::new(
+            1,
+            "add column",
+            vec![String::from(
+                r#"ALTER TABLE "test" ADD COLUMN IF NOT EXISTS "name" UUID"#,
+            )],
+        );
+
+        assert_eq!(migration.checksum().len(), 64);
+        assert_eq!(migration.checksum(), same.checksum());
+        assert_ne!(migration.checksum(), changed.checksum());
+
+        ut_info!("success");
+    }
+
+    #[tokio::test]
+    async fn test_validate_migrations() {
+        assert_init_done().await;
+        ut_info!("start");
+
+        let baseline = Migration::new(BASELINE_VERSION, "baseline", vec![]);
+        let first = Migration::new(1, "first", vec![]);
+        let second = Migration::new(2, "second", vec![]);
+
+        assert!(
+            validate_migrations("test", &[baseline.clone(), first.clone(), second.clone()]).is_ok()
+        );
+        assert!(
+            validate_migrations("test", &[baseline.clone(), second.clone(), first.clone()])
+                .is_err()
+        );
+        assert!(validate_migrations("test", &[baseline.clone(), first.clone(), first]).is_err());
+        assert!(validate_migrations("test", &[second]).is_err());
+
+        ut_info!("success");
+    }
+
+    #[tokio::test]
+    async fn test_get_definition_drift_none() {
+        assert_init_done().await;
+        ut_info!("start");
+
+        let columns = vec![
+            get_column("test_id", "uuid", false),
+            get_column("name", "text", false),
+            get_column("status", "text", false),
+            get_column("location", "geometry", true),
+            get_column("deleted_at", "timestamptz", true),
+        ];
+        assert!(get_definition_drift(&get_test_definition(), &columns).is_empty());
+
+        ut_info!("success");
+    }
+
+    #[tokio::test]
+    async fn test_get_definition_drift() {
+        assert_init_done().await;
+        ut_info!("start");
+
+        let definition = get_test_definition();
+        assert_eq!(
+            get_definition_drift(&definition, &[]),
+            vec![SchemaDrift::MissingTable {
+                table: String::from("test")
+            }]
+        );
+
+        let columns = vec![
+            get_column("test_id", "uuid", false),
+            get_column("name", "int8", false),
+            get_column("status", "text", true),
+            get_column("legacy", "text", true),
+        ];
+        let drift = get_definition_drift(&definition, &columns);
+        assert_eq!(
+            drift,
+            vec![
+                SchemaDrift::TypeMismatch {
+                    table: String::from("test"),
+                    column: String::from("name"),
+                    expected: String::from("text"),
+                    found: String::from("int8"),
+                },
+                SchemaDrift::NullableMismatch {
+                    table: String::from("test"),
+                    column: String::from("status"),
+                    expected_nullable: false,
+                },
+                SchemaDrift::UnknownColumn {
+                    table: String::from("test"),
+                    column: String::from("legacy"),
+                },
+                SchemaDrift::MissingColumn {
+                    table: String::from("test"),
+                    column: String::from("deleted_at"),
+                },
+                SchemaDrift::MissingColumn {
+                    table: String::from("test"),
+                    column: String::from("location"),
+                },
+            ]
+        );
+
+        ut_info!("success");
+    }
+}
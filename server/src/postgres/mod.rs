@@ -12,10 +12,14 @@ pub mod macros;
 ))]
 pub mod tests;
 
+pub mod audit;
 pub mod init;
 pub mod linked_resource;
+pub mod migrations;
+pub mod purge;
 pub mod simple_resource;
 pub mod simple_resource_linked;
+pub mod timeslot;
 pub(crate) mod util;
 
 mod pool;
@@ -31,7 +35,7 @@ use std::collections::HashMap;
 use std::fmt::Debug;
 use tokio_postgres::types::Type as PsqlFieldType;
 
-pub use self::search::{PsqlSearch, SearchCol};
+pub use self::search::{PagingInfo, PsqlRowStream, PsqlSearch, SearchCol};
 pub use crate::common::ArrErr;
 
 /// Provides a more readable format of a dynamic PostgreSQL field value
@@ -1,5 +1,6 @@
 use crate::config::Config;
 use deadpool::managed::{Object, PoolError};
+use deadpool::Status;
 use deadpool_postgres::{
     tokio_postgres::NoTls, Manager, ManagerConfig, Pool, RecyclingMethod, Runtime,
 };
@@ -7,10 +8,14 @@ use native_tls::{Certificate, Identity, TlsConnector};
 use postgres_native_tls::MakeTlsConnector;
 use std::fmt::Debug;
 use std::fs;
+use std::time::Duration;
 use tokio::sync::OnceCell;
 
 pub use crate::common::ArrErr;
 
+/// Maximum time a database readiness check may take before the database is considered unavailable
+pub const READINESS_TIMEOUT: Duration = Duration::from_secs(2);
+
 /// Create global variable to access our database pool
 pub(crate) static DB_POOL: OnceCell<Pool> = OnceCell::const_new();
 /// Shorthand function to get the database connection pool
@@ -53,6 +58,54 @@ pub(crate) async fn get_psql_client() -> Result<Object<Manager>, PoolError<tokio
     get_psql_pool().await.get().await
 }
 
+/// Returns the current [`Status`] of the database connection pool, or [`None`] if the pool
+/// has not been initialized yet
+pub fn get_psql_pool_status() -> Option<Status> {
+    DB_POOL.get().map(|pool| pool.status())
+}
+
+/// Checks if the database can serve queries
+///
+/// Will initialize the database connection pool if this didn't succeed before, so a
+/// database which was unreachable during startup will be picked up once it's available.
+/// Returns an error if the database could not be reached within [`READINESS_TIMEOUT`].
+#[cfg(any(not(feature = "stub_backends"), feature = "vendored-openssl"))]
+pub async fn psql_readiness() -> Result<(), ArrErr> {
+    let check = async {
+        match DB_POOL.get() {
+            Some(pool) => PostgresPool { pool: pool.clone() }.readiness().await,
+            None => {
+                let pg = PostgresPool::from_config()?;
+                pg.readiness().await?;
+                // Another task might have initialized the pool in the meantime, which is fine.
+                let _ = DB_POOL.set(pg.pool);
+                Ok(())
+            }
+        }
+    };
+
+    match tokio::time::timeout(READINESS_TIMEOUT, check).await {
+        Ok(result) => result,
+        Err(_) => {
+            let error = format!(
+                "Database readiness check timed out after {} seconds.",
+                READINESS_TIMEOUT.as_secs()
+            );
+            psql_error!("{}", error);
+            Err(ArrErr::Error(error))
+        }
+    }
+}
+
+/// Checks if the database can serve queries
+///
+/// Always succeeds since there is no database to check when using stubbed backends.
+#[cfg(all(feature = "stub_backends", not(feature = "vendored-openssl")))]
+pub async fn psql_readiness() -> Result<(), ArrErr> {
+    psql_debug!("(MOCK) Database is ready.");
+    Ok(())
+}
+
 /// Postgres Pool
 #[derive(Debug)]
 pub struct PostgresPool {
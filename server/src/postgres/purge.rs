@@ -0,0 +1,390 @@
+//! Permanently removes archived records
+//!
+//! Archived records are kept in the database with a `deleted_at` value. Once this value is older
+//! than the retention period configured for the resource ([`Config::purge_retention_days`](crate::config::Config::purge_retention_days)),
+//! the record can be purged. Any link table entries referencing the record will be removed as well.
+//!
+//! Purged records and link table entries are recorded in the audit log using their ids only, the
+//! values of earlier audit log entries of the record are redacted (see [`audit::redact_entries`]), and a
+//! [`ChangeType::Delete`](crate::grpc::server::ChangeType::Delete) change is published to the
+//! resource's `watch` streams for each purged record.
+
+use super::audit;
+use super::simple_resource::PsqlType;
+use super::{get_psql_client, ArrErr};
+use crate::cache;
+use crate::grpc::server::audit::AuditAction;
+use crate::grpc::server::*;
+use crate::grpc::GrpcSimpleService;
+use crate::resources::base::{Resource, ResourceDefinition, ResourceObject};
+
+use lib_common::uuid::Uuid;
+use serde_json::{Map, Value};
+use std::collections::HashMap;
+use tokio_postgres::Row;
+
+/// Records purged for a single resource
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PurgeResult {
+    /// name of the resource's table
+    pub resource: String,
+    /// ids of the purged records
+    pub ids: Vec<Uuid>,
+}
+
+/// Generic PostgreSQL trait to provide purge functions for simple `Resource` structs
+#[tonic::async_trait]
+pub trait PsqlPurgeResource
+where
+    Self: PsqlType,
+{
+    /// Permanently deletes all archived records of which the `deleted_at` value is older than
+    /// the provided retention period. Entries referencing the record in any of the provided link
+    /// tables will be deleted in the same transaction.
+    ///
+    /// Records which can not be deleted (still referenced by another record for example) are
+    /// skipped and will be retried on the next run.
+    ///
+    /// Returns the ids of the purged records.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ArrErr`] if no id column is configured for the resource
+    /// Returns [`ArrErr`] Database Error if the archived records could not be retrieved
+    async fn purge_table(
+        retention_days: u32,
+        links: &[ResourceDefinition],
+    ) -> Result<Vec<Uuid>, ArrErr> {
+        Ok(Self::purge_table_rows(retention_days, links)
+            .await?
+            .into_iter()
+            .map(|(id, _)| id)
+            .collect())
+    }
+
+    /// Same as [`purge_table`](Self::purge_table), but returns the purged rows together with
+    /// their ids.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ArrErr`] if no id column is configured for the resource
+    /// Returns [`ArrErr`] Database Error if the archived records could not be retrieved
+    async fn purge_table_rows(
+        retention_days: u32,
+        links: &[ResourceDefinition],
+    ) -> Result<Vec<(Uuid, Row)>, ArrErr> {
+        let definition = Self::get_definition();
+        if !definition.has_field("deleted_at") {
+            psql_debug!(
+                "Table [{}] has no [deleted_at] field, nothing to purge.",
+                definition.psql_table
+            );
+            return Ok(vec![]);
+        }
+
+        let id_col = Self::try_get_id_field()?;
+        let links = get_links(&id_col, links);
+        let query = format!(
+            r#"SELECT "{}" FROM "{}" WHERE "deleted_at" < NOW() - ($1::INT8 * INTERVAL '1 day')"#,
+            id_col, definition.psql_table
+        );
+        psql_debug!("[{}].", query);
+
+        let client = get_psql_client().await?;
+        let rows = client.query(&query, &[&i64::from(retention_days)]).await?;
+
+        let mut purged = vec![];
+        for row in rows {
+            let id: Uuid = row.get(id_col.as_str());
+            match purge_record(
+                &definition,
+                &Self::get_psql_from(None),
+                &id_col,
+                &id,
+                &links,
+            )
+            .await
+            {
+                Ok(row) => purged.push((id, row)),
+                Err(e) => psql_warn!(
+                    "Could not purge [{}] record [{}], skipping: {}",
+                    definition.psql_table,
+                    id,
+                    e
+                ),
+            }
+        }
+        Ok(purged)
+    }
+}
+impl<T> PsqlPurgeResource for T where T: PsqlType {}
+
+/// Purges archived records for all resources with a configured retention period.
+///
+/// Resources are purged in the same order as [`drop_db`](super::init::drop_db), using
+/// [`with_resources_in_drop_order`](crate::with_resources_in_drop_order), making sure referencing
+/// records are removed before the records they reference.
+pub async fn purge(retention_days: &HashMap<String, u32>) -> Result<Vec<PurgeResult>, ArrErr> {
+    psql_info!("Purging archived records.");
+    let links = get_link_definitions();
+
+    let mut results = vec![];
+    macro_rules! purge_resources {
+        ($($kind:ident $resource:ident),+ $(,)?) => {
+            $(purge_resources!(@$kind $resource);)+
+        };
+        (@simple $resource:ident) => {
+            let rows = purge_resource::<ResourceObject<$resource::Data>>(
+                retention_days,
+                &links,
+                &mut results,
+            )
+            .await?;
+            <$resource::GrpcServer as GrpcSimpleService>::publish_removed(rows);
+        };
+        (@$kind:ident $resource:ident) => {};
+    }
+    crate::with_resources_in_drop_order!(purge_resources);
+
+    for resource in retention_days.keys() {
+        if !results.iter().any(|result| &result.resource == resource) {
+            psql_warn!(
+                "Retention period configured for unknown resource [{}], ignoring.",
+                resource
+            );
+        }
+    }
+
+    let count: usize = results.iter().map(|result| result.ids.len()).sum();
+    psql_info!("Purged [{}] archived records.", count);
+    Ok(results)
+}
+
+/// Purges the archived records of a single resource if a retention period is configured for it.
+///
+/// Returns the purged rows together with their ids.
+async fn purge_resource<T: PsqlPurgeResource>(
+    retention_days: &HashMap<String, u32>,
+    links: &[ResourceDefinition],
+    results: &mut Vec<PurgeResult>,
+) -> Result<Vec<(Uuid, Row)>, ArrErr> {
+    let resource = T::get_psql_table();
+    let days = match retention_days.get(&resource) {
+        Some(days) => *days,
+        None => {
+            psql_debug!(
+                "No retention period configured for [{}], skipping.",
+                resource
+            );
+            return Ok(vec![]);
+        }
+    };
+
+    let rows = T::purge_table_rows(days, links).await?;
+    let ids: Vec<Uuid> = rows.iter().map(|(id, _)| *id).collect();
+    for id in &ids {
+        psql_info!("Purged [{}] record [{}].", resource, id);
+    }
+    psql_info!(
+        "Purged [{}] archived records from [{}] older than [{}] days.",
+        ids.len(),
+        resource,
+        days
+    );
+    results.push(PurgeResult { resource, ids });
+    Ok(rows)
+}
+
+/// Deletes a single archived record, including its link table entries, in a single transaction.
+///
+/// The deletions are recorded in the audit log using the ids only, and the record's earlier audit
+/// log entries are redacted, so none of the purged values are kept. Returns the deleted row, as selected from the provided `from` clause
+/// (see [`Resource::get_psql_from`]).
+async fn purge_record(
+    definition: &ResourceDefinition,
+    from: &str,
+    id_col: &str,
+    id: &Uuid,
+    links: &[ResourceDefinition],
+) -> Result<Row, ArrErr> {
+    let table = &definition.psql_table;
+    let mut client = get_psql_client().await?;
+    let transaction = client.transaction().await?;
+
+    // Select the row first, the resource's select might include columns of other tables
+    let query = format!(r#"SELECT * FROM {} WHERE "{}" = $1"#, from, id_col);
+    psql_debug!("[{}].", query);
+    let row = match transaction.query_opt(&query, &[id]).await {
+        Ok(Some(row)) => row,
+        Ok(None) => {
+            transaction.rollback().await?;
+            return Err(ArrErr::Error(format!(
+                "Record [{}] does not exist anymore",
+                id
+            )));
+        }
+        Err(e) => {
+            transaction.rollback().await?;
+            return Err(e.into());
+        }
+    };
+
+    // Make sure the record is still archived, it might have been restored in the meantime
+    let query = format!(
+        r#"DELETE FROM "{}" WHERE "{}" = $1 AND "deleted_at" IS NOT NULL"#,
+        table, id_col
+    );
+    psql_debug!("[{}].", query);
+    match transaction.execute(&query, &[id]).await {
+        Ok(0) => {
+            transaction.rollback().await?;
+            return Err(ArrErr::Error(format!(
+                "Record [{}] is not archived anymore",
+                id
+            )));
+        }
+        Ok(_) => {}
+        Err(e) => {
+            transaction.rollback().await?;
+            return Err(e.into());
+        }
+    }
+
+    // Values recorded for the record before should not be kept either
+    if let Err(e) = audit::redact_entries(&transaction, id).await {
+        transaction.rollback().await?;
+        return Err(e);
+    }
+
+    let mut ids = Map::new();
+    ids.insert(id_col.to_owned(), Value::String(id.to_string()));
+    let ids = Value::Object(ids);
+    if let Err(e) = audit::write_entry(
+        &transaction,
+        definition,
+        AuditAction::Purge,
+        Some(&ids),
+        None,
+    )
+    .await
+    {
+        transaction.rollback().await?;
+        return Err(e);
+    }
+
+    for link in links {
+        let query = format!(
+            r#"DELETE FROM "{}" WHERE "{}" = $1 RETURNING {}"#,
+            link.psql_table,
+            id_col,
+            link.psql_id_cols
+                .iter()
+                .map(|col| format!(r#""{}""#, col))
+                .collect::<Vec<String>>()
+                .join(", ")
+        );
+        psql_debug!("[{}].", query);
+        let link_rows = match transaction.query(&query, &[id]).await {
+            Ok(rows) => rows,
+            Err(e) => {
+                transaction.rollback().await?;
+                return Err(e.into());
+            }
+        };
+        for link_row in link_rows {
+            let mut link_ids = Map::new();
+            for col in &link.psql_id_cols {
+                let value: Uuid = link_row.get(col.as_str());
+                link_ids.insert(col.clone(), Value::String(value.to_string()));
+            }
+            let link_ids = Value::Object(link_ids);
+            if let Err(e) = audit::write_entry(
+                &transaction,
+                link,
+                AuditAction::Unlink,
+                Some(&link_ids),
+                None,
+            )
+            .await
+            {
+                transaction.rollback().await?;
+                return Err(e);
+            }
+        }
+    }
+    transaction.commit().await?;
+
+    cache::invalidate_resource(table).await;
+    for link in links {
+        cache::invalidate_resource(&link.psql_table).await;
+    }
+    Ok(row)
+}
+
+/// Returns the definitions of all link tables which could reference a simple resource
+fn get_link_definitions() -> Vec<ResourceDefinition> {
+    macro_rules! link_definitions {
+        ($($kind:ident $resource:ident),+ $(,)?) => {{
+            let mut definitions = vec![];
+            $(link_definitions!(@$kind $resource, definitions);)+
+            definitions
+        }};
+        (@link $resource:ident, $definitions:ident) => {
+            $definitions.push(ResourceObject::<$resource::Data>::get_definition());
+        };
+        (@$kind:ident $resource:ident, $definitions:ident) => {};
+    }
+    crate::with_resources_in_drop_order!(link_definitions)
+}
+
+/// Returns the definitions of the link tables using the provided id column
+fn get_links(id_col: &str, links: &[ResourceDefinition]) -> Vec<ResourceDefinition> {
+    links
+        .iter()
+        .filter(|definition| definition.psql_id_cols.iter().any(|col| col == id_col))
+        .cloned()
+        .collect()
+}
+
+/// Returns the names of the link tables using the provided id column
+#[cfg(test)]
+fn get_link_tables(id_col: &str, links: &[ResourceDefinition]) -> Vec<String> {
+    get_links(id_col, links)
+        .iter()
+        .map(|definition| definition.get_psql_table())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::*;
+
+    #[tokio::test]
+    async fn test_get_link_tables() {
+        assert_init_done().await;
+        ut_info!("start");
+
+        let links = get_link_definitions();
+
+        let tables = get_link_tables("user_id", &links);
+        assert_eq!(tables, vec![String::from("user_group")]);
+
+        let tables = get_link_tables("flight_plan_id", &links);
+        assert_eq!(
+            tables,
+            vec![
+                String::from("flight_plan_parcel"),
+                String::from("itinerary_flight_plan")
+            ]
+        );
+
+        let tables = get_link_tables("group_id", &links);
+        assert_eq!(tables.len(), 4);
+
+        let tables = get_link_tables("pilot_id", &links);
+        assert!(tables.is_empty());
+
+        ut_info!("success");
+    }
+}
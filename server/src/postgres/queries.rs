@@ -1,18 +1,26 @@
 //! Psql Simple resource Traits
 
 use super::get_psql_client;
-use super::{util::*, ArrErr, PsqlData, PsqlField, PsqlFieldSend};
+use super::{audit, timeslot, util::*, ArrErr, PsqlData, PsqlField, PsqlFieldSend};
+use crate::cache;
+use crate::grpc::server::audit::AuditAction;
+use crate::grpc::server::ValidationError;
 use crate::grpc::GrpcDataObjectType;
-use crate::resources::base::Resource;
-use deadpool_postgres::GenericClient;
+use crate::metrics;
+use crate::resources::base::{Resource, ResourceDefinition};
+use crate::telemetry;
+use deadpool_postgres::{GenericClient, Transaction};
+use lib_common::time::{DateTime, Utc};
 use lib_common::uuid::Uuid;
 use std::collections::HashMap;
 use std::vec;
 use tokio_postgres::Row;
+use tracing::{field::Empty, instrument};
 
 /// Generic get by id function to get a row using the UUID column
 #[cfg(not(tarpaulin_include))]
 // no_coverage: (R5) Can not be tested in unittest until https://github.com/sfackler/rust-postgres/pull/979 has been merged. Is part of integration tests, coverage report will need to be merged to show.
+#[instrument(skip_all, err, fields(db.system = "postgresql", db.sql.table = %V::get_psql_table(), db.statement = Empty))]
 pub async fn get_by_id<V>(id: &Uuid) -> Result<Row, ArrErr>
 where
     V: Resource + super::simple_resource::PsqlType,
@@ -22,10 +30,13 @@ where
     let definition = V::get_definition();
     let id_col = V::try_get_id_field()?;
     let client = get_psql_client().await?;
+    let _timer = metrics::query_timer(&definition.psql_table, "get_by_id");
     let query = format!(
-        r#"SELECT * FROM "{}" WHERE "{}" = $1"#,
-        definition.psql_table, id_col
+        r#"SELECT * FROM {} WHERE "{}" = $1"#,
+        V::get_psql_from(None),
+        id_col
     );
+    telemetry::record_statement(&query);
     let stmt = client.prepare_cached(&query).await?;
 
     psql_info!(
@@ -37,11 +48,50 @@ where
 
     client.query_one(&stmt, &[&id]).await.map_err(|e| e.into())
 }
+/// Generic find by id function to get a row using the UUID column
+///
+/// Archived rows will be skipped for resources with a `deleted_at` field, unless
+/// `include_archived` is set. Returns [`None`] if no matching row could be found.
+#[cfg(not(tarpaulin_include))]
+// no_coverage: (R5) Can not be tested in unittest until https://github.com/sfackler/rust-postgres/pull/979 has been merged. Is part of integration tests, coverage report will need to be merged to show.
+#[instrument(skip_all, err, fields(db.system = "postgresql", db.sql.table = %V::get_psql_table(), db.statement = Empty))]
+pub async fn find_by_id<V>(id: &Uuid, include_archived: bool) -> Result<Option<Row>, ArrErr>
+where
+    V: Resource + super::simple_resource::PsqlType,
+{
+    psql_debug!("Start: {:?}", id);
+
+    let definition = V::get_definition();
+    let id_col = V::try_get_id_field()?;
+    let client = get_psql_client().await?;
+    let _timer = metrics::query_timer(&definition.psql_table, "find_by_id");
+    let mut query = format!(
+        r#"SELECT * FROM {} WHERE "{}" = $1"#,
+        V::get_psql_from(None),
+        id_col
+    );
+    if !include_archived && definition.has_field("deleted_at") {
+        query.push_str(r#" AND "deleted_at" IS NULL"#);
+    }
+    telemetry::record_statement(&query);
+    let stmt = client.prepare_cached(&query).await?;
+
+    psql_info!(
+        "Fetching row data for table [{}]. uuid: {}",
+        definition.psql_table,
+        id
+    );
+    psql_debug!("[{}].", &query);
+
+    client.query_opt(&stmt, &[&id]).await.map_err(|e| e.into())
+}
+
 /// Generic get for id function to get rows for the provided key fields
 /// Since this is a linked resource, the id is expected to be given as a [Vec\<FieldValuePair\>]
 /// to specify the id_column / value pairs to match
 #[cfg(not(tarpaulin_include))]
 // no_coverage: (R5) Can not be tested in unittest until https://github.com/sfackler/rust-postgres/pull/979 has been merged. Is part of integration tests, coverage report will need to be merged to show.
+#[instrument(skip_all, err, fields(db.system = "postgresql", db.sql.table = %V::get_psql_table(), db.statement = Empty))]
 pub async fn get_for_ids<V>(ids: &HashMap<String, Uuid>) -> Result<Row, ArrErr>
 where
     V: Resource,
@@ -67,6 +117,8 @@ where
     }
 
     let client = get_psql_client().await?;
+    let _timer = metrics::query_timer(&definition.psql_table, "get_for_ids");
+    telemetry::record_statement(&query);
     let stmt = client.prepare_cached(&query).await?;
 
     psql_info!(
@@ -90,18 +142,29 @@ where
 
 /// Update the Object's database record using provided data
 ///
+/// If an `expected_updated_at` value is provided, the record will only be updated if its
+/// current `updated_at` value matches. The record will only be updated if the current value
+/// of each of the `expected_states` fields matches as well, so state transitions which have been
+/// validated against these values can't be bypassed by concurrent updates.
+///
 /// # Errors
 ///
 /// Returns [`ArrErr`] composing update vars error in field conversion.
+/// Returns [`ArrErr::VersionConflict`] if the provided `expected_updated_at` does not match the record's `updated_at` value
+/// Returns [`ArrErr::StateConflict`] if any of the `expected_states` does not match the record's current state
+/// Returns [`ArrErr::TimeslotConflict`] if the updated record's timeslot reservations conflict with any other record
 /// Returns [`ArrErr`] from [`PoolError`](deadpool::managed::PoolError) if no client connection could be returned from the connection [`Pool`](deadpool::managed::Pool)
 /// Returns [`ArrErr`] "Failed to update entries" if database query execution returns zero updated rows
 /// Returns [`ArrErr`] Database Error if database query execution failed
 #[cfg(not(tarpaulin_include))]
 // no_coverage: (R5) Is part of integration tests, coverage report will need to be merged to show.
+#[instrument(skip_all, err, fields(db.system = "postgresql", db.sql.table = %V::get_psql_table(), db.statement = Empty))]
 pub async fn update<'a, V, T>(
     ids: &HashMap<String, Uuid>,
     data: &T,
     psql_data: &PsqlData,
+    expected_updated_at: Option<&DateTime<Utc>>,
+    expected_states: &HashMap<String, String>,
 ) -> Result<(), ArrErr>
 where
     V: Send + super::simple_resource::SimpleResource<T>,
@@ -110,6 +173,7 @@ where
     psql_debug!("Start with data [{:?}] for ids [{:?}].", psql_data, ids);
 
     let definition = V::get_definition();
+    check_versioned(&definition, expected_updated_at)?;
     let (mut updates, mut params) = get_update_vars(data, psql_data, &definition)?;
 
     if definition.has_field("updated_at") {
@@ -121,20 +185,77 @@ where
         keys.push(format!(r#""{}" = ${}"#, id_field, &params.len() + 1));
         params.push(value);
     }
+    for (state_field, state) in expected_states {
+        keys.push(format!(
+            r#""{}"::TEXT = ${}"#,
+            state_field,
+            &params.len() + 1
+        ));
+        params.push(state);
+    }
 
-    update_with_params(&definition.psql_table, &updates, &params, &keys).await
+    match update_with_params::<V>(
+        &updates,
+        &params,
+        &keys,
+        ids,
+        expected_updated_at,
+        AuditAction::Update,
+    )
+    .await
+    {
+        Err(ArrErr::Error(error)) if !expected_states.is_empty() => {
+            let current =
+                match get_current_states(&definition.psql_table, ids, expected_states).await? {
+                    Some(current) => current,
+                    None => return Err(ArrErr::Error(error)),
+                };
+            let mut errors = vec![];
+            for (state_field, state) in expected_states {
+                match current.get(state_field) {
+                    Some(current) if current != state => errors.push(ValidationError {
+                        field: state_field.clone(),
+                        error: format!(
+                            "State changed from [{}] to [{}] by another update, the state transition has not been applied.",
+                            state, current
+                        ),
+                    }),
+                    _ => {}
+                }
+            }
+            if errors.is_empty() {
+                return Err(ArrErr::Error(error));
+            }
+            psql_info!(
+                "State conflict for [{}] with ids [{:?}]: {:?}",
+                definition.psql_table,
+                ids,
+                errors
+            );
+            Err(ArrErr::StateConflict(errors))
+        }
+        result => result,
+    }
 }
 
 /// Updates the database record setting the `deleted_at` field to current timestamp using the Object's UUID
 ///
+/// If an `expected_updated_at` value is provided, the record will only be updated if its
+/// current `updated_at` value matches.
+///
 /// # Errors
 ///
+/// Returns [`ArrErr::VersionConflict`] if the provided `expected_updated_at` does not match the record's `updated_at` value
 /// Returns [`ArrErr`] from [`PoolError`](deadpool::managed::PoolError) if no client connection could be returned from the connection [`Pool`](deadpool::managed::Pool)
 /// Returns [`ArrErr`] "Failed to update \[deleted_at\] col" if database query execution returns zero updated rows
 /// Returns [`ArrErr`] Database Error if database query execution failed
 #[cfg(not(tarpaulin_include))]
 // no_coverage: (R5) Is part of integration tests, coverage report will need to be merged to show.
-pub async fn set_deleted_at_now<'a, V, T>(ids: &HashMap<String, Uuid>) -> Result<(), ArrErr>
+#[instrument(skip_all, err, fields(db.system = "postgresql", db.sql.table = %V::get_psql_table(), db.statement = Empty))]
+pub async fn set_deleted_at_now<'a, V, T>(
+    ids: &HashMap<String, Uuid>,
+    expected_updated_at: Option<&DateTime<Utc>>,
+) -> Result<(), ArrErr>
 where
     V: Send + super::simple_resource::SimpleResource<T>,
     T: GrpcDataObjectType,
@@ -142,6 +263,7 @@ where
     psql_debug!("Start for ids [{:?}].", ids);
 
     let definition = V::get_definition();
+    check_versioned(&definition, expected_updated_at)?;
 
     let mut params: Vec<&PsqlField> = vec![];
     let mut keys: Vec<String> = vec![];
@@ -151,19 +273,35 @@ where
     }
     let updates = vec![r#""deleted_at" = NOW()"#.to_string()];
 
-    update_with_params(&definition.psql_table, &updates, &params, &keys).await
+    update_with_params::<V>(
+        &updates,
+        &params,
+        &keys,
+        ids,
+        expected_updated_at,
+        AuditAction::Delete,
+    )
+    .await
 }
 
 /// Delete database record from the database using the Object's UUID
 ///
+/// If an `expected_updated_at` value is provided, the record will only be deleted if its
+/// current `updated_at` value matches.
+///
 /// # Errors
 ///
+/// Returns [`ArrErr::VersionConflict`] if the provided `expected_updated_at` does not match the record's `updated_at` value
 /// Returns [`ArrErr`] from [`PoolError`](deadpool::managed::PoolError) if no client connection could be returned from the connection [`Pool`](deadpool::managed::Pool)
 /// Returns [`ArrErr`] "Failed to delete entry" if database query execution returns zero updated rows
 /// Returns [`ArrErr`] Database Error if database query execution failed
 #[cfg(not(tarpaulin_include))]
 // no_coverage: (R5) Is part of integration tests, coverage report will need to be merged to show.
-pub async fn delete_row<'a, V, T>(ids: &HashMap<String, Uuid>) -> Result<(), ArrErr>
+#[instrument(skip_all, err, fields(db.system = "postgresql", db.sql.table = %V::get_psql_table(), db.statement = Empty))]
+pub async fn delete_row<'a, V, T>(
+    ids: &HashMap<String, Uuid>,
+    expected_updated_at: Option<&DateTime<Utc>>,
+) -> Result<(), ArrErr>
 where
     V: Send + super::simple_resource::SimpleResource<T>,
     T: GrpcDataObjectType,
@@ -171,6 +309,7 @@ where
     psql_debug!("Start for ids [{:?}].", ids);
 
     let definition = V::get_definition();
+    check_versioned(&definition, expected_updated_at)?;
 
     let mut params: Vec<&PsqlField> = vec![];
     let mut keys: Vec<String> = vec![];
@@ -178,6 +317,10 @@ where
         keys.push(format!(r#""{}" = ${}"#, id_field, &params.len() + 1));
         params.push(value);
     }
+    if let Some(expected_updated_at) = expected_updated_at {
+        keys.push(format!(r#""updated_at" = ${}"#, &params.len() + 1));
+        params.push(expected_updated_at);
+    }
 
     let delete_sql = &format!(
         r#"DELETE FROM "{}" WHERE {}"#,
@@ -193,24 +336,273 @@ where
     psql_debug!("[{}].", delete_sql);
     psql_debug!("[{:?}].", &params);
 
-    let client = get_psql_client().await?;
-    let stmt = client.prepare_cached(delete_sql).await?;
+    let mut client = get_psql_client().await?;
+    telemetry::record_statement(delete_sql);
+    let timer = metrics::query_timer(&definition.psql_table, "delete_row");
+    let transaction = client.transaction().await?;
+    let before = audit::get_rows_json(&transaction, &definition.psql_table, ids).await?;
 
-    client
-        .execute(&stmt, &params)
-        .await
-        .map_err(|e| e.into())
-        .map(|_| ())
+    let stmt = transaction.prepare_cached(delete_sql).await?;
+    let num_rows = transaction.execute(&stmt, &params).await?;
+    if num_rows == 0 {
+        if expected_updated_at.is_some() {
+            if let Some(current) = get_current_updated_at(&definition.psql_table, ids).await? {
+                return Err(ArrErr::VersionConflict(current));
+            }
+        }
+        return Ok(());
+    }
+
+    for row in &before {
+        audit::write_entry(
+            &transaction,
+            &definition,
+            AuditAction::Delete,
+            Some(row),
+            None,
+        )
+        .await?;
+    }
+    transaction.commit().await?;
+    timer.observe_duration();
+    cache::invalidate::<V>().await;
+
+    Ok(())
+}
+
+/// Restores an archived database record by clearing the `deleted_at` field using the Object's UUID
+///
+/// The resource's unique fields (see [`Resource::get_unique_fields`]) will be checked against
+/// all non archived records first. Both the check and the update are executed within a single
+/// transaction.
+///
+/// # Errors
+///
+/// Returns [`ArrErr::UniqueViolation`] if a non archived record exists with the same value(s) for any of the unique fields
+/// Returns [`ArrErr::TimeslotConflict`] if the restored record's timeslot reservations conflict with any other record
+/// Returns [`ArrErr`] from [`PoolError`](deadpool::managed::PoolError) if no client connection could be returned from the connection [`Pool`](deadpool::managed::Pool)
+/// Returns [`ArrErr`] "Failed to restore entry" if database query execution returns zero updated rows
+/// Returns [`ArrErr`] Database Error if database query execution failed
+#[cfg(not(tarpaulin_include))]
+// no_coverage: (R5) Is part of integration tests, coverage report will need to be merged to show.
+#[instrument(skip_all, err, fields(db.system = "postgresql", db.sql.table = %V::get_psql_table(), db.statement = Empty))]
+pub async fn restore<'a, V, T>(ids: &HashMap<String, Uuid>) -> Result<(), ArrErr>
+where
+    V: Send + super::simple_resource::SimpleResource<T>,
+    T: GrpcDataObjectType,
+{
+    psql_debug!("Start for ids [{:?}].", ids);
+
+    let definition = V::get_definition();
+
+    let mut params: Vec<&PsqlField> = vec![];
+    let mut keys: Vec<String> = vec![];
+    let mut other_keys: Vec<String> = vec![];
+    for (id_field, value) in ids {
+        keys.push(format!(r#""{}" = ${}"#, id_field, &params.len() + 1));
+        other_keys.push(format!(r#"other."{}" <> ${}"#, id_field, &params.len() + 1));
+        params.push(value);
+    }
+
+    let mut client = get_psql_client().await?;
+    let timer = metrics::query_timer(&definition.psql_table, "restore");
+    let transaction = client.transaction().await?;
+
+    for fields in V::get_unique_fields() {
+        let mut conditions: Vec<String> =
+            keys.iter().map(|key| format!("restored.{}", key)).collect();
+        conditions.push(format!("({})", other_keys.join(" OR ")));
+        conditions.push(r#"other."deleted_at" IS NULL"#.to_string());
+        for field in &fields {
+            conditions.push(format!(r#"other."{}" = restored."{}""#, field, field));
+        }
+        let exists_sql = &format!(
+            r#"SELECT EXISTS(SELECT 1 FROM "{}" AS other, "{}" AS restored WHERE {})"#,
+            definition.psql_table,
+            definition.psql_table,
+            conditions.join(" AND "),
+        );
+        psql_debug!("[{}].", exists_sql);
+        psql_debug!("[{:?}].", &params);
+
+        let row = transaction.query_one(exists_sql, &params).await?;
+        if row.get::<usize, bool>(0) {
+            let error = format!(
+                "A non archived record with the same value(s) for [{}] already exists in table [{}].",
+                fields.join(", "),
+                definition.psql_table
+            );
+            psql_info!("{}", error);
+            return Err(ArrErr::UniqueViolation(fields.join(", ")));
+        }
+    }
+
+    let mut updates = vec![r#""deleted_at" = NULL"#.to_string()];
+    if definition.has_field("updated_at") {
+        updates.push(r#""updated_at" = NOW()"#.to_string());
+    }
+    keys.push(r#""deleted_at" IS NOT NULL"#.to_string());
+    let update_sql = &format!(
+        r#"UPDATE "{}" SET {} WHERE {}"#,
+        definition.psql_table,
+        updates.join(", "),
+        keys.join(" AND "),
+    );
+
+    psql_info!(
+        "Restoring entry for table [{}]. uuids: {:?}",
+        definition.psql_table,
+        ids
+    );
+    psql_debug!("[{}].", update_sql);
+    psql_debug!("[{:?}].", &params);
+
+    let before = audit::get_rows_json(&transaction, &definition.psql_table, ids).await?;
+    telemetry::record_statement(update_sql);
+    let num_rows = transaction.execute(update_sql, &params).await?;
+    if num_rows == 0 {
+        let error = format!(
+            "Failed to restore entry for [{}] with where fields [{:?}] (does not exist or not archived?).",
+            definition.psql_table, keys
+        );
+        psql_info!("{}", error);
+        return Err(ArrErr::Error(error));
+    }
+    timeslot::check_conflicts::<V>(&transaction, ids).await?;
+
+    let after = audit::get_rows_json(&transaction, &definition.psql_table, ids).await?;
+    audit::write_entry(
+        &transaction,
+        &definition,
+        AuditAction::Restore,
+        before.first(),
+        after.first(),
+    )
+    .await?;
+    transaction.commit().await?;
+    timer.observe_duration();
+    cache::invalidate::<V>().await;
+
+    Ok(())
+}
+
+/// Returns an error if an `expected_updated_at` value was provided for a resource that
+/// does not have an `updated_at` field to compare it with.
+fn check_versioned(
+    definition: &ResourceDefinition,
+    expected_updated_at: Option<&DateTime<Utc>>,
+) -> Result<(), ArrErr> {
+    if expected_updated_at.is_some() && !definition.has_field("updated_at") {
+        let error = format!(
+            "An expected [updated_at] value was provided, but table [{}] has no [updated_at] col.",
+            definition.psql_table
+        );
+        psql_info!("{}", error);
+        return Err(ArrErr::Error(error));
+    }
+    Ok(())
 }
 
+/// Fetches the current `updated_at` value for the record matching the provided ids.
+///
+/// Returns [`None`] if no record could be found.
 #[cfg(not(tarpaulin_include))]
 // no_coverage: (R5) Is part of integration tests, coverage report will need to be merged to show.
-async fn update_with_params<'a>(
+#[instrument(skip_all, err, fields(db.system = "postgresql", db.sql.table = %table, db.statement = Empty))]
+async fn get_current_updated_at(
     table: &String,
+    ids: &HashMap<String, Uuid>,
+) -> Result<Option<DateTime<Utc>>, ArrErr> {
+    let mut params: Vec<&PsqlField> = vec![];
+    let mut keys: Vec<String> = vec![];
+    for (id_field, value) in ids {
+        keys.push(format!(r#""{}" = ${}"#, id_field, &params.len() + 1));
+        params.push(value);
+    }
+
+    let select_sql = &format!(
+        r#"SELECT "updated_at" FROM "{}" WHERE {}"#,
+        table,
+        keys.join(" AND "),
+    );
+    psql_debug!("[{}].", select_sql);
+    psql_debug!("[{:?}].", &params);
+
+    let client = get_psql_client().await?;
+    let _timer = metrics::query_timer(table, "get_current_updated_at");
+    telemetry::record_statement(select_sql);
+    let stmt = client.prepare_cached(select_sql).await?;
+    match client.query_opt(&stmt, &params).await? {
+        Some(row) => Ok(Some(row.try_get::<&str, DateTime<Utc>>("updated_at")?)),
+        None => Ok(None),
+    }
+}
+
+/// Returns the current values of the provided state fields as text for the record matching the
+/// provided ids, or [`None`] if no record could be found
+#[cfg(not(tarpaulin_include))]
+// no_coverage: (R5) Is part of integration tests, coverage report will need to be merged to show.
+async fn get_current_states(
+    table: &str,
+    ids: &HashMap<String, Uuid>,
+    states: &HashMap<String, String>,
+) -> Result<Option<HashMap<String, String>>, ArrErr> {
+    let mut params: Vec<&PsqlField> = vec![];
+    let mut keys: Vec<String> = vec![];
+    for (id_field, value) in ids {
+        keys.push(format!(r#""{}" = ${}"#, id_field, params.len() + 1));
+        params.push(value);
+    }
+    let fields: Vec<&String> = states.keys().collect();
+
+    let select_sql = &format!(
+        r#"SELECT {} FROM "{}" WHERE {}"#,
+        fields
+            .iter()
+            .map(|field| format!(r#""{0}"::TEXT AS "{0}""#, field))
+            .collect::<Vec<String>>()
+            .join(", "),
+        table,
+        keys.join(" AND "),
+    );
+    psql_debug!("[{}].", select_sql);
+    psql_debug!("[{:?}].", &params);
+
+    let client = get_psql_client().await?;
+    let _timer = metrics::query_timer(table, "get_current_states");
+    telemetry::record_statement(select_sql);
+    let stmt = client.prepare_cached(select_sql).await?;
+    match client.query_opt(&stmt, &params).await? {
+        Some(row) => {
+            let mut current = HashMap::new();
+            for field in fields {
+                current.insert(field.clone(), row.try_get::<&str, String>(field)?);
+            }
+            Ok(Some(current))
+        }
+        None => Ok(None),
+    }
+}
+
+#[cfg(not(tarpaulin_include))]
+// no_coverage: (R5) Is part of integration tests, coverage report will need to be merged to show.
+async fn update_with_params<'a, V: Resource>(
     updates: &[String],
-    params: &Vec<&'a PsqlField>,
-    where_fields: &Vec<String>,
+    params: &[&'a PsqlField],
+    where_fields: &[String],
+    ids: &HashMap<String, Uuid>,
+    expected_updated_at: Option<&'a DateTime<Utc>>,
+    action: AuditAction,
 ) -> Result<(), ArrErr> {
+    let definition = V::get_definition();
+    let table = &definition.psql_table;
+    let mut params = params.to_vec();
+    let mut where_fields = where_fields.to_vec();
+    if let Some(expected_updated_at) = expected_updated_at {
+        where_fields.push(format!(r#""updated_at" = ${}"#, params.len() + 1));
+        params.push(expected_updated_at);
+    }
+
     let update_sql = &format!(
         r#"UPDATE "{}" SET {} WHERE {}"#,
         table,
@@ -222,14 +614,48 @@ async fn update_with_params<'a>(
     psql_debug!("[{}].", update_sql);
     psql_debug!("[{:?}].", params);
 
-    let client = get_psql_client().await?;
-    let stmt = client.prepare_cached(update_sql).await?;
-    match client.execute(&stmt, params).await {
+    let mut client = get_psql_client().await?;
+    let operation = match action {
+        AuditAction::Delete => "set_deleted_at_now",
+        _ => "update",
+    };
+    telemetry::record_statement(update_sql);
+    let timer = metrics::query_timer(table, operation);
+    let transaction = client.transaction().await?;
+    let before = audit::get_rows_json(&transaction, table, ids).await?;
+
+    let stmt = transaction.prepare_cached(update_sql).await?;
+    match transaction.execute(&stmt, &params).await {
         Ok(num_rows) => {
             if num_rows >= 1 {
-                //TODO(R5): flush shared memcache for this resource when memcache is implemented
+                if action != AuditAction::Delete {
+                    timeslot::check_conflicts::<V>(&transaction, ids).await?;
+                }
+                let after = audit::get_rows_json(&transaction, table, ids).await?;
+                audit::write_entry(
+                    &transaction,
+                    &definition,
+                    action,
+                    before.first(),
+                    after.first(),
+                )
+                .await?;
+                transaction.commit().await?;
+                timer.observe_duration();
+                cache::invalidate::<V>().await;
                 Ok(())
             } else {
+                if expected_updated_at.is_some() {
+                    if let Some(current) = get_current_updated_at(table, ids).await? {
+                        psql_info!(
+                            "Version conflict for [{}] with where fields [{:?}], current [updated_at]: {}.",
+                            table,
+                            where_fields,
+                            current
+                        );
+                        return Err(ArrErr::VersionConflict(current));
+                    }
+                }
                 let error = format!(
                     "Failed to update [deleted_at] col for [{}] with where fields [{:?}] (does not exist?).",
                     table, where_fields
@@ -241,3 +667,48 @@ async fn update_with_params<'a>(
         Err(e) => Err(e.into()),
     }
 }
+
+/// Deletes all rows matching the provided ids using the provided transaction, recording an audit
+/// log entry with the given [`AuditAction`] for each deleted row.
+///
+/// All rows of the table will be deleted if no ids are provided.
+///
+/// Returns the number of deleted rows.
+///
+/// # Errors
+///
+/// Returns [`ArrErr`] Database Error if database query execution failed
+#[cfg(not(tarpaulin_include))]
+// no_coverage: (R5) Is part of integration tests, coverage report will need to be merged to show.
+#[instrument(skip_all, err, fields(db.system = "postgresql", db.sql.table = %definition.psql_table, db.statement = Empty))]
+pub async fn delete_for_ids(
+    transaction: &Transaction<'_>,
+    definition: &ResourceDefinition,
+    ids: &HashMap<String, Uuid>,
+    action: AuditAction,
+) -> Result<u64, ArrErr> {
+    let mut params: Vec<&PsqlField> = vec![];
+    let mut keys: Vec<String> = vec![];
+    for (id_field, value) in ids {
+        keys.push(format!(r#""{}" = ${}"#, id_field, &params.len() + 1));
+        params.push(value);
+    }
+
+    let mut delete_sql = format!(r#"DELETE FROM "{}""#, definition.psql_table);
+    if !keys.is_empty() {
+        delete_sql.push_str(&format!(" WHERE {}", keys.join(" AND ")));
+    }
+    psql_debug!("[{}].", delete_sql);
+    psql_debug!("[{:?}].", &params);
+
+    telemetry::record_statement(&delete_sql);
+    let _timer = metrics::query_timer(&definition.psql_table, "delete_for_ids");
+    let before = audit::get_rows_json(transaction, &definition.psql_table, ids).await?;
+    let stmt = transaction.prepare_cached(&delete_sql).await?;
+    let num_rows = transaction.execute(&stmt, &params).await?;
+
+    for row in &before {
+        audit::write_entry(transaction, definition, action, Some(row), None).await?;
+    }
+    Ok(num_rows)
+}
@@ -2,17 +2,39 @@
 
 use super::{get_psql_client, ArrErr, PsqlField, PsqlFieldType};
 use crate::grpc::server::{
-    search::get_single_search_value, AdvancedSearchFilter, ComparisonOperator, PredicateOperator,
-    SortOption, SortOrder,
+    search::get_single_search_value, AdvancedSearchFilter, ComparisonOperator, FilterOption,
+    PredicateOperator, SortOption, SortOrder,
 };
+use crate::metrics;
 use crate::postgres::PsqlFieldSend;
-use crate::resources::base::Resource;
+use crate::resources::base::{Resource, ResourceDefinition};
+use crate::telemetry;
 
+use futures::stream::{self, Stream, StreamExt};
 use lib_common::time::{DateTime, Utc};
 use lib_common::uuid::Uuid;
 use postgres_types::ToSql;
 use std::collections::VecDeque;
+use std::pin::Pin;
 use tokio_postgres::Row;
+use tracing::{field::Empty, instrument};
+
+/// Prefix used for the cursor value columns added to paged search queries
+const CURSOR_COL_PREFIX: &str = "_cursor_";
+
+/// Stream of database rows returned by [`PsqlSearch::advanced_search_stream`]
+pub type PsqlRowStream = Pin<Box<dyn Stream<Item = Result<Row, ArrErr>> + Send>>;
+
+/// Paging information for a search result
+#[derive(Eq, PartialEq, Clone, Debug, Default)]
+pub struct PagingInfo {
+    /// the total number of records matching the search filter, if known
+    pub total_count: Option<i64>,
+    /// indicates if more records are available after the current page
+    pub has_more: bool,
+    /// opaque cursor which can be used to retrieve the next page
+    pub next_cursor: Option<String>,
+}
 
 /// struct to save search col values while processing the [AdvancedSearchFilter](crate::resources::AdvancedSearchFilter)
 /// needed to save column information for a search value so it can be converted later
@@ -38,74 +60,156 @@ where
     Self: Resource + Sized,
 {
     /// Generic search function based on advanced filters
+    ///
+    /// Paged searches will always be sorted on the primary key columns after the provided
+    /// `order_by` columns, so the position of the last result can be used as cursor.
+    /// If a `cursor` is provided, only results after the cursor position will be returned.
+    #[instrument(skip_all, err, fields(db.system = "postgresql", db.sql.table = %Self::get_psql_table(), db.statement = Empty))]
     async fn advanced_search(filter: AdvancedSearchFilter) -> Result<Vec<Row>, ArrErr> {
         let definition = Self::get_definition();
         let client = get_psql_client().await?;
+        let _timer = metrics::query_timer(&definition.psql_table, "advanced_search");
 
-        let mut filter_params: Vec<SearchCol> = vec![];
-        let mut sort_expressions: Vec<String> = vec![];
-        let mut search_query = format!(r#"SELECT * FROM "{}""#, definition.psql_table);
-        let mut next_param_index: i32 = 1;
+        let (search_query, params) = Self::_get_search_query(&filter)?;
+        telemetry::record_statement(&search_query);
+        let search_sql = &client.prepare_cached(&search_query).await?;
 
-        // Go over all the filters and compose the search query string.
-        for filter in filter.filters.iter() {
-            let col = filter.search_field.clone();
+        psql_info!(
+            "Searching table [{}] with query [{}].",
+            definition.psql_table,
+            search_query
+        );
+        psql_debug!("Params: {:?}", params);
 
-            // Check if provided search col is part of the primary key
-            let field_type = if definition.get_psql_id_cols().contains(&col) {
-                PsqlFieldType::UUID
-            } else {
-                definition.try_get_field(&col)?.field_type.clone()
-            };
+        let mut ref_params: Vec<&PsqlField> = vec![];
+        for field in params.iter() {
+            ref_params.push(field.as_ref());
+        }
+        let rows = client
+            .query(search_sql, &ref_params[..])
+            .await
+            .map_err(|e| {
+                let error = format!(
+                    "Failed to search table [{}], an error returned from the database.",
+                    definition.psql_table
+                );
+                psql_error!("{}: [{}]", error, e);
+                ArrErr::Error(error)
+            })?;
 
-            let operator: PredicateOperator =
-                match PredicateOperator::try_from(filter.predicate_operator) {
-                    Ok(val) => val,
-                    Err(e) => {
-                        return Err(ArrErr::Error(format!(
-                            "Can't convert i32 [{}] into PredicateOperator Enum value: {}",
-                            filter.predicate_operator, e
-                        )));
-                    }
-                };
-            let comparison_operator = match filter.comparison_operator {
-                Some(operator) => match ComparisonOperator::try_from(operator) {
-                    Ok(operator) => operator.as_str_name(),
-                    Err(e) => {
-                        return Err(ArrErr::Error(format!(
-                            "Can't convert i32 [{}] into ComparisonOperator Enum value: {}",
-                            operator, e
-                        )));
-                    }
-                },
-                None => "WHERE",
-            };
+        Ok(rows)
+    }
 
-            let (filter_str, cur_param_index) = get_filter_str(
-                SearchCol {
-                    col_name: col,
-                    col_type: field_type,
-                    value: None,
-                },
-                filter.search_value.clone(),
-                &mut filter_params,
-                next_param_index,
-                operator,
-            )?;
+    /// Streaming variant of [`advanced_search`](PsqlSearch::advanced_search)
+    ///
+    /// Rows are returned one by one as they are received from the database instead of
+    /// collecting them all in memory first. The database connection is held until the
+    /// returned stream has been consumed or dropped.
+    #[instrument(skip_all, err, fields(db.system = "postgresql", db.sql.table = %Self::get_psql_table(), db.statement = Empty))]
+    async fn advanced_search_stream(filter: AdvancedSearchFilter) -> Result<PsqlRowStream, ArrErr> {
+        let definition = Self::get_definition();
+        let client = get_psql_client().await?;
+        let timer = metrics::query_timer(&definition.psql_table, "advanced_search_stream");
 
-            search_query.push_str(&format!(" {} {} ", comparison_operator, filter_str));
-            next_param_index = cur_param_index;
+        let (search_query, params) = Self::_get_search_query(&filter)?;
+        telemetry::record_statement(&search_query);
+        let search_sql = client.prepare_cached(&search_query).await?;
+
+        psql_info!(
+            "Streaming search on table [{}] with query [{}].",
+            definition.psql_table,
+            search_query
+        );
+        psql_debug!("Params: {:?}", params);
+
+        let rows = client
+            .query_raw(
+                &search_sql,
+                params.iter().map(|field| field.as_ref() as &PsqlField),
+            )
+            .await
+            .map_err(|e| {
+                let error = format!(
+                    "Failed to search table [{}], an error returned from the database.",
+                    definition.psql_table
+                );
+                psql_error!("{}: [{}]", error, e);
+                ArrErr::Error(error)
+            })?;
+
+        // Move the client into the stream so the connection is not returned to the pool
+        // while rows are still being received. The query latency will be recorded once the
+        // stream has been consumed or dropped.
+        let stream = stream::unfold(
+            (client, timer, Box::pin(rows)),
+            |(client, timer, mut rows)| async move {
+                let row = rows.next().await?;
+                Some((row.map_err(ArrErr::from), (client, timer, rows)))
+            },
+        );
+
+        Ok(Box::pin(stream))
+    }
+
+    /// Builds the search query and its parameters for the provided filter
+    fn _get_search_query(
+        filter: &AdvancedSearchFilter,
+    ) -> Result<(String, Vec<Box<PsqlFieldSend>>), ArrErr> {
+        let definition = Self::get_definition();
+
+        let limit_offset = get_limit_offset(filter);
+        let cursor_cols = get_cursor_cols(&definition, &filter.order_by)?;
+
+        let mut select_cols = vec![String::from("*")];
+        if limit_offset.is_some() {
+            // Add the cursor values as text so we can compose a cursor from the last row
+            for (index, (col, _)) in cursor_cols.iter().enumerate() {
+                select_cols.push(format!(
+                    r#""{}"::TEXT AS "{}{}""#,
+                    col.col_name, CURSOR_COL_PREFIX, index
+                ));
+            }
         }
+        let mut search_query = format!(
+            r#"SELECT {} FROM {}"#,
+            select_cols.join(", "),
+            Self::get_psql_from(None)
+        );
 
-        // Validate filter params making sure they are conform the column field type.
-        // Adding the value to the list of query parameters if valid.
-        let mut params: Vec<Box<PsqlFieldSend>> = vec![];
-        for search_col in filter_params.iter() {
-            params.push(Self::_param_from_search_col(search_col)?);
+        let (filters_str, mut params, mut next_param_index) = Self::_get_search_conditions(filter)?;
+
+        let mut conditions: Vec<String> = vec![];
+        if !filters_str.is_empty() {
+            conditions.push(filters_str);
+        }
+        if let Some(cursor) = &filter.cursor {
+            let values = decode_cursor(cursor, &cursor_cols)?;
+            let mut cursor_params: Vec<SearchCol> = vec![];
+            let (cursor_str, cur_param_index) =
+                get_cursor_str(&cursor_cols, values, &mut cursor_params, next_param_index)?;
+            for search_col in cursor_params.iter() {
+                params.push(param_from_search_col(search_col)?);
+            }
+            conditions.push(cursor_str);
+            next_param_index = cur_param_index;
+        }
+        match conditions.len() {
+            0 => (),
+            1 => search_query.push_str(&format!(" WHERE {}", conditions[0])),
+            _ => search_query.push_str(&format!(" WHERE ({})", conditions.join(") AND ("))),
         }
 
         // Check if we need to order the results on given parameters
-        if !filter.order_by.is_empty() {
+        if limit_offset.is_some() {
+            let sort_expressions: Vec<String> = cursor_cols
+                .iter()
+                .map(|(col, sort_order)| {
+                    format!(r#""{}" {}"#, col.col_name, sort_order.as_str_name())
+                })
+                .collect();
+            search_query.push_str(&format!(" ORDER BY {}", sort_expressions.join(",")));
+        } else if !filter.order_by.is_empty() {
+            let mut sort_expressions: Vec<String> = vec![];
             for sort_option in filter.order_by.iter() {
                 if definition.has_field(&sort_option.sort_field) {
                     sort_expressions.push(try_get_sort_str(sort_option)?);
@@ -118,20 +222,41 @@ where
             }
             search_query.push_str(&format!(" ORDER BY {}", sort_expressions.join(",")));
         }
-        if filter.results_per_page >= 0 && filter.page_number > 0 {
-            let offset: i64 = (filter.results_per_page * (filter.page_number - 1)).into();
+        if let Some((limit, offset)) = limit_offset {
             search_query.push_str(&format!(" LIMIT ${}", next_param_index));
-            params.push(Box::new(filter.results_per_page as i64));
+            params.push(Box::new(limit));
             next_param_index += 1;
-            search_query.push_str(&format!(" OFFSET ${}", next_param_index));
-            params.push(Box::new(offset));
+            // The cursor replaces the offset
+            if filter.cursor.is_none() {
+                search_query.push_str(&format!(" OFFSET ${}", next_param_index));
+                params.push(Box::new(offset));
+            }
         }
-        let search_sql = &client.prepare_cached(&search_query).await?;
+
+        Ok((search_query, params))
+    }
+
+    /// Counts the total number of records matching the provided filters, ignoring any paging
+    /// and ordering options.
+    #[instrument(skip_all, err, fields(db.system = "postgresql", db.sql.table = %Self::get_psql_table(), db.statement = Empty))]
+    async fn advanced_search_count(filter: &AdvancedSearchFilter) -> Result<i64, ArrErr> {
+        let definition = Self::get_definition();
+        let client = get_psql_client().await?;
+        let _timer = metrics::query_timer(&definition.psql_table, "advanced_search_count");
+
+        let mut count_query = format!(r#"SELECT COUNT(*) FROM "{}""#, definition.psql_table);
+        let (filters_str, params, _) = Self::_get_search_conditions(filter)?;
+        if !filters_str.is_empty() {
+            count_query.push_str(&format!(" WHERE {}", filters_str));
+        }
+
+        telemetry::record_statement(&count_query);
+        let count_sql = &client.prepare_cached(&count_query).await?;
 
         psql_info!(
-            "Searching table [{}] with query [{}].",
+            "Counting search results for table [{}] with query [{}].",
             definition.psql_table,
-            search_query
+            count_query
         );
         psql_debug!("Params: {:?}", params);
 
@@ -139,19 +264,108 @@ where
         for field in params.iter() {
             ref_params.push(field.as_ref());
         }
-        let rows = client
-            .query(search_sql, &ref_params[..])
+        let row = client
+            .query_one(count_sql, &ref_params[..])
             .await
             .map_err(|e| {
                 let error = format!(
-                    "Failed to search table [{}], an error returned from the database.",
+                    "Failed to count search results for table [{}], an error returned from the database.",
                     definition.psql_table
                 );
                 psql_error!("{}: [{}]", error, e);
                 ArrErr::Error(error)
             })?;
 
-        Ok(rows)
+        Ok(row.get(0))
+    }
+
+    /// Returns the total number of records matching the provided filter and a flag
+    /// indicating if there are more records available after the current page.
+    ///
+    /// The count query will only be executed if the total can't be determined
+    /// from the number of results returned for the requested page.
+    async fn get_paging_info(
+        filter: &AdvancedSearchFilter,
+        num_results: usize,
+    ) -> Result<(i64, bool), ArrErr> {
+        if let Some(paging_info) = get_paging_info_from_results(filter, num_results) {
+            return Ok(paging_info);
+        }
+
+        let total_count = Self::advanced_search_count(filter).await?;
+        let offset = get_limit_offset(filter).map_or(0, |(_, offset)| offset);
+        Ok((total_count, offset + (num_results as i64) < total_count))
+    }
+
+    /// Returns the [`PagingInfo`] for the provided filter and search results.
+    ///
+    /// For cursor based searches, the total count is not determined as this
+    /// would require a full count of the table for each page.
+    /// A `next_cursor` will only be provided if more results are available.
+    async fn get_search_paging_info(
+        filter: &AdvancedSearchFilter,
+        rows: &[Row],
+    ) -> Result<PagingInfo, ArrErr> {
+        let next_cursor = get_next_cursor(&Self::get_definition(), filter, rows)?;
+        if filter.cursor.is_some() {
+            return Ok(PagingInfo {
+                total_count: None,
+                has_more: next_cursor.is_some(),
+                next_cursor,
+            });
+        }
+
+        let (total_count, has_more) = Self::get_paging_info(filter, rows.len()).await?;
+        Ok(PagingInfo {
+            total_count: Some(total_count),
+            has_more,
+            next_cursor: next_cursor.filter(|_| has_more),
+        })
+    }
+
+    /// Composes the search conditions for the provided [`AdvancedSearchFilter`] and
+    /// returns them together with the validated query parameters and the next
+    /// parameter index.
+    /// Archived records will be excluded for resources with a `deleted_at` field,
+    /// unless `include_archived` is set.
+    /// for internal use
+    fn _get_search_conditions(
+        filter: &AdvancedSearchFilter,
+    ) -> Result<(String, Vec<Box<PsqlFieldSend>>, i32), ArrErr> {
+        let definition = Self::get_definition();
+        let mut filter_params: Vec<SearchCol> = vec![];
+        let mut filters_str = String::new();
+        let mut next_param_index: i32 = 1;
+
+        // Go over all the filters and compose the search query string.
+        if !filter.filters.is_empty() {
+            let (conditions_str, cur_param_index) = get_filters_str(
+                &definition,
+                &filter.filters,
+                &mut filter_params,
+                next_param_index,
+            )?;
+            filters_str = conditions_str;
+            next_param_index = cur_param_index;
+        }
+
+        // Archived records are excluded, unless explicitly requested
+        if !filter.include_archived && definition.has_field("deleted_at") {
+            filters_str = if filters_str.is_empty() {
+                String::from(r#""deleted_at" IS NULL"#)
+            } else {
+                format!(r#"({}) AND "deleted_at" IS NULL"#, filters_str)
+            };
+        }
+
+        // Validate filter params making sure they are conform the column field type.
+        // Adding the value to the list of query parameters if valid.
+        let mut params: Vec<Box<PsqlFieldSend>> = vec![];
+        for search_col in filter_params.iter() {
+            params.push(Self::_param_from_search_col(search_col)?);
+        }
+
+        Ok((filters_str, params, next_param_index))
     }
 
     /// Converts the passed string value for the search field into the right Sql type.
@@ -196,6 +410,301 @@ where
     }
 }
 
+/// Returns the `LIMIT` and `OFFSET` values for the provided [`AdvancedSearchFilter`]
+/// or [`None`] if no paging was requested.
+///
+/// The `page_number` is ignored if a `cursor` is provided, using `results_per_page` as limit
+/// without an offset.
+pub(crate) fn get_limit_offset(filter: &AdvancedSearchFilter) -> Option<(i64, i64)> {
+    if filter.results_per_page >= 0 && filter.cursor.is_some() {
+        Some((filter.results_per_page.into(), 0))
+    } else if filter.results_per_page >= 0 && filter.page_number > 0 {
+        let limit: i64 = filter.results_per_page.into();
+        let offset: i64 = limit * i64::from(filter.page_number - 1);
+        Some((limit, offset))
+    } else {
+        None
+    }
+}
+
+/// Tries to determine the total count and `has_more` flag based on the number of results
+/// returned for the requested page.
+///
+/// Returns [`None`] if a count query is needed to determine the total, which is the case
+/// when the page is full or if an empty page was returned for a page other than the first.
+pub(crate) fn get_paging_info_from_results(
+    filter: &AdvancedSearchFilter,
+    num_results: usize,
+) -> Option<(i64, bool)> {
+    let num_results = num_results as i64;
+    match get_limit_offset(filter) {
+        None => Some((num_results, false)),
+        Some((limit, offset)) => {
+            if num_results < limit && (num_results > 0 || offset == 0) {
+                Some((offset + num_results, false))
+            } else {
+                None
+            }
+        }
+    }
+}
+
+/// Returns the columns used to compose a search cursor with their sort order.
+///
+/// These are the valid `order_by` fields followed by the primary key columns
+/// of the resource, making sure each row has a unique position.
+pub(crate) fn get_cursor_cols(
+    definition: &ResourceDefinition,
+    order_by: &[SortOption],
+) -> Result<Vec<(SearchCol, SortOrder)>, ArrErr> {
+    let mut cursor_cols: Vec<(SearchCol, SortOrder)> = vec![];
+    for sort_option in order_by.iter() {
+        if !definition.has_field(&sort_option.sort_field) {
+            continue;
+        }
+        let sort_order = SortOrder::try_from(sort_option.sort_order).map_err(|e| {
+            ArrErr::Error(format!(
+                "Can't convert i32 [{}] into SortOperator Enum value: {}",
+                sort_option.sort_order, e
+            ))
+        })?;
+        // Enums are provided as their string value in the cursor
+        let field_type = definition
+            .try_get_field(&sort_option.sort_field)?
+            .field_type
+            .clone();
+        let col_type = match field_type {
+            PsqlFieldType::ANYENUM => PsqlFieldType::TEXT,
+            _ => field_type,
+        };
+        cursor_cols.push((
+            SearchCol {
+                col_name: sort_option.sort_field.clone(),
+                col_type,
+                value: None,
+            },
+            sort_order,
+        ));
+    }
+    for id_col in definition.get_psql_id_cols() {
+        if cursor_cols.iter().any(|(col, _)| col.col_name == id_col) {
+            continue;
+        }
+        cursor_cols.push((
+            SearchCol {
+                col_name: id_col,
+                col_type: PsqlFieldType::UUID,
+                value: None,
+            },
+            SortOrder::Asc,
+        ));
+    }
+
+    Ok(cursor_cols)
+}
+
+/// Composes an opaque cursor from the provided field / value pairs.
+pub(crate) fn encode_cursor(values: &[(String, Option<String>)]) -> Result<String, ArrErr> {
+    let json = serde_json::to_string(values)
+        .map_err(|e| ArrErr::Error(format!("Could not serialize search cursor: {}", e)))?;
+    Ok(json.bytes().map(|byte| format!("{:02x}", byte)).collect())
+}
+
+/// Decodes the provided cursor and returns the values for the given cursor columns.
+///
+/// Returns an error if the cursor can't be decoded or if it doesn't match the provided
+/// columns, which is the case if the sort order changed in between requests.
+pub(crate) fn decode_cursor(
+    cursor: &str,
+    cursor_cols: &[(SearchCol, SortOrder)],
+) -> Result<Vec<Option<String>>, ArrErr> {
+    let error = || ArrErr::Error(format!("Invalid search cursor provided [{}].", cursor));
+    if cursor.len() % 2 != 0 || !cursor.is_ascii() {
+        return Err(error());
+    }
+    let bytes = (0..cursor.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(&cursor[i..i + 2], 16))
+        .collect::<Result<Vec<u8>, _>>()
+        .map_err(|_| error())?;
+    let values: Vec<(String, Option<String>)> =
+        serde_json::from_slice(&bytes).map_err(|_| error())?;
+
+    if values.len() != cursor_cols.len()
+        || values
+            .iter()
+            .zip(cursor_cols.iter())
+            .any(|((field, _), (col, _))| *field != col.col_name)
+    {
+        return Err(ArrErr::Error(String::from(
+            "The provided search cursor does not match the requested sort order.",
+        )));
+    }
+
+    Ok(values.into_iter().map(|(_, value)| value).collect())
+}
+
+/// Composes the search condition selecting all rows positioned after the provided
+/// cursor values, taking the sort order of each column into account.
+///
+/// `NULL` values are considered lower than any other value, matching the sort
+/// order used by CockroachDB.
+pub(crate) fn get_cursor_str(
+    cursor_cols: &[(SearchCol, SortOrder)],
+    values: Vec<Option<String>>,
+    params: &mut Vec<SearchCol>,
+    cur_param_index: i32,
+) -> Result<(String, i32), ArrErr> {
+    let mut next_param_index = cur_param_index;
+    let mut conditions: Vec<String> = vec![];
+    let mut equals: Vec<String> = vec![];
+
+    for ((col, sort_order), value) in cursor_cols.iter().zip(values) {
+        let param = match value {
+            Some(value) => {
+                let mut search_col = col.clone();
+                search_col.set_value(value);
+                params.push(search_col);
+                next_param_index += 1;
+                Some(next_param_index - 1)
+            }
+            None => None,
+        };
+
+        let after = match (sort_order, param) {
+            (SortOrder::Asc, Some(index)) => Some(format!(r#""{}" > ${}"#, col.col_name, index)),
+            (SortOrder::Asc, None) => Some(format!(r#""{}" IS NOT NULL"#, col.col_name)),
+            (SortOrder::Desc, Some(index)) => Some(format!(
+                r#"("{}" < ${} OR "{}" IS NULL)"#,
+                col.col_name, index, col.col_name
+            )),
+            (SortOrder::Desc, None) => None,
+        };
+        if let Some(after) = after {
+            let mut condition = equals.clone();
+            condition.push(after);
+            conditions.push(condition.join(" AND "));
+        }
+
+        equals.push(match param {
+            Some(index) => format!(r#""{}" = ${}"#, col.col_name, index),
+            None => format!(r#""{}" IS NULL"#, col.col_name),
+        });
+    }
+
+    if conditions.is_empty() {
+        return Ok((String::from("FALSE"), next_param_index));
+    }
+    Ok((format!("({})", conditions.join(") OR (")), next_param_index))
+}
+
+/// Returns a cursor pointing to the last row of the provided search results,
+/// or [`None`] if the results don't fill the requested page.
+pub(crate) fn get_next_cursor(
+    definition: &ResourceDefinition,
+    filter: &AdvancedSearchFilter,
+    rows: &[Row],
+) -> Result<Option<String>, ArrErr> {
+    let last_row = match (get_limit_offset(filter), rows.last()) {
+        (Some((limit, _)), Some(row)) if limit > 0 && rows.len() as i64 >= limit => row,
+        _ => return Ok(None),
+    };
+
+    let cursor_cols = get_cursor_cols(definition, &filter.order_by)?;
+    let mut values: Vec<(String, Option<String>)> = vec![];
+    for (index, (col, _)) in cursor_cols.iter().enumerate() {
+        let value: Option<String> = last_row
+            .try_get(format!("{}{}", CURSOR_COL_PREFIX, index).as_str())
+            .map_err(|e| {
+                ArrErr::Error(format!(
+                    "Could not get cursor value for column [{}]: {}",
+                    col.col_name, e
+                ))
+            })?;
+        values.push((col.col_name.clone(), value));
+    }
+
+    Ok(Some(encode_cursor(&values)?))
+}
+
+/// Composes the search conditions for the provided list of [`FilterOption`]s.
+///
+/// Each filter is combined with the previous one using its [`ComparisonOperator`], defaulting
+/// to `AND` if none was provided. The comparison operator of the first filter is ignored.
+/// Filters containing `group_filters` will be composed recursively and placed between
+/// parentheses so they are evaluated as a whole.
+pub(crate) fn get_filters_str(
+    definition: &ResourceDefinition,
+    filters: &[FilterOption],
+    params: &mut Vec<SearchCol>,
+    cur_param_index: i32,
+) -> Result<(String, i32), ArrErr> {
+    let mut filters_str = String::new();
+    let mut next_param_index = cur_param_index;
+
+    for (index, filter) in filters.iter().enumerate() {
+        if index > 0 {
+            let comparison_operator = match filter.comparison_operator {
+                Some(operator) => match ComparisonOperator::try_from(operator) {
+                    Ok(operator) => operator,
+                    Err(e) => {
+                        return Err(ArrErr::Error(format!(
+                            "Can't convert i32 [{}] into ComparisonOperator Enum value: {}",
+                            operator, e
+                        )));
+                    }
+                },
+                None => ComparisonOperator::And,
+            };
+            filters_str.push_str(&format!(" {} ", comparison_operator.as_str_name()));
+        }
+
+        if !filter.group_filters.is_empty() {
+            let (group_str, cur_param_index) =
+                get_filters_str(definition, &filter.group_filters, params, next_param_index)?;
+            filters_str.push_str(&format!("({})", group_str));
+            next_param_index = cur_param_index;
+            continue;
+        }
+
+        let col = filter.search_field.clone();
+
+        // Check if provided search col is part of the primary key
+        let field_type = if definition.get_psql_id_cols().contains(&col) {
+            PsqlFieldType::UUID
+        } else {
+            definition.try_get_field(&col)?.field_type.clone()
+        };
+
+        let operator: PredicateOperator =
+            match PredicateOperator::try_from(filter.predicate_operator) {
+                Ok(val) => val,
+                Err(e) => {
+                    return Err(ArrErr::Error(format!(
+                        "Can't convert i32 [{}] into PredicateOperator Enum value: {}",
+                        filter.predicate_operator, e
+                    )));
+                }
+            };
+
+        let (filter_str, cur_param_index) = get_filter_str(
+            SearchCol {
+                col_name: col,
+                col_type: field_type,
+                value: None,
+            },
+            filter.search_value.clone(),
+            params,
+            next_param_index,
+            operator,
+        )?;
+        filters_str.push_str(filter_str.trim_start());
+        next_param_index = cur_param_index;
+    }
+
+    Ok((filters_str, next_param_index))
+}
+
 pub(crate) fn get_filter_str(
     mut search_col: SearchCol,
     values: Vec<String>,
@@ -906,6 +1415,273 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn test_get_filters_str_with_group() {
+        assert_init_done().await;
+        ut_info!("start");
+
+        let definition = ResourceObject::<Data>::get_definition();
+        let id = Uuid::new_v4().to_string();
+        let filter = AdvancedSearchFilter::search_in(
+            String::from("string"),
+            vec![String::from("READY"), String::from("BOARDING")],
+        )
+        .and_group(|g| {
+            g.or_equals(String::from("uuid"), id.clone())
+                .or_equals(String::from("optional_uuid"), id.clone())
+        })
+        .and_is_null(String::from("deleted_at"));
+
+        let mut filter_params: Vec<SearchCol> = vec![];
+        let result = get_filters_str(&definition, &filter.filters, &mut filter_params, 1);
+        assert!(result.is_ok());
+        let (filters_str, next_param_index) = result.unwrap();
+        assert_eq!(next_param_index, 5);
+        assert_eq!(filter_params.len(), 4);
+        assert_eq!(
+            filters_str,
+            String::from(
+                r#""string" IN ($1,$2) AND ("uuid" = $3 OR "optional_uuid" = $4) AND "deleted_at" IS NULL"#
+            )
+        );
+
+        // Invalid fields inside a group should still return an error
+        let filter = AdvancedSearchFilter::search_group(|g| {
+            g.and_equals(String::from("invalid_field"), id.clone())
+        });
+        let result = get_filters_str(&definition, &filter.filters, &mut filter_params, 1);
+        assert!(result.is_err());
+
+        ut_info!("success");
+    }
+
+    #[tokio::test]
+    async fn test_get_paging_info_from_results() {
+        assert_init_done().await;
+        ut_info!("start");
+
+        // No paging requested, total equals the number of results
+        let filter = AdvancedSearchFilter::search_is_null(String::from("deleted_at"));
+        assert_eq!(get_limit_offset(&filter), None);
+        assert_eq!(get_paging_info_from_results(&filter, 7), Some((7, false)));
+
+        // Partial first page, no count query needed
+        let filter = filter.results_per_page(10).page_number(1);
+        assert_eq!(get_limit_offset(&filter), Some((10, 0)));
+        assert_eq!(get_paging_info_from_results(&filter, 4), Some((4, false)));
+        assert_eq!(get_paging_info_from_results(&filter, 0), Some((0, false)));
+
+        // Partial last page, total can be derived from the offset
+        let filter = filter.page_number(3);
+        assert_eq!(get_limit_offset(&filter), Some((10, 20)));
+        assert_eq!(get_paging_info_from_results(&filter, 5), Some((25, false)));
+
+        // Full page or empty page beyond the first, count query needed
+        assert_eq!(get_paging_info_from_results(&filter, 10), None);
+        assert_eq!(get_paging_info_from_results(&filter, 0), None);
+
+        ut_info!("success");
+    }
+
+    #[tokio::test]
+    async fn test_get_search_query_cursor() {
+        assert_init_done().await;
+        ut_info!("start");
+
+        let cursor = encode_cursor(&[(
+            String::from("simple_resource_id"),
+            Some(Uuid::new_v4().to_string()),
+        )])
+        .unwrap();
+
+        // The page number should be ignored if a cursor is provided
+        let filter = AdvancedSearchFilter::default()
+            .results_per_page(10)
+            .page_number(0)
+            .cursor(cursor);
+        assert_eq!(get_limit_offset(&filter), Some((10, 0)));
+
+        let result = ResourceObject::<Data>::_get_search_query(&filter);
+        assert!(result.is_ok(), "Expected 'Ok' but got {:?}", result.err());
+        let (search_query, params) = result.unwrap();
+        assert!(search_query.ends_with(r#" ORDER BY "simple_resource_id" ASC LIMIT $2"#));
+        assert_eq!(params.len(), 2);
+
+        // Without cursor, the page number is still required for paging
+        let filter = AdvancedSearchFilter::default()
+            .results_per_page(10)
+            .page_number(0);
+        assert_eq!(get_limit_offset(&filter), None);
+
+        ut_info!("success");
+    }
+
+    #[tokio::test]
+    async fn test_get_cursor_cols() {
+        assert_init_done().await;
+        ut_info!("start");
+
+        let definition = ResourceObject::<Data>::get_definition();
+        let order_by = vec![
+            SortOption {
+                sort_field: String::from("timestamp"),
+                sort_order: SortOrder::Desc as i32,
+            },
+            SortOption {
+                sort_field: String::from("invalid_field"),
+                sort_order: SortOrder::Asc as i32,
+            },
+        ];
+        let result = get_cursor_cols(&definition, &order_by);
+        assert!(result.is_ok());
+        let cursor_cols = result.unwrap();
+        assert_eq!(cursor_cols.len(), 2);
+        assert_eq!(cursor_cols[0].0.col_name, "timestamp");
+        assert_eq!(cursor_cols[0].0.col_type, PsqlFieldType::TIMESTAMPTZ);
+        assert_eq!(cursor_cols[0].1, SortOrder::Desc);
+        // Primary key is always added as last sort column
+        assert_eq!(cursor_cols[1].0.col_name, "simple_resource_id");
+        assert_eq!(cursor_cols[1].0.col_type, PsqlFieldType::UUID);
+        assert_eq!(cursor_cols[1].1, SortOrder::Asc);
+
+        ut_info!("success");
+    }
+
+    #[tokio::test]
+    async fn test_encode_decode_cursor() {
+        assert_init_done().await;
+        ut_info!("start");
+
+        let definition = ResourceObject::<Data>::get_definition();
+        let order_by = vec![SortOption {
+            sort_field: String::from("optional_string"),
+            sort_order: SortOrder::Asc as i32,
+        }];
+        let cursor_cols = get_cursor_cols(&definition, &order_by).unwrap();
+
+        let id = Uuid::new_v4().to_string();
+        let cursor = encode_cursor(&[
+            (String::from("optional_string"), None),
+            (String::from("simple_resource_id"), Some(id.clone())),
+        ]);
+        assert!(cursor.is_ok());
+        let cursor = cursor.unwrap();
+
+        let result = decode_cursor(&cursor, &cursor_cols);
+        assert!(result.is_ok());
+        assert_eq!(result.unwrap(), vec![None, Some(id)]);
+
+        // A cursor for a different sort order should be rejected
+        let cursor_cols = get_cursor_cols(&definition, &[]).unwrap();
+        assert!(decode_cursor(&cursor, &cursor_cols).is_err());
+
+        // Invalid cursors should be rejected
+        assert!(decode_cursor("invalid", &cursor_cols).is_err());
+        assert!(decode_cursor("zz", &cursor_cols).is_err());
+
+        ut_info!("success");
+    }
+
+    #[tokio::test]
+    async fn test_get_cursor_str() {
+        assert_init_done().await;
+        ut_info!("start");
+
+        let definition = ResourceObject::<Data>::get_definition();
+        let order_by = vec![
+            SortOption {
+                sort_field: String::from("string"),
+                sort_order: SortOrder::Asc as i32,
+            },
+            SortOption {
+                sort_field: String::from("optional_i64"),
+                sort_order: SortOrder::Desc as i32,
+            },
+        ];
+        let cursor_cols = get_cursor_cols(&definition, &order_by).unwrap();
+        let id = Uuid::new_v4().to_string();
+
+        let mut params: Vec<SearchCol> = vec![];
+        let result = get_cursor_str(
+            &cursor_cols,
+            vec![
+                Some(String::from("value")),
+                Some(String::from("10")),
+                Some(id.clone()),
+            ],
+            &mut params,
+            3,
+        );
+        assert!(result.is_ok());
+        let (cursor_str, next_param_index) = result.unwrap();
+        assert_eq!(next_param_index, 6);
+        assert_eq!(params.len(), 3);
+        assert_eq!(params[2].value, Some(id.clone()));
+        assert_eq!(
+            cursor_str,
+            String::from(
+                r#"("string" > $3) OR ("string" = $3 AND ("optional_i64" < $4 OR "optional_i64" IS NULL)) OR ("string" = $3 AND "optional_i64" = $4 AND "simple_resource_id" > $5)"#
+            )
+        );
+
+        // NULL values are considered the lowest possible value
+        let mut params: Vec<SearchCol> = vec![];
+        let result = get_cursor_str(
+            &cursor_cols,
+            vec![Some(String::from("value")), None, Some(id)],
+            &mut params,
+            1,
+        );
+        assert!(result.is_ok());
+        let (cursor_str, next_param_index) = result.unwrap();
+        assert_eq!(next_param_index, 3);
+        assert_eq!(params.len(), 2);
+        assert_eq!(
+            cursor_str,
+            String::from(
+                r#"("string" > $1) OR ("string" = $1 AND "optional_i64" IS NULL AND "simple_resource_id" > $2)"#
+            )
+        );
+
+        ut_info!("success");
+    }
+
+    #[tokio::test]
+    async fn test_get_search_conditions_archived() {
+        assert_init_done().await;
+        ut_info!("start");
+
+        // Archived records should be excluded by default
+        let filter =
+            AdvancedSearchFilter::search_equals(String::from("string"), String::from("value"));
+        let result = ResourceObject::<Data>::_get_search_conditions(&filter);
+        assert!(result.is_ok());
+        let (filters_str, params, next_param_index) = result.unwrap();
+        assert_eq!(next_param_index, 2);
+        assert_eq!(params.len(), 1);
+        assert_eq!(
+            filters_str,
+            String::from(r#"( "string" = $1) AND "deleted_at" IS NULL"#)
+        );
+
+        let filter = AdvancedSearchFilter::default();
+        let (filters_str, _, _) = ResourceObject::<Data>::_get_search_conditions(&filter).unwrap();
+        assert_eq!(filters_str, String::from(r#""deleted_at" IS NULL"#));
+
+        // Archived records should be included when requested
+        let filter = filter.include_archived(true);
+        let (filters_str, _, _) = ResourceObject::<Data>::_get_search_conditions(&filter).unwrap();
+        assert!(filters_str.is_empty());
+
+        // Resources without a `deleted_at` field should not be filtered
+        type NoArchive = ResourceObject<crate::resources::simple_resource_linked_no_archive::Data>;
+        let filter = AdvancedSearchFilter::default();
+        let (filters_str, _, _) = NoArchive::_get_search_conditions(&filter).unwrap();
+        assert!(filters_str.is_empty());
+
+        ut_info!("success");
+    }
+
     #[tokio::test]
     async fn test_get_param_from_search_col() {
         assert_init_done().await;
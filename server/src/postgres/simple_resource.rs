@@ -3,14 +3,21 @@
 pub use crate::resources::base::simple_resource::*;
 
 use super::get_psql_client;
-use super::{util::*, ArrErr};
+use super::{audit, timeslot, util::*, ArrErr, PsqlField};
+use crate::grpc::server::audit::AuditAction;
 use crate::grpc::server::ValidationResult;
 use crate::grpc::GrpcDataObjectType;
 
+use deadpool_postgres::Transaction;
 use lib_common::time::{DateTime, Utc};
 use lib_common::uuid::Uuid;
+use serde_json::Value;
+use std::collections::HashMap;
 use tokio_postgres::Row;
 
+/// Maximum number of bind parameters PostgreSQL accepts for a single statement
+const MAX_STATEMENT_PARAMS: usize = 65535;
+
 /// Generic PostgreSQL trait to provide wrappers for common `Resource` functions
 #[cfg(not(tarpaulin_include))]
 // no_coverage: (R5) is part of integration tests, coverage report will need to be merged to show
@@ -41,11 +48,35 @@ where
         super::queries::get_by_id::<Self>(id).await
     }
 
+    /// Generic find by id function to get a row using the UUID column
+    ///
+    /// Archived rows (with a `deleted_at` value) will be skipped unless `include_archived` is set.
+    /// Returns [`None`] if no matching row could be found.
+    async fn find_by_id(id: &Uuid, include_archived: bool) -> Result<Option<Row>, ArrErr> {
+        psql_debug!("Start [{:?}], include_archived [{}].", id, include_archived);
+        super::queries::find_by_id::<Self>(id, include_archived).await
+    }
+
     /// Generic create function based on resource definition and provided data.
     ///
     /// The data will be validated first, returning all possible errors at once.
     /// If no validation errors are found, a new row will be inserted in the database and the new UUID will be returned.
+    /// The insert will be recorded in the audit log within the same transaction.
+    /// Inserts resulting in timeslot conflicts (see [`Resource::get_timeslot_reservations`]) are
+    /// rolled back and returned as validation errors.
     async fn create<'a, T>(data: &T) -> Result<(Option<Uuid>, ValidationResult), ArrErr>
+    where
+        T: GrpcDataObjectType,
+    {
+        let id_col = Self::try_get_id_field()?;
+        let (row, validation_result) = Self::create_returning(data).await?;
+        Ok((row.map(|row| row.get(&*id_col)), validation_result))
+    }
+
+    /// Same as [`create`](Self::create), but returns the inserted [`Row`] instead of its UUID.
+    ///
+    /// The returned row contains the same columns as the rows returned by [`find_by_id`](Self::find_by_id).
+    async fn create_returning<'a, T>(data: &T) -> Result<(Option<Row>, ValidationResult), ArrErr>
     where
         T: GrpcDataObjectType,
     {
@@ -71,20 +102,326 @@ where
         };
 
         let insert_sql = &format!(
-            r#"INSERT INTO "{}"{} RETURNING "{}""#,
-            definition.psql_table, col_data, id_col
+            r#"INSERT INTO "{}"{} RETURNING *, {}"#,
+            definition.psql_table,
+            col_data,
+            audit::get_row_json_column(&definition.psql_table)
         );
         psql_debug!("[{}].", insert_sql);
         psql_debug!("[{:?}].", &params);
 
         psql_info!("Inserting new entry for table [{}].", definition.psql_table);
-        let client = get_psql_client().await?;
-        let row = client.query_one(insert_sql, &params[..]).await?;
+        let mut client = get_psql_client().await?;
+        let transaction = client.transaction().await?;
+        let mut row = transaction.query_one(insert_sql, &params[..]).await?;
+        let id: Uuid = row.get(&*id_col);
+        let ids = HashMap::from([(id_col.clone(), id)]);
 
-        Ok((Some(row.get(&*id_col)), validation_result))
+        match timeslot::check_conflicts::<Self>(&transaction, &ids).await {
+            Ok(_) => {}
+            Err(ArrErr::TimeslotConflict(errors)) => {
+                return Ok((
+                    None,
+                    ValidationResult {
+                        success: false,
+                        errors,
+                    },
+                ))
+            }
+            Err(e) => return Err(e),
+        }
+
+        let after = audit::get_row_json(&definition.psql_table, row.get(audit::ROW_JSON_COLUMN))?;
+        audit::write_entry(
+            &transaction,
+            &definition,
+            AuditAction::Insert,
+            None,
+            Some(&after),
+        )
+        .await?;
+
+        if Self::get_psql_select().is_some() {
+            if let Some(selected) = get_selected_rows::<Self>(&transaction, &id_col, &[id])
+                .await?
+                .pop()
+            {
+                row = selected;
+            }
+        }
+        transaction.commit().await?;
+
+        Ok((Some(row), validation_result))
+    }
+
+    /// Generic batch create function based on resource definition and provided list of data.
+    ///
+    /// Every item will be validated separately, returning the validation result for each item in the same order.
+    /// All valid items will be inserted using multi-row inserts within a single transaction, returning the new
+    /// UUID for each inserted item. The UUIDs are generated up front instead of by the database, so the
+    /// returned rows can be matched to the provided items. If `all_or_nothing` is set, nothing will be
+    /// inserted when any of the items fails validation. All inserts will be recorded in the audit log
+    /// within the same transaction, using the row values returned by the inserts.
+    /// If any of the inserted items results in a timeslot conflict, the transaction will be rolled back,
+    /// returning the conflicts as validation errors for the conflicting items. Items conflicting with each
+    /// other are all rejected. If `all_or_nothing` is set, nothing will be inserted. Otherwise, the
+    /// remaining items will be inserted using a new transaction.
+    async fn create_batch<T>(
+        data: &[T],
+        all_or_nothing: bool,
+    ) -> Result<Vec<(Option<Uuid>, ValidationResult)>, ArrErr>
+    where
+        T: GrpcDataObjectType,
+    {
+        let id_col = Self::try_get_id_field()?;
+        Ok(Self::create_batch_returning(data, all_or_nothing)
+            .await?
+            .into_iter()
+            .map(|(row, validation_result)| (row.map(|row| row.get(&*id_col)), validation_result))
+            .collect())
+    }
+
+    /// Same as [`create_batch`](Self::create_batch), but returns the inserted [`Row`]s instead of their UUIDs.
+    ///
+    /// The returned rows contain the same columns as the rows returned by [`find_by_id`](Self::find_by_id).
+    async fn create_batch_returning<T>(
+        data: &[T],
+        all_or_nothing: bool,
+    ) -> Result<Vec<(Option<Row>, ValidationResult)>, ArrErr>
+    where
+        T: GrpcDataObjectType,
+    {
+        psql_debug!("Start [{}] items.", data.len());
+        let mut validated = vec![];
+        for item in data {
+            validated.push(validate::<Self>(item)?);
+        }
+
+        let mut results: Vec<(Option<Row>, ValidationResult)> = validated
+            .iter()
+            .map(|(_, validation_result)| (None, validation_result.clone()))
+            .collect();
+
+        let invalid_count = results
+            .iter()
+            .filter(|(_, validation_result)| !validation_result.success)
+            .count();
+        if invalid_count > 0 && all_or_nothing {
+            psql_info!(
+                "Found [{}] invalid items, nothing will be inserted.",
+                invalid_count
+            );
+            return Ok(results);
+        }
+
+        let definition = Self::get_definition();
+        let id_col = Self::try_get_id_field()?;
+        // Ids are generated up front, so the returned rows can be matched to the provided items
+        let ids: Vec<Uuid> = data.iter().map(|_| Uuid::new_v4()).collect();
+
+        loop {
+            // Rows can only be combined in a single statement if they provide the same fields,
+            // so group the valid items by their field list first.
+            let mut groups: Vec<(Vec<String>, Vec<usize>)> = vec![];
+            for (index, (psql_data, _)) in validated.iter().enumerate() {
+                // Skip invalid items and items rejected because of timeslot conflicts
+                if !results[index].1.success {
+                    continue;
+                }
+                let (_, fields, _) = get_insert_vars(&data[index], psql_data, &definition, false)?;
+                match groups
+                    .iter_mut()
+                    .find(|(group_fields, _)| *group_fields == fields)
+                {
+                    Some((_, indices)) => indices.push(index),
+                    None => groups.push((fields, vec![index])),
+                }
+            }
+
+            if groups.is_empty() {
+                psql_info!("No valid items left, nothing will be inserted.");
+                return Ok(results);
+            }
+
+            let insert_count: usize = groups.iter().map(|(_, indices)| indices.len()).sum();
+            psql_info!(
+                "Inserting [{}] new entries for table [{}].",
+                insert_count,
+                definition.psql_table
+            );
+            let mut client = get_psql_client().await?;
+            let transaction = client.transaction().await?;
+            let mut inserted: Vec<(usize, Uuid)> = vec![];
+            let mut audit_rows: Vec<Value> = vec![];
+
+            for (fields, indices) in groups {
+                let (_, _, row_params) = get_insert_vars(
+                    &data[indices[0]],
+                    &validated[indices[0]].0,
+                    &definition,
+                    false,
+                )?;
+                // Every row provides its id as well
+                let rows_per_statement = (MAX_STATEMENT_PARAMS / (row_params.len() + 1)).max(1);
+
+                for chunk in indices.chunks(rows_per_statement) {
+                    let mut values = vec![];
+                    let mut params: Vec<&PsqlField> = vec![];
+                    for index in chunk {
+                        params.push(&ids[*index]);
+                        let mut inserts = vec![format!("${}", params.len())];
+                        let (mut row_inserts, _, mut row_params) = get_insert_vars_from_index(
+                            &data[*index],
+                            &validated[*index].0,
+                            &definition,
+                            false,
+                            params.len() + 1,
+                        )?;
+                        inserts.append(&mut row_inserts);
+                        values.push(format!("({})", inserts.join(", ")));
+                        params.append(&mut row_params);
+                    }
+
+                    let mut cols = vec![format!(r#""{}""#, id_col)];
+                    cols.extend(fields.iter().cloned());
+                    let insert_sql = &format!(
+                        r#"INSERT INTO "{}" ({}) VALUES {} RETURNING *, {}"#,
+                        definition.psql_table,
+                        cols.join(", "),
+                        values.join(", "),
+                        audit::get_row_json_column(&definition.psql_table)
+                    );
+                    psql_debug!("[{}].", insert_sql);
+                    psql_debug!("[{:?}].", &params);
+
+                    let rows = transaction.query(insert_sql, &params[..]).await?;
+                    if rows.len() != chunk.len() {
+                        let error = format!(
+                            "Expected [{}] inserted rows for table [{}], got [{}].",
+                            chunk.len(),
+                            definition.psql_table,
+                            rows.len()
+                        );
+                        psql_error!("{}", error);
+                        return Err(ArrErr::Error(error));
+                    }
+                    // The order of the returned rows is not guaranteed, so match them on their id
+                    for row in rows {
+                        let id: Uuid = row.get(&*id_col);
+                        let index = match chunk.iter().find(|index| ids[**index] == id) {
+                            Some(index) => *index,
+                            None => {
+                                let error = format!(
+                                    "Unexpected id [{}] returned for table [{}].",
+                                    id, definition.psql_table
+                                );
+                                psql_error!("{}", error);
+                                return Err(ArrErr::Error(error));
+                            }
+                        };
+                        inserted.push((index, id));
+                        audit_rows.push(audit::get_row_json(
+                            &definition.psql_table,
+                            row.get(audit::ROW_JSON_COLUMN),
+                        )?);
+                        results[index].0 = Some(row);
+                    }
+                }
+            }
+
+            let audit_entries: Vec<(Option<&Value>, Option<&Value>)> =
+                audit_rows.iter().map(|after| (None, Some(after))).collect();
+            audit::write_entries(
+                &transaction,
+                &definition,
+                AuditAction::Insert,
+                &audit_entries,
+            )
+            .await?;
+
+            // Check conflicts once all items are inserted, so conflicts between the items are found as well
+            let mut conflicts = false;
+            for (index, id) in &inserted {
+                match timeslot::check_conflicts::<Self>(
+                    &transaction,
+                    &HashMap::from([(id_col.clone(), *id)]),
+                )
+                .await
+                {
+                    Ok(_) => {}
+                    Err(ArrErr::TimeslotConflict(mut errors)) => {
+                        results[*index].1.success = false;
+                        results[*index].1.errors.append(&mut errors);
+                        conflicts = true;
+                    }
+                    Err(e) => return Err(e),
+                }
+            }
+            if conflicts {
+                transaction.rollback().await?;
+                for result in results.iter_mut() {
+                    result.0 = None;
+                }
+                if all_or_nothing {
+                    psql_info!("Timeslot conflicts found, nothing will be inserted.");
+                    return Ok(results);
+                }
+                // Removing the conflicting items can't cause new conflicts, so the remaining
+                // items will be inserted by the next attempt.
+                psql_info!("Timeslot conflicts found, retrying without the conflicting items.");
+                continue;
+            }
+
+            if Self::get_psql_select().is_some() {
+                let ids: Vec<Uuid> = inserted.iter().map(|(_, id)| *id).collect();
+                for selected in get_selected_rows::<Self>(&transaction, &id_col, &ids).await? {
+                    let id: Uuid = selected.get(&*id_col);
+                    if let Some((index, _)) =
+                        inserted.iter().find(|(_, inserted_id)| *inserted_id == id)
+                    {
+                        results[*index].0 = Some(selected);
+                    }
+                }
+            }
+            transaction.commit().await?;
+
+            return Ok(results);
+        }
     }
 }
 
+/// Returns the rows matching the provided ids, using the resource's [`Resource::get_psql_select`]
+/// so the rows contain the same columns as the rows returned by [`PsqlType::find_by_id`].
+///
+/// The provided transaction is used so uncommitted inserts will be returned as well.
+///
+/// # Errors
+///
+/// Returns [`ArrErr`] Database Error if database query execution failed
+#[cfg(not(tarpaulin_include))]
+// no_coverage: (R5) Is part of integration tests, coverage report will need to be merged to show.
+async fn get_selected_rows<V>(
+    transaction: &Transaction<'_>,
+    id_col: &str,
+    ids: &[Uuid],
+) -> Result<Vec<Row>, ArrErr>
+where
+    V: Resource,
+{
+    let select_sql = format!(
+        r#"SELECT * FROM {} WHERE "{}" = ANY($1)"#,
+        V::get_psql_from(None),
+        id_col
+    );
+    psql_debug!("[{}].", select_sql);
+    psql_debug!("[{:?}].", ids);
+
+    transaction
+        .query(&select_sql, &[&ids])
+        .await
+        .map_err(|e| e.into())
+}
+
 /// Generic trait for the Realm Resources that are stored in the CockroachDB backend.
 /// TODO Rust 1.74: use `#![feature(async_fn_in_trait)]` once available: <https://blog.rust-lang.org/inside-rust/2023/05/03/stabilizing-async-fn-in-trait.html>
 #[cfg(not(tarpaulin_include))]
@@ -103,13 +440,20 @@ where
     /// returns [Row] on success
     async fn read(&self) -> Result<Row, ArrErr> {
         psql_debug!("Start [{:?}].", self.try_get_uuid());
-        //TODO(R5): implement shared memcache here to get object data if present
         let id = self.try_get_uuid()?;
         Self::get_by_id(&id).await
     }
 
     /// Update the Object's database record using provided data
     ///
+    /// If the Object has a field mask set, only the fields listed in the mask will be validated and updated.
+    /// Changes to fields with state transitions (see [`Resource::get_state_transitions`]) are
+    /// checked against the record's current state, invalid transitions are returned as
+    /// [`ValidationError`](crate::grpc::server::ValidationError)s. The record is only updated if
+    /// its state has not been changed by another update in the meantime, which is returned as a
+    /// validation error as well. Updates resulting in timeslot
+    /// conflicts (see [`Resource::get_timeslot_reservations`]) are returned as validation errors as well.
+    ///
     /// returns [Option(Row)] and [ValidationResult]
     ///
     /// # Errors
@@ -117,18 +461,65 @@ where
     /// Returns [`ArrErr`] Validation "Conversion error, unknown field type" if the provided field type could not be matched.
     /// Returns [`ArrErr`] "No id column configured for table" id_col could not be found
     /// Returns [`ArrErr`] if the `id` [`String`] could not be converted to a valid [`Uuid`]
+    /// Returns [`ArrErr::VersionConflict`] if the Object's `expected_updated_at` does not match the record's `updated_at` value
     /// Returns [`ArrErr`] from [`PoolError`](deadpool::managed::PoolError) if no client connection could be returned from the connection [`Pool`](deadpool::managed::Pool)
     /// Returns [`ArrErr`] Database Error if database query execution failed
     async fn update<'a>(&self, data: &T) -> Result<(Option<Row>, ValidationResult), ArrErr> {
         psql_debug!("Start [{:?}].", data);
 
-        let (psql_data, validation_result) = validate::<Self>(data)?;
+        let mask = self.get_mask();
+        let (mut psql_data, mut validation_result) =
+            validate_with_mask::<Self>(data, mask.as_ref())?;
         if !validation_result.success {
             return Ok((None, validation_result));
         }
 
+        let transitions = Self::get_state_transitions();
+        let mut expected_states: HashMap<String, String> = HashMap::new();
+        if !transitions.is_empty() {
+            let row = self.read().await?;
+            let mut current: HashMap<String, String> = HashMap::new();
+            for transition in transitions {
+                if !current.contains_key(&transition.field) {
+                    let state: String = row.try_get(transition.field.as_str())?;
+                    current.insert(transition.field, state);
+                }
+            }
+            validation_result = validate_state_transitions::<Self>(data, &current, &mut psql_data)?;
+            if !validation_result.success {
+                return Ok((None, validation_result));
+            }
+            // The transitions are only valid as long as the current states don't change
+            expected_states = current
+                .into_iter()
+                .filter(|(field, _)| psql_data.contains_key(field))
+                .collect();
+        }
+
         let ids = self.try_get_uuids()?;
-        super::queries::update::<Self, T>(&ids, data, &psql_data).await?;
+        let expected_updated_at: Option<DateTime<Utc>> =
+            self.get_expected_updated_at().map(|ts| ts.into());
+        match super::queries::update::<Self, T>(
+            &ids,
+            data,
+            &psql_data,
+            expected_updated_at.as_ref(),
+            &expected_states,
+        )
+        .await
+        {
+            Ok(_) => {}
+            Err(ArrErr::TimeslotConflict(errors)) | Err(ArrErr::StateConflict(errors)) => {
+                return Ok((
+                    None,
+                    ValidationResult {
+                        success: false,
+                        errors,
+                    },
+                ))
+            }
+            Err(e) => return Err(e),
+        }
 
         Ok((Some(self.read().await?), validation_result))
     }
@@ -157,6 +548,7 @@ where
     /// Returns [`ArrErr`] "No id column configured for table" id_col could not be found
     /// Returns [`ArrErr`] if the `id` [`String`] could not be converted to a valid [`Uuid`]
     /// Returns [`ArrErr`] "\[deleted_at\] column is already set" if [`is_archived`](Self::is_archived) returned `true`
+    /// Returns [`ArrErr::VersionConflict`] if the Object's `expected_updated_at` does not match the record's `updated_at` value
     /// Returns [`ArrErr`] from [`PoolError`](deadpool::managed::PoolError) if no client connection could be returned from the connection [`Pool`](deadpool::managed::Pool)
     /// Returns [`ArrErr`] "Failed to update \[deleted_at\] col" if database query execution returns zero updated rows
     /// Returns [`ArrErr`] Database Error if database query execution failed
@@ -164,6 +556,8 @@ where
         psql_debug!("Start.");
         let definition = Self::get_definition();
         let ids = self.try_get_uuids()?;
+        let expected_updated_at: Option<DateTime<Utc>> =
+            self.get_expected_updated_at().map(|ts| ts.into());
 
         if definition.fields.contains_key("deleted_at") {
             if self.is_archived().await {
@@ -178,11 +572,54 @@ where
                 ));
             }
 
-            super::queries::set_deleted_at_now::<Self, T>(&ids).await
+            super::queries::set_deleted_at_now::<Self, T>(&ids, expected_updated_at.as_ref()).await
         } else {
-            super::queries::delete_row::<Self, T>(&ids).await
+            super::queries::delete_row::<Self, T>(&ids, expected_updated_at.as_ref()).await
         }
     }
+
+    /// Clear the Object's `deleted_at` field, restoring the archived record.
+    /// Returns the restored [Row] on success.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ArrErr`] "No \[deleted_at\] column" if the resource does not support archiving
+    /// Returns [`ArrErr`] "No id column configured for table" id_col could not be found
+    /// Returns [`ArrErr`] if the `id` [`String`] could not be converted to a valid [`Uuid`]
+    /// Returns [`ArrErr`] "\[deleted_at\] column is not set" if [`is_archived`](Self::is_archived) returned `false`
+    /// Returns [`ArrErr::UniqueViolation`] if restoring the record would result in duplicate values for the resource's unique fields
+    /// Returns [`ArrErr::TimeslotConflict`] if restoring the record would result in conflicting timeslot reservations
+    /// Returns [`ArrErr`] from [`PoolError`](deadpool::managed::PoolError) if no client connection could be returned from the connection [`Pool`](deadpool::managed::Pool)
+    /// Returns [`ArrErr`] Database Error if database query execution failed
+    async fn restore(&self) -> Result<Row, ArrErr> {
+        psql_debug!("Start.");
+        let definition = Self::get_definition();
+        let ids = self.try_get_uuids()?;
+
+        if !definition.fields.contains_key("deleted_at") {
+            let error = format!(
+                "No [deleted_at] column for [{}], can not restore records.",
+                definition.psql_table
+            );
+            psql_info!("{}", error);
+            return Err(ArrErr::Error(error));
+        }
+
+        if !self.is_archived().await {
+            psql_info!(
+                "[deleted_at] column is not set, nothing to restore for [{}]. uuids: {:?}",
+                definition.psql_table,
+                ids
+            );
+            return Err(ArrErr::Error(
+                "(restore) [deleted_at] column is not set, will not restore.".to_owned(),
+            ));
+        }
+
+        super::queries::restore::<Self, T>(&ids).await?;
+
+        self.read().await
+    }
 }
 
 #[cfg(test)]
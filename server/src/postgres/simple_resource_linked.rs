@@ -1,7 +1,9 @@
 //! Psql Simple resource linked Traits
 
 use super::get_psql_client;
-use super::{util::*, ArrErr, PsqlField, PsqlFieldSend};
+use super::{audit, util::*, ArrErr};
+use crate::cache;
+use crate::grpc::server::audit::AuditAction;
 use crate::grpc::server::ValidationResult;
 use crate::grpc::GrpcDataObjectType;
 use crate::resources::base::simple_resource::*;
@@ -10,7 +12,6 @@ use deadpool_postgres::Transaction;
 use lib_common::time::{DateTime, Utc};
 use lib_common::uuid::Uuid;
 use std::collections::HashMap;
-use std::vec;
 use tokio_postgres::Row;
 
 /// Generic PostgreSQL trait to provide wrappers for common `Resource` functions
@@ -35,83 +36,55 @@ where
     /// Generic delete for ids function to delete rows for the provided key fields
     /// Since this is a linked resource, the id is expected to be given as a [Vec\<FieldValuePair\>]
     /// to specify the id_column / value pairs to match
+    /// Each deleted row will be recorded in the audit log using the provided [`AuditAction`].
     /// An optional transaction handle can be provided, which will be used if present.
     /// This function will not commit, so the caller is responsible for committing the transaction when done.
     async fn delete_for_ids(
         ids: HashMap<String, Uuid>,
         transaction: Option<&Transaction>,
+        action: AuditAction,
     ) -> Result<(), ArrErr> {
         psql_debug!("Start [{:?}].", ids);
         let definition = Self::get_definition();
 
-        let mut params: Vec<Box<PsqlFieldSend>> = vec![];
-        let mut query = format!(r#"DELETE FROM "{}""#, definition.get_psql_table());
-        let mut search_operator = "WHERE";
-        let mut next_param_index: i32 = 1;
+        let ids: HashMap<String, Uuid> = ids
+            .into_iter()
+            .filter(|(field, _)| Self::has_id_col(field))
+            .collect();
 
-        for (field, value) in ids.clone() {
-            if Self::has_id_col(&field) {
-                query.push_str(&format!(
-                    r#" {} "{}" = ${}"#,
-                    search_operator, field, next_param_index
-                ));
-                params.push(Box::new(value));
-                search_operator = "AND";
-                next_param_index += 1;
-            }
-        }
-
-        psql_debug!("[{}].", &query);
-        psql_debug!("[{:?}].", &params);
         psql_info!(
             "Deleting rows for table [{}]. uuids: {:?}",
             definition.psql_table,
             ids
         );
 
-        let mut ref_params: Vec<&PsqlField> = vec![];
-        for field in params.iter() {
-            ref_params.push(field.as_ref());
-        }
-
-        // TODO(R5): Move this to 2 separate functions which can be used in other places as well
-        match transaction {
-            Some(client) => {
-                let stmt = client.prepare_cached(&query).await?;
-                match client.execute(&stmt, &ref_params[..]).await {
-                    Ok(rows) => {
-                        psql_debug!(
-                            "Removed [{}] entries from [{}].",
-                            rows,
-                            definition.get_psql_table()
-                        );
-                        Ok(())
-                    }
-                    Err(e) => Err(e.into()),
-                }
+        let rows = match transaction {
+            Some(transaction) => {
+                super::queries::delete_for_ids(transaction, &definition, &ids, action).await?
             }
             None => {
-                let client = get_psql_client().await?;
-                let stmt = client.prepare_cached(&query).await?;
-                match client.execute(&stmt, &ref_params[..]).await {
-                    Ok(rows) => {
-                        psql_debug!(
-                            "Removed [{}] entries from [{}].",
-                            rows,
-                            definition.get_psql_table()
-                        );
-                        Ok(())
-                    }
-                    Err(e) => Err(e.into()),
-                }
+                let mut client = get_psql_client().await?;
+                let transaction = client.transaction().await?;
+                let rows =
+                    super::queries::delete_for_ids(&transaction, &definition, &ids, action).await?;
+                transaction.commit().await?;
+                cache::invalidate::<Self>().await;
+                rows
             }
-        }
+        };
+        psql_debug!(
+            "Removed [{}] entries from [{}].",
+            rows,
+            definition.get_psql_table()
+        );
+        Ok(())
     }
 
     /// Generic create function based on resource definition and provided data.
     ///
     /// The data will be validated first, returning all possible errors at once.
     /// If no validation errors are found, a new row will be inserted in the database.
+    /// The insert will be recorded in the audit log within the same transaction.
     async fn create<'a, T>(row_data: &T) -> Result<ValidationResult, ArrErr>
     where
         T: GrpcDataObjectType,
@@ -126,23 +99,46 @@ where
         let definition = Self::get_definition();
         let (inserts, fields, params) = get_insert_vars(row_data, &psql_data, &definition, true)?;
 
+        let id_cols: Vec<String> = definition
+            .psql_id_cols
+            .iter()
+            .map(|col| format!(r#""{}""#, col))
+            .collect();
         let insert_sql = &format!(
-            r#"INSERT INTO "{}" ({}) VALUES ({})"#,
+            r#"INSERT INTO "{}" ({}) VALUES ({}) RETURNING {}"#,
             definition.psql_table,
             fields.join(", "),
             inserts.join(", "),
+            id_cols.join(", "),
         );
 
         psql_info!("Inserting new entry for table [{}].", definition.psql_table);
         psql_debug!("[{}].", insert_sql);
         psql_debug!("[{:?}].", &params);
 
-        let client = get_psql_client().await?;
-        client
-            .execute(insert_sql, &params[..])
+        let mut client = get_psql_client().await?;
+        let transaction = client.transaction().await?;
+        let row = transaction
+            .query_one(insert_sql, &params[..])
             .await
             .map_err(ArrErr::from)?;
 
+        let mut ids = HashMap::new();
+        for id_col in &definition.psql_id_cols {
+            ids.insert(id_col.clone(), row.try_get::<&str, Uuid>(id_col)?);
+        }
+        let after = audit::get_rows_json(&transaction, &definition.psql_table, &ids).await?;
+        audit::write_entry(
+            &transaction,
+            &definition,
+            AuditAction::Insert,
+            None,
+            after.first(),
+        )
+        .await?;
+        transaction.commit().await?;
+        cache::invalidate::<Self>().await;
+
         Ok(validation_result)
     }
 }
@@ -165,13 +161,14 @@ where
     /// returns [Row] on success
     async fn read(&self) -> Result<Row, ArrErr> {
         psql_debug!("Start [{:?}].", self.try_get_uuid());
-        //TODO(R5): implement shared memcache here to get object data if present
         let ids = self.try_get_uuids()?;
         Self::get_for_ids(&ids).await
     }
 
     /// Update the Object's database record using provided data
     ///
+    /// If the Object has a field mask set, only the fields listed in the mask will be validated and updated.
+    ///
     /// returns [Option(Row)] and [ValidationResult]
     ///
     /// # Errors
@@ -179,19 +176,30 @@ where
     /// Returns [`ArrErr`] Validation "Conversion error, unknown field type" if the provided field type could not be matched.
     /// Returns [`ArrErr`] "No id column configured for table" id_col could not be found
     /// Returns [`ArrErr`] if the `id` [`String`] could not be converted to a valid [`Uuid`]
+    /// Returns [`ArrErr::VersionConflict`] if the Object's `expected_updated_at` does not match the record's `updated_at` value
     /// Returns [`ArrErr`] from [`PoolError`](deadpool::managed::PoolError) if no client connection could be returned from the connection [`Pool`](deadpool::managed::Pool)
     /// Returns [`ArrErr`] "Failed to update entries" if database query execution returns zero updated rows
     /// Returns [`ArrErr`] Database Error if database query execution failed
     async fn update<'a>(&self, data: &T) -> Result<(Option<Row>, ValidationResult), ArrErr> {
         psql_debug!("Start [{:?}].", data);
 
-        let (psql_data, validation_result) = validate::<Self>(data)?;
+        let mask = self.get_mask();
+        let (psql_data, validation_result) = validate_with_mask::<Self>(data, mask.as_ref())?;
         if !validation_result.success {
             return Ok((None, validation_result));
         }
 
         let ids = self.try_get_uuids()?;
-        super::queries::update::<Self, T>(&ids, data, &psql_data).await?;
+        let expected_updated_at: Option<DateTime<Utc>> =
+            self.get_expected_updated_at().map(|ts| ts.into());
+        super::queries::update::<Self, T>(
+            &ids,
+            data,
+            &psql_data,
+            expected_updated_at.as_ref(),
+            &HashMap::new(),
+        )
+        .await?;
 
         Ok((Some(self.read().await?), validation_result))
     }
@@ -220,6 +228,7 @@ where
     /// Returns [`ArrErr`] "No id column configured for table" id_col could not be found
     /// Returns [`ArrErr`] if the `id` [`String`] could not be converted to a valid [`Uuid`]
     /// Returns [`ArrErr`] "\[deleted_at\] column is already set" if [`is_archived`](Self::is_archived) returned `true`
+    /// Returns [`ArrErr::VersionConflict`] if the Object's `expected_updated_at` does not match the record's `updated_at` value
     /// Returns [`ArrErr`] from [`PoolError`](deadpool::managed::PoolError) if no client connection could be returned from the connection [`Pool`](deadpool::managed::Pool)
     /// Returns [`ArrErr`] "Failed to update \[deleted_at\] col" if database query execution returns zero updated rows
     /// Returns [`ArrErr`] Database Error if database query execution failed
@@ -227,6 +236,8 @@ where
         psql_debug!("Start.");
         let definition = Self::get_definition();
         let ids = self.try_get_uuids()?;
+        let expected_updated_at: Option<DateTime<Utc>> =
+            self.get_expected_updated_at().map(|ts| ts.into());
 
         if definition.fields.contains_key("deleted_at") {
             if self.is_archived().await {
@@ -241,9 +252,9 @@ where
                 ));
             }
 
-            super::queries::set_deleted_at_now::<Self, T>(&ids).await
+            super::queries::set_deleted_at_now::<Self, T>(&ids, expected_updated_at.as_ref()).await
         } else {
-            super::queries::delete_row::<Self, T>(&ids).await
+            super::queries::delete_row::<Self, T>(&ids, expected_updated_at.as_ref()).await
         }
     }
 }
@@ -0,0 +1,156 @@
+//! Migration test helper functions
+
+use deadpool_postgres::Client;
+use lib_common::uuid::Uuid;
+use std::collections::HashMap;
+
+use crate::grpc::server::{vehicle, vehicle_model, vertipad, vertiport};
+use crate::postgres::get_psql_client;
+use crate::postgres::init::PsqlInitResource;
+use crate::postgres::migrations::{init_migrations_table, PsqlMigrateResource, MIGRATIONS_TABLE};
+use crate::resources::base::ResourceObject;
+use crate::resources::vehicle::PLACEHOLDER_MANUFACTURER_ID;
+
+/// Creates the `vehicle` table as it was before the `vehicle_model` foreign key was added
+/// (version `1`), populated with vehicles referencing unknown vehicle models
+pub async fn setup_vehicle_v1(count: usize) -> Vec<Uuid> {
+    ResourceObject::<vertiport::Data>::init_table()
+        .await
+        .expect("Could not create vertiport table");
+    ResourceObject::<vertipad::Data>::init_table()
+        .await
+        .expect("Could not create vertipad table");
+    ResourceObject::<vehicle_model::Data>::init_table()
+        .await
+        .expect("Could not create vehicle_model table");
+    ResourceObject::<vehicle::Data>::drop_table()
+        .await
+        .expect("Could not drop vehicle table");
+    init_migrations_table()
+        .await
+        .expect("Could not create migrations table");
+
+    let client = get_psql_client().await.expect("Could not get psql client");
+
+    // Apply the baseline and first migration the way they were before the foreign key existed
+    let migrations = ResourceObject::<vehicle::Data>::get_all_migrations();
+    client
+        .execute(
+            &ResourceObject::<vehicle::Data>::_get_create_table_query(),
+            &[],
+        )
+        .await
+        .expect("Could not create vehicle table");
+    for query in &migrations[1].queries {
+        client
+            .execute(query.as_str(), &[])
+            .await
+            .expect("Could not apply vehicle migration [1]");
+    }
+
+    client
+        .execute(
+            &format!(
+                r#"DELETE FROM "{}" WHERE "resource" = 'vehicle'"#,
+                MIGRATIONS_TABLE
+            ),
+            &[],
+        )
+        .await
+        .expect("Could not clear vehicle migrations");
+    for migration in &migrations[0..2] {
+        client
+            .execute(
+                &format!(
+                    r#"INSERT INTO "{}" ("resource", "version", "description", "checksum") VALUES ($1, $2, $3, $4)"#,
+                    MIGRATIONS_TABLE
+                ),
+                &[
+                    &"vehicle",
+                    &migration.version,
+                    &migration.description,
+                    &migration.checksum(),
+                ],
+            )
+            .await
+            .expect("Could not record vehicle migration");
+    }
+
+    let mut ids = vec![];
+    for i in 0..count {
+        let id = Uuid::new_v4();
+        client
+            .execute(
+                r#"INSERT INTO vehicle (vehicle_id, vehicle_model_id, serial_number, registration_number) VALUES ($1, $2, $3, $4)"#,
+                &[
+                    &id,
+                    &Uuid::new_v4(),
+                    &format!("serial-{}", i),
+                    &format!("registration-{}-{}", i, id),
+                ],
+            )
+            .await
+            .expect("Could not insert vehicle");
+        ids.push(id);
+    }
+    ids
+}
+
+/// Returns the `vehicle_model_id` of each of the provided vehicles
+async fn get_vehicle_model_ids(client: &Client, ids: &[Uuid]) -> HashMap<Uuid, Uuid> {
+    client
+        .query(
+            r#"SELECT vehicle_id, vehicle_model_id FROM vehicle WHERE vehicle_id = ANY($1)"#,
+            &[&ids],
+        )
+        .await
+        .expect("Could not get vehicles")
+        .iter()
+        .map(|row| (row.get("vehicle_id"), row.get("vehicle_model_id")))
+        .collect()
+}
+
+/// Runs the pending `vehicle` migrations and checks if the existing vehicles kept their vehicle
+/// model ids, for which placeholder vehicle models have been inserted
+pub async fn test_vehicle_model_fk_migration(ids: &[Uuid]) {
+    let client = get_psql_client().await.expect("Could not get psql client");
+    let expected = get_vehicle_model_ids(&client, ids).await;
+    assert_eq!(expected.len(), ids.len());
+
+    let result = ResourceObject::<vehicle::Data>::migrate_table().await;
+    assert!(result.is_ok(), "Expected 'Ok' but got {:?}", result);
+    assert_eq!(result.unwrap(), 1);
+
+    // The vehicles should not have been changed
+    assert_eq!(get_vehicle_model_ids(&client, ids).await, expected);
+
+    let vehicle_model_ids: Vec<Uuid> = expected.values().cloned().collect();
+    let placeholder_manufacturer_id =
+        Uuid::parse_str(PLACEHOLDER_MANUFACTURER_ID).expect("Invalid placeholder id");
+    let rows = client
+        .query(
+            r#"SELECT vehicle_model_id, manufacturer_id FROM vehicle_model WHERE vehicle_model_id = ANY($1)"#,
+            &[&vehicle_model_ids],
+        )
+        .await
+        .expect("Could not get placeholder vehicle models");
+    assert_eq!(rows.len(), vehicle_model_ids.len());
+    for row in rows {
+        let manufacturer_id: Uuid = row.get("manufacturer_id");
+        assert_eq!(manufacturer_id, placeholder_manufacturer_id);
+    }
+
+    // The foreign key should be in place now
+    let result = client
+        .execute(
+            r#"UPDATE vehicle SET vehicle_model_id = $1 WHERE vehicle_id = $2"#,
+            &[&Uuid::new_v4(), &ids[0]],
+        )
+        .await;
+    assert!(result.is_err());
+
+    // Nothing left to apply
+    let result = ResourceObject::<vehicle::Data>::migrate_table().await;
+    assert!(result.is_ok(), "Expected 'Ok' but got {:?}", result);
+    assert_eq!(result.unwrap(), 0);
+}
@@ -1,6 +1,7 @@
 //! Test utility functions
 
 pub mod linked_resource;
+pub mod migrations;
 pub mod simple_resource;
 pub mod simple_resource_linked;
 pub mod simple_resource_linked_no_archive;
@@ -71,17 +72,42 @@ async fn test_simple_resource_scenario() {
 
     // Check if we can insert a new message
     let new_object = insert_one().await;
+    let _second_object = insert_one().await;
 
-    test_not_deleted(1).await;
+    // Check if we can insert multiple messages at once
+    let _batch_objects = test_create_batch().await;
+
+    test_not_deleted(4).await;
+
+    // Check if we get the right paging information
+    test_paging_info(2).await;
+
+    // Check if we can page through all results using cursors
+    test_cursor_paging(2).await;
+
+    // Check if we can stream all results
+    test_search_stream(2).await;
 
     // Check if we can get a single message based on their id
     let _object_from_db = get_by_id(&new_object.id).await;
 
     // Check if we can update the newly inserted message with new data
-    test_update_one(&new_object.id, new_object.data.unwrap()).await;
+    test_update_one(&new_object.id, new_object.data.clone().unwrap()).await;
+
+    // Check if outdated versions are refused for update and delete actions
+    test_version_conflict(&new_object.id, new_object.data.unwrap()).await;
 
     // Check if we can delete the message
     test_delete_one(&new_object.id).await;
+
+    // Check if archived messages are only found when requested
+    test_find_archived(&new_object.id).await;
+
+    // Check if we can restore the deleted message
+    test_restore_one(&new_object.id).await;
+
+    // Check if we can purge the message once archived
+    test_purge_one(&new_object.id).await;
 }
 
 #[tokio::test]
@@ -171,3 +197,14 @@ async fn test_linked_resource_scenario() {
     // Check if we can delete the message
     test_delete_one(&resource_list.list[0].id, &linked_list.list[0].id).await;
 }
+
+#[tokio::test]
+async fn test_vehicle_migration_from_v1_scenario() {
+    crate::test_util::assert_init_done().await;
+
+    use migrations::*;
+
+    // Existing vehicles should be migrated without violating the vehicle_model foreign key
+    let ids = setup_vehicle_v1(3).await;
+    test_vehicle_model_fk_migration(&ids).await;
+}
@@ -1,6 +1,10 @@
 //! Simple Resource test helper functions
 
-use lib_common::time::{Timestamp, Utc};
+use futures::TryStreamExt;
+use lib_common::time::{DateTime, Timestamp, Utc};
+use tokio_postgres::Row;
+
+use crate::common::ArrErr;
 
 pub use crate::resources::test_util::simple_resource::*;
 
@@ -23,9 +27,61 @@ pub async fn insert_one() -> Object {
     get_by_id(&uuid.unwrap().to_string()).await
 }
 
+/// Insert multiple objects at once, including an invalid one
+pub async fn test_create_batch() -> Vec<Object> {
+    let list = vec![
+        get_valid_data(
+            Uuid::new_v4(),
+            Uuid::new_v4(),
+            Some(Utc::now().into()),
+            Some(Utc::now().into()),
+        ),
+        get_invalid_data(),
+        get_valid_data(
+            Uuid::new_v4(),
+            Uuid::new_v4(),
+            Some(Utc::now().into()),
+            Some(Utc::now().into()),
+        ),
+    ];
+
+    // Nothing should be inserted if any of the items is invalid
+    let result = <ResourceObject<Data> as PsqlType>::create_batch(&list, true).await;
+    assert!(result.is_ok(), "Expected 'Ok' but got {:?}", result);
+    let results = result.unwrap();
+    assert_eq!(results.len(), list.len());
+    assert!(results.iter().all(|(uuid, _)| uuid.is_none()));
+    assert!(results[0].1.success);
+    assert!(!results[1].1.success);
+    assert!(results[2].1.success);
+
+    // Only the valid items should be inserted
+    let result = <ResourceObject<Data> as PsqlType>::create_batch(&list, false).await;
+    assert!(result.is_ok(), "Expected 'Ok' but got {:?}", result);
+    let results = result.unwrap();
+    assert_eq!(results.len(), list.len());
+    assert!(results[1].0.is_none());
+    assert!(!results[1].1.success);
+
+    let mut objects = vec![];
+    for index in [0, 2] {
+        let (uuid, validation_result) = &results[index];
+        assert!(validation_result.success);
+        assert!(uuid.is_some());
+
+        let object = get_by_id(&uuid.unwrap().to_string()).await;
+        assert_eq!(object.data.as_ref().unwrap().string, list[index].string);
+        objects.push(object);
+    }
+    objects
+}
+
 /// Get object for id
 pub async fn get_by_id(id: &str) -> Object {
-    let id: Id = Id { id: id.to_owned() };
+    let id: Id = Id {
+        id: id.to_owned(),
+        include_archived: false,
+    };
     let mut resource: ResourceObject<Data> = id.clone().into();
     let obj =
         <ResourceObject<Data> as PsqlType>::get_by_id(&resource.try_get_uuid().unwrap()).await;
@@ -54,9 +110,89 @@ pub async fn test_not_deleted(min_expected: usize) {
     assert!(list.list.len() >= min_expected);
 }
 
+/// Get the total count and `has_more` flag for a paged search
+pub async fn test_paging_info(min_expected: usize) {
+    let message_filter = AdvancedSearchFilter::search_is_null("deleted_at".to_owned());
+
+    let result = <ResourceObject<Data> as PsqlSearch>::advanced_search_count(&message_filter).await;
+    assert!(result.is_ok(), "Expected 'Ok' but got {:?}", result);
+    let total_count = result.unwrap();
+    assert!(total_count >= min_expected as i64);
+
+    // Requesting a single result per page should tell us there are more results available
+    let message_filter = message_filter.page_number(1).results_per_page(1);
+    let result =
+        <ResourceObject<Data> as PsqlSearch>::advanced_search(message_filter.clone()).await;
+    assert!(result.is_ok(), "Expected 'Ok' but got {:?}", result);
+    let num_results = result.unwrap().len();
+    assert_eq!(num_results, 1);
+
+    let result =
+        <ResourceObject<Data> as PsqlSearch>::get_paging_info(&message_filter, num_results).await;
+    assert!(result.is_ok(), "Expected 'Ok' but got {:?}", result);
+    assert_eq!(result.unwrap(), (total_count, true));
+}
+
+/// Page through all objects using the returned cursors
+pub async fn test_cursor_paging(min_expected: usize) {
+    let mut message_filter = AdvancedSearchFilter::search_is_null("deleted_at".to_owned())
+        .page_number(1)
+        .results_per_page(1);
+
+    let mut ids: Vec<String> = vec![];
+    loop {
+        let result =
+            <ResourceObject<Data> as PsqlSearch>::advanced_search(message_filter.clone()).await;
+        assert!(result.is_ok(), "Expected 'Ok' but got {:?}", result);
+        let rows = result.unwrap();
+
+        let paging_info =
+            <ResourceObject<Data> as PsqlSearch>::get_search_paging_info(&message_filter, &rows)
+                .await;
+        assert!(
+            paging_info.is_ok(),
+            "Expected 'Ok' but got {:?}",
+            paging_info
+        );
+
+        let list: List = rows.try_into().unwrap();
+        ids.extend(list.list.into_iter().map(|object| object.id));
+
+        match paging_info.unwrap().next_cursor {
+            Some(cursor) => message_filter = message_filter.cursor(cursor),
+            None => break,
+        }
+    }
+
+    assert!(ids.len() >= min_expected);
+    let num_found = ids.len();
+    ids.sort();
+    ids.dedup();
+    assert_eq!(ids.len(), num_found);
+}
+
+/// Stream all objects from the database
+pub async fn test_search_stream(min_expected: usize) {
+    let message_filter = AdvancedSearchFilter::search_is_null("deleted_at".to_owned());
+
+    ut_info!("Starting streaming search");
+    let result = <ResourceObject<Data> as PsqlSearch>::advanced_search_stream(message_filter).await;
+    assert!(result.is_ok(), "Expected 'Ok' but got an error");
+
+    let rows: Vec<Row> = result.unwrap().try_collect().await.unwrap();
+    let mut objects: Vec<Object> = vec![];
+    for row in rows {
+        objects.push(row.try_into().unwrap());
+    }
+    assert!(objects.len() >= min_expected);
+}
+
 /// Update a single object with new data
 pub async fn test_update_one(id: &str, data: Data) {
-    let id: Id = Id { id: id.to_owned() };
+    let id: Id = Id {
+        id: id.to_owned(),
+        include_archived: false,
+    };
     let obj: ResourceObject<Data> = id.clone().into();
 
     // Create a timestamp with nanos set to zero since the database doesn't provide any nanos
@@ -92,9 +228,55 @@ pub async fn test_update_one(id: &str, data: Data) {
     assert_eq!(data, new_data);
 }
 
+/// Update and delete with an outdated `expected_updated_at` value should be refused
+pub async fn test_version_conflict(id: &str, data: Data) {
+    let uuid = Uuid::parse_str(id).unwrap();
+    let row = <ResourceObject<Data> as PsqlType>::get_by_id(&uuid)
+        .await
+        .unwrap();
+    let current: DateTime<Utc> = row.get("updated_at");
+
+    let mut outdated: Timestamp = current.into();
+    outdated.seconds -= 1;
+
+    let mut obj: ResourceObject<Data> = Id {
+        id: id.to_owned(),
+        include_archived: false,
+    }
+    .into();
+    obj.set_expected_updated_at(Some(outdated));
+
+    let result = obj.update(&data).await;
+    match result {
+        Err(ArrErr::VersionConflict(updated_at)) => assert_eq!(updated_at, current),
+        _ => panic!("Expected 'VersionConflict' but got {:?}", result),
+    }
+
+    let result = obj.delete().await;
+    match result {
+        Err(ArrErr::VersionConflict(updated_at)) => assert_eq!(updated_at, current),
+        _ => panic!("Expected 'VersionConflict' but got {:?}", result),
+    }
+    assert!(!obj.is_archived().await);
+
+    // The update should succeed when the current version is provided
+    obj.set_expected_updated_at(Some(current.into()));
+    let result = obj.update(&data).await;
+    assert!(result.is_ok(), "Expected 'Ok' but got {:?}", result);
+
+    let row = <ResourceObject<Data> as PsqlType>::get_by_id(&uuid)
+        .await
+        .unwrap();
+    let updated_at: DateTime<Utc> = row.get("updated_at");
+    assert!(updated_at > current);
+}
+
 /// Delete for given id
 pub async fn test_delete_one(id: &str) {
-    let id: Id = Id { id: id.to_owned() };
+    let id: Id = Id {
+        id: id.to_owned(),
+        include_archived: false,
+    };
     let obj: ResourceObject<Data> = id.clone().into();
 
     // check if we are a correct result from the is_archived function (should not be archived yet)
@@ -120,3 +302,108 @@ pub async fn test_delete_one(id: &str) {
     let result = obj.delete().await;
     assert!(result.is_err(), "Expected 'Err' but got {:?}", result);
 }
+
+/// Find archived object for given id, only returned if archived records are requested
+pub async fn test_find_archived(id: &str) {
+    let uuid = Uuid::parse_str(id).unwrap();
+
+    let result = <ResourceObject<Data> as PsqlType>::find_by_id(&uuid, false).await;
+    assert!(result.is_ok(), "Expected 'Ok' but got {:?}", result);
+    assert!(result.unwrap().is_none());
+
+    let result = <ResourceObject<Data> as PsqlType>::find_by_id(&uuid, true).await;
+    assert!(result.is_ok(), "Expected 'Ok' but got {:?}", result);
+    let row = result.unwrap();
+    assert!(row.is_some());
+    let deleted_at: Option<DateTime<Utc>> = row.unwrap().get("deleted_at");
+    assert!(deleted_at.is_some());
+}
+
+/// Restore an archived object for given id
+pub async fn test_restore_one(id: &str) {
+    let id: Id = Id {
+        id: id.to_owned(),
+        include_archived: false,
+    };
+    let obj: ResourceObject<Data> = id.clone().into();
+
+    let result = obj.restore().await;
+    assert!(result.is_ok(), "Expected 'Ok' but got {:?}", result);
+    let deleted_at: Option<DateTime<Utc>> = result.unwrap().get("deleted_at");
+    assert!(deleted_at.is_none());
+
+    // check if we are a correct result from the is_archived function (should not be archived anymore)
+    let result = obj.is_archived().await;
+    assert!(
+        !result,
+        "Expected 'false' but got {} when checking is_archived on object after restore.",
+        result
+    );
+
+    // try to restore again (should fail with 'not archived' message)
+    let result = obj.restore().await;
+    assert!(result.is_err(), "Expected 'Err' but got {:?}", result);
+}
+
+/// Purge an archived object for given id
+pub async fn test_purge_one(id: &str) {
+    use crate::postgres::purge::PsqlPurgeResource;
+
+    let uuid = Uuid::parse_str(id).unwrap();
+    let obj: ResourceObject<Data> = Id {
+        id: id.to_owned(),
+        include_archived: false,
+    }
+    .into();
+
+    // records which are not archived should never be purged
+    let result = ResourceObject::<Data>::purge_table(0, &[]).await;
+    assert!(result.is_ok(), "Expected 'Ok' but got {:?}", result);
+    assert!(!result.unwrap().contains(&uuid));
+
+    let result = obj.delete().await;
+    assert!(result.is_ok(), "Expected 'Ok' but got {:?}", result);
+
+    // archived records should be kept until the retention period has passed
+    let result = ResourceObject::<Data>::purge_table(1, &[]).await;
+    assert!(result.is_ok(), "Expected 'Ok' but got {:?}", result);
+    assert!(!result.unwrap().contains(&uuid));
+
+    let result = ResourceObject::<Data>::purge_table(0, &[]).await;
+    assert!(result.is_ok(), "Expected 'Ok' but got {:?}", result);
+    assert!(result.unwrap().contains(&uuid));
+
+    let result = <ResourceObject<Data> as PsqlType>::find_by_id(&uuid, true).await;
+    assert!(result.is_ok(), "Expected 'Ok' but got {:?}", result);
+    assert!(result.unwrap().is_none());
+
+    // the purge should be recorded in the audit log, without any of the record's values
+    let client = crate::postgres::get_psql_client().await.unwrap();
+    let rows = client
+        .query(
+            r#"SELECT "diff" FROM "audit_log" WHERE "resource_id" = $1 AND "action" = 'PURGE'"#,
+            &[&uuid],
+        )
+        .await
+        .unwrap();
+    assert_eq!(rows.len(), 1);
+    let diff: serde_json::Value = serde_json::from_str(rows[0].get("diff")).unwrap();
+    assert_eq!(
+        diff,
+        serde_json::json!({"before": {"simple_resource_id": id}, "after": null})
+    );
+
+    // values recorded before the purge should be redacted
+    let rows = client
+        .query(
+            r#"SELECT "diff" FROM "audit_log" WHERE "resource_id" = $1 AND "action" <> 'PURGE'"#,
+            &[&uuid],
+        )
+        .await
+        .unwrap();
+    assert!(!rows.is_empty());
+    for row in rows {
+        let diff: serde_json::Value = serde_json::from_str(row.get("diff")).unwrap();
+        assert_eq!(diff, crate::postgres::audit::redact_diff(&diff));
+    }
+}
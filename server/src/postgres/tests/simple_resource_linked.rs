@@ -74,6 +74,7 @@ pub async fn get_for_ids(id: &str, linked_id: &str) -> Object {
                 (String::from("linked_id"), linked_id.to_owned()),
             ])),
             data: Some(obj.try_into().unwrap()),
+            expected_updated_at: None,
             mask: None,
         };
         resource.into()
@@ -56,6 +56,7 @@ pub async fn get_list() -> &'static List {
 
             let id: Id = Id {
                 id: uuid.unwrap().to_string(),
+                include_archived: false,
             };
             let mut resource: ResourceObject<Data> = id.clone().into();
             let obj = <ResourceObject<Data> as PsqlType>::get_by_id(&uuid.unwrap().clone()).await;
@@ -66,7 +67,10 @@ pub async fn get_list() -> &'static List {
             }
         }
 
-        List { list: objects }
+        List {
+            list: objects,
+            ..Default::default()
+        }
     })
     .await
 }
@@ -106,6 +110,7 @@ pub mod vertiport {
 
                 let id: Id = Id {
                     id: uuid.unwrap().to_string(),
+                    include_archived: false,
                 };
                 let mut resource: ResourceObject<Data> = id.clone().into();
                 let obj =
@@ -117,7 +122,10 @@ pub mod vertiport {
                 }
             }
 
-            List { list: objects }
+            List {
+                list: objects,
+                ..Default::default()
+            }
         })
         .await
     }
@@ -161,6 +169,7 @@ pub mod vertipad {
 
                 let id: Id = Id {
                     id: uuid.unwrap().to_string(),
+                    include_archived: false,
                 };
                 let mut resource: ResourceObject<Data> = id.clone().into();
                 let obj =
@@ -172,7 +181,10 @@ pub mod vertipad {
                 }
             }
 
-            List { list: objects }
+            List {
+                list: objects,
+                ..Default::default()
+            }
         })
         .await
     }
@@ -232,6 +244,7 @@ pub mod vehicle {
 
                 let id: Id = Id {
                     id: uuid.unwrap().to_string(),
+                    include_archived: false,
                 };
                 let mut resource: ResourceObject<Data> = id.clone().into();
                 let obj =
@@ -243,7 +256,10 @@ pub mod vehicle {
                 }
             }
 
-            List { list: objects }
+            List {
+                list: objects,
+                ..Default::default()
+            }
         })
         .await
     }
@@ -282,6 +298,7 @@ pub mod pilot {
 
                 let id: Id = Id {
                     id: uuid.unwrap().to_string(),
+                    include_archived: false,
                 };
                 let mut resource: ResourceObject<Data> = id.clone().into();
                 let obj =
@@ -293,7 +310,10 @@ pub mod pilot {
                 }
             }
 
-            List { list: objects }
+            List {
+                list: objects,
+                ..Default::default()
+            }
         })
         .await
     }
@@ -321,7 +341,10 @@ pub async fn get_by_id(id: &str) -> Object {
     let result = <ResourceObject<Data> as PsqlType>::get_by_id(&uuid).await;
     it_debug!("{:?}", result);
 
-    let id: Id = Id { id: id.to_owned() };
+    let id: Id = Id {
+        id: id.to_owned(),
+        include_archived: false,
+    };
     let mut resource: ResourceObject<Data> = id.clone().into();
     let obj =
         <ResourceObject<Data> as PsqlType>::get_by_id(&resource.try_get_uuid().unwrap()).await;
@@ -347,7 +370,10 @@ pub async fn insert_one(data: Data) -> Object {
 }
 
 pub async fn test_update_one(id: &str, new_data: Data) {
-    let id: Id = Id { id: id.to_owned() };
+    let id: Id = Id {
+        id: id.to_owned(),
+        include_archived: false,
+    };
     let obj: ResourceObject<Data> = id.clone().into();
     let result = obj.update(&new_data).await;
     it_debug!("{:?}", result);
@@ -362,7 +388,10 @@ pub async fn test_update_one(id: &str, new_data: Data) {
 
 /// Delete for given id
 pub async fn test_delete_one(id: &str) {
-    let id: Id = Id { id: id.to_owned() };
+    let id: Id = Id {
+        id: id.to_owned(),
+        include_archived: false,
+    };
     let obj: ResourceObject<Data> = id.clone().into();
     let result = obj.delete().await;
     it_debug!("{:?}", result);
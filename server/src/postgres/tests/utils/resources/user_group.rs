@@ -43,6 +43,7 @@ pub mod user {
 
                 let id: Id = Id {
                     id: uuid.unwrap().to_string(),
+                    include_archived: false,
                 };
                 let mut resource: ResourceObject<Data> = id.clone().into();
                 let obj =
@@ -55,7 +56,10 @@ pub mod user {
                 }
             }
 
-            List { list: objects }
+            List {
+                list: objects,
+                ..Default::default()
+            }
         })
         .await
     }
@@ -92,6 +96,7 @@ pub mod group {
 
                 let id: Id = Id {
                     id: uuid.unwrap().to_string(),
+                    include_archived: false,
                 };
                 let mut resource: ResourceObject<Data> = id.clone().into();
                 let obj =
@@ -103,7 +108,10 @@ pub mod group {
                 }
             }
 
-            List { list: objects }
+            List {
+                list: objects,
+                ..Default::default()
+            }
         })
         .await
     }
@@ -0,0 +1,296 @@
+//! Timeslot reservations
+//!
+//! Records can reserve another resource during a timeslot (see [`Resource::get_timeslot_reservations`]),
+//! a flight plan reserves its origin and target vertipad for example. Two records may not reserve
+//! the same resource during overlapping timeslots.
+//!
+//! Conflicts are checked with [`check_conflicts`] after a record has been written, within the same
+//! transaction, so the write can be rolled back if any conflicts are found.
+
+use super::{get_psql_client, ArrErr, PsqlField};
+use crate::grpc::server::ValidationError;
+use crate::resources::base::{Resource, ResourceDefinition, TimeslotReservation};
+
+use deadpool_postgres::Transaction;
+use lib_common::time::{DateTime, Utc};
+use lib_common::uuid::Uuid;
+use std::collections::HashMap;
+use tokio_postgres::Row;
+
+/// Checks the timeslot reservations of the record matching the provided ids against the
+/// reservations of all other records, using the provided transaction.
+///
+/// Should be called after the record has been written, so its current values are used.
+///
+/// # Errors
+///
+/// Returns [`ArrErr::TimeslotConflict`] with a [`ValidationError`] for each conflicting reservation
+/// Returns [`ArrErr`] Database Error if database query execution failed
+#[cfg(not(tarpaulin_include))]
+// no_coverage: (R5) Is part of integration tests, coverage report will need to be merged to show.
+pub async fn check_conflicts<V: Resource>(
+    transaction: &Transaction<'_>,
+    ids: &HashMap<String, Uuid>,
+) -> Result<(), ArrErr> {
+    let reservations = V::get_timeslot_reservations();
+    if reservations.is_empty() {
+        return Ok(());
+    }
+
+    let definition = V::get_definition();
+    let id_col = get_id_col(&definition)?;
+
+    let mut params: Vec<&PsqlField> = vec![];
+    let mut keys: Vec<String> = vec![];
+    let mut other_keys: Vec<String> = vec![];
+    for (id_field, value) in ids {
+        keys.push(format!(
+            r#"reserved."{}" = ${}"#,
+            id_field,
+            params.len() + 1
+        ));
+        other_keys.push(format!(r#"other."{}" <> ${}"#, id_field, params.len() + 1));
+        params.push(value);
+    }
+
+    let mut errors: Vec<ValidationError> = vec![];
+    for reservation in &reservations {
+        let mut conditions = keys.clone();
+        conditions.push(format!("({})", other_keys.join(" OR ")));
+        conditions.append(&mut get_active_conditions(
+            &definition,
+            reservation,
+            "reserved",
+        ));
+        conditions.push(get_overlap_condition(
+            &definition,
+            &reservations,
+            &format!(r#"reserved."{}""#, reservation.field),
+            &format!(r#"reserved."{}""#, reservation.start_field),
+            &format!(r#"reserved."{}""#, reservation.end_field),
+        ));
+        let conflicts_sql = format!(
+            r#"SELECT other."{}" AS "id" FROM "{}" AS other, "{}" AS reserved WHERE {}"#,
+            id_col,
+            definition.psql_table,
+            definition.psql_table,
+            conditions.join(" AND "),
+        );
+        psql_debug!("[{}].", conflicts_sql);
+        psql_debug!("[{:?}].", &params);
+
+        let rows = transaction.query(&conflicts_sql, &params).await?;
+        if rows.is_empty() {
+            continue;
+        }
+        let conflicting: Vec<String> = rows
+            .iter()
+            .map(|row| row.get::<&str, Uuid>("id").to_string())
+            .collect();
+        errors.push(ValidationError {
+            field: reservation.field.clone(),
+            error: format!(
+                "Already reserved between [{}] and [{}] by [{}] record(s) [{}].",
+                reservation.start_field,
+                reservation.end_field,
+                definition.psql_table,
+                conflicting.join(", ")
+            ),
+        });
+    }
+
+    if errors.is_empty() {
+        return Ok(());
+    }
+    psql_info!(
+        "Timeslot conflicts found for [{}] with ids [{:?}]: {:?}",
+        definition.psql_table,
+        ids,
+        errors
+    );
+    Err(ArrErr::TimeslotConflict(errors))
+}
+
+/// Returns all records reserving the provided resource during (part of) the provided timeslot.
+///
+/// Archived records and records for which the reservation has been released are ignored, as well
+/// as the record matching the optional `exclude_id`.
+///
+/// # Errors
+///
+/// Returns [`ArrErr`] if no id column is configured for the resource
+/// Returns [`ArrErr`] from [`PoolError`](deadpool::managed::PoolError) if no client connection could be returned from the connection [`Pool`](deadpool::managed::Pool)
+/// Returns [`ArrErr`] Database Error if database query execution failed
+#[cfg(not(tarpaulin_include))]
+// no_coverage: (R5) Is part of integration tests, coverage report will need to be merged to show.
+pub async fn get_conflicts<V: Resource>(
+    resource_id: &Uuid,
+    start: &DateTime<Utc>,
+    end: &DateTime<Utc>,
+    exclude_id: Option<&Uuid>,
+) -> Result<Vec<Row>, ArrErr> {
+    let reservations = V::get_timeslot_reservations();
+    if reservations.is_empty() {
+        return Ok(vec![]);
+    }
+
+    let definition = V::get_definition();
+    let id_col = get_id_col(&definition)?;
+
+    let mut params: Vec<&PsqlField> = vec![resource_id, start, end];
+    let mut conditions = vec![get_overlap_condition(
+        &definition,
+        &reservations,
+        "$1",
+        "$2",
+        "$3",
+    )];
+    if let Some(exclude_id) = exclude_id {
+        conditions.push(format!(r#"other."{}" <> $4"#, id_col));
+        params.push(exclude_id);
+    }
+    let query = format!(
+        r#"SELECT other.* FROM {} WHERE {}"#,
+        V::get_psql_from(Some("other")),
+        conditions.join(" AND "),
+    );
+    psql_debug!("[{}].", query);
+    psql_debug!("[{:?}].", &params);
+
+    let client = get_psql_client().await?;
+    Ok(client.query(&query, &params).await?)
+}
+
+/// Returns the conditions a record needs to match for the provided reservation to be active,
+/// using the provided table alias.
+fn get_active_conditions(
+    definition: &ResourceDefinition,
+    reservation: &TimeslotReservation,
+    alias: &str,
+) -> Vec<String> {
+    let mut conditions = vec![];
+    if definition.has_field("deleted_at") {
+        conditions.push(format!(r#"{}."deleted_at" IS NULL"#, alias));
+    }
+    if let Some((field, states)) = &reservation.released_by {
+        let states: Vec<String> = states
+            .iter()
+            .map(|state| format!("'{}'", state.replace('\'', "''")))
+            .collect();
+        conditions.push(format!(
+            r#"{}."{}" NOT IN ({})"#,
+            alias,
+            field,
+            states.join(", ")
+        ));
+    }
+    conditions
+}
+
+/// Returns a condition matching `other` records with an active reservation of the provided
+/// resource overlapping the provided timeslot, for any of the provided reservations.
+///
+/// The provided `resource`, `start` and `end` values are used as is, so they can either refer to
+/// columns or to query parameters.
+fn get_overlap_condition(
+    definition: &ResourceDefinition,
+    reservations: &[TimeslotReservation],
+    resource: &str,
+    start: &str,
+    end: &str,
+) -> String {
+    let overlaps: Vec<String> = reservations
+        .iter()
+        .map(|reservation| {
+            let mut conditions = vec![
+                format!(r#"other."{}" = {}"#, reservation.field, resource),
+                format!(r#"other."{}" < {}"#, reservation.start_field, end),
+                format!(r#"other."{}" > {}"#, reservation.end_field, start),
+            ];
+            conditions.append(&mut get_active_conditions(definition, reservation, "other"));
+            format!("({})", conditions.join(" AND "))
+        })
+        .collect();
+    format!("({})", overlaps.join(" OR "))
+}
+
+/// Returns the first id column of the provided [`ResourceDefinition`]
+fn get_id_col(definition: &ResourceDefinition) -> Result<&String, ArrErr> {
+    match definition.psql_id_cols.first() {
+        Some(id_col) => Ok(id_col),
+        None => {
+            let error = format!(
+                "No id column configured for table {}",
+                definition.psql_table
+            );
+            psql_error!("{}", error);
+            Err(ArrErr::Error(error))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::resources::base::ResourceObject;
+    use crate::resources::flight_plan;
+    use crate::test_util::*;
+
+    #[tokio::test]
+    async fn test_get_active_conditions() {
+        assert_init_done().await;
+        ut_info!("start");
+
+        let definition = ResourceObject::<flight_plan::Data>::get_definition();
+        let reservation = TimeslotReservation::new("pad_id", "start", "end");
+        assert_eq!(
+            get_active_conditions(&definition, &reservation, "other"),
+            vec![String::from(r#"other."deleted_at" IS NULL"#)]
+        );
+
+        let reservation = reservation.released_by("status", &["CANCELLED", "DON'T"]);
+        assert_eq!(
+            get_active_conditions(&definition, &reservation, "reserved"),
+            vec![
+                String::from(r#"reserved."deleted_at" IS NULL"#),
+                String::from(r#"reserved."status" NOT IN ('CANCELLED', 'DON''T')"#)
+            ]
+        );
+
+        ut_info!("success");
+    }
+
+    #[tokio::test]
+    async fn test_get_overlap_condition() {
+        assert_init_done().await;
+        ut_info!("start");
+
+        let definition = ResourceObject::<flight_plan::Data>::get_definition();
+        let reservations = ResourceObject::<flight_plan::Data>::get_timeslot_reservations();
+        let condition = get_overlap_condition(&definition, &reservations, "$1", "$2", "$3");
+        assert_eq!(
+            condition,
+            format!(
+                "(({}) OR ({}))",
+                [
+                    r#"other."origin_vertipad_id" = $1"#,
+                    r#"other."origin_timeslot_start" < $3"#,
+                    r#"other."origin_timeslot_end" > $2"#,
+                    r#"other."deleted_at" IS NULL"#,
+                    r#"other."flight_status" NOT IN ('CANCELLED')"#,
+                ]
+                .join(" AND "),
+                [
+                    r#"other."target_vertipad_id" = $1"#,
+                    r#"other."target_timeslot_start" < $3"#,
+                    r#"other."target_timeslot_end" > $2"#,
+                    r#"other."deleted_at" IS NULL"#,
+                    r#"other."flight_status" NOT IN ('CANCELLED')"#,
+                ]
+                .join(" AND ")
+            )
+        );
+
+        ut_info!("success");
+    }
+}
@@ -2,6 +2,7 @@
 
 use super::{PsqlData, PsqlField, PsqlFieldSend};
 use crate::common::ArrErr;
+use crate::grpc::server::calendar::Calendar;
 use crate::grpc::server::geo_types::{GeoLineStringZ, GeoPointZ, GeoPolygonZ};
 use crate::grpc::server::ValidationError;
 use crate::grpc::{GrpcDataObjectType, GrpcField};
@@ -9,7 +10,9 @@ use crate::resources::base::{Resource, ResourceDefinition};
 use crate::resources::ValidationResult;
 use lib_common::time::{DateTime, Timestamp, Utc};
 use lib_common::uuid::Uuid;
+use prost_types::FieldMask;
 use serde_json::json;
+use std::collections::HashMap;
 use tokio_postgres::types::Type as PsqlFieldType;
 type InsertVars<'a> = (Vec<String>, Vec<String>, Vec<&'a PsqlField>);
 
@@ -73,6 +76,24 @@ pub fn validate_enum(
     }
 }
 
+/// Parses a schedule [`String`] into a [`Calendar`], returning the normalized schedule (used by postgres).
+/// Creates an error entry in the errors list if the schedule could not be parsed.
+pub fn validate_schedule(
+    field: String,
+    value: &str,
+    errors: &mut Vec<ValidationError>,
+) -> Option<String> {
+    match value.parse::<Calendar>() {
+        Ok(calendar) => Some(calendar.to_string()),
+        Err(e) => {
+            let error = format!("Could not convert [{}] to schedule: {}", field, e);
+            psql_warn!("{}", error);
+            errors.push(ValidationError { field, error });
+            None
+        }
+    }
+}
+
 /// Validates a [`PointZ`] (used by postgres).
 /// Creates an error entry in the errors list if a conversion was not possible.
 /// Returns `true` on success, `false` if the conversion failed.
@@ -178,11 +199,25 @@ pub fn get_insert_vars<'a>(
     psql_data: &'a PsqlData,
     definition: &'a ResourceDefinition,
     add_keys: bool,
+) -> Result<InsertVars<'a>, ArrErr> {
+    get_insert_vars_from_index(data, psql_data, definition, add_keys, 1)
+}
+
+/// Generates the insert statements and list of variables for the provided data,
+/// numbering the statement placeholders starting at `start_index`.
+///
+/// Used to build multi-row inserts where the params of multiple rows are combined in a single statement.
+pub fn get_insert_vars_from_index<'a>(
+    data: &'a impl GrpcDataObjectType,
+    psql_data: &'a PsqlData,
+    definition: &'a ResourceDefinition,
+    add_keys: bool,
+    start_index: usize,
 ) -> Result<InsertVars<'a>, ArrErr> {
     let mut params: Vec<&PsqlField> = vec![];
     let mut fields = vec![];
     let mut inserts = vec![];
-    let mut index = 1;
+    let mut index = start_index;
 
     if add_keys {
         let id_fields = definition.get_psql_id_cols();
@@ -310,6 +345,9 @@ pub fn get_insert_vars<'a>(
 }
 
 /// Generates the update statements and list of variables for the provided data
+///
+/// Only fields present in the provided `psql_data` will be part of the update statement.
+/// Use [`validate_with_mask`] to get `psql_data` restricted to a [`FieldMask`].
 pub fn get_update_vars<'a>(
     data: &'a impl GrpcDataObjectType,
     psql_data: &'a PsqlData,
@@ -464,13 +502,66 @@ pub fn validate<T>(data: &impl GrpcDataObjectType) -> Result<(PsqlData, Validati
 where
     T: Resource,
 {
-    psql_debug!("Start: [{:?}].", data);
+    validate_with_mask::<T>(data, None)
+}
+
+/// Validation function for partial updates.
+///
+/// If a [`FieldMask`] with paths is provided, only the fields listed in the mask will be validated
+/// and returned in the [`PsqlData`] map, meaning only those fields will be written by
+/// [`get_update_vars`]. Mandatory checks are skipped for all fields not part of the mask.
+/// Paths that are not known (or can not be updated) for the resource will result in a
+/// [`ValidationError`].
+/// An empty or missing mask validates all fields, just like [`validate`].
+pub fn validate_with_mask<T>(
+    data: &impl GrpcDataObjectType,
+    mask: Option<&FieldMask>,
+) -> Result<(PsqlData, ValidationResult), ArrErr>
+where
+    T: Resource,
+{
+    psql_debug!("Start: [{:?}] with mask [{:?}].", data, mask);
     let definition = T::get_definition();
 
     let mut converted: PsqlData = PsqlData::new();
     let mut success = true;
     let mut errors: Vec<ValidationError> = vec![];
 
+    // Paths can be provided relative to the `UpdateObject` (`data.<field>`) or to the `Data` object (`<field>`)
+    let paths: Option<Vec<String>> = match mask {
+        Some(mask) if !mask.paths.is_empty() => Some(
+            mask.paths
+                .iter()
+                .map(|path| path.strip_prefix("data.").unwrap_or(path).to_owned())
+                .collect(),
+        ),
+        _ => None,
+    };
+    if let Some(paths) = &paths {
+        for path in paths {
+            let error = match definition.fields.get(path) {
+                Some(field) if field.is_internal() => format!(
+                    "Unknown field [{}] provided in update mask for table [{}].",
+                    path, definition.psql_table
+                ),
+                Some(field) if field.is_read_only() => format!(
+                    "Field [{}] provided in update mask for table [{}] is read only.",
+                    path, definition.psql_table
+                ),
+                Some(_) => continue,
+                None => format!(
+                    "Unknown field [{}] provided in update mask for table [{}].",
+                    path, definition.psql_table
+                ),
+            };
+            psql_warn!("{}", error);
+            errors.push(ValidationError {
+                field: path.clone(),
+                error,
+            });
+        }
+    }
+
     // Check if we have any id_fields as part of our data object.
     // They will need to be inserted as well.
     let id_fields = definition.get_psql_id_cols();
@@ -497,6 +588,12 @@ where
             // internal / read_only field, skip for validation
             continue;
         }
+        if let Some(paths) = &paths {
+            if !paths.contains(&key) {
+                // field is not part of the update mask, skip for validation
+                continue;
+            }
+        }
 
         let field_value = data.get_field_value(&key)?;
         let val_to_validate = match field_value {
@@ -627,7 +724,14 @@ where
             }
             PsqlFieldType::TEXT => {
                 let val: String = val_to_validate.into();
-                converted.insert(key, Box::new(val));
+                if field.is_schedule() {
+                    let schedule = validate_schedule(key.to_string(), &val, &mut errors);
+                    if let Some(val) = schedule {
+                        converted.insert(key, Box::new(val));
+                    }
+                } else {
+                    converted.insert(key, Box::new(val));
+                }
             }
             PsqlFieldType::INT8 => {
                 let val: i64 = val_to_validate.into();
@@ -683,6 +787,90 @@ where
     Ok((converted, ValidationResult { errors, success }))
 }
 
+/// Validates the state transitions of the resource's enum fields (see
+/// [`Resource::get_state_transitions`]) for the provided update data.
+///
+/// The provided `current` map should contain the current (database) enum string value for each
+/// field with state transitions. Only fields part of the provided `psql_data` are checked.
+/// Any invalid transition will result in a [`ValidationError`]. For each valid transition, the
+/// transition's `set_now` fields will be added to `psql_data` with the current time, unless a
+/// value has been provided for them.
+pub fn validate_state_transitions<T>(
+    data: &impl GrpcDataObjectType,
+    current: &HashMap<String, String>,
+    psql_data: &mut PsqlData,
+) -> Result<ValidationResult, ArrErr>
+where
+    T: Resource,
+{
+    let transitions = T::get_state_transitions();
+    let mut errors: Vec<ValidationError> = vec![];
+
+    for (field, from) in current {
+        if !psql_data.contains_key(field) {
+            // field is not part of the update, nothing to check
+            continue;
+        }
+
+        let to = match data.get_field_value(field)? {
+            GrpcField::I32(value) => match T::get_enum_string_val(field, value) {
+                Some(to) => to,
+                // invalid enum values are reported by the field validation already
+                None => continue,
+            },
+            _ => {
+                let error = format!(
+                    "State transition configured for field [{}], but it's not an enum field.",
+                    field
+                );
+                psql_error!("{}", error);
+                return Err(ArrErr::Error(error));
+            }
+        };
+        if &to == from {
+            continue;
+        }
+
+        match transitions
+            .iter()
+            .find(|transition| &transition.field == field && transition.allows(from, &to))
+        {
+            Some(transition) => {
+                for now_field in &transition.set_now {
+                    let provided = psql_data.contains_key(now_field)
+                        && match data.get_field_value(now_field)? {
+                            GrpcField::Option(option) => {
+                                let option: Option<GrpcField> = option.into();
+                                option.is_some()
+                            }
+                            _ => true,
+                        };
+                    if !provided {
+                        psql_debug!(
+                            "Setting [{}] for state transition of [{}] from [{}] to [{}].",
+                            now_field,
+                            field,
+                            from,
+                            to
+                        );
+                        psql_data.insert(now_field.clone(), Box::new(Utc::now()));
+                    }
+                }
+            }
+            None => errors.push(ValidationError {
+                field: field.clone(),
+                error: format!("Invalid state transition from [{}] to [{}].", from, to),
+            }),
+        }
+    }
+
+    let success = errors.is_empty();
+    if !success {
+        psql_info!("Invalid state transitions found: {:?}", errors);
+    }
+    Ok(ValidationResult { errors, success })
+}
+
 #[cfg(test)]
 mod tests {
     use lib_common::uuid::Uuid;
@@ -759,6 +947,41 @@ mod tests {
         ut_info!("success");
     }
 
+    #[tokio::test]
+    async fn test_validate_schedule() {
+        assert_init_done().await;
+        ut_info!("start");
+
+        let mut errors: Vec<ValidationError> = vec![];
+        let result = validate_schedule(
+            String::from("schedule"),
+            "BEGIN:VEVENT\nDTSTART:20221020T180000Z\nDTEND:20221021T080000Z\nRRULE:FREQ=WEEKLY;BYDAY=FR,MO\nEND:VEVENT",
+            &mut errors,
+        );
+        assert_eq!(
+            result,
+            Some(String::from(
+                "DTSTART:20221020T180000Z;DURATION:PT14H\nRRULE:FREQ=WEEKLY;BYDAY=MO,FR"
+            ))
+        );
+        assert!(errors.is_empty());
+
+        let result = validate_schedule(
+            String::from("schedule"),
+            "DTSTART:20221020T180000Z;DURATION:PT14H\nRRULE:FREQ=WEKLY",
+            &mut errors,
+        );
+        assert!(result.is_none());
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].field, "schedule");
+        assert_eq!(
+            errors[0].error,
+            "Could not convert [schedule] to schedule: line 2: unsupported FREQ [WEKLY], should be DAILY or WEEKLY"
+        );
+
+        ut_info!("success");
+    }
+
     #[tokio::test]
     async fn test_validate_point_valid() {
         assert_init_done().await;
@@ -1091,6 +1314,61 @@ mod tests {
         ut_info!("success");
     }
 
+    #[tokio::test]
+    async fn test_get_insert_vars_from_index() {
+        assert_init_done().await;
+        ut_info!("start");
+
+        let valid_data = get_valid_data(
+            Uuid::new_v4(),
+            Uuid::new_v4(),
+            Some(Utc::now().into()),
+            Some(Utc::now().into()),
+        );
+
+        let (psql_data, validation_result) = match validate::<ResourceObject<Data>>(&valid_data) {
+            Ok(result) => result,
+            Err(e) => {
+                panic!("Validation errors found but not expected: {}", e);
+            }
+        };
+        assert!(validation_result.success);
+
+        let definition = <ResourceObject<Data>>::get_definition();
+        let (inserts, fields, params) =
+            match get_insert_vars(&valid_data, &psql_data, &definition, false) {
+                Ok(result) => result,
+                Err(e) => {
+                    panic!("Conversion errors found but not expected: {}", e);
+                }
+            };
+        let (offset_inserts, offset_fields, offset_params) =
+            match get_insert_vars_from_index(&valid_data, &psql_data, &definition, false, 20) {
+                Ok(result) => result,
+                Err(e) => {
+                    panic!("Conversion errors found but not expected: {}", e);
+                }
+            };
+
+        // Same fields and params, only the placeholder numbering should differ
+        assert_eq!(fields, offset_fields);
+        assert_eq!(params.len(), offset_params.len());
+        assert_eq!(inserts.len(), offset_inserts.len());
+        let placeholder = Regex::new(r"\$(\d+)").unwrap();
+        for (insert, offset_insert) in inserts.iter().zip(offset_inserts.iter()) {
+            match placeholder.captures(insert) {
+                Some(captures) => {
+                    let index: usize = captures[1].parse().unwrap();
+                    let expected = insert.replace(&captures[0], &format!("${}", index + 19));
+                    assert_eq!(*offset_insert, expected);
+                }
+                None => assert_eq!(insert, offset_insert),
+            }
+        }
+
+        ut_info!("success");
+    }
+
     #[tokio::test]
     async fn test_get_update_vars() {
         assert_init_done().await;
@@ -1190,6 +1468,102 @@ mod tests {
         ut_info!("success");
     }
 
+    #[tokio::test]
+    async fn test_validate_with_mask() {
+        assert_init_done().await;
+        ut_info!("start");
+
+        let uuid = Uuid::new_v4();
+        let optional_uuid = Uuid::new_v4();
+        let timestamp = Some(Utc::now().into());
+        let optional_timestamp = Some(Utc::now().into());
+
+        let valid_data = get_valid_data(
+            uuid,
+            optional_uuid,
+            timestamp.clone(),
+            optional_timestamp.clone(),
+        );
+        let mask = FieldMask {
+            paths: vec![
+                String::from("optional_uuid"),
+                String::from("data.geo_point"),
+            ],
+        };
+
+        let (psql_data, validation_result) =
+            match validate_with_mask::<ResourceObject<Data>>(&valid_data, Some(&mask)) {
+                Ok(result) => result,
+                Err(e) => {
+                    panic!("Validation errors found but not expected: {}", e);
+                }
+            };
+        assert!(validation_result.success);
+        assert_eq!(psql_data.len(), 2);
+        assert!(psql_data.contains_key("optional_uuid"));
+        assert!(psql_data.contains_key("geo_point"));
+
+        let definition = <ResourceObject<Data>>::get_definition();
+        match get_update_vars(&valid_data, &psql_data, &definition) {
+            Ok((updates, params)) => {
+                assert_eq!(updates.len(), 2);
+                assert_eq!(params.len(), 1);
+                assert!(updates.contains(&String::from(r#""optional_uuid" = $1"#)));
+            }
+            Err(e) => {
+                panic!("Conversion errors found but not expected: {}", e);
+            }
+        }
+
+        // An empty mask should validate all fields
+        let (psql_data, validation_result) = match validate_with_mask::<ResourceObject<Data>>(
+            &valid_data,
+            Some(&FieldMask { paths: vec![] }),
+        ) {
+            Ok(result) => result,
+            Err(e) => {
+                panic!("Validation errors found but not expected: {}", e);
+            }
+        };
+        assert!(validation_result.success);
+        assert_eq!(psql_data.len(), 25);
+
+        ut_info!("success");
+    }
+
+    #[tokio::test]
+    async fn test_validate_with_mask_invalid_paths() {
+        assert_init_done().await;
+        ut_info!("start");
+
+        let valid_data = get_valid_data(
+            Uuid::new_v4(),
+            Uuid::new_v4(),
+            Some(Utc::now().into()),
+            Some(Utc::now().into()),
+        );
+        let mask = FieldMask {
+            paths: vec![
+                String::from("string"),
+                String::from("unknown_field"),
+                String::from("read_only"),
+            ],
+        };
+
+        let (_, validation_result) =
+            match validate_with_mask::<ResourceObject<Data>>(&valid_data, Some(&mask)) {
+                Ok(result) => result,
+                Err(e) => panic!("Validation errors found but not expected: {}", e),
+            };
+
+        assert!(!validation_result.success);
+        assert_eq!(validation_result.errors.len(), 2);
+        assert_eq!(validation_result.errors[0].field, "unknown_field");
+        assert_eq!(validation_result.errors[1].field, "read_only");
+
+        ut_info!("success");
+    }
+
     #[tokio::test]
     async fn test_validate_invalid_object() {
         assert_init_done().await;
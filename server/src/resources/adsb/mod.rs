@@ -98,6 +98,7 @@ mod tests {
         let object: ResourceObject<Data> = Object {
             id,
             data: Some(data.clone()),
+            deleted_at: None,
         }
         .into();
         test_schema::<ResourceObject<Data>, Data>(object);
@@ -0,0 +1,204 @@
+//! Audit log
+
+pub use crate::grpc::server::audit::*;
+
+use anyhow::{Context, Result};
+use lib_common::uuid::Uuid;
+use std::collections::HashMap;
+use tokio_postgres::row::Row;
+use tokio_postgres::types::Type as PsqlFieldType;
+
+use super::base::simple_resource::*;
+use super::base::{FieldDefinition, ResourceDefinition};
+use crate::common::ArrErr;
+use crate::grpc::{GrpcDataObjectType, GrpcField, GrpcFieldOption, GrpcSearchResultList};
+use crate::postgres::audit::AUDIT_LOG_TABLE;
+use crate::postgres::PagingInfo;
+
+impl PsqlSearch for ResourceObject<Data> {}
+impl PsqlInitSimpleResource for ResourceObject<Data> {}
+impl PsqlInitResource for ResourceObject<Data> {
+    fn _get_create_table_query() -> String {
+        <ResourceObject<Data> as PsqlInitSimpleResource>::_get_create_table_query()
+    }
+}
+
+impl Resource for ResourceObject<Data> {
+    fn get_definition() -> ResourceDefinition {
+        ResourceDefinition {
+            psql_table: String::from(AUDIT_LOG_TABLE),
+            psql_id_cols: vec![String::from("audit_log_id")],
+            fields: HashMap::from([
+                (
+                    "resource".to_string(),
+                    FieldDefinition::new(PsqlFieldType::TEXT, true),
+                ),
+                (
+                    "resource_id".to_string(),
+                    FieldDefinition::new(PsqlFieldType::UUID, true),
+                ),
+                (
+                    "linked_id".to_string(),
+                    FieldDefinition::new(PsqlFieldType::UUID, false),
+                ),
+                (
+                    "action".to_string(),
+                    FieldDefinition::new(PsqlFieldType::ANYENUM, true),
+                ),
+                (
+                    "actor".to_string(),
+                    FieldDefinition::new(PsqlFieldType::TEXT, true),
+                ),
+                (
+                    "diff".to_string(),
+                    FieldDefinition::new(PsqlFieldType::TEXT, true),
+                ),
+                (
+                    "created_at".to_string(),
+                    FieldDefinition::new_internal(PsqlFieldType::TIMESTAMPTZ, true)
+                        .set_default(String::from("CURRENT_TIMESTAMP")),
+                ),
+            ]),
+        }
+    }
+
+    /// Converts raw i32 values into string based on matching Enum value
+    fn get_enum_string_val(field: &str, value: i32) -> Option<String> {
+        match field {
+            "action" => Some(AuditAction::try_from(value).ok()?.as_str_name().to_string()),
+            _ => None,
+        }
+    }
+
+    fn get_table_indices() -> Vec<String> {
+        [
+            r#"CREATE INDEX IF NOT EXISTS audit_log_resource_idx ON "audit_log" ("resource", "resource_id")"#.to_string(),
+            r#"CREATE INDEX IF NOT EXISTS audit_log_created_at_idx ON "audit_log" ("created_at")"#.to_string(),
+        ]
+        .to_vec()
+    }
+}
+
+impl GrpcDataObjectType for Data {
+    fn get_field_value(&self, key: &str) -> Result<GrpcField, ArrErr> {
+        match key {
+            "resource" => Ok(GrpcField::String(self.resource.clone())),
+            "resource_id" => Ok(GrpcField::String(self.resource_id.clone())),
+            "linked_id" => Ok(GrpcField::Option(GrpcFieldOption::String(
+                self.linked_id.clone(),
+            ))),
+            "action" => Ok(GrpcField::I32(self.action)),
+            "actor" => Ok(GrpcField::String(self.actor.clone())),
+            "diff" => Ok(GrpcField::String(self.diff.clone())),
+            "created_at" => Ok(GrpcField::Option(GrpcFieldOption::Timestamp(
+                self.created_at.clone(),
+            ))),
+            _ => Err(ArrErr::Error(format!(
+                "Invalid key specified [{}], no such field found",
+                key
+            ))),
+        }
+    }
+}
+
+#[cfg(not(tarpaulin_include))]
+// no_coverage: (Rwaiting) Can not be tested in unittest until https://github.com/sfackler/rust-postgres/pull/979 has been merged
+impl TryFrom<Row> for Data {
+    type Error = ArrErr;
+
+    fn try_from(row: Row) -> Result<Self, ArrErr> {
+        resources_debug!("Converting Row to audit::Data: {:?}", row);
+        let resource_id: Uuid = row.get("resource_id");
+        let linked_id: Option<Uuid> = row.get("linked_id");
+        let created_at: lib_common::time::DateTime<lib_common::time::Utc> = row.get("created_at");
+
+        let action = AuditAction::from_str_name(row.get("action"))
+            .context("(try_from) Could not convert database value to AuditAction Enum type.")?
+            as i32;
+
+        Ok(Data {
+            resource: row.get("resource"),
+            resource_id: resource_id.to_string(),
+            linked_id: linked_id.map(|id| id.to_string()),
+            action,
+            actor: row.get("actor"),
+            created_at: Some(created_at.into()),
+            diff: row.get("diff"),
+        })
+    }
+}
+
+#[cfg(not(tarpaulin_include))]
+// no_coverage: (Rwaiting) Can not be tested in unittest until https://github.com/sfackler/rust-postgres/pull/979 has been merged
+impl TryFrom<Row> for Object {
+    type Error = ArrErr;
+
+    fn try_from(row: Row) -> Result<Self, ArrErr> {
+        let id: Uuid = row.get("audit_log_id");
+        Ok(Object {
+            id: id.to_string(),
+            data: Some(row.try_into()?),
+        })
+    }
+}
+
+#[cfg(not(tarpaulin_include))]
+// no_coverage: (Rwaiting) Can not be tested in unittest until https://github.com/sfackler/rust-postgres/pull/979 has been merged
+impl TryFrom<Vec<Row>> for List {
+    type Error = ArrErr;
+
+    fn try_from(rows: Vec<Row>) -> Result<Self, ArrErr> {
+        resources_debug!("Converting Vec<Row> to List: {:?}", rows);
+        let mut res: Vec<Object> = Vec::with_capacity(rows.len());
+
+        for row in rows.into_iter() {
+            res.push(row.try_into()?);
+        }
+        Ok(List {
+            list: res,
+            ..Default::default()
+        })
+    }
+}
+
+impl GrpcSearchResultList for List {
+    fn set_paging_info(&mut self, paging_info: PagingInfo) {
+        self.total_count = paging_info.total_count;
+        self.has_more = paging_info.has_more;
+        self.next_cursor = paging_info.next_cursor;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::*;
+
+    #[tokio::test]
+    async fn test_audit_get_enum_string_val() {
+        assert_init_done().await;
+        ut_info!("start");
+
+        assert_eq!(
+            ResourceObject::<Data>::get_enum_string_val("action", AuditAction::Insert.into()),
+            Some(String::from("INSERT"))
+        );
+        assert_eq!(
+            ResourceObject::<Data>::get_enum_string_val(
+                "action",
+                AuditAction::ReplaceLinked.into()
+            ),
+            Some(String::from("REPLACE_LINKED"))
+        );
+        assert_eq!(
+            ResourceObject::<Data>::get_enum_string_val("action", -1),
+            None
+        );
+        assert_eq!(
+            ResourceObject::<Data>::get_enum_string_val("invalid_field", 0),
+            None
+        );
+
+        ut_info!("success");
+    }
+}
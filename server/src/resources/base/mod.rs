@@ -4,10 +4,12 @@ pub mod linked_resource;
 pub mod simple_resource;
 pub mod simple_resource_linked;
 
-use crate::grpc::server::{Id, IdList, Ids};
+use crate::grpc::server::{DeleteRequest, Id, IdList, Ids, LinkedDeleteRequest};
+use crate::postgres::migrations::Migration;
 use crate::postgres::PsqlJsonValue;
 use crate::{common::ArrErr, grpc::GrpcDataObjectType};
 use core::fmt::Debug;
+use lib_common::time::Timestamp;
 use lib_common::uuid::Uuid;
 use log::error;
 use std::collections::HashMap;
@@ -31,6 +33,53 @@ where
     fn get_table_indices() -> Vec<String> {
         vec![]
     }
+    /// Returns the migration steps that should be applied on top of the resource's baseline table creation.
+    /// This function should be implemented for the resources where applicable (example implementation can be found in the vehicle module).
+    fn get_migrations() -> Vec<Migration> {
+        vec![]
+    }
+    /// Returns the sets of fields which should be unique among the non archived records.
+    /// These are checked when restoring an archived record.
+    /// This function should be implemented for the resources where applicable (example implementation can be found in the user module).
+    fn get_unique_fields() -> Vec<Vec<String>> {
+        vec![]
+    }
+    /// Returns the allowed state transitions for the resource's enum fields.
+    /// Updates changing one of these fields to a value not covered by a transition will be rejected.
+    /// This function should be implemented for the resources where applicable (example implementation can be found in the flight_plan module).
+    fn get_state_transitions() -> Vec<StateTransition> {
+        vec![]
+    }
+    /// Returns the timeslots during which a record reserves another resource.
+    /// Inserts and updates resulting in overlapping reservations of the same resource will be rejected.
+    /// This function should be implemented for the resources where applicable (example implementation can be found in the flight_plan module).
+    fn get_timeslot_reservations() -> Vec<TimeslotReservation> {
+        vec![]
+    }
+    /// Returns the query records should be read from, if they can not be read from the resource's table directly.
+    /// Can be used to add columns from other tables to the resulting rows, so they don't have to be fetched for each row separately.
+    /// The resulting columns should include all columns of the resource's table.
+    /// This function should be implemented for the resources where applicable (example implementation can be found in the flight_plan module).
+    fn get_psql_select() -> Option<String> {
+        None
+    }
+    /// Returns the `FROM` expression used to read records, using the provided alias.
+    /// Defaults to the resource's table name as alias, so conditions using the table's columns can be used as is.
+    fn get_psql_from(alias: Option<&str>) -> String {
+        let table = format!(r#""{}""#, Self::get_psql_table());
+        match (Self::get_psql_select(), alias) {
+            (Some(select), Some(alias)) => format!("({}) AS {}", select, alias),
+            (Some(select), None) => format!("({}) AS {}", select, table),
+            (None, Some(alias)) => format!("{} AS {}", table, alias),
+            (None, None) => table,
+        }
+    }
+    /// Returns the resources (table names) of which the cached values include data of this resource.
+    /// Their cached values will be removed as well when this resource's records change.
+    /// This function should be implemented for the resources where applicable (example implementation can be found in the vertipad module).
+    fn get_cache_dependents() -> Vec<String> {
+        vec![]
+    }
     /// Returns `true` if the given column name is part of the resource's combined id
     fn has_id_col(id_col: &str) -> bool {
         for col in Self::get_definition().get_psql_id_cols() {
@@ -60,10 +109,22 @@ where
     fn get_data(&self) -> Option<T> {
         None
     }
+    /// Get [`ObjectType<T>`]'s `mask` field, to be implemented by trait implementor
+    fn get_mask(&self) -> Option<::prost_types::FieldMask> {
+        None
+    }
+    /// Get [`ObjectType<T>`]'s `expected_updated_at` field, to be implemented by trait implementor
+    fn get_expected_updated_at(&self) -> Option<Timestamp> {
+        None
+    }
     /// Set [`ObjectType<T>`]'s `ids` field, to be implemented by trait implementor
     fn set_ids(&mut self, ids: HashMap<String, String>);
     /// Set [`ObjectType<T>`]'s `data` field, to be implemented by trait implementor
     fn set_data(&mut self, data: T);
+    /// Set [`ObjectType<T>`]'s `mask` field, to be implemented by trait implementor
+    fn set_mask(&mut self, _mask: Option<::prost_types::FieldMask>) {}
+    /// Set [`ObjectType<T>`]'s `expected_updated_at` field, to be implemented by trait implementor
+    fn set_expected_updated_at(&mut self, _expected_updated_at: Option<Timestamp>) {}
 
     /// Returns [`ObjectType<T>`]'s `data` [`GrpcDataObjectType`] value
     ///
@@ -157,6 +218,74 @@ impl ResourceDefinition {
     }
 }
 
+/// struct object defining an allowed state transition of an enum field
+#[derive(Clone, Debug)]
+pub struct StateTransition {
+    /// name of the enum field holding the state
+    pub field: String,
+    /// enum string values the field is allowed to transition from
+    pub from: Vec<String>,
+    /// enum string value the field is allowed to transition to
+    pub to: String,
+    /// timestamp fields which should be set to the current time when this transition occurs,
+    /// unless a value has been provided for them in the update
+    pub set_now: Vec<String>,
+}
+
+impl StateTransition {
+    /// Create a new [`StateTransition`] for the provided field, from the provided states to the provided state
+    pub fn new(field: &str, from: &[&str], to: &str) -> Self {
+        Self {
+            field: field.to_owned(),
+            from: from.iter().map(|state| state.to_string()).collect(),
+            to: to.to_owned(),
+            set_now: vec![],
+        }
+    }
+    /// Sets the timestamp fields which should be set to the current time when this transition occurs
+    pub fn set_now(mut self, fields: &[&str]) -> Self {
+        self.set_now = fields.iter().map(|field| field.to_string()).collect();
+        self
+    }
+    /// Returns [`bool`] `true` if this transition allows the field to change from the provided `from` state to the provided `to` state
+    pub fn allows(&self, from: &str, to: &str) -> bool {
+        self.to == to && self.from.iter().any(|state| state == from)
+    }
+}
+
+/// struct object defining a timeslot during which a record reserves another resource
+#[derive(Clone, Debug)]
+pub struct TimeslotReservation {
+    /// name of the field holding the id of the reserved resource
+    pub field: String,
+    /// name of the field holding the start of the timeslot
+    pub start_field: String,
+    /// name of the field holding the end of the timeslot
+    pub end_field: String,
+    /// enum field and string values for which the record does not reserve the timeslot
+    pub released_by: Option<(String, Vec<String>)>,
+}
+
+impl TimeslotReservation {
+    /// Create a new [`TimeslotReservation`] for the provided resource id field and timeslot fields
+    pub fn new(field: &str, start_field: &str, end_field: &str) -> Self {
+        Self {
+            field: field.to_owned(),
+            start_field: start_field.to_owned(),
+            end_field: end_field.to_owned(),
+            released_by: None,
+        }
+    }
+    /// Sets the enum field and string values for which the record does not reserve the timeslot
+    pub fn released_by(mut self, field: &str, states: &[&str]) -> Self {
+        self.released_by = Some((
+            field.to_owned(),
+            states.iter().map(|state| state.to_string()).collect(),
+        ));
+        self
+    }
+}
+
 /// Generic resource wrapper struct used to implement our generic traits
 #[derive(Clone, Debug, PartialEq)]
 pub struct ResourceObject<T>
@@ -167,6 +296,8 @@ where
     pub ids: Option<HashMap<String, String>>,
     /// resource field data
     pub data: Option<T>,
+    /// expected `updated_at` value of the resource, used for optimistic concurrency checks
+    pub expected_updated_at: Option<Timestamp>,
     /// field mask used for update actions
     pub mask: Option<::prost_types::FieldMask>,
 }
@@ -186,6 +317,18 @@ where
     fn set_data(&mut self, data: T) {
         self.data = Some(data)
     }
+    fn get_mask(&self) -> Option<::prost_types::FieldMask> {
+        self.mask.clone()
+    }
+    fn set_mask(&mut self, mask: Option<::prost_types::FieldMask>) {
+        self.mask = mask
+    }
+    fn get_expected_updated_at(&self) -> Option<Timestamp> {
+        self.expected_updated_at.clone()
+    }
+    fn set_expected_updated_at(&mut self, expected_updated_at: Option<Timestamp>) {
+        self.expected_updated_at = expected_updated_at
+    }
 }
 
 /// Field definition struct defining field properties
@@ -201,6 +344,9 @@ pub struct FieldDefinition {
     read_only: bool,
     /// [`String`] option to provide a default value used during database inserts
     default: Option<String>,
+    /// [`bool`] to set if the field contains a schedule, which will be validated and normalized
+    /// using a [`Calendar`](crate::resources::calendar::Calendar)
+    schedule: bool,
 }
 
 impl FieldDefinition {
@@ -212,6 +358,7 @@ impl FieldDefinition {
             internal: false,
             read_only: false,
             default: None,
+            schedule: false,
         }
     }
     /// Create a new internal [`FieldDefinition`] with provided field_type and mandatory setting
@@ -222,6 +369,7 @@ impl FieldDefinition {
             internal: true,
             read_only: true,
             default: None,
+            schedule: false,
         }
     }
     /// Create a new read_only [`FieldDefinition`] with provided field_type and mandatory setting
@@ -232,6 +380,7 @@ impl FieldDefinition {
             internal: false,
             read_only: true,
             default: None,
+            schedule: false,
         }
     }
 
@@ -248,6 +397,17 @@ impl FieldDefinition {
         self.read_only
     }
 
+    /// Returns [`bool`] schedule
+    pub fn is_schedule(&self) -> bool {
+        self.schedule
+    }
+    /// Marks the field as schedule field, so its values will be validated and normalized as
+    /// [`Calendar`](crate::resources::calendar::Calendar)
+    pub fn set_schedule(&mut self) -> Self {
+        self.schedule = true;
+        self.clone()
+    }
+
     /// Returns [`bool`] `true` if a `default` value has been provided for this field and `false`if not
     pub fn has_default(&self) -> bool {
         self.default.is_some()
@@ -277,6 +437,22 @@ impl TryFrom<Id> for Uuid {
         Uuid::try_parse(&id.id).map_err(ArrErr::UuidError)
     }
 }
+impl From<Id> for DeleteRequest {
+    fn from(id: Id) -> Self {
+        Self {
+            id: id.id,
+            expected_updated_at: None,
+        }
+    }
+}
+impl From<Ids> for LinkedDeleteRequest {
+    fn from(ids: Ids) -> Self {
+        Self {
+            ids: ids.ids,
+            expected_updated_at: None,
+        }
+    }
+}
 impl TryFrom<IdList> for Vec<Uuid> {
     type Error = ArrErr;
     fn try_from(list: IdList) -> Result<Self, ArrErr> {
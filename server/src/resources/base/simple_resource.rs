@@ -122,6 +122,7 @@ where
         Self {
             ids: Some(HashMap::from([(id_field, id.id)])),
             data: None,
+            expected_updated_at: None,
             mask: None,
         }
     }
@@ -136,6 +137,7 @@ where
         Self {
             ids: None,
             data: Some(obj),
+            expected_updated_at: None,
             mask: None,
         }
     }
@@ -153,6 +155,27 @@ macro_rules! build_grpc_simple_resource_impl {
             }
         }
 
+        impl TryFrom<Row> for Object {
+            type Error = ArrErr;
+
+            fn try_from(row: Row) -> Result<Self, ArrErr> {
+                let id: Uuid = row.get(format!("{}_id", stringify!($resource)).as_str());
+                // Only resources that can be archived have a `deleted_at` column
+                let deleted_at: Option<::prost_wkt_types::Timestamp> = row
+                    .try_get::<&str, Option<lib_common::time::DateTime<lib_common::time::Utc>>>(
+                        "deleted_at",
+                    )
+                    .ok()
+                    .flatten()
+                    .map(|val| val.into());
+                Ok(Object {
+                    id: id.to_string(),
+                    data: Some(row.try_into()?),
+                    deleted_at,
+                })
+            }
+        }
+
         impl TryFrom<Vec<Row>> for List {
             type Error = ArrErr;
 
@@ -161,14 +184,26 @@ macro_rules! build_grpc_simple_resource_impl {
                 let mut res: Vec<Object> = Vec::with_capacity(rows.len());
 
                 for row in rows.into_iter() {
-                    let id: Uuid = row.get(format!("{}_id", stringify!($resource)).as_str());
-                    let converted = Object {
-                        id: id.to_string(),
-                        data: Some(row.try_into()?),
-                    };
-                    res.push(converted);
+                    res.push(row.try_into()?);
                 }
-                Ok(List { list: res })
+                Ok(List {
+                    list: res,
+                    ..Default::default()
+                })
+            }
+        }
+
+        impl $crate::grpc::GrpcSearchResultList for List {
+            fn set_paging_info(&mut self, paging_info: $crate::postgres::PagingInfo) {
+                self.total_count = paging_info.total_count;
+                self.has_more = paging_info.has_more;
+                self.next_cursor = paging_info.next_cursor;
+            }
+        }
+
+        impl $crate::grpc::GrpcInsertBatchRequest<Data> for InsertBatchRequest {
+            fn into_parts(self) -> (Vec<Data>, bool) {
+                (self.list, self.all_or_nothing)
             }
         }
     };
@@ -191,6 +226,7 @@ macro_rules! build_generic_resource_impl_from {
                 Self {
                     ids: Some(HashMap::from([(id_field, obj.id)])),
                     data: obj.data,
+                    expected_updated_at: None,
                     mask: None,
                 }
             }
@@ -202,6 +238,7 @@ macro_rules! build_generic_resource_impl_from {
                     Ok(id) => Self {
                         id,
                         data: obj.get_data(),
+                        deleted_at: None,
                     },
                     Err(e) => {
                         panic!(
@@ -225,6 +262,7 @@ macro_rules! build_generic_resource_impl_from {
                 Self {
                     ids: Some(HashMap::from([(id_field, obj.id)])),
                     data: obj.data,
+                    expected_updated_at: obj.expected_updated_at,
                     mask: obj.mask,
                 }
             }
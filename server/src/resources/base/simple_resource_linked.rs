@@ -56,6 +56,7 @@ where
         Self {
             ids: Some(ids_hash),
             data: None,
+            expected_updated_at: None,
             mask: None,
         }
     }
@@ -109,7 +110,18 @@ macro_rules! build_grpc_simple_resource_linked_impl {
                 for row in rows.into_iter() {
                     res.push(row.try_into()?);
                 }
-                Ok(RowDataList { list: res })
+                Ok(RowDataList {
+                    list: res,
+                    ..Default::default()
+                })
+            }
+        }
+
+        impl $crate::grpc::GrpcSearchResultList for RowDataList {
+            fn set_paging_info(&mut self, paging_info: $crate::postgres::PagingInfo) {
+                self.total_count = paging_info.total_count;
+                self.has_more = paging_info.has_more;
+                self.next_cursor = paging_info.next_cursor;
             }
         }
     };
@@ -128,6 +140,7 @@ macro_rules! build_generic_resource_linked_impl_from {
                 Self {
                     ids: Some(ids),
                     data: obj.data,
+                    expected_updated_at: None,
                     mask: None,
                 }
             }
@@ -162,6 +175,7 @@ macro_rules! build_generic_resource_linked_impl_from {
                 Self {
                     ids: Some(ids),
                     data: obj.data,
+                    expected_updated_at: obj.expected_updated_at,
                     mask: obj.mask,
                 }
             }
@@ -7,16 +7,13 @@ use anyhow::{Context, Result};
 use lib_common::time::{DateTime, Utc};
 use lib_common::uuid::Uuid;
 use std::collections::HashMap;
-use tokio::task;
 use tokio_postgres::row::Row;
 use tokio_postgres::types::Type as PsqlFieldType;
 
 use super::base::simple_resource::*;
-use super::base::{FieldDefinition, ResourceDefinition};
+use super::base::{FieldDefinition, ResourceDefinition, StateTransition, TimeslotReservation};
 use crate::common::ArrErr;
-use crate::grpc::get_runtime_handle;
 use crate::grpc::{GrpcDataObjectType, GrpcField, GrpcFieldOption};
-use crate::resources::vertipad;
 
 // Generate `From` trait implementations for GenericResource into and from Grpc defined Resource
 crate::build_generic_resource_impl_from!();
@@ -147,12 +144,62 @@ impl Resource for ResourceObject<Data> {
 
     fn get_table_indices() -> Vec<String> {
         [
-            r#"ALTER TABLE "flight_plan" ADD CONSTRAINT fk_origin_vertipad_id FOREIGN KEY("origin_vertipad_id") REFERENCES "vertipad"("vertipad_id")"#.to_string(),
-            r#"ALTER TABLE "flight_plan" ADD CONSTRAINT fk_target_vertipad_id FOREIGN KEY("target_vertipad_id") REFERENCES "vertipad"("vertipad_id")"#.to_string(),
+            r#"ALTER TABLE "flight_plan" ADD CONSTRAINT IF NOT EXISTS fk_origin_vertipad_id FOREIGN KEY("origin_vertipad_id") REFERENCES "vertipad"("vertipad_id")"#.to_string(),
+            r#"ALTER TABLE "flight_plan" ADD CONSTRAINT IF NOT EXISTS fk_target_vertipad_id FOREIGN KEY("target_vertipad_id") REFERENCES "vertipad"("vertipad_id")"#.to_string(),
             r#"CREATE INDEX IF NOT EXISTS flight_plan_flight_status_idx ON "flight_plan" ("flight_status")"#.to_string(),
             r#"CREATE INDEX IF NOT EXISTS flight_plan_flight_priority_idx ON "flight_plan" ("flight_priority")"#.to_string(),
         ].to_vec()
     }
+
+    /// The flight status follows DRAFT -> READY -> BOARDING -> IN_FLIGHT -> FINISHED.
+    /// A flight plan can be cancelled as long as it's not in flight yet.
+    fn get_state_transitions() -> Vec<StateTransition> {
+        vec![
+            StateTransition::new("flight_status", &["DRAFT"], "READY"),
+            StateTransition::new("flight_status", &["READY"], "BOARDING"),
+            StateTransition::new("flight_status", &["BOARDING"], "IN_FLIGHT")
+                .set_now(&["actual_departure_time"]),
+            StateTransition::new("flight_status", &["IN_FLIGHT"], "FINISHED")
+                .set_now(&["actual_arrival_time"]),
+            StateTransition::new(
+                "flight_status",
+                &["DRAFT", "READY", "BOARDING"],
+                "CANCELLED",
+            ),
+        ]
+    }
+
+    /// The origin and target vertiport ids are read from the flight plan's vertipads.
+    /// Archived vertipads are skipped, leaving the vertiport id empty.
+    fn get_psql_select() -> Option<String> {
+        Some(
+            [
+                r#"SELECT "flight_plan".*, origin."vertiport_id" AS "origin_vertiport_id", target."vertiport_id" AS "target_vertiport_id""#,
+                r#"FROM "flight_plan""#,
+                r#"LEFT JOIN "vertipad" AS origin ON origin."vertipad_id" = "flight_plan"."origin_vertipad_id" AND origin."deleted_at" IS NULL"#,
+                r#"LEFT JOIN "vertipad" AS target ON target."vertipad_id" = "flight_plan"."target_vertipad_id" AND target."deleted_at" IS NULL"#,
+            ]
+            .join(" "),
+        )
+    }
+
+    /// A flight plan reserves both its origin and target vertipad, unless it has been cancelled.
+    fn get_timeslot_reservations() -> Vec<TimeslotReservation> {
+        vec![
+            TimeslotReservation::new(
+                "origin_vertipad_id",
+                "origin_timeslot_start",
+                "origin_timeslot_end",
+            )
+            .released_by("flight_status", &["CANCELLED"]),
+            TimeslotReservation::new(
+                "target_vertipad_id",
+                "target_timeslot_start",
+                "target_timeslot_end",
+            )
+            .released_by("flight_status", &["CANCELLED"]),
+        ]
+    }
 }
 
 impl GrpcDataObjectType for Data {
@@ -231,23 +278,10 @@ impl TryFrom<Row> for Data {
         let approved_by: Option<Uuid> = row.get("approved_by");
         let approved_by = approved_by.map(|val| val.to_string());
 
-        let handle = get_runtime_handle()?;
-        let vertipad_id = row.get("origin_vertipad_id");
-        let data = task::block_in_place(move || {
-            handle.block_on(async move {
-                <ResourceObject<vertipad::Data> as PsqlType>::get_by_id(&vertipad_id).await
-            })
-        })?;
-        let origin_vertiport_id = data.get::<&str, Uuid>("vertiport_id").to_string();
-
-        let handle = get_runtime_handle()?;
-        let vertipad_id = row.get("target_vertipad_id");
-        let data = task::block_in_place(move || {
-            handle.block_on(async move {
-                <ResourceObject<vertipad::Data> as PsqlType>::get_by_id(&vertipad_id).await
-            })
-        })?;
-        let target_vertiport_id = data.get::<&str, Uuid>("vertiport_id").to_string();
+        let origin_vertiport_id: Option<Uuid> = row.get("origin_vertiport_id");
+        let origin_vertiport_id = origin_vertiport_id.map(|val| val.to_string());
+        let target_vertiport_id: Option<Uuid> = row.get("target_vertiport_id");
+        let target_vertiport_id = target_vertiport_id.map(|val| val.to_string());
 
         let flight_plan_submitted: Option<prost_wkt_types::Timestamp> = row
             .get::<&str, Option<DateTime<Utc>>>("flight_plan_submitted")
@@ -298,9 +332,9 @@ impl TryFrom<Row> for Data {
             vehicle_id,
             path: Some(path.into()),
             weather_conditions: row.get("weather_conditions"),
-            origin_vertiport_id: Some(origin_vertiport_id),
+            origin_vertiport_id,
             origin_vertipad_id,
-            target_vertiport_id: Some(target_vertiport_id),
+            target_vertiport_id,
             target_vertipad_id,
             origin_timeslot_start,
             origin_timeslot_end,
@@ -321,6 +355,7 @@ impl TryFrom<Row> for Data {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::postgres::util::{validate_state_transitions, validate_with_mask};
     use crate::resources::geo_types::GeoLineStringZ;
     use crate::test_util::*;
 
@@ -334,6 +369,7 @@ mod tests {
         let object: ResourceObject<Data> = Object {
             id,
             data: Some(data.clone()),
+            deleted_at: None,
         }
         .into();
         test_schema::<ResourceObject<Data>, Data>(object);
@@ -597,4 +633,121 @@ mod tests {
 
         ut_info!("success");
     }
+
+    #[tokio::test]
+    async fn test_flight_status_transitions() {
+        assert_init_done().await;
+        ut_info!("start");
+
+        let mask = prost_types::FieldMask {
+            paths: vec![String::from("flight_status")],
+        };
+        let check = |from: FlightStatus, to: FlightStatus| {
+            let data = Data {
+                flight_status: to.into(),
+                actual_departure_time: None,
+                actual_arrival_time: None,
+                ..mock::get_data_obj()
+            };
+            let (mut psql_data, validation_result) =
+                validate_with_mask::<ResourceObject<Data>>(&data, Some(&mask)).unwrap();
+            assert!(validation_result.success);
+
+            let current = HashMap::from([(
+                String::from("flight_status"),
+                from.as_str_name().to_string(),
+            )]);
+            let validation_result = validate_state_transitions::<ResourceObject<Data>>(
+                &data,
+                &current,
+                &mut psql_data,
+            )
+            .unwrap();
+            (validation_result, psql_data)
+        };
+
+        // valid transitions
+        for (from, to) in [
+            (FlightStatus::Draft, FlightStatus::Ready),
+            (FlightStatus::Ready, FlightStatus::Boarding),
+            (FlightStatus::Draft, FlightStatus::Cancelled),
+            (FlightStatus::Ready, FlightStatus::Cancelled),
+            (FlightStatus::Boarding, FlightStatus::Cancelled),
+            (FlightStatus::Finished, FlightStatus::Finished),
+        ] {
+            let (validation_result, psql_data) = check(from, to);
+            assert!(validation_result.success);
+            assert!(!psql_data.contains_key("actual_departure_time"));
+            assert!(!psql_data.contains_key("actual_arrival_time"));
+        }
+
+        let (validation_result, psql_data) = check(FlightStatus::Boarding, FlightStatus::InFlight);
+        assert!(validation_result.success);
+        assert!(psql_data.contains_key("actual_departure_time"));
+        assert!(!psql_data.contains_key("actual_arrival_time"));
+
+        let (validation_result, psql_data) = check(FlightStatus::InFlight, FlightStatus::Finished);
+        assert!(validation_result.success);
+        assert!(!psql_data.contains_key("actual_departure_time"));
+        assert!(psql_data.contains_key("actual_arrival_time"));
+
+        // invalid transitions
+        for (from, to) in [
+            (FlightStatus::Finished, FlightStatus::Draft),
+            (FlightStatus::Cancelled, FlightStatus::InFlight),
+            (FlightStatus::Draft, FlightStatus::InFlight),
+            (FlightStatus::InFlight, FlightStatus::Cancelled),
+            (FlightStatus::Boarding, FlightStatus::Ready),
+        ] {
+            let (validation_result, _) = check(from, to);
+            assert!(!validation_result.success);
+            assert_eq!(validation_result.errors.len(), 1);
+            assert_eq!(validation_result.errors[0].field, "flight_status");
+        }
+
+        // status is not part of the update, nothing to check
+        let data = mock::get_data_obj();
+        let mask = prost_types::FieldMask {
+            paths: vec![String::from("weather_conditions")],
+        };
+        let (mut psql_data, _) =
+            validate_with_mask::<ResourceObject<Data>>(&data, Some(&mask)).unwrap();
+        let current = HashMap::from([(String::from("flight_status"), String::from("FINISHED"))]);
+        let validation_result =
+            validate_state_transitions::<ResourceObject<Data>>(&data, &current, &mut psql_data)
+                .unwrap();
+        assert!(validation_result.success);
+
+        ut_info!("success");
+    }
+
+    #[tokio::test]
+    async fn test_flight_plan_psql_from() {
+        assert_init_done().await;
+        ut_info!("start");
+
+        let select = ResourceObject::<Data>::get_psql_select().unwrap();
+        assert!(select.contains(r#"origin."vertiport_id" AS "origin_vertiport_id""#));
+        assert!(select.contains(r#"target."vertiport_id" AS "target_vertiport_id""#));
+        assert_eq!(
+            ResourceObject::<Data>::get_psql_from(None),
+            format!(r#"({}) AS "flight_plan""#, select)
+        );
+        assert_eq!(
+            ResourceObject::<Data>::get_psql_from(Some("other")),
+            format!("({}) AS other", select)
+        );
+
+        // Resources without a select query are read from their table directly
+        assert_eq!(
+            ResourceObject::<crate::resources::vertipad::Data>::get_psql_from(None),
+            String::from(r#""vertipad""#)
+        );
+        assert_eq!(
+            ResourceObject::<crate::resources::vertipad::Data>::get_psql_from(Some("other")),
+            String::from(r#""vertipad" AS other"#)
+        );
+
+        ut_info!("success");
+    }
 }
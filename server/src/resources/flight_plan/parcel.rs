@@ -109,6 +109,7 @@ impl From<RowData> for ResourceObject<Data> {
                 acquire: row_data.acquire,
                 deliver: row_data.deliver,
             }),
+            expected_updated_at: None,
             mask: None,
         }
     }
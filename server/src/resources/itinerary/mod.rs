@@ -54,7 +54,7 @@ impl Resource for ResourceObject<Data> {
 
     fn get_table_indices() -> Vec<String> {
         [
-            r#"ALTER TABLE "itinerary" ADD CONSTRAINT fk_user_id FOREIGN KEY("user_id") REFERENCES "user"("user_id")"#.to_string()
+            r#"ALTER TABLE "itinerary" ADD CONSTRAINT IF NOT EXISTS fk_user_id FOREIGN KEY("user_id") REFERENCES "user"("user_id")"#.to_string()
         ]
         .to_vec()
     }
@@ -105,6 +105,7 @@ mod tests {
         let object: ResourceObject<Data> = Object {
             id,
             data: Some(data.clone()),
+            deleted_at: None,
         }
         .into();
         test_schema::<ResourceObject<Data>, Data>(object);
@@ -16,6 +16,7 @@ pub mod test_util;
 pub mod base;
 
 pub mod adsb;
+pub mod audit;
 pub mod flight_plan;
 pub mod group;
 pub mod itinerary;
@@ -25,6 +26,7 @@ pub mod pilot;
 pub mod scanner;
 pub mod user;
 pub mod vehicle;
+pub mod vehicle_model;
 pub mod vertipad;
 pub mod vertiport;
 
@@ -70,7 +70,7 @@ impl Resource for ResourceObject<Data> {
 
     fn get_table_indices() -> Vec<String> {
         [
-            r#"ALTER TABLE "parcel" ADD CONSTRAINT fk_user_id FOREIGN KEY("user_id") REFERENCES "user"("user_id")"#.to_string()
+            r#"ALTER TABLE "parcel" ADD CONSTRAINT IF NOT EXISTS fk_user_id FOREIGN KEY("user_id") REFERENCES "user"("user_id")"#.to_string()
         ]
         .to_vec()
     }
@@ -126,6 +126,7 @@ mod tests {
         let object: ResourceObject<Data> = Object {
             id,
             data: Some(data.clone()),
+            deleted_at: None,
         }
         .into();
         test_schema::<ResourceObject<Data>, Data>(object);
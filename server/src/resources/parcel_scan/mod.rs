@@ -56,9 +56,9 @@ impl Resource for ResourceObject<Data> {
 
     fn get_table_indices() -> Vec<String> {
         [
-            r#"ALTER TABLE parcel_scan ADD CONSTRAINT fk_parcel_id FOREIGN KEY(parcel_id) REFERENCES parcel(parcel_id)"#.to_owned(),
-            r#"ALTER TABLE parcel_scan ADD CONSTRAINT fk_scanner_id FOREIGN KEY(scanner_id) REFERENCES scanner(scanner_id)"#.to_owned(),
-            r#"ALTER TABLE parcel_scan ADD CONSTRAINT uk_parcel_id_scanner_id_created_at UNIQUE (parcel_id, scanner_id, created_at)"#.to_owned(),
+            r#"ALTER TABLE parcel_scan ADD CONSTRAINT IF NOT EXISTS fk_parcel_id FOREIGN KEY(parcel_id) REFERENCES parcel(parcel_id)"#.to_owned(),
+            r#"ALTER TABLE parcel_scan ADD CONSTRAINT IF NOT EXISTS fk_scanner_id FOREIGN KEY(scanner_id) REFERENCES scanner(scanner_id)"#.to_owned(),
+            r#"ALTER TABLE parcel_scan ADD CONSTRAINT IF NOT EXISTS uk_parcel_id_scanner_id_created_at UNIQUE (parcel_id, scanner_id, created_at)"#.to_owned(),
             r#"CREATE INDEX IF NOT EXISTS parcel_scan_geo_location_idx ON parcel_scan USING GIST(geo_location)"#.to_owned(),
         ].to_vec()
     }
@@ -119,6 +119,7 @@ mod tests {
         let object: ResourceObject<Data> = Object {
             id,
             data: Some(data.clone()),
+            deleted_at: None,
         }
         .into();
         test_schema::<ResourceObject<Data>, Data>(object);
@@ -40,6 +40,7 @@ pub struct Object {
 /// * `id` \[`String`\] in [\`Uuid](lib_common::uuid::Uuid) format
 /// * `data` \[`Data`\] struct with test data which should be used for update
 /// * `mask` \[`FieldMask`\] struct with test fields that should be updated
+/// * `expected_updated_at` \[`Timestamp`\] optional expected `updated_at` value of the record
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
 pub struct UpdateObject {
@@ -52,6 +53,9 @@ pub struct UpdateObject {
     /// struct with test fields that should be updated
     #[prost(message, optional, tag = "3")]
     pub mask: ::core::option::Option<::prost_types::FieldMask>,
+    /// expected `updated_at` value of the record to be updated
+    #[prost(message, optional, tag = "4")]
+    pub expected_updated_at: ::core::option::Option<::prost_wkt_types::Timestamp>,
 }
 
 /// Response struct returning an \[`Object`\] on success and \[`ValidationResult`\] if invalid fields were provided
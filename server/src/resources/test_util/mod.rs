@@ -47,12 +47,30 @@ pub mod resource {
         /// data
         #[prost(message, optional, tag = "2")]
         pub data: Option<Data>,
+        /// time the record was archived, only set for archived records
+        #[prost(message, optional, tag = "3")]
+        pub deleted_at: Option<::prost_wkt_types::Timestamp>,
+    }
+    /// ChangeEvent struct sent by the `watch` stream for every change of a test \[`Object`\]
+    #[allow(clippy::derive_partial_eq_without_eq)]
+    #[derive(Clone, PartialEq, ::prost::Message)]
+    pub struct ChangeEvent {
+        /// type of change
+        #[prost(enumeration = "crate::resources::ChangeType", tag = "1")]
+        pub change_type: i32,
+        /// id UUID v4 of the changed record
+        #[prost(string, tag = "2")]
+        pub id: ::prost::alloc::string::String,
+        /// the record's current data, if it could still be found
+        #[prost(message, optional, tag = "3")]
+        pub object: Option<Object>,
     }
     /// UpdateObject struct with `id`, `data` and `mask` fields
     ///
     /// * `id` \[`String`\] in \[`Uuid`\](lib_common::uuid::Uuid) format
     /// * `data` \[`Data`\] struct with test data which should be used for update
     /// * `mask` \[`FieldMask`\] struct with test fields that should be updated
+    /// * `expected_updated_at` \[`Timestamp`\] optional expected `updated_at` value of the record
     #[allow(clippy::derive_partial_eq_without_eq)]
     #[derive(Clone, PartialEq, ::prost::Message)]
     pub struct UpdateObject {
@@ -65,6 +83,9 @@ pub mod resource {
         /// struct with test fields that should be updated
         #[prost(message, optional, tag = "3")]
         pub mask: Option<::prost_types::FieldMask>,
+        /// expected `updated_at` value of the record to be updated
+        #[prost(message, optional, tag = "4")]
+        pub expected_updated_at: Option<::prost_wkt_types::Timestamp>,
     }
 
     /// Response struct returning an \[`Object`\] on success and \[`ValidationResult`\] if invalid fields were provided
@@ -80,6 +101,19 @@ pub mod resource {
         pub object: Option<Object>,
     }
 
+    /// Request struct to insert multiple test \[`Data`\] objects at once
+    #[derive(Serialize, Deserialize)]
+    #[allow(clippy::derive_partial_eq_without_eq)]
+    #[derive(Clone, PartialEq, ::prost::Message)]
+    pub struct InsertBatchRequest {
+        /// array/vector of test data objects to insert
+        #[prost(message, repeated, tag = "1")]
+        pub list: ::prost::alloc::vec::Vec<Data>,
+        /// if set, nothing will be inserted when any of the provided items fails validation
+        #[prost(bool, tag = "2")]
+        pub all_or_nothing: bool,
+    }
+
     /// Struct containing a `list` of test \[\`Vec\<Object\>\``\]
     #[derive(Serialize, Deserialize)]
     #[allow(clippy::derive_partial_eq_without_eq)]
@@ -88,6 +122,15 @@ pub mod resource {
         /// array/vector of test items
         #[prost(message, repeated, tag = "1")]
         pub list: ::prost::alloc::vec::Vec<Object>,
+        /// total number of records matching the search filter, regardless of paging
+        #[prost(int64, optional, tag = "2")]
+        pub total_count: ::core::option::Option<i64>,
+        /// true if more records are available after the current page
+        #[prost(bool, tag = "3")]
+        pub has_more: bool,
+        /// opaque cursor which can be used to request the next page of results
+        #[prost(string, optional, tag = "4")]
+        pub next_cursor: ::core::option::Option<::prost::alloc::string::String>,
     }
     /// Struct used to link `linked` object to a `resource` object
     #[allow(clippy::derive_partial_eq_without_eq)]
@@ -193,12 +236,30 @@ pub mod linked {
         /// data
         #[prost(message, optional, tag = "2")]
         pub data: Option<Data>,
+        /// time the record was archived, only set for archived records
+        #[prost(message, optional, tag = "3")]
+        pub deleted_at: Option<::prost_wkt_types::Timestamp>,
+    }
+    /// ChangeEvent struct sent by the `watch` stream for every change of a test \[`Object`\]
+    #[allow(clippy::derive_partial_eq_without_eq)]
+    #[derive(Clone, PartialEq, ::prost::Message)]
+    pub struct ChangeEvent {
+        /// type of change
+        #[prost(enumeration = "crate::resources::ChangeType", tag = "1")]
+        pub change_type: i32,
+        /// id UUID v4 of the changed record
+        #[prost(string, tag = "2")]
+        pub id: ::prost::alloc::string::String,
+        /// the record's current data, if it could still be found
+        #[prost(message, optional, tag = "3")]
+        pub object: Option<Object>,
     }
     /// UpdateObject struct with `id`, `data` and `mask` fields
     ///
     /// * `id` \[`String`\] in \[`Uuid`\](lib_common::uuid::Uuid) format
     /// * `data` \[`Data`\] struct with test data which should be used for update
     /// * `mask` \[`FieldMask`\] struct with test fields that should be updated
+    /// * `expected_updated_at` \[`Timestamp`\] optional expected `updated_at` value of the record
     #[allow(clippy::derive_partial_eq_without_eq)]
     #[derive(Clone, PartialEq, ::prost::Message)]
     pub struct UpdateObject {
@@ -211,6 +272,9 @@ pub mod linked {
         /// struct with test fields that should be updated
         #[prost(message, optional, tag = "3")]
         pub mask: Option<::prost_types::FieldMask>,
+        /// expected `updated_at` value of the record to be updated
+        #[prost(message, optional, tag = "4")]
+        pub expected_updated_at: Option<::prost_wkt_types::Timestamp>,
     }
 
     /// Response struct returning an \[`Object`\] on success and \[`ValidationResult`\] if invalid fields were provided
@@ -226,6 +290,19 @@ pub mod linked {
         pub object: Option<Object>,
     }
 
+    /// Request struct to insert multiple test \[`Data`\] objects at once
+    #[derive(Serialize, Deserialize)]
+    #[allow(clippy::derive_partial_eq_without_eq)]
+    #[derive(Clone, PartialEq, ::prost::Message)]
+    pub struct InsertBatchRequest {
+        /// array/vector of test data objects to insert
+        #[prost(message, repeated, tag = "1")]
+        pub list: ::prost::alloc::vec::Vec<Data>,
+        /// if set, nothing will be inserted when any of the provided items fails validation
+        #[prost(bool, tag = "2")]
+        pub all_or_nothing: bool,
+    }
+
     /// Struct containing a `list` of test \[\`Vec\<Object\>\``\]
     #[derive(Serialize, Deserialize)]
     #[allow(clippy::derive_partial_eq_without_eq)]
@@ -234,6 +311,15 @@ pub mod linked {
         /// array/vector of test items
         #[prost(message, repeated, tag = "1")]
         pub list: ::prost::alloc::vec::Vec<Object>,
+        /// total number of records matching the search filter, regardless of paging
+        #[prost(int64, optional, tag = "2")]
+        pub total_count: ::core::option::Option<i64>,
+        /// true if more records are available after the current page
+        #[prost(bool, tag = "3")]
+        pub has_more: bool,
+        /// opaque cursor which can be used to request the next page of results
+        #[prost(string, optional, tag = "4")]
+        pub next_cursor: ::core::option::Option<::prost::alloc::string::String>,
     }
 
     /// Struct used to link `resource` object to a `linked` object
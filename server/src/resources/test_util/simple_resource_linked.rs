@@ -74,6 +74,7 @@ pub struct Object {
 /// * `id` \[`String`\] in [\`Uuid](lib_common::uuid::Uuid) format
 /// * `data` \[`Data`\] struct with test data which should be used for update
 /// * `mask` \[`FieldMask`\] struct with test fields that should be updated
+/// * `expected_updated_at` \[`Timestamp`\] optional expected `updated_at` value of the record
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
 pub struct UpdateObject {
@@ -86,6 +87,9 @@ pub struct UpdateObject {
     /// struct with test fields that should be updated
     #[prost(message, optional, tag = "3")]
     pub mask: ::core::option::Option<::prost_types::FieldMask>,
+    /// expected `updated_at` value of the record to be updated
+    #[prost(message, optional, tag = "4")]
+    pub expected_updated_at: ::core::option::Option<::prost_wkt_types::Timestamp>,
 }
 
 /// Response struct returning an \[`Object`\] on success and \[`ValidationResult`\] if invalid fields were provided
@@ -119,6 +123,15 @@ pub struct RowDataList {
     /// array/vector of flight plan linked items including id fields
     #[prost(message, repeated, tag = "1")]
     pub list: ::prost::alloc::vec::Vec<RowData>,
+    /// total number of records matching the search filter, regardless of paging
+    #[prost(int64, optional, tag = "2")]
+    pub total_count: ::core::option::Option<i64>,
+    /// true if more records are available after the current page
+    #[prost(bool, tag = "3")]
+    pub has_more: bool,
+    /// opaque cursor which can be used to request the next page of results
+    #[prost(string, optional, tag = "4")]
+    pub next_cursor: ::core::option::Option<::prost::alloc::string::String>,
 }
 
 crate::build_generic_resource_linked_impl_from!();
@@ -228,6 +241,7 @@ impl From<RowData> for ResourceObject<Data> {
                 test_bool: row_data.test_bool,
                 test_string: row_data.test_string,
             }),
+            expected_updated_at: None,
             mask: None,
         }
     }
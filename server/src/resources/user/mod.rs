@@ -67,6 +67,10 @@ impl Resource for ResourceObject<Data> {
             _ => None,
         }
     }
+
+    fn get_unique_fields() -> Vec<Vec<String>> {
+        vec![vec![String::from("email")]]
+    }
 }
 
 impl GrpcDataObjectType for Data {
@@ -115,6 +119,7 @@ mod tests {
         let object: ResourceObject<Data> = Object {
             id,
             data: Some(data.clone()),
+            deleted_at: None,
         }
         .into();
         test_schema::<ResourceObject<Data>, Data>(object);
@@ -12,8 +12,10 @@ use tokio_postgres::types::Type as PsqlFieldType;
 
 use super::base::simple_resource::*;
 use super::base::{FieldDefinition, ResourceDefinition};
+use super::calendar::Schedule;
 use crate::common::ArrErr;
 use crate::grpc::{GrpcDataObjectType, GrpcField, GrpcFieldOption};
+use crate::postgres::migrations::Migration;
 
 // Generate `From` trait implementations for GenericResource into and from Grpc defined Resource
 crate::build_generic_resource_impl_from!();
@@ -49,7 +51,7 @@ impl Resource for ResourceObject<Data> {
                 ),
                 (
                     String::from("schedule"),
-                    FieldDefinition::new(PsqlFieldType::TEXT, false),
+                    FieldDefinition::new(PsqlFieldType::TEXT, false).set_schedule(),
                 ),
                 (
                     String::from("last_maintenance"),
@@ -86,10 +88,64 @@ impl Resource for ResourceObject<Data> {
     }
 
     fn get_table_indices() -> Vec<String> {
-        [
-            r#"ALTER TABLE vehicle ADD CONSTRAINT fk_hangar_id FOREIGN KEY(hangar_id) REFERENCES vertiport(vertiport_id)"#.to_owned(),
-            r#"ALTER TABLE vehicle ADD CONSTRAINT fk_hangar_bay_id FOREIGN KEY(hangar_bay_id) REFERENCES vertipad(vertipad_id)"#.to_owned(),
-        ].to_vec()
+        let mut queries = vec![
+            r#"ALTER TABLE vehicle ADD CONSTRAINT IF NOT EXISTS fk_hangar_id FOREIGN KEY(hangar_id) REFERENCES vertiport(vertiport_id)"#.to_owned(),
+            r#"ALTER TABLE vehicle ADD CONSTRAINT IF NOT EXISTS fk_hangar_bay_id FOREIGN KEY(hangar_bay_id) REFERENCES vertipad(vertipad_id)"#.to_owned(),
+        ];
+        queries.append(&mut get_vehicle_model_fk_queries());
+        queries
+    }
+
+    fn get_migrations() -> Vec<Migration> {
+        vec![
+            Migration::new(
+                1,
+                "add hangar columns",
+                vec![
+                    r#"ALTER TABLE vehicle ADD COLUMN IF NOT EXISTS hangar_id UUID"#.to_owned(),
+                    r#"ALTER TABLE vehicle ADD COLUMN IF NOT EXISTS hangar_bay_id UUID"#.to_owned(),
+                    r#"ALTER TABLE vehicle ADD CONSTRAINT IF NOT EXISTS fk_hangar_id FOREIGN KEY(hangar_id) REFERENCES vertiport(vertiport_id)"#.to_owned(),
+                    r#"ALTER TABLE vehicle ADD CONSTRAINT IF NOT EXISTS fk_hangar_bay_id FOREIGN KEY(hangar_bay_id) REFERENCES vertipad(vertipad_id)"#.to_owned(),
+                ],
+            ),
+            Migration::new(
+                2,
+                "add vehicle_model foreign key",
+                get_vehicle_model_fk_queries(),
+            ),
+        ]
+    }
+
+    fn get_unique_fields() -> Vec<Vec<String>> {
+        vec![vec![String::from("registration_number")]]
+    }
+}
+
+/// Manufacturer of the placeholder vehicle models inserted for vehicles referencing an unknown
+/// vehicle model when the `vehicle_model` foreign key is added
+pub const PLACEHOLDER_MANUFACTURER_ID: &str = "00000000-0000-0000-0000-000000000000";
+
+/// Returns the queries adding the `vehicle_model` foreign key.
+///
+/// Vehicles created before the `vehicle_model` table existed don't reference a known vehicle
+/// model. A placeholder vehicle model is inserted for each of their distinct vehicle model ids
+/// first, so the vehicles keep their original `vehicle_model_id`.
+fn get_vehicle_model_fk_queries() -> Vec<String> {
+    vec![
+        format!(
+            r#"INSERT INTO vehicle_model (vehicle_model_id, manufacturer_id, model_name, max_payload_kg, max_range_km) SELECT DISTINCT vehicle.vehicle_model_id, '{}'::UUID, 'Unknown (migrated)', 0, 0 FROM vehicle WHERE vehicle.vehicle_model_id IS NOT NULL AND NOT EXISTS (SELECT 1 FROM vehicle_model WHERE vehicle_model.vehicle_model_id = vehicle.vehicle_model_id) ON CONFLICT (vehicle_model_id) DO NOTHING"#,
+            PLACEHOLDER_MANUFACTURER_ID
+        ),
+        r#"ALTER TABLE vehicle ADD CONSTRAINT IF NOT EXISTS fk_vehicle_model_id FOREIGN KEY(vehicle_model_id) REFERENCES vehicle_model(vehicle_model_id)"#.to_owned(),
+    ]
+}
+
+impl Schedule for Data {
+    fn get_schedule(&self) -> Option<&str> {
+        self.schedule.as_deref()
+    }
+    fn set_schedule(&mut self, schedule: Option<String>) {
+        self.schedule = schedule;
     }
 }
 
@@ -196,6 +252,7 @@ mod tests {
         let object: ResourceObject<Data> = Object {
             id,
             data: Some(data.clone()),
+            deleted_at: None,
         }
         .into();
         test_schema::<ResourceObject<Data>, Data>(object);
@@ -0,0 +1,232 @@
+//! Vehicle Model
+
+pub use crate::grpc::server::vehicle_model::*;
+
+use anyhow::{Context, Result};
+use lib_common::uuid::Uuid;
+use std::collections::HashMap;
+use tokio_postgres::row::Row;
+use tokio_postgres::types::Type as PsqlFieldType;
+
+use super::base::simple_resource::*;
+use super::base::{FieldDefinition, ResourceDefinition};
+use crate::common::ArrErr;
+use crate::grpc::{GrpcDataObjectType, GrpcField};
+
+// Generate `From` trait implementations for GenericResource into and from Grpc defined Resource
+crate::build_generic_resource_impl_from!();
+
+// Generate grpc server implementations
+crate::build_grpc_simple_resource_impl!(vehicle_model);
+
+impl Resource for ResourceObject<Data> {
+    fn get_definition() -> ResourceDefinition {
+        ResourceDefinition {
+            psql_table: String::from("vehicle_model"),
+            psql_id_cols: vec![String::from("vehicle_model_id")],
+            fields: HashMap::from([
+                (
+                    String::from("manufacturer_id"),
+                    FieldDefinition::new(PsqlFieldType::UUID, true),
+                ),
+                (
+                    String::from("model_name"),
+                    FieldDefinition::new(PsqlFieldType::TEXT, true),
+                ),
+                (
+                    String::from("type"),
+                    FieldDefinition::new(PsqlFieldType::ANYENUM, true)
+                        .set_default(String::from("'VTOL_CARGO'")),
+                ),
+                (
+                    String::from("max_payload_kg"),
+                    FieldDefinition::new(PsqlFieldType::FLOAT8, true),
+                ),
+                (
+                    String::from("max_range_km"),
+                    FieldDefinition::new(PsqlFieldType::FLOAT8, true),
+                ),
+                (
+                    String::from("created_at"),
+                    FieldDefinition::new_internal(PsqlFieldType::TIMESTAMPTZ, true)
+                        .set_default(String::from("CURRENT_TIMESTAMP")),
+                ),
+                (
+                    String::from("updated_at"),
+                    FieldDefinition::new_internal(PsqlFieldType::TIMESTAMPTZ, true)
+                        .set_default(String::from("CURRENT_TIMESTAMP")),
+                ),
+                (
+                    String::from("deleted_at"),
+                    FieldDefinition::new_internal(PsqlFieldType::TIMESTAMPTZ, false),
+                ),
+            ]),
+        }
+    }
+
+    /// Converts raw i32 values into string based on matching Enum value
+    fn get_enum_string_val(field: &str, value: i32) -> Option<String> {
+        match field {
+            "type" => Some(
+                VehicleModelType::try_from(value)
+                    .ok()?
+                    .as_str_name()
+                    .to_string(),
+            ),
+            _ => None,
+        }
+    }
+}
+
+impl GrpcDataObjectType for Data {
+    fn get_field_value(&self, key: &str) -> Result<GrpcField, ArrErr> {
+        match key {
+            "manufacturer_id" => Ok(GrpcField::String(self.manufacturer_id.clone())),
+            "model_name" => Ok(GrpcField::String(self.model_name.clone())),
+            "type" => Ok(GrpcField::I32(self.r#type)),
+            "max_payload_kg" => Ok(GrpcField::F64(self.max_payload_kg)),
+            "max_range_km" => Ok(GrpcField::F64(self.max_range_km)),
+            _ => Err(ArrErr::Error(format!(
+                "Invalid key specified [{}], no such field found",
+                key
+            ))),
+        }
+    }
+}
+
+#[cfg(not(tarpaulin_include))]
+// no_coverage: (Rwaiting) Can not be tested in unittest until https://github.com/sfackler/rust-postgres/pull/979 has been merged
+impl TryFrom<Row> for Data {
+    type Error = ArrErr;
+
+    fn try_from(row: Row) -> Result<Self, ArrErr> {
+        resources_debug!("Converting Row to vehicle_model::Data: {:?}", row);
+        let manufacturer_id: Uuid = row.get("manufacturer_id");
+
+        let r#type = VehicleModelType::from_str_name(row.get("type"))
+            .context("(try_from) Could not convert database value to VehicleModelType Enum type.")?
+            as i32;
+
+        Ok(Data {
+            manufacturer_id: manufacturer_id.to_string(),
+            model_name: row.get("model_name"),
+            r#type,
+            max_payload_kg: row.get("max_payload_kg"),
+            max_range_km: row.get("max_range_km"),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::*;
+
+    #[tokio::test]
+    async fn test_vehicle_model_schema() {
+        assert_init_done().await;
+        ut_info!("start");
+
+        let id = Uuid::new_v4().to_string();
+        let data = mock::get_data_obj();
+        let object: ResourceObject<Data> = Object {
+            id,
+            data: Some(data.clone()),
+            deleted_at: None,
+        }
+        .into();
+        test_schema::<ResourceObject<Data>, Data>(object);
+
+        let result = validate::<ResourceObject<Data>>(&data);
+        assert!(result.is_ok());
+        if let Ok((sql_fields, validation_result)) = result {
+            ut_info!("{:?}", sql_fields);
+            ut_info!("{:?}", validation_result);
+            assert_eq!(validation_result.success, true);
+        }
+        ut_info!("success");
+    }
+
+    #[tokio::test]
+    async fn test_vehicle_model_invalid_data() {
+        assert_init_done().await;
+        ut_info!("start");
+
+        let data = Data {
+            manufacturer_id: String::from("INVALID"),
+            model_name: String::from("Mock Model"),
+            r#type: -1,
+            max_payload_kg: 100.0,
+            max_range_km: 50.0,
+        };
+
+        let result = validate::<ResourceObject<Data>>(&data);
+        assert!(result.is_ok());
+        if let Ok((_, validation_result)) = result {
+            ut_info!("{:?}", validation_result);
+            assert_eq!(validation_result.success, false);
+
+            let expected_errors = vec!["manufacturer_id", "type"];
+            assert_eq!(expected_errors.len(), validation_result.errors.len());
+            assert!(contains_field_errors(&validation_result, &expected_errors));
+        }
+        ut_info!("success");
+    }
+
+    #[tokio::test]
+    async fn test_vehicle_model_type_get_enum_string_val() {
+        assert_init_done().await;
+        ut_info!("start");
+
+        assert_eq!(
+            ResourceObject::<Data>::get_enum_string_val("type", VehicleModelType::VtolCargo.into()),
+            Some(String::from("VTOL_CARGO"))
+        );
+        assert_eq!(
+            ResourceObject::<Data>::get_enum_string_val(
+                "type",
+                VehicleModelType::VtolPassenger.into()
+            ),
+            Some(String::from("VTOL_PASSENGER"))
+        );
+
+        assert_eq!(
+            ResourceObject::<Data>::get_enum_string_val("type", -1),
+            None
+        );
+
+        ut_info!("success");
+    }
+
+    #[tokio::test]
+    async fn test_vehicle_model_type_as_str_name() {
+        assert_init_done().await;
+        ut_info!("start");
+
+        assert_eq!(VehicleModelType::VtolCargo.as_str_name(), "VTOL_CARGO");
+        assert_eq!(
+            VehicleModelType::VtolPassenger.as_str_name(),
+            "VTOL_PASSENGER"
+        );
+
+        ut_info!("success");
+    }
+
+    #[tokio::test]
+    async fn test_vehicle_model_type_from_str_name() {
+        assert_init_done().await;
+        ut_info!("start");
+
+        assert_eq!(
+            VehicleModelType::from_str_name("VTOL_CARGO"),
+            Some(VehicleModelType::VtolCargo)
+        );
+        assert_eq!(
+            VehicleModelType::from_str_name("VTOL_PASSENGER"),
+            Some(VehicleModelType::VtolPassenger)
+        );
+        assert_eq!(VehicleModelType::from_str_name("INVALID"), None);
+
+        ut_info!("success");
+    }
+}
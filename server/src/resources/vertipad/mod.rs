@@ -11,6 +11,7 @@ use tokio_postgres::types::Type as PsqlFieldType;
 
 use super::base::simple_resource::*;
 use super::base::{FieldDefinition, ResourceDefinition};
+use super::calendar::Schedule;
 use crate::common::ArrErr;
 use crate::grpc::{GrpcDataObjectType, GrpcField, GrpcFieldOption};
 
@@ -40,7 +41,7 @@ impl Resource for ResourceObject<Data> {
                 ),
                 (
                     "schedule".to_string(),
-                    FieldDefinition::new(PsqlFieldType::TEXT, false),
+                    FieldDefinition::new(PsqlFieldType::TEXT, false).set_schedule(),
                 ),
                 (
                     "enabled".to_string(),
@@ -70,11 +71,25 @@ impl Resource for ResourceObject<Data> {
 
     fn get_table_indices() -> Vec<String> {
         [
-            r#"ALTER TABLE vertipad ADD CONSTRAINT fk_vertiport_id FOREIGN KEY(vertiport_id) REFERENCES vertiport(vertiport_id)"#.to_owned(),
+            r#"ALTER TABLE vertipad ADD CONSTRAINT IF NOT EXISTS fk_vertiport_id FOREIGN KEY(vertiport_id) REFERENCES vertiport(vertiport_id)"#.to_owned(),
             r#"CREATE INDEX IF NOT EXISTS vertipad_occupied_idx ON vertipad(occupied)"#.to_owned(),
             r#"CREATE INDEX IF NOT EXISTS vertipad_geo_location_idx ON vertipad USING GIST(geo_location)"#.to_owned(),
         ].to_vec()
     }
+
+    /// Cached flight plans include the vertiport ids of their vertipads.
+    fn get_cache_dependents() -> Vec<String> {
+        vec![String::from("flight_plan")]
+    }
+}
+
+impl Schedule for Data {
+    fn get_schedule(&self) -> Option<&str> {
+        self.schedule.as_deref()
+    }
+    fn set_schedule(&mut self, schedule: Option<String>) {
+        self.schedule = schedule;
+    }
 }
 
 impl GrpcDataObjectType for Data {
@@ -147,6 +162,7 @@ mod tests {
         let object: ResourceObject<Data> = Object {
             id,
             data: Some(data.clone()),
+            deleted_at: None,
         }
         .into();
         test_schema::<ResourceObject<Data>, Data>(object);
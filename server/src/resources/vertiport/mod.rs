@@ -11,6 +11,7 @@ use tokio_postgres::types::Type as PsqlFieldType;
 
 use super::base::simple_resource::*;
 use super::base::{FieldDefinition, ResourceDefinition};
+use super::calendar::Schedule;
 use crate::common::ArrErr;
 use crate::grpc::{GrpcDataObjectType, GrpcField, GrpcFieldOption};
 use postgis::ewkb::PolygonZ;
@@ -41,7 +42,7 @@ impl Resource for ResourceObject<Data> {
                 ),
                 (
                     "schedule".to_string(),
-                    FieldDefinition::new(PsqlFieldType::TEXT, false),
+                    FieldDefinition::new(PsqlFieldType::TEXT, false).set_schedule(),
                 ),
                 (
                     "created_at".to_string(),
@@ -68,6 +69,15 @@ impl Resource for ResourceObject<Data> {
     }
 }
 
+impl Schedule for Data {
+    fn get_schedule(&self) -> Option<&str> {
+        self.schedule.as_deref()
+    }
+    fn set_schedule(&mut self, schedule: Option<String>) {
+        self.schedule = schedule;
+    }
+}
+
 impl GrpcDataObjectType for Data {
     fn get_field_value(&self, key: &str) -> Result<GrpcField, ArrErr> {
         match key {
@@ -134,6 +144,7 @@ mod tests {
         let object: ResourceObject<Data> = Object {
             id,
             data: Some(data.clone()),
+            deleted_at: None,
         }
         .into();
         test_schema::<ResourceObject<Data>, Data>(object);
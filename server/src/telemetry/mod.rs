@@ -0,0 +1,190 @@
+//! OpenTelemetry tracing
+//!
+//! gRPC requests and database queries are recorded as [`tracing`] spans. Incoming requests
+//! continue the trace found in their W3C `traceparent` metadata, see
+//! [`set_parent_from_metadata`](crate::grpc::server::trace_context::set_parent_from_metadata).
+//! Spans are exported with OTLP to the collector configured by [`Config::otlp_endpoint`],
+//! tracing stays disabled if no endpoint is configured.
+
+#[macro_use]
+pub mod macros;
+
+use crate::common::ArrErr;
+use crate::config::Config;
+
+use opentelemetry::trace::TraceError;
+use opentelemetry::{global, KeyValue};
+use opentelemetry_otlp::WithExportConfig;
+use opentelemetry_sdk::propagation::TraceContextPropagator;
+use opentelemetry_sdk::{runtime, trace, Resource};
+use tracing_subscriber::layer::SubscriberExt;
+use tracing_subscriber::util::SubscriberInitExt;
+
+/// Service name reported with all exported spans
+pub const SERVICE_NAME: &str = "svc-storage";
+
+/// Initializes tracing using the provided [`Config`].
+///
+/// The W3C trace context propagator is always installed, so trace context can be passed on even
+/// if no spans are exported. Spans are only exported if an `otlp_endpoint` has been configured.
+///
+/// # Errors
+///
+/// Returns [`ArrErr`] if the OTLP exporter could not be created
+/// Returns [`ArrErr`] if a global tracing subscriber has already been set
+#[cfg(not(tarpaulin_include))]
+// no_coverage: (R5) Will be part of integration tests, coverage report will need to be merged to show.
+pub fn init(config: &Config) -> Result<(), ArrErr> {
+    global::set_text_map_propagator(TraceContextPropagator::new());
+
+    let endpoint = match &config.otlp_endpoint {
+        Some(endpoint) => endpoint,
+        None => {
+            telemetry_info!("No [otlp_endpoint] configured, traces will not be exported.");
+            return Ok(());
+        }
+    };
+
+    telemetry_info!("Exporting traces to [{}].", endpoint);
+    let tracer = opentelemetry_otlp::new_pipeline()
+        .tracing()
+        .with_exporter(
+            opentelemetry_otlp::new_exporter()
+                .tonic()
+                .with_endpoint(endpoint),
+        )
+        .with_trace_config(
+            trace::config().with_resource(Resource::new(vec![KeyValue::new(
+                "service.name",
+                SERVICE_NAME,
+            )])),
+        )
+        .install_batch(runtime::Tokio)
+        .map_err(|e: TraceError| {
+            let error = format!("Could not create OTLP exporter: {}", e);
+            telemetry_error!("{}", error);
+            ArrErr::Error(error)
+        })?;
+
+    tracing_subscriber::registry()
+        .with(tracing_opentelemetry::layer().with_tracer(tracer))
+        .try_init()
+        .map_err(|e| {
+            let error = format!("Could not set tracing subscriber: {}", e);
+            telemetry_error!("{}", error);
+            ArrErr::Error(error)
+        })
+}
+
+/// Exports all remaining spans and shuts down the tracer provider
+#[cfg(not(tarpaulin_include))]
+// no_coverage: (R5) Will be part of integration tests, coverage report will need to be merged to show.
+pub fn shutdown() {
+    telemetry_debug!("Shutting down tracer provider.");
+    global::shutdown_tracer_provider();
+}
+
+/// Records the provided SQL statement as `db.statement` on the current span
+///
+/// The span must have been created with a `db.statement` field.
+pub fn record_statement(statement: &str) {
+    tracing::Span::current().record("db.statement", statement);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::grpc::server::trace_context::{set_parent_from_metadata, with_trace_context};
+    use crate::test_util::*;
+    use opentelemetry::trace::TracerProvider as _;
+    use opentelemetry_sdk::testing::trace::InMemorySpanExporter;
+    use opentelemetry_sdk::trace::TracerProvider;
+    use tonic::metadata::MetadataMap;
+
+    const TRACE_ID: &str = "0af7651916cd43dd8448eb211c80319c";
+    const PARENT_ID: &str = "b7ad6b7169203331";
+
+    #[tokio::test]
+    async fn test_trace_context_propagation() {
+        assert_init_done().await;
+        ut_info!("start");
+
+        global::set_text_map_propagator(TraceContextPropagator::new());
+        let exporter = InMemorySpanExporter::default();
+        let provider = TracerProvider::builder()
+            .with_simple_exporter(exporter.clone())
+            .build();
+        let subscriber = tracing_subscriber::registry()
+            .with(tracing_opentelemetry::layer().with_tracer(provider.tracer("test")));
+
+        let mut metadata = MetadataMap::new();
+        let traceparent = format!("00-{}-{}-01", TRACE_ID, PARENT_ID);
+        metadata.insert("traceparent", traceparent.parse().unwrap());
+
+        let outgoing = tracing::subscriber::with_default(subscriber, || {
+            let span = tracing::info_span!("get_by_id", db.statement = tracing::field::Empty);
+            let _guard = span.enter();
+            set_parent_from_metadata(&metadata);
+            record_statement(r#"SELECT * FROM "vertipad""#);
+            with_trace_context(())
+        });
+        for result in provider.force_flush() {
+            assert!(result.is_ok());
+        }
+
+        // The span continues the incoming trace
+        let spans = exporter.get_finished_spans().unwrap();
+        assert_eq!(spans.len(), 1);
+        let span = &spans[0];
+        assert_eq!(span.span_context.trace_id().to_string(), TRACE_ID);
+        assert_eq!(span.parent_span_id.to_string(), PARENT_ID);
+        assert!(span
+            .attributes
+            .iter()
+            .any(|attribute| attribute.key.as_str() == "db.statement"
+                && attribute.value.as_str() == r#"SELECT * FROM "vertipad""#));
+
+        // Outgoing requests are made part of the same trace, with the span as parent
+        let traceparent = outgoing.metadata().get("traceparent").unwrap();
+        assert_eq!(
+            traceparent.to_str().unwrap(),
+            format!("00-{}-{}-01", TRACE_ID, span.span_context.span_id())
+        );
+
+        ut_info!("success");
+    }
+
+    #[tokio::test]
+    async fn test_set_parent_without_trace_context() {
+        assert_init_done().await;
+        ut_info!("start");
+
+        global::set_text_map_propagator(TraceContextPropagator::new());
+        let exporter = InMemorySpanExporter::default();
+        let provider = TracerProvider::builder()
+            .with_simple_exporter(exporter.clone())
+            .build();
+        let subscriber = tracing_subscriber::registry()
+            .with(tracing_opentelemetry::layer().with_tracer(provider.tracer("test")));
+
+        tracing::subscriber::with_default(subscriber, || {
+            let parent = tracing::info_span!("parent");
+            let _parent_guard = parent.enter();
+            let span = tracing::info_span!("child");
+            let _guard = span.enter();
+            set_parent_from_metadata(&MetadataMap::new());
+        });
+        for result in provider.force_flush() {
+            assert!(result.is_ok());
+        }
+
+        // The local parent is kept if no trace context is provided
+        let spans = exporter.get_finished_spans().unwrap();
+        assert_eq!(spans.len(), 2);
+        let child = spans.iter().find(|span| span.name == "child").unwrap();
+        let parent = spans.iter().find(|span| span.name == "parent").unwrap();
+        assert_eq!(child.parent_span_id, parent.span_context.span_id());
+
+        ut_info!("success");
+    }
+}